@@ -0,0 +1,29 @@
+// Copyright 2018-2025 the Deno authors. MIT license.
+// deno-lint-ignore-file no-console
+
+// Hashes 1 GiB with BLAKE3 in chunks below and above the size from which
+// updates are spread across threads. Compare against `b3sum` or the
+// single-threaded run to see the parallel speedup.
+import { createHash } from "node:crypto";
+
+const total = 1024 * 1024 * 1024;
+const data = new Uint8Array(16 * 1024 * 1024).fill(0xab);
+
+function bench(chunkSize) {
+  const chunk = data.subarray(0, chunkSize);
+  const hash = createHash("blake3");
+  const start = performance.now();
+  for (let i = 0; i < total / chunkSize; i++) hash.update(chunk);
+  const digest = hash.digest("hex");
+  const elapsed = performance.now() - start;
+  const rate = Math.round((total / 1024 / 1024) / (elapsed / 1000));
+  console.log(
+    `chunk ${chunkSize / 1024} KiB: ${Math.round(elapsed)} ms, ` +
+      `${rate} MiB/s (${digest.slice(0, 16)})`,
+  );
+}
+
+// Single-threaded: 64 KiB chunks are under the threshold.
+bench(64 * 1024);
+// Multi-threaded: 16 MiB chunks.
+bench(16 * 1024 * 1024);
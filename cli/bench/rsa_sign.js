@@ -0,0 +1,24 @@
+// Copyright 2018-2025 the Deno authors. MIT license.
+// deno-lint-ignore-file no-console
+
+// Signs with an RSA-2048 key given as PEM, which is parsed on every call,
+// and as a KeyObject, whose handle holds the parsed key. The difference is
+// the cost of parsing the key.
+import { createPrivateKey, generateKeyPairSync, sign } from "node:crypto";
+
+const count = 2000;
+const { privateKey } = generateKeyPairSync("rsa", { modulusLength: 2048 });
+const pem = privateKey.export({ type: "pkcs8", format: "pem" });
+const keyObject = createPrivateKey(pem);
+const data = new TextEncoder().encode("Hello, World!");
+
+function bench(name, key) {
+  const start = performance.now();
+  for (let i = 0; i < count; i++) sign("sha256", data, key);
+  const elapsed = performance.now() - start;
+  const rate = Math.round(count / (elapsed / 1000));
+  console.log(`${name}: ${Math.round(elapsed)} ms, ${rate} signatures/s`);
+}
+
+bench("PEM", pem);
+bench("KeyObject", keyObject);
@@ -5666,4 +5666,39 @@ const dictEcdhKeyDeriveParams = [
 webidl.converters.EcdhKeyDeriveParams = webidl
   .createDictionaryConverter("EcdhKeyDeriveParams", dictEcdhKeyDeriveParams);
 
-export { Crypto, crypto, CryptoKey, SubtleCrypto };
+/**
+ * The key material behind a CryptoKey, for node:crypto's `KeyObject.from()`.
+ * @param {CryptoKey} key
+ * @returns {object}
+ */
+function getCryptoKeyData(key) {
+  webidl.assertBranded(key, CryptoKeyPrototype);
+  return WeakMapPrototypeGet(KEY_STORE, key[_handle]);
+}
+
+/**
+ * Wraps key material in a new CryptoKey, for node:crypto's
+ * `KeyObject.prototype.toCryptoKey()`. `keyData` must have the shape that
+ * `importKey` stores for the algorithm.
+ * @param {string} type
+ * @param {boolean} extractable
+ * @param {string[]} usages
+ * @param {object} algorithm
+ * @param {object} keyData
+ * @returns {CryptoKey}
+ */
+function createCryptoKey(type, extractable, usages, algorithm, keyData) {
+  const handle = {};
+  WeakMapPrototypeSet(KEY_STORE, handle, keyData);
+  return constructKey(type, extractable, usages, algorithm, handle);
+}
+
+export {
+  createCryptoKey,
+  Crypto,
+  crypto,
+  CryptoKey,
+  getCryptoKeyData,
+  normalizeAlgorithm,
+  SubtleCrypto,
+};
@@ -277,12 +277,16 @@ deno_core::extension!(deno_node,
     ops::crypto::op_node_create_cipheriv,
     ops::crypto::op_node_create_decipheriv,
     ops::crypto::op_node_create_hash,
+    ops::crypto::digest::op_node_crypto_hash,
+    ops::crypto::digest::op_node_crypto_hash_str,
+    ops::crypto::op_node_decapsulate,
     ops::crypto::op_node_decipheriv_decrypt,
     ops::crypto::op_node_decipheriv_final,
     ops::crypto::op_node_decipheriv_set_aad,
     ops::crypto::op_node_decipheriv_auth_tag,
     ops::crypto::op_node_dh_compute_secret,
     ops::crypto::op_node_diffie_hellman,
+    ops::crypto::op_node_encapsulate,
     ops::crypto::op_node_ecdh_compute_public_key,
     ops::crypto::op_node_ecdh_compute_secret,
     ops::crypto::op_node_ecdh_encode_pubkey,
@@ -291,13 +295,47 @@ deno_core::extension!(deno_node,
     ops::crypto::op_node_fill_random,
     ops::crypto::op_node_gen_prime_async,
     ops::crypto::op_node_gen_prime,
+    ops::crypto::op_node_get_fips,
+    ops::crypto::op_node_get_hash_block_size,
     ops::crypto::op_node_get_hash_size,
     ops::crypto::op_node_get_hashes,
     ops::crypto::op_node_hash_clone,
+    ops::crypto::op_node_hash_export_state,
+    ops::crypto::op_node_hash_import_state,
+    ops::crypto::op_node_hash_digest_encoded,
     ops::crypto::op_node_hash_digest_hex,
     ops::crypto::op_node_hash_digest,
+    ops::crypto::digest::op_node_hash_file<P>,
+    ops::crypto::digest::op_node_hash_verify,
+    ops::crypto::digest::op_node_hash_verify_str,
+    ops::crypto::op_node_hash_reset,
+    ops::crypto::op_node_hash_squeeze,
+    ops::crypto::op_node_hash_read_resource,
     ops::crypto::op_node_hash_update_str,
+    ops::crypto::op_node_hash_update_str_encoded,
+    ops::crypto::op_node_hash_update_async,
+    ops::crypto::op_node_hash_update_vectored,
     ops::crypto::op_node_hash_update,
+    ops::crypto::hmac::op_node_create_hmac,
+    ops::crypto::hmac::op_node_create_hmac_from_key,
+    ops::crypto::hmac::op_node_hmac_batch,
+    ops::crypto::hmac::op_node_hmac_clone,
+    ops::crypto::hmac::op_node_hmac_digest,
+    ops::crypto::hmac::op_node_hmac_oneshot,
+    ops::crypto::hmac::op_node_hmac_oneshot_encoded,
+    ops::crypto::hmac::op_node_hmac_update,
+    ops::crypto::hmac::op_node_hmac_update_str,
+    ops::crypto::hmac::op_node_hmac_validate,
+    ops::crypto::hmac::op_node_hmac_verify,
+    ops::crypto::hmac::op_node_hmac_verify_str,
+    ops::crypto::hmac::op_node_hmac_verify_with_key,
+    ops::crypto::hmac::op_node_hmac_verify_with_key_str,
+    ops::crypto::op_node_create_multi_hash,
+    ops::crypto::op_node_multi_hash_digest,
+    ops::crypto::op_node_multi_hash_update,
+    ops::crypto::op_node_multi_hash_update_str,
+    ops::crypto::op_node_set_fips,
+    ops::crypto::op_node_set_legacy_provider,
     ops::crypto::op_node_hkdf_async,
     ops::crypto::op_node_hkdf,
     ops::crypto::op_node_pbkdf2_async,
@@ -305,31 +343,50 @@ deno_core::extension!(deno_node,
     ops::crypto::op_node_pbkdf2_validate,
     ops::crypto::op_node_private_decrypt,
     ops::crypto::op_node_private_encrypt,
+    ops::crypto::op_node_public_decrypt,
     ops::crypto::op_node_public_encrypt,
     ops::crypto::op_node_random_int,
+    ops::crypto::op_node_recover_public_key,
     ops::crypto::op_node_scrypt_async,
     ops::crypto::op_node_scrypt_sync,
     ops::crypto::op_node_sign,
     ops::crypto::op_node_sign_ed25519,
+    ops::crypto::op_node_sign_ed448,
+    ops::crypto::op_node_sign_ml_dsa,
+    ops::crypto::op_node_sign_slh_dsa,
+    ops::crypto::op_node_sign_recoverable,
+    ops::crypto::op_node_sign_sm2,
+    ops::crypto::op_node_timing_safe_equal,
     ops::crypto::op_node_verify,
     ops::crypto::op_node_verify_ed25519,
+    ops::crypto::op_node_verify_ed448,
+    ops::crypto::op_node_verify_ml_dsa,
+    ops::crypto::op_node_verify_slh_dsa,
+    ops::crypto::op_node_verify_sm2,
     ops::crypto::op_node_verify_spkac,
     ops::crypto::op_node_cert_export_public_key,
     ops::crypto::op_node_cert_export_challenge,
     ops::crypto::keys::op_node_create_private_key,
-    ops::crypto::keys::op_node_create_ed_raw,
+    ops::crypto::keys::op_node_create_okp_jwk,
     ops::crypto::keys::op_node_create_rsa_jwk,
     ops::crypto::keys::op_node_create_ec_jwk,
+    ops::crypto::keys::op_node_create_key_object_from_crypto_key,
+    ops::crypto::keys::op_node_create_ml_dsa_raw,
+    ops::crypto::keys::op_node_create_ml_kem_raw,
     ops::crypto::keys::op_node_create_public_key,
     ops::crypto::keys::op_node_create_secret_key,
+    ops::crypto::keys::op_node_create_secret_key_from_string,
     ops::crypto::keys::op_node_derive_public_key_from_private_key,
     ops::crypto::keys::op_node_dh_keys_generate_and_export,
+    ops::crypto::keys::op_node_export_crypto_key_data,
     ops::crypto::keys::op_node_export_private_key_der,
     ops::crypto::keys::op_node_export_private_key_jwk,
     ops::crypto::keys::op_node_export_private_key_pem,
+    ops::crypto::keys::op_node_export_private_key_raw,
     ops::crypto::keys::op_node_export_public_key_der,
     ops::crypto::keys::op_node_export_public_key_pem,
     ops::crypto::keys::op_node_export_public_key_jwk,
+    ops::crypto::keys::op_node_export_public_key_raw,
     ops::crypto::keys::op_node_export_secret_key_b64url,
     ops::crypto::keys::op_node_export_secret_key,
     ops::crypto::keys::op_node_generate_dh_group_key_async,
@@ -342,6 +399,14 @@ deno_core::extension!(deno_node,
     ops::crypto::keys::op_node_generate_ec_key,
     ops::crypto::keys::op_node_generate_ed25519_key_async,
     ops::crypto::keys::op_node_generate_ed25519_key,
+    ops::crypto::keys::op_node_generate_ed448_key_async,
+    ops::crypto::keys::op_node_generate_ed448_key,
+    ops::crypto::keys::op_node_generate_ml_dsa_key_async,
+    ops::crypto::keys::op_node_generate_ml_dsa_key,
+    ops::crypto::keys::op_node_generate_ml_kem_key_async,
+    ops::crypto::keys::op_node_generate_ml_kem_key,
+    ops::crypto::keys::op_node_generate_slh_dsa_key_async,
+    ops::crypto::keys::op_node_generate_slh_dsa_key,
     ops::crypto::keys::op_node_generate_rsa_key_async,
     ops::crypto::keys::op_node_generate_rsa_key,
     ops::crypto::keys::op_node_generate_rsa_pss_key,
@@ -350,26 +415,36 @@ deno_core::extension!(deno_node,
     ops::crypto::keys::op_node_generate_secret_key,
     ops::crypto::keys::op_node_generate_x25519_key_async,
     ops::crypto::keys::op_node_generate_x25519_key,
+    ops::crypto::keys::op_node_generate_x448_key_async,
+    ops::crypto::keys::op_node_generate_x448_key,
     ops::crypto::keys::op_node_get_asymmetric_key_details,
     ops::crypto::keys::op_node_get_asymmetric_key_type,
     ops::crypto::keys::op_node_get_private_key_from_pair,
     ops::crypto::keys::op_node_get_public_key_from_pair,
     ops::crypto::keys::op_node_get_symmetric_key_size,
+    ops::crypto::keys::op_node_key_equals,
     ops::crypto::keys::op_node_key_type,
     ops::crypto::x509::op_node_x509_parse,
     ops::crypto::x509::op_node_x509_ca,
     ops::crypto::x509::op_node_x509_check_email,
     ops::crypto::x509::op_node_x509_check_host,
+    ops::crypto::x509::op_node_x509_check_ip,
+    ops::crypto::x509::op_node_x509_check_issued,
     ops::crypto::x509::op_node_x509_fingerprint,
     ops::crypto::x509::op_node_x509_fingerprint256,
     ops::crypto::x509::op_node_x509_fingerprint512,
+    ops::crypto::x509::op_node_x509_fingerprint_with,
     ops::crypto::x509::op_node_x509_get_issuer,
     ops::crypto::x509::op_node_x509_get_subject,
     ops::crypto::x509::op_node_x509_get_valid_from,
     ops::crypto::x509::op_node_x509_get_valid_to,
     ops::crypto::x509::op_node_x509_get_serial_number,
-    ops::crypto::x509::op_node_x509_key_usage,
+    ops::crypto::x509::op_node_x509_ext_key_usage,
+    ops::crypto::x509::op_node_x509_get_info_access,
+    ops::crypto::x509::op_node_x509_get_subject_alt_name,
     ops::crypto::x509::op_node_x509_public_key,
+    ops::crypto::x509::op_node_x509_to_legacy_object,
+    ops::crypto::x509::op_node_x509_verify,
     ops::dns::op_node_getaddrinfo<P>,
     ops::fs::op_node_fs_exists_sync<P>,
     ops::fs::op_node_fs_exists<P>,
@@ -514,6 +589,8 @@ deno_core::extension!(deno_node,
     ops::handle_wrap::HandleWrap,
     ops::sqlite::StatementSync,
     ops::crypto::digest::Hasher,
+    ops::crypto::digest::MultiHasher,
+    ops::crypto::hmac::Hmac,
   ],
   esm_entry_point = "ext:deno_node/02_init.js",
   esm = [
@@ -632,6 +709,7 @@ deno_core::extension!(deno_node,
     "internal/crypto/hash.ts",
     "internal/crypto/hkdf.ts",
     "internal/crypto/keygen.ts",
+    "internal/crypto/kem.ts",
     "internal/crypto/keys.ts",
     "internal/crypto/pbkdf2.ts",
     "internal/crypto/random.ts",
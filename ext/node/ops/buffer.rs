@@ -34,6 +34,46 @@ pub fn op_transcode(
   }
 }
 
+/// Decodes hex as `Buffer.from(string, "hex")` does: pairs of digits up to
+/// the first invalid pair, ignoring a trailing odd digit.
+pub fn decode_hex_lenient(encoded: &[u8]) -> Vec<u8> {
+  encoded
+    .chunks_exact(2)
+    .map_while(|pair| {
+      let high = (pair[0] as char).to_digit(16)?;
+      let low = (pair[1] as char).to_digit(16)?;
+      Some((high << 4 | low) as u8)
+    })
+    .collect()
+}
+
+/// Decodes base64 or base64url as `Buffer.from(string, "base64")` does:
+/// characters outside both alphabets are skipped, and the first `=` ends
+/// the input.
+pub fn decode_base64_lenient(encoded: &[u8]) -> Vec<u8> {
+  let mut decoded = Vec::with_capacity(encoded.len() / 4 * 3 + 2);
+  let mut bits = 0u32;
+  let mut bit_count = 0;
+  for &c in encoded {
+    let value = match c {
+      b'A'..=b'Z' => c - b'A',
+      b'a'..=b'z' => c - b'a' + 26,
+      b'0'..=b'9' => c - b'0' + 52,
+      b'+' | b'-' => 62,
+      b'/' | b'_' => 63,
+      b'=' => break,
+      _ => continue,
+    };
+    bits = (bits << 6 | value as u32) & 0xfff;
+    bit_count += 6;
+    if bit_count >= 8 {
+      bit_count -= 8;
+      decoded.push((bits >> bit_count) as u8);
+    }
+  }
+  decoded
+}
+
 fn latin1_ascii_to_utf16le(source: &[u8]) -> Vec<u8> {
   let mut result = Vec::with_capacity(source.len() * 2);
   for &byte in source {
@@ -119,3 +159,42 @@ fn utf8_to_ascii(source: &[u8]) -> Vec<u8> {
   }
   ascii_bytes
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  // Each output matches `Buffer.from(input, encoding)` in Node.js.
+  #[test]
+  fn lenient_decoding() {
+    for (input, expected) in [
+      ("zz", ""),
+      ("abc", "ab"),
+      ("ab zz cd", "ab"),
+      ("0g12", ""),
+      ("ABCD", "abcd"),
+    ] {
+      assert_eq!(
+        faster_hex::hex_string(&decode_hex_lenient(input.as_bytes())),
+        expected,
+        "{input}"
+      );
+    }
+    for (input, expected) in [
+      ("aGVsbG8", "68656c6c6f"),
+      ("aGVs bG8=", "68656c6c6f"),
+      ("aGVs\nbG8=", "68656c6c6f"),
+      ("aG!Vs", "68656c"),
+      ("a-_b", "6befdb"),
+      ("aGVsbG8=extra", "68656c6c6f"),
+      ("a", ""),
+      ("ab=c", "69"),
+    ] {
+      assert_eq!(
+        faster_hex::hex_string(&decode_base64_lenient(input.as_bytes())),
+        expected,
+        "{input}"
+      );
+    }
+  }
+}
@@ -197,6 +197,10 @@ pub enum CipherError {
   #[class(type)]
   #[error("Unknown cipher {0}")]
   UnknownCipher(String),
+  #[class(generic)]
+  #[error("Cipher not allowed in FIPS mode: {0}")]
+  #[property("code" = "ERR_OSSL_EVP_UNSUPPORTED")]
+  NotFipsApproved(String),
 }
 
 impl Cipher {
@@ -206,6 +210,9 @@ impl Cipher {
     iv: &[u8],
   ) -> Result<Self, CipherError> {
     use Cipher::*;
+    if !super::fips::cipher_allowed(algorithm_name) {
+      return Err(CipherError::NotFipsApproved(algorithm_name.to_string()));
+    }
     Ok(match algorithm_name {
       "aes128" | "aes-128-cbc" => {
         Aes128Cbc(Box::new(cbc::Encryptor::new(key.into(), iv.into())))
@@ -467,6 +474,9 @@ pub enum DecipherError {
   #[class(type)]
   #[error("Unknown cipher {0}")]
   UnknownCipher(String),
+  #[class(generic)]
+  #[error("Cipher not allowed in FIPS mode: {0}")]
+  NotFipsApproved(String),
 }
 
 impl DecipherError {
@@ -484,6 +494,9 @@ impl DecipherError {
       Self::InvalidFinalBlockLength => deno_error::PropertyValue::String(
         "ERR_OSSL_WRONG_FINAL_BLOCK_LENGTH".into(),
       ),
+      Self::NotFipsApproved(_) => {
+        deno_error::PropertyValue::String("ERR_OSSL_EVP_UNSUPPORTED".into())
+      }
       _ => deno_error::PropertyValue::String("ERR_CRYPTO_DECIPHER".into()),
     }
   }
@@ -518,6 +531,9 @@ impl Decipher {
     auth_tag_length: Option<usize>,
   ) -> Result<Self, DecipherError> {
     use Decipher::*;
+    if !super::fips::cipher_allowed(algorithm_name) {
+      return Err(DecipherError::NotFipsApproved(algorithm_name.to_string()));
+    }
     Ok(match algorithm_name {
       "aes-128-cbc" => {
         Aes128Cbc(Box::new(cbc::Decryptor::new(key.into(), iv.into())))
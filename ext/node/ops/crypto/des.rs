@@ -0,0 +1,93 @@
+// Copyright 2018-2025 the Deno authors. MIT license.
+
+//! DES and triple DES (EDE, with two or three keys) in CBC mode, for the
+//! legacy private key encryption that OpenSSL still reads.
+
+use aes::cipher::BlockDecryptMut as _;
+use aes::cipher::KeyIvInit as _;
+use aes::cipher::block_padding::Pkcs7;
+
+/// Decrypts with DES for an 8 byte key, or triple DES for a 16 or 24 byte
+/// one, and removes the PKCS#7 padding. `None` if a length or the padding
+/// is wrong.
+pub fn decrypt_cbc(
+  key: &[u8],
+  iv: &[u8],
+  ciphertext: &[u8],
+) -> Option<Vec<u8>> {
+  match key.len() {
+    8 => cbc::Decryptor::<des::Des>::new_from_slices(key, iv)
+      .ok()?
+      .decrypt_padded_vec_mut::<Pkcs7>(ciphertext),
+    16 => cbc::Decryptor::<des::TdesEde2>::new_from_slices(key, iv)
+      .ok()?
+      .decrypt_padded_vec_mut::<Pkcs7>(ciphertext),
+    24 => cbc::Decryptor::<des::TdesEde3>::new_from_slices(key, iv)
+      .ok()?
+      .decrypt_padded_vec_mut::<Pkcs7>(ciphertext),
+    _ => return None,
+  }
+  .ok()
+}
+
+#[cfg(test)]
+mod tests {
+  use aes::cipher::BlockEncryptMut as _;
+  use aes::cipher::KeyInit as _;
+  use aes::cipher::block_padding::NoPadding;
+
+  use super::*;
+
+  fn hex(s: &str) -> Vec<u8> {
+    (0..s.len())
+      .step_by(2)
+      .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+      .collect()
+  }
+
+  // The worked example of "The DES Algorithm Illustrated", which
+  // `openssl enc -des-ecb -nopad` agrees with.
+  #[test]
+  fn des_block() {
+    let key = hex("133457799bbcdff1");
+    let ciphertext =
+      ecb::Encryptor::<des::Des>::new_from_slice(&key)
+        .unwrap()
+        .encrypt_padded_vec_mut::<NoPadding>(&hex("0123456789abcdef"));
+    assert_eq!(ciphertext, hex("85e813540f0ab405"));
+    let plaintext = ecb::Decryptor::<des::Des>::new_from_slice(&key)
+      .unwrap()
+      .decrypt_padded_vec_mut::<NoPadding>(&ciphertext)
+      .unwrap();
+    assert_eq!(plaintext, hex("0123456789abcdef"));
+  }
+
+  // Encrypted with `openssl enc -des-cbc`, `-des-ede-cbc` and `-des-ede3-cbc`.
+  #[test]
+  fn openssl_vectors() {
+    let iv = hex("0001020304050607");
+    for (key, ciphertext) in [
+      (
+        "0123456789abcdef",
+        "b0b609f7167998c07947ffad924414a8aabe241c3abc1348",
+      ),
+      (
+        "0123456789abcdeffedcba9876543210",
+        "4d28774dd3eff6330e1ceb3a5a5afdaad11b8c5f2a6d36ee",
+      ),
+      (
+        "0123456789abcdeffedcba987654321089abcdef01234567",
+        "9b819c6a68c2c303fb82e8deb35e68bb800ebfbe3bc2be98",
+      ),
+    ] {
+      assert_eq!(
+        decrypt_cbc(&hex(key), &iv, &hex(ciphertext)).unwrap(),
+        b"The quick brown fox"
+      );
+    }
+    let key = hex("0123456789abcdef");
+    assert!(decrypt_cbc(&key, &iv, &hex("b0b609f7167998c0")).is_none());
+    assert!(decrypt_cbc(&key, &iv, &[]).is_none());
+    assert!(decrypt_cbc(&key[..7], &iv, &hex("b0b609f7167998c0")).is_none());
+  }
+}
@@ -62,10 +62,10 @@ impl DiffieHellman {
   where
     G: DiffieHellmanGroup,
   {
-    let private_key = PrivateKey::new(G::EXPONENT_SIZE / 8);
+    let private_key = PrivateKey::new(G::EXPONENT_SIZE);
 
     let generator = BigUint::from_usize(G::GENERATOR).unwrap();
-    let modulus = BigUint::from_slice(G::MODULUS);
+    let modulus = G::modulus();
 
     let public_key = private_key.compute_public_key(&generator, &modulus);
 
@@ -100,6 +100,15 @@ pub trait DiffieHellmanGroup {
   const MODULUS: &'static [u32];
   /// Size of the exponent in bits
   const EXPONENT_SIZE: usize;
+
+  /// The modulus, whose words `MODULUS` lists most significant first.
+  fn modulus() -> BigUint {
+    let bytes = Self::MODULUS
+      .iter()
+      .flat_map(|word| word.to_be_bytes())
+      .collect::<Vec<_>>();
+    BigUint::from_bytes_be(&bytes)
+  }
 }
 
 /// 1536-bit MODP Group
@@ -1,18 +1,59 @@
 // Copyright 2018-2025 the Deno authors. MIT license.
+use std::borrow::Cow;
 use std::cell::RefCell;
+use std::future::Future;
+use std::io::Read;
+use std::path::Path;
 use std::rc::Rc;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
 
+use base64::Engine;
+use base64::prelude::BASE64_STANDARD;
+use base64::prelude::BASE64_STANDARD_NO_PAD;
+use base64::prelude::BASE64_URL_SAFE_NO_PAD;
 use deno_core::GarbageCollected;
+use deno_core::JsBuffer;
+use deno_core::OpState;
+use deno_core::ToJsBuffer;
 use deno_core::op2;
+use deno_core::unsync::spawn_blocking;
 use digest::Digest;
 use digest::DynDigest;
 use digest::ExtendableOutput;
+use digest::Mac;
+use digest::OutputSizeUser;
 use digest::Update;
+use digest::VariableOutput;
+use digest::XofReader;
+use digest::core_api::BlockSizeUser;
+use digest::typenum::Unsigned;
 
+use super::wipe::Wiped;
+use crate::NodePermissions;
+
+mod blake2_params;
+mod kmac;
+mod resumable;
 mod ring_sha2;
+mod siphash;
 
 pub struct Hasher {
   pub hash: Rc<RefCell<Option<Hash>>>,
+  /// Set once an XOF starts being squeezed; `hash` is consumed at that point.
+  xof_reader: RefCell<Option<Box<dyn XofReader>>>,
+  /// What `reset()` rebuilds the hash from.
+  initial: HasherInit,
+}
+
+enum HasherInit {
+  Unkeyed {
+    algorithm: &'static DigestInfo,
+    output_length: Option<usize>,
+  },
+  /// Keyed and customized hashes can't be rebuilt without retaining their
+  /// parameters, so hold on to their freshly initialized state instead.
+  Initialized(Hash),
 }
 
 impl GarbageCollected for Hasher {
@@ -35,14 +76,90 @@ impl Hasher {
   pub fn new(
     algorithm: &str,
     output_length: Option<usize>,
+    key: Option<&[u8]>,
   ) -> Result<Self, HashError> {
-    let hash = Hash::new(algorithm, output_length)?;
+    let info = DigestInfo::lookup_or_err(algorithm)?;
+    let hash = Hash::from_info(info, output_length, key)?;
+    let initial = match key {
+      Some(_) => HasherInit::Initialized(hash.clone_hash(None)?),
+      None => HasherInit::Unkeyed {
+        algorithm: info,
+        output_length,
+      },
+    };
 
     Ok(Self {
       hash: Rc::new(RefCell::new(Some(hash))),
+      xof_reader: RefCell::new(None),
+      initial,
     })
   }
 
+  /// Creates a cSHAKE hash with the given function name and customization
+  /// string, or a KMAC with the given key and customization string (NIST
+  /// SP 800-185).
+  pub fn new_customized(
+    algorithm: &str,
+    output_length: Option<usize>,
+    key: Option<&[u8]>,
+    function_name: &[u8],
+    customization: &[u8],
+  ) -> Result<Self, HashError> {
+    let info = DigestInfo::lookup_or_err(algorithm)?;
+    let hash = Hash::new_customized(
+      info,
+      output_length,
+      key,
+      function_name,
+      customization,
+    )?;
+    Ok(Self {
+      initial: HasherInit::Initialized(hash.clone_hash(None)?),
+      hash: Rc::new(RefCell::new(Some(hash))),
+      xof_reader: RefCell::new(None),
+    })
+  }
+
+  /// Creates a BLAKE2 hash with a salt and personalization string, either
+  /// of which may be empty, byte-compatible with libsodium's
+  /// `crypto_generichash_blake2b_salt_personal`.
+  pub fn new_salted(
+    algorithm: &str,
+    output_length: Option<usize>,
+    key: Option<&[u8]>,
+    salt: &[u8],
+    personalization: &[u8],
+  ) -> Result<Self, HashError> {
+    let info = DigestInfo::lookup_or_err(algorithm)?;
+    let hash = Hash::new_salted(
+      info,
+      output_length,
+      key.unwrap_or_default(),
+      salt,
+      personalization,
+    )?;
+    Ok(Self {
+      initial: HasherInit::Initialized(hash.clone_hash(None)?),
+      hash: Rc::new(RefCell::new(Some(hash))),
+      xof_reader: RefCell::new(None),
+    })
+  }
+
+  /// Restores the hash to its freshly created state, discarding any input
+  /// and making a finalized hasher usable again.
+  pub fn reset(&self) -> Result<(), HashError> {
+    let hash = match &self.initial {
+      HasherInit::Unkeyed {
+        algorithm,
+        output_length,
+      } => Hash::from_info(algorithm, *output_length, None)?,
+      HasherInit::Initialized(hash) => hash.clone_hash(None)?,
+    };
+    *self.xof_reader.borrow_mut() = None;
+    *self.hash.borrow_mut() = Some(hash);
+    Ok(())
+  }
+
   pub fn update(&self, data: &[u8]) -> bool {
     if let Some(hash) = self.hash.borrow_mut().as_mut() {
       hash.update(data);
@@ -52,11 +169,95 @@ impl Hasher {
     }
   }
 
+  /// Moves the hash to the blocking pool to absorb `data` there, and puts
+  /// it back when done. Until then the hasher looks finalized, so callers
+  /// must not use it concurrently.
+  pub fn update_async(
+    &self,
+    data: JsBuffer,
+  ) -> impl Future<Output = Result<bool, tokio::task::JoinError>> + use<> {
+    let slot = self.hash.clone();
+    let hash = slot.borrow_mut().take();
+    async move {
+      let Some(mut hash) = hash else {
+        return Ok(false);
+      };
+      let hash = spawn_blocking(move || {
+        hash.update(&data);
+        hash
+      })
+      .await?;
+      *slot.borrow_mut() = Some(hash);
+      Ok(true)
+    }
+  }
+
   pub fn digest(&self) -> Option<Box<[u8]>> {
     let hash = self.hash.borrow_mut().take()?;
     Some(hash.digest_and_drop())
   }
 
+  /// Reads the next `length` bytes of XOF output. The first call finalizes
+  /// the hash, so later `update()` and `digest()` calls fail.
+  pub fn squeeze(&self, length: usize) -> Result<Option<Box<[u8]>>, HashError> {
+    let mut xof_reader = self.xof_reader.borrow_mut();
+    let reader = match &mut *xof_reader {
+      Some(reader) => reader,
+      None => {
+        let mut hash = self.hash.borrow_mut();
+        let reader: Box<dyn XofReader> = match hash.take() {
+          Some(Shake128(context, _)) => Box::new(context.finalize_xof()),
+          Some(Shake256(context, _)) => Box::new(context.finalize_xof()),
+          Some(CShake128(context, _)) => Box::new(context.finalize_xof()),
+          Some(CShake256(context, _)) => Box::new(context.finalize_xof()),
+          // Squeezing a KMAC yields KMACXOF output.
+          Some(Kmac128(context, _)) => Box::new(context.finalize_xof()),
+          Some(Kmac256(context, _)) => Box::new(context.finalize_xof()),
+          Some(other) => {
+            *hash = Some(other);
+            return Err(HashError::SqueezeUnsupported);
+          }
+          None => return Ok(None),
+        };
+        xof_reader.insert(reader)
+      }
+    };
+    let mut output = vec![0; length].into_boxed_slice();
+    reader.read(&mut output);
+    Ok(Some(output))
+  }
+
+  /// Finalizes the hash and encodes the digest. Returns `None` without
+  /// consuming the hash if `encoding` isn't handled here, so the caller
+  /// can fall back to the raw digest.
+  pub fn digest_encoded(&self, encoding: &str) -> Option<String> {
+    let encoding = DigestEncoding::parse(encoding)?;
+    let digest = self.digest()?;
+    Some(encoding.encode(&digest))
+  }
+
+  /// Returns `None` once the hash has been finalized.
+  pub fn export_state(&self) -> Result<Option<Vec<u8>>, HashError> {
+    self
+      .hash
+      .borrow()
+      .as_ref()
+      .map(Hash::export_state)
+      .transpose()
+  }
+
+  pub fn import_state(state: &[u8]) -> Result<Self, HashError> {
+    let (hash, algorithm) = Hash::import_state(state)?;
+    Ok(Self {
+      hash: Rc::new(RefCell::new(Some(hash))),
+      xof_reader: RefCell::new(None),
+      initial: HasherInit::Unkeyed {
+        algorithm,
+        output_length: None,
+      },
+    })
+  }
+
   pub fn clone_inner(
     &self,
     output_length: Option<usize>,
@@ -65,13 +266,276 @@ impl Hasher {
     let Some(hash) = hash.as_ref() else {
       return Ok(None);
     };
+    let is_xof = matches!(
+      hash,
+      Shake128(..)
+        | Shake256(..)
+        | CShake128(..)
+        | CShake256(..)
+        | Kmac128(..)
+        | Kmac256(..)
+        | Blake3(..)
+    );
     let hash = hash.clone_hash(output_length)?;
+    let initial = match &self.initial {
+      HasherInit::Unkeyed {
+        algorithm,
+        output_length: initial_length,
+      } => HasherInit::Unkeyed {
+        algorithm,
+        output_length: if is_xof {
+          output_length.or(*initial_length)
+        } else {
+          *initial_length
+        },
+      },
+      HasherInit::Initialized(hash) => {
+        HasherInit::Initialized(hash.clone_hash(output_length)?)
+      }
+    };
     Ok(Some(Self {
       hash: Rc::new(RefCell::new(Some(hash))),
+      xof_reader: RefCell::new(None),
+      initial,
     }))
   }
 }
 
+/// Several hashes fed the same input, so that each chunk crosses into Rust
+/// once however many digests of it are needed.
+pub struct MultiHasher {
+  hashes: RefCell<Option<Vec<Hash>>>,
+}
+
+impl GarbageCollected for MultiHasher {
+  fn get_name(&self) -> &'static std::ffi::CStr {
+    c"MultiHasher"
+  }
+}
+
+#[op2]
+impl MultiHasher {
+  #[constructor]
+  #[cppgc]
+  fn create(_: bool) -> MultiHasher {
+    unreachable!()
+  }
+}
+
+impl MultiHasher {
+  /// Fails on the first unsupported algorithm.
+  pub fn new(algorithms: &[String]) -> Result<Self, HashError> {
+    let hashes = algorithms
+      .iter()
+      .map(|algorithm| Hash::new(algorithm, None, None))
+      .collect::<Result<_, _>>()?;
+    Ok(Self {
+      hashes: RefCell::new(Some(hashes)),
+    })
+  }
+
+  pub fn update(&self, data: &[u8]) -> bool {
+    let mut hashes = self.hashes.borrow_mut();
+    let Some(hashes) = hashes.as_mut() else {
+      return false;
+    };
+    for hash in hashes {
+      hash.update(data);
+    }
+    true
+  }
+
+  /// The digests in the order the algorithms were given.
+  pub fn digest(&self) -> Option<Vec<Box<[u8]>>> {
+    let hashes = self.hashes.borrow_mut().take()?;
+    Some(hashes.into_iter().map(Hash::digest_and_drop).collect())
+  }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DigestEncoding {
+  Hex,
+  Base64,
+  Base64Url,
+  Latin1,
+}
+
+impl DigestEncoding {
+  pub fn parse(encoding: &str) -> Option<Self> {
+    match encoding {
+      "hex" => Some(Self::Hex),
+      "base64" => Some(Self::Base64),
+      "base64url" => Some(Self::Base64Url),
+      "latin1" | "binary" => Some(Self::Latin1),
+      _ => None,
+    }
+  }
+
+  pub fn encode(self, digest: &[u8]) -> String {
+    match self {
+      Self::Hex => faster_hex::hex_string(digest),
+      Self::Base64 => BASE64_STANDARD.encode(digest),
+      // Node.js omits the padding for base64url
+      Self::Base64Url => BASE64_URL_SAFE_NO_PAD.encode(digest),
+      Self::Latin1 => digest.iter().map(|&b| b as char).collect(),
+    }
+  }
+
+  /// The inverse of `encode`, with optional padding for base64. Returns
+  /// `None` if `encoded` is malformed.
+  pub fn decode(self, encoded: &str) -> Option<Vec<u8>> {
+    match self {
+      Self::Hex => {
+        let mut decoded = vec![0; encoded.len() / 2];
+        faster_hex::hex_decode(encoded.as_bytes(), &mut decoded).ok()?;
+        Some(decoded)
+      }
+      Self::Base64 => BASE64_STANDARD_NO_PAD
+        .decode(encoded.trim_end_matches('='))
+        .ok(),
+      Self::Base64Url => BASE64_URL_SAFE_NO_PAD
+        .decode(encoded.trim_end_matches('='))
+        .ok(),
+      Self::Latin1 => encoded.chars().map(|c| u8::try_from(c).ok()).collect(),
+    }
+  }
+}
+
+/// A string encoding that `op_node_hash_update_str_encoded` can read out of
+/// a V8 string without going through a `Buffer`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StringEncoding {
+  Utf8,
+  Latin1,
+  Utf16Le,
+}
+
+impl StringEncoding {
+  /// Takes the names `normalizeEncoding()` returns.
+  pub fn parse(encoding: &str) -> Option<Self> {
+    match encoding {
+      "utf8" => Some(Self::Utf8),
+      "latin1" => Some(Self::Latin1),
+      "utf16le" => Some(Self::Utf16Le),
+      _ => None,
+    }
+  }
+}
+
+/// One-shot digest for `crypto.hash()`. Returns `None` for unknown
+/// algorithms. The boxed digest becomes the backing store of a new
+/// `ArrayBuffer`, so JS can view it without copying.
+#[op2]
+#[buffer]
+pub fn op_node_crypto_hash(
+  #[string] algorithm: &str,
+  #[anybuffer] data: &[u8],
+) -> Option<Box<[u8]>> {
+  Hash::digest_oneshot(algorithm, data)
+}
+
+#[op2]
+#[buffer]
+pub fn op_node_crypto_hash_str(
+  #[string] algorithm: &str,
+  #[string] data: &str,
+) -> Option<Box<[u8]>> {
+  Hash::digest_oneshot(algorithm, data.as_bytes())
+}
+
+/// Hashes `data` and compares the digest with `expected` in constant time,
+/// without handing the digest to JS. A mismatch is not an error.
+#[op2]
+pub fn op_node_hash_verify(
+  #[string] algorithm: &str,
+  #[anybuffer] data: &[u8],
+  #[anybuffer] expected: &[u8],
+) -> Result<bool, HashError> {
+  Hash::verify_oneshot(algorithm, data, expected)
+}
+
+/// Like `op_node_hash_verify`, with the expected digest encoded as a
+/// string. One that doesn't decode never matches.
+#[op2]
+pub fn op_node_hash_verify_str(
+  #[string] algorithm: &str,
+  #[anybuffer] data: &[u8],
+  #[string] expected: &str,
+  #[string] encoding: &str,
+) -> Result<bool, HashError> {
+  let encoding = DigestEncoding::parse(encoding)
+    .ok_or_else(|| HashError::UnsupportedEncoding(encoding.to_string()))?;
+  let Some(expected) = encoding.decode(expected) else {
+    DigestInfo::lookup_or_err(algorithm)?;
+    return Ok(false);
+  };
+  Hash::verify_oneshot(algorithm, data, &expected)
+}
+
+#[derive(Debug, thiserror::Error, deno_error::JsError)]
+pub enum HashFileError {
+  #[class(inherit)]
+  #[error(transparent)]
+  Permission(#[from] deno_permissions::PermissionCheckError),
+  #[class(inherit)]
+  #[error(transparent)]
+  Hash(#[from] HashError),
+  #[class(inherit)]
+  #[error("{0}")]
+  Io(
+    #[from]
+    #[inherit]
+    std::io::Error,
+  ),
+  #[class(inherit)]
+  #[error(transparent)]
+  Join(#[from] tokio::task::JoinError),
+}
+
+/// Streams the file at `path` through the digest on the blocking pool, so
+/// the contents never have to cross into JS. This reads the real file system
+/// directly since `FileSystem` handles can't be moved off the main thread.
+#[op2(async, stack_trace)]
+#[serde]
+pub async fn op_node_hash_file<P>(
+  state: Rc<RefCell<OpState>>,
+  #[string] algorithm: String,
+  #[string] path: String,
+  output_length: Option<u32>,
+) -> Result<ToJsBuffer, HashFileError>
+where
+  P: NodePermissions + 'static,
+{
+  let path = state
+    .borrow_mut()
+    .borrow_mut::<P>()
+    .check_read_with_api_name(&path, Some("node:crypto.hashFile()"))?;
+  let output_length = output_length.map(|length| length as usize);
+  spawn_blocking(move || {
+    hash_file_sync(&algorithm, &path, output_length).map(Into::into)
+  })
+  .await?
+}
+
+fn hash_file_sync(
+  algorithm: &str,
+  path: &Path,
+  output_length: Option<usize>,
+) -> Result<Box<[u8]>, HashFileError> {
+  let mut hash = Hash::new(algorithm, output_length, None)?;
+  let mut file = std::fs::File::open(path)?;
+  let mut buf = vec![0; 64 * 1024];
+  loop {
+    match file.read(&mut buf) {
+      Ok(0) => break,
+      Ok(n) => hash.update(&buf[..n]),
+      Err(err) if err.kind() == std::io::ErrorKind::Interrupted => continue,
+      Err(err) => return Err(err.into()),
+    }
+  }
+  Ok(hash.digest_and_drop())
+}
+
 macro_rules! match_fixed_digest {
   ($algorithm_name:expr, fn <$type:ident>() $body:block, _ => $other:block) => {
     match $algorithm_name {
@@ -83,6 +547,34 @@ macro_rules! match_fixed_digest {
         type $type = ::blake2::Blake2s256;
         $body
       }
+      "whirlpool" => {
+        type $type = ::whirlpool::Whirlpool;
+        $body
+      }
+      "md_gost12_256" | "streebog256" => {
+        type $type = ::streebog::Streebog256;
+        $body
+      }
+      "md_gost12_512" | "streebog512" => {
+        type $type = ::streebog::Streebog512;
+        $body
+      }
+      "keccak224" => {
+        type $type = ::sha3::Keccak224;
+        $body
+      }
+      "keccak256" => {
+        type $type = ::sha3::Keccak256;
+        $body
+      }
+      "keccak384" => {
+        type $type = ::sha3::Keccak384;
+        $body
+      }
+      "keccak512" => {
+        type $type = ::sha3::Keccak512;
+        $body
+      }
       #[allow(dead_code)]
       _ => crate::ops::crypto::digest::match_fixed_digest_with_eager_block_buffer!($algorithm_name, fn <$type>() $body, _ => $other)
     }
@@ -97,6 +589,12 @@ macro_rules! match_fixed_digest_with_eager_block_buffer {
         type $type = ::sm3::Sm3;
         $body
       }
+      #[cfg(feature = "legacy_digests")]
+      "rsa-md2" | "md2" | "md2withrsaencryption" => {
+        type $type = ::md2::Md2;
+        $body
+      }
+      #[cfg(feature = "legacy_digests")]
       "rsa-md4" | "md4" | "md4withrsaencryption" => {
         type $type = ::md4::Md4;
         $body
@@ -105,6 +603,14 @@ macro_rules! match_fixed_digest_with_eager_block_buffer {
         type $type = crate::ops::crypto::md5_sha1::Md5Sha1;
         $body
       }
+      "ripemd256" | "rmd256" => {
+        type $type = ::ripemd::Ripemd256;
+        $body
+      }
+      "ripemd320" | "rmd320" => {
+        type $type = ::ripemd::Ripemd320;
+        $body
+      }
       _ => crate::ops::crypto::digest::match_fixed_digest_with_oid!($algorithm_name, fn <$type>() $body, _ => $other)
     }
   };
@@ -192,87 +698,931 @@ macro_rules! match_fixed_digest_with_oid {
 
 pub(crate) use match_fixed_digest_with_oid;
 
+/// OpenSSL's digest lookup is case-insensitive and also accepts the
+/// dashed `SHA-256` spelling. Only allocates if the name needs rewriting.
+fn normalize_algorithm_name(name: &str) -> Cow<'_, str> {
+  let mut name = if name.bytes().any(|b| b.is_ascii_uppercase()) {
+    Cow::Owned(name.to_ascii_lowercase())
+  } else {
+    Cow::Borrowed(name)
+  };
+  let suffix = match name.strip_prefix("sha-") {
+    Some("512/224") => Some("512-224"),
+    Some("512/256") => Some("512-256"),
+    Some(rest)
+      if !rest.is_empty() && rest.bytes().all(|b| b.is_ascii_digit()) =>
+    {
+      Some(rest)
+    }
+    _ => None,
+  };
+  if let Some(normalized) = suffix.map(|suffix| format!("sha{suffix}")) {
+    name = Cow::Owned(normalized);
+  }
+  name
+}
+
+/// OpenSSL 1.x names that OpenSSL 3 only accepts with the legacy provider
+/// loaded, and the digests they stand for. Keys are lowercase.
+const LEGACY_ALIASES: &[(&str, &str)] = &[
+  ("sha", "sha1"),
+  ("dss1", "sha1"),
+  ("dsaencryption", "sha1"),
+  ("dsa-sha", "sha1"),
+  ("dsa-sha1", "sha1"),
+  ("dsawithsha1", "sha1"),
+];
+
+fn legacy_alias_target(name: &str) -> Option<&'static str> {
+  LEGACY_ALIASES
+    .iter()
+    .find(|(alias, _)| alias.eq_ignore_ascii_case(name))
+    .map(|(_, target)| *target)
+}
+
+/// Object identifiers of the digests in `DIGESTS`, which OpenSSL also
+/// resolves by name. Only looked up, never listed by `getHashes()`.
+const DIGEST_OIDS: &[(&str, &str)] = &[
+  ("1.2.840.113549.2.2", "md2"),
+  ("1.2.840.113549.2.4", "md4"),
+  ("1.2.840.113549.2.5", "md5"),
+  ("1.3.36.3.2.1", "ripemd160"),
+  ("1.3.36.3.2.3", "ripemd256"),
+  ("1.3.14.3.2.26", "sha1"),
+  ("2.16.840.1.101.3.4.2.4", "sha224"),
+  ("2.16.840.1.101.3.4.2.1", "sha256"),
+  ("2.16.840.1.101.3.4.2.2", "sha384"),
+  ("2.16.840.1.101.3.4.2.3", "sha512"),
+  ("2.16.840.1.101.3.4.2.5", "sha512-224"),
+  ("2.16.840.1.101.3.4.2.6", "sha512-256"),
+  ("2.16.840.1.101.3.4.2.7", "sha3-224"),
+  ("2.16.840.1.101.3.4.2.8", "sha3-256"),
+  ("2.16.840.1.101.3.4.2.9", "sha3-384"),
+  ("2.16.840.1.101.3.4.2.10", "sha3-512"),
+  ("2.16.840.1.101.3.4.2.11", "shake128"),
+  ("2.16.840.1.101.3.4.2.12", "shake256"),
+  ("1.2.156.10197.1.401", "sm3"),
+  ("1.3.6.1.4.1.1722.12.2.1.16", "blake2b512"),
+  ("1.3.6.1.4.1.1722.12.2.2.8", "blake2s256"),
+  ("1.0.10118.3.0.55", "whirlpool"),
+  ("1.2.643.7.1.1.2.2", "streebog256"),
+  ("1.2.643.7.1.1.2.3", "streebog512"),
+];
+
+/// Resolves a dotted OID, or the ASN.1 `id-` name of a digest that has one
+/// (`id-sha256`, `id-sha3-256`), to the digest's canonical name.
+fn oid_digest_name(name: &str) -> Option<&'static str> {
+  if let Some(name) = name.strip_prefix("id-") {
+    return DIGEST_OIDS
+      .iter()
+      .find(|(_, digest)| digest.eq_ignore_ascii_case(name))
+      .map(|(_, digest)| *digest);
+  }
+  DIGEST_OIDS
+    .iter()
+    .find(|(oid, _)| *oid == name)
+    .map(|(_, digest)| *digest)
+}
+
+/// Maps a legacy alias to its digest if the legacy provider is enabled, for
+/// code that matches on digest names directly.
+pub fn resolve_legacy_alias(name: &str) -> &str {
+  resolve_alias(name, super::legacy_provider::enabled())
+}
+
+fn resolve_alias(name: &str, legacy_provider: bool) -> &str {
+  match legacy_alias_target(name) {
+    Some(target) if legacy_provider => target,
+    _ => name,
+  }
+}
+
+/// A digest supported by `createHash()`. This table is the single source of
+/// the names accepted by `Hash::new` and listed by `getHashes()`.
+pub struct DigestInfo {
+  /// Canonical name, in the spelling `getHashes()` reports.
+  pub name: &'static str,
+  pub aliases: &'static [&'static str],
+  /// Extra names accepted here that Node.js doesn't list.
+  pub deno_aliases: &'static [&'static str],
+  /// Whether Node.js (OpenSSL 3's default provider) supports this digest.
+  /// `getHashes()` lists Node.js's names first, in Node.js's order, followed
+  /// by the Deno-only ones.
+  pub in_node: bool,
+  /// `None` for extendable-output functions.
+  pub output_size: Option<usize>,
+  pub block_size: usize,
+  kind: DigestKind,
+}
+
+/// A `DynDigest` that can be moved to the blocking pool.
+pub trait SendDigest: DynDigest + Send {
+  fn box_clone_send(&self) -> Box<dyn SendDigest>;
+}
+
+impl<D: DynDigest + Clone + Send + 'static> SendDigest for D {
+  fn box_clone_send(&self) -> Box<dyn SendDigest> {
+    Box::new(self.clone())
+  }
+}
+
+#[derive(Clone, Copy)]
+enum DigestKind {
+  Fixed(fn() -> Box<dyn SendDigest>),
+  /// A fixed-size digest whose state can be exported.
+  Resumable(fn() -> Hash),
+  Shake128,
+  Shake256,
+  CShake128,
+  CShake256,
+  /// A MAC; only usable with a key.
+  Kmac128,
+  Kmac256,
+  /// A keyed PRF with 64- or 128-bit output; only usable with a key.
+  SipHash24,
+  SipHash13,
+  Blake3,
+}
+
+macro_rules! digest_info {
+  ($type:ty, $name:literal $(, $alias:literal)* $(,)?) => {
+    digest_info!(
+      $type,
+      DigestKind::Fixed(|| Box::new(<$type as Default>::default())),
+      Some(<<$type as OutputSizeUser>::OutputSize as Unsigned>::USIZE),
+      $name $(, $alias)*
+    )
+  };
+  ($type:ty, $kind:expr, $output_size:expr, $name:literal $(, $alias:literal)* $(,)?) => {
+    DigestInfo {
+      name: $name,
+      aliases: &[$($alias),*],
+      deno_aliases: &[],
+      in_node: true,
+      output_size: $output_size,
+      block_size: <<$type as BlockSizeUser>::BlockSize as Unsigned>::USIZE,
+      kind: $kind,
+    }
+  };
+}
+
+static DIGESTS: &[DigestInfo] = &[
+  #[cfg(feature = "legacy_digests")]
+  digest_info!(::md2::Md2, "md2", "RSA-MD2", "md2WithRSAEncryption")
+    .deno_only(),
+  #[cfg(feature = "legacy_digests")]
+  digest_info!(::md4::Md4, "md4", "RSA-MD4", "md4WithRSAEncryption")
+    .deno_only(),
+  digest_info!(
+    ::md5::Md5,
+    "md5",
+    "RSA-MD5",
+    "md5WithRSAEncryption",
+    "ssl3-md5",
+  ),
+  digest_info!(crate::ops::crypto::md5_sha1::Md5Sha1, "md5-sha1"),
+  digest_info!(
+    ::ripemd::Ripemd160,
+    "ripemd160",
+    "RSA-RIPEMD160",
+    "ripemd",
+    "ripemd160WithRSA",
+    "rmd160",
+  ),
+  digest_info!(::ripemd::Ripemd256, "ripemd256", "rmd256").deno_only(),
+  digest_info!(::ripemd::Ripemd320, "ripemd320", "rmd320").deno_only(),
+  digest_info!(
+    ring_sha2::RingSha1,
+    "sha1",
+    "RSA-SHA1",
+    "RSA-SHA1-2",
+    "sha1WithRSAEncryption",
+    "ssl3-sha1",
+  )
+  .with_deno_aliases(&["sha1-2"]),
+  digest_info!(
+    resumable::ResumableSha224,
+    DigestKind::Resumable(|| Sha2(Box::new(resumable::Sha2::Sha224(
+      Default::default()
+    )))),
+    Some(28),
+    "sha224",
+    "RSA-SHA224",
+    "sha224WithRSAEncryption",
+  ),
+  digest_info!(
+    resumable::ResumableSha256,
+    DigestKind::Resumable(|| Sha2(Box::new(resumable::Sha2::Sha256(
+      Default::default()
+    )))),
+    Some(32),
+    "sha256",
+    "RSA-SHA256",
+    "sha256WithRSAEncryption",
+  ),
+  digest_info!(
+    resumable::ResumableSha384,
+    DigestKind::Resumable(|| Sha2(Box::new(resumable::Sha2::Sha384(
+      Default::default()
+    )))),
+    Some(48),
+    "sha384",
+    "RSA-SHA384",
+    "sha384WithRSAEncryption",
+  ),
+  digest_info!(
+    resumable::ResumableSha512,
+    DigestKind::Resumable(|| Sha2(Box::new(resumable::Sha2::Sha512(
+      Default::default()
+    )))),
+    Some(64),
+    "sha512",
+    "RSA-SHA512",
+    "sha512WithRSAEncryption",
+  ),
+  digest_info!(
+    ::sha2::Sha512_224,
+    "sha512-224",
+    "RSA-SHA512/224",
+    "sha512-224WithRSAEncryption",
+  ),
+  digest_info!(
+    ::sha2::Sha512_256,
+    "sha512-256",
+    "RSA-SHA512/256",
+    "sha512-256WithRSAEncryption",
+  ),
+  digest_info!(
+    ::sha3::Sha3_224,
+    "sha3-224",
+    "RSA-SHA3-224",
+    "id-rsassa-pkcs1-v1_5-with-sha3-224",
+  ),
+  digest_info!(
+    ::sha3::Sha3_256,
+    "sha3-256",
+    "RSA-SHA3-256",
+    "id-rsassa-pkcs1-v1_5-with-sha3-256",
+  ),
+  digest_info!(
+    ::sha3::Sha3_384,
+    "sha3-384",
+    "RSA-SHA3-384",
+    "id-rsassa-pkcs1-v1_5-with-sha3-384",
+  ),
+  digest_info!(
+    ::sha3::Sha3_512,
+    "sha3-512",
+    "RSA-SHA3-512",
+    "id-rsassa-pkcs1-v1_5-with-sha3-512",
+  ),
+  digest_info!(::sha3::Shake128, DigestKind::Shake128, None, "shake128")
+    .with_deno_aliases(&["shake-128"]),
+  digest_info!(::sha3::Shake256, DigestKind::Shake256, None, "shake256")
+    .with_deno_aliases(&["shake-256"]),
+  digest_info!(::sha3::CShake128, DigestKind::CShake128, None, "cshake128")
+    .deno_only(),
+  digest_info!(::sha3::CShake256, DigestKind::CShake256, None, "cshake256")
+    .deno_only(),
+  digest_info!(::sha3::CShake128, DigestKind::Kmac128, None, "kmac128")
+    .with_deno_aliases(&["kmac-128"])
+    .deno_only(),
+  digest_info!(::sha3::CShake256, DigestKind::Kmac256, None, "kmac256")
+    .with_deno_aliases(&["kmac-256"])
+    .deno_only(),
+  digest_info!(::sm3::Sm3, "sm3", "RSA-SM3", "sm3WithRSAEncryption"),
+  digest_info!(::blake2::Blake2b512, "blake2b512"),
+  digest_info!(::blake2::Blake2s256, "blake2s256"),
+  DigestInfo {
+    name: "blake3",
+    aliases: &[],
+    deno_aliases: &[],
+    in_node: false,
+    output_size: None,
+    block_size: blake3::BLOCK_LEN,
+    kind: DigestKind::Blake3,
+  },
+  digest_info!(::whirlpool::Whirlpool, "whirlpool").deno_only(),
+  digest_info!(::streebog::Streebog256, "streebog256", "md_gost12_256")
+    .deno_only(),
+  digest_info!(::streebog::Streebog512, "streebog512", "md_gost12_512")
+    .deno_only(),
+  digest_info!(::sha3::Keccak224, "keccak224").deno_only(),
+  digest_info!(::sha3::Keccak256, "keccak256").deno_only(),
+  digest_info!(::sha3::Keccak384, "keccak384").deno_only(),
+  digest_info!(::sha3::Keccak512, "keccak512").deno_only(),
+  DigestInfo {
+    name: "siphash24",
+    aliases: &[],
+    deno_aliases: &["siphash-2-4"],
+    in_node: false,
+    output_size: Some(8),
+    block_size: 8,
+    kind: DigestKind::SipHash24,
+  },
+  DigestInfo {
+    name: "siphash13",
+    aliases: &[],
+    deno_aliases: &["siphash-1-3"],
+    in_node: false,
+    output_size: Some(8),
+    block_size: 8,
+    kind: DigestKind::SipHash13,
+  },
+];
+
+impl DigestInfo {
+  pub fn lookup(algorithm_name: &str) -> Option<&'static Self> {
+    Self::lookup_with(algorithm_name, super::legacy_provider::enabled())
+  }
+
+  /// `lookup` as if the legacy provider were enabled or not.
+  fn lookup_with(
+    algorithm_name: &str,
+    legacy_provider: bool,
+  ) -> Option<&'static Self> {
+    let name =
+      normalize_algorithm_name(resolve_alias(algorithm_name, legacy_provider));
+    let name = oid_digest_name(&name).unwrap_or(&name);
+    DIGESTS.iter().find(|info| {
+      info
+        .names()
+        .any(|(candidate, _)| candidate.eq_ignore_ascii_case(name))
+    })
+  }
+
+  pub fn lookup_or_err(
+    algorithm_name: &str,
+  ) -> Result<&'static Self, HashError> {
+    Self::lookup(algorithm_name).ok_or_else(|| {
+      match legacy_alias_target(algorithm_name) {
+        Some(target) => {
+          HashError::LegacyAliasDisabled(algorithm_name.to_string(), target)
+        }
+        None => HashError::DigestMethodUnsupported(algorithm_name.to_string()),
+      }
+    })
+  }
+
+  /// All accepted names, each paired with whether Node.js lists it.
+  fn names(&self) -> impl Iterator<Item = (&'static str, bool)> + '_ {
+    std::iter::once(self.name)
+      .chain(self.aliases.iter().copied())
+      .map(|name| (name, self.in_node))
+      .chain(self.deno_aliases.iter().map(|name| (*name, false)))
+  }
+
+  const fn with_deno_aliases(
+    mut self,
+    deno_aliases: &'static [&'static str],
+  ) -> Self {
+    self.deno_aliases = deno_aliases;
+    self
+  }
+
+  const fn deno_only(mut self) -> Self {
+    self.in_node = false;
+    self
+  }
+
+  pub fn requires_key(&self) -> bool {
+    matches!(
+      self.kind,
+      DigestKind::Kmac128
+        | DigestKind::Kmac256
+        | DigestKind::SipHash24
+        | DigestKind::SipHash13
+    )
+  }
+}
+
 pub enum Hash {
-  FixedSize(Box<dyn DynDigest>),
+  FixedSize(Box<dyn SendDigest>),
+
+  Sha2(Box<resumable::Sha2>),
 
   Shake128(Box<sha3::Shake128>, /* output_length: */ Option<usize>),
   Shake256(Box<sha3::Shake256>, /* output_length: */ Option<usize>),
+  CShake128(
+    Box<sha3::CShake128>,
+    /* output_length: */ Option<usize>,
+  ),
+  CShake256(
+    Box<sha3::CShake256>,
+    /* output_length: */ Option<usize>,
+  ),
+  Kmac128(Box<kmac::Kmac128>, /* output_length: */ Option<usize>),
+  Kmac256(Box<kmac::Kmac256>, /* output_length: */ Option<usize>),
+  SipHash24(Wiped<siphash::SipHash24>),
+  SipHash13(Wiped<siphash::SipHash13>),
+  Blake3(Box<blake3::Hasher>, /* output_length: */ Option<usize>),
+
+  Blake2bVar(Box<blake2::Blake2bVar>),
+  Blake2sVar(Box<blake2::Blake2sVar>),
+
+  // Keyed contexts are wiped when dropped.
+  Blake2bMac(Wiped<blake2::Blake2bMac512>),
+  Blake2sMac(Wiped<blake2::Blake2sMac256>),
+
+  Blake2bParams(Wiped<blake2_params::Blake2bParams>),
+  Blake2sParams(Wiped<blake2_params::Blake2sParams>),
 }
 
 use Hash::*;
 
+/// BLAKE3 updates at least this large are spread across rayon's thread pool.
+/// Below it, the cost of splitting the work outweighs the gain.
+static BLAKE3_PARALLEL_THRESHOLD: AtomicUsize = AtomicUsize::new(1024 * 1024);
+
+fn blake3_parallel_threshold() -> usize {
+  BLAKE3_PARALLEL_THRESHOLD.load(Ordering::Relaxed)
+}
+
+/// Sets the size from which a single BLAKE3 update is hashed in parallel.
+/// `usize::MAX` disables parallel hashing. The digest is the same either way.
+pub fn set_blake3_parallel_threshold(bytes: usize) {
+  BLAKE3_PARALLEL_THRESHOLD.store(bytes, Ordering::Relaxed);
+}
+
 #[derive(Debug, thiserror::Error, deno_error::JsError)]
 #[class(generic)]
 pub enum HashError {
-  #[error("Output length mismatch for non-extendable algorithm")]
+  #[error("Digest already called")]
+  #[property("code" = "ERR_CRYPTO_HASH_FINALIZED")]
+  Finalized,
+  #[class(range)]
+  #[error("Invalid XOF digest length")]
+  #[property("code" = "ERR_OSSL_EVP_NOT_XOF_OR_INVALID_LENGTH")]
   OutputLengthMismatch,
-  #[error("Digest method not supported: {0}")]
+  #[class(range)]
+  #[error("Expected digest must be {0} bytes, received {1}")]
+  #[property("code" = "ERR_CRYPTO_TIMING_SAFE_EQUAL_LENGTH")]
+  ExpectedDigestLength(usize, usize),
+  #[class(type)]
+  #[error("Unsupported encoding: {0}")]
+  #[property("code" = "ERR_INVALID_ARG_VALUE")]
+  UnsupportedEncoding(String),
+  #[class(type)]
+  #[error("Invalid digest: {0}")]
+  #[property("code" = "ERR_CRYPTO_INVALID_DIGEST")]
   DigestMethodUnsupported(String),
+  #[class(type)]
+  #[error(
+    "Invalid digest: {0}. HMAC is not defined over extendable-output functions, use createHash(\"kmac128\") or createHash(\"kmac256\") with a key instead"
+  )]
+  #[property("code" = "ERR_CRYPTO_INVALID_DIGEST")]
+  HmacOverXof(String),
+  #[error(
+    "Digest method not supported: {0} is a legacy alias of {1}, enable it with NODE_OPTIONS=--openssl-legacy-provider"
+  )]
+  #[property("code" = "ERR_OSSL_EVP_UNSUPPORTED")]
+  LegacyAliasDisabled(String, &'static str),
+  #[error("Digest method not allowed in FIPS mode: {0}")]
+  #[property("code" = "ERR_OSSL_EVP_UNSUPPORTED")]
+  DigestNotFipsApproved(String),
+  #[class(range)]
+  #[error("Output length {0} out of range for {1} (max {2})")]
+  #[property("code" = "ERR_OUT_OF_RANGE")]
+  OutputLengthOutOfRange(usize, &'static str, usize),
+  #[class(type)]
+  #[error("Digest method does not support squeezing output")]
+  SqueezeUnsupported,
+  #[class(type)]
+  #[error("Digest method does not support a key: {0}")]
+  KeyedDigestUnsupported(String),
+  #[class(type)]
+  #[error("Invalid key length for {0}, must be at most {1} bytes")]
+  InvalidKeyLength(&'static str, usize),
+  #[class(type)]
+  #[error("Invalid key length for {0}, must be {1} bytes")]
+  #[property("code" = "ERR_CRYPTO_INVALID_KEYLEN")]
+  KeyLengthMismatch(&'static str, usize),
+  #[class(range)]
+  #[error("Output length {0} out of range for {1}, must be 8 or 16")]
+  #[property("code" = "ERR_OUT_OF_RANGE")]
+  SipHashOutputLength(usize, &'static str),
+  #[class(type)]
+  #[error("Digest method requires a key: {0}")]
+  KeyRequired(String),
+  #[class(type)]
+  #[error("Digest method does not support a customization string: {0}")]
+  CustomizationUnsupported(String),
+  #[class(type)]
+  #[error("Digest method does not support a function name: {0}")]
+  FunctionNameUnsupported(String),
+  #[class(range)]
+  #[error("Digest method does not support a salt or personalization: {0}")]
+  #[property("code" = "ERR_OUT_OF_RANGE")]
+  SaltUnsupported(String),
+  #[class(range)]
+  #[error("Invalid {0} length for {1}, must be at most {2} bytes")]
+  #[property("code" = "ERR_OUT_OF_RANGE")]
+  ParameterTooLong(&'static str, &'static str, usize),
+  #[class(type)]
+  #[error("Digest method does not support exporting its state")]
+  #[property("code" = "ERR_CRYPTO_HASH_NOT_RESUMABLE")]
+  StateNotResumable,
+  #[class(type)]
+  #[error("Invalid hash state")]
+  #[property("code" = "ERR_CRYPTO_INVALID_STATE")]
+  InvalidState,
+  #[class(type)]
+  #[error("Unsupported hash state version {0}")]
+  #[property("code" = "ERR_CRYPTO_INVALID_STATE")]
+  UnsupportedStateVersion(u8),
 }
 
+/// Leading byte of exported hash states. Bump it whenever the layout of
+/// any state changes so old checkpoints are rejected rather than misparsed.
+const STATE_VERSION: u8 = 1;
+
 impl Hash {
   pub fn new(
     algorithm_name: &str,
     output_length: Option<usize>,
+    key: Option<&[u8]>,
   ) -> Result<Self, HashError> {
-    match algorithm_name {
-      "shake128" | "shake-128" => {
+    let info = DigestInfo::lookup_or_err(algorithm_name)?;
+    Self::from_info(info, output_length, key)
+  }
+
+  fn from_info(
+    info: &DigestInfo,
+    output_length: Option<usize>,
+    key: Option<&[u8]>,
+  ) -> Result<Self, HashError> {
+    if !super::fips::digest_allowed(info.name) {
+      return Err(HashError::DigestNotFipsApproved(info.name.to_string()));
+    }
+    if let Some(key) = key {
+      return Self::new_keyed(info, output_length, key);
+    }
+
+    let new_digest = match info.kind {
+      DigestKind::Shake128 => {
         return Ok(Shake128(Default::default(), output_length));
       }
-      "shake256" | "shake-256" => {
+      DigestKind::Shake256 => {
         return Ok(Shake256(Default::default(), output_length));
       }
-      "sha256" => {
-        let digest = ring_sha2::RingSha256::new();
-        if let Some(length) = output_length {
-          if length != digest.output_size() {
-            return Err(HashError::OutputLengthMismatch);
-          }
+      DigestKind::CShake128 | DigestKind::CShake256 => {
+        return Self::new_customized(info, output_length, None, &[], &[]);
+      }
+      DigestKind::Kmac128
+      | DigestKind::Kmac256
+      | DigestKind::SipHash24
+      | DigestKind::SipHash13 => {
+        return Err(HashError::KeyRequired(info.name.to_string()));
+      }
+      DigestKind::Blake3 => {
+        return Ok(Blake3(Default::default(), output_length));
+      }
+      DigestKind::Resumable(new_hash) => {
+        if output_length.is_some_and(|length| Some(length) != info.output_size)
+        {
+          return Err(HashError::OutputLengthMismatch);
         }
-        return Ok(Hash::FixedSize(Box::new(digest)));
+        return Ok(new_hash());
       }
-      "sha512" => {
-        let digest = ring_sha2::RingSha512::new();
-        if let Some(length) = output_length {
-          if length != digest.output_size() {
-            return Err(HashError::OutputLengthMismatch);
-          }
+      DigestKind::Fixed(new_digest) => new_digest,
+    };
+
+    match info.name {
+      "blake2b512" => {
+        if let Some(length) = output_length.filter(|length| *length != 64) {
+          let digest = blake2::Blake2bVar::new(length).map_err(|_| {
+            HashError::OutputLengthOutOfRange(length, "blake2b512", 64)
+          })?;
+          return Ok(Blake2bVar(Box::new(digest)));
+        }
+      }
+      "blake2s256" => {
+        if let Some(length) = output_length.filter(|length| *length != 32) {
+          let digest = blake2::Blake2sVar::new(length).map_err(|_| {
+            HashError::OutputLengthOutOfRange(length, "blake2s256", 32)
+          })?;
+          return Ok(Blake2sVar(Box::new(digest)));
         }
-        return Ok(Hash::FixedSize(Box::new(digest)));
       }
       _ => {}
     }
 
-    let algorithm = match_fixed_digest!(
-      algorithm_name,
+    if let Some(length) = output_length {
+      if Some(length) != info.output_size {
+        return Err(HashError::OutputLengthMismatch);
+      }
+    }
+    Ok(FixedSize(new_digest()))
+  }
+
+  /// Creates a cSHAKE hash or a KMAC. With an empty function name and
+  /// customization string cSHAKE is the same as SHAKE. KMAC has a fixed
+  /// function name and requires a key.
+  fn new_customized(
+    info: &DigestInfo,
+    output_length: Option<usize>,
+    key: Option<&[u8]>,
+    function_name: &[u8],
+    customization: &[u8],
+  ) -> Result<Self, HashError> {
+    if !super::fips::digest_allowed(info.name) {
+      return Err(HashError::DigestNotFipsApproved(info.name.to_string()));
+    }
+    let is_kmac =
+      matches!(info.kind, DigestKind::Kmac128 | DigestKind::Kmac256);
+    if key.is_some() && !info.requires_key() {
+      return Err(HashError::KeyedDigestUnsupported(info.name.to_string()));
+    }
+    if is_kmac && !function_name.is_empty() {
+      return Err(HashError::FunctionNameUnsupported(info.name.to_string()));
+    }
+    match (info.kind, key) {
+      (DigestKind::Kmac128, Some(key)) => Ok(Kmac128(
+        Box::new(kmac::Kmac128::new(key, customization)),
+        output_length,
+      )),
+      (DigestKind::Kmac256, Some(key)) => Ok(Kmac256(
+        Box::new(kmac::Kmac256::new(key, customization)),
+        output_length,
+      )),
+      (DigestKind::Kmac128 | DigestKind::Kmac256, None) => {
+        Err(HashError::KeyRequired(info.name.to_string()))
+      }
+      (DigestKind::CShake128, _) => Ok(CShake128(
+        Box::new(sha3::CShake128::from_core(
+          sha3::CShake128Core::new_with_function_name(
+            function_name,
+            customization,
+          ),
+        )),
+        output_length,
+      )),
+      (DigestKind::CShake256, _) => Ok(CShake256(
+        Box::new(sha3::CShake256::from_core(
+          sha3::CShake256Core::new_with_function_name(
+            function_name,
+            customization,
+          ),
+        )),
+        output_length,
+      )),
+      _ => Err(HashError::CustomizationUnsupported(info.name.to_string())),
+    }
+  }
+
+  /// Creates a BLAKE2b or BLAKE2s hash with a salt and personalization
+  /// string, and optionally a key.
+  fn new_salted(
+    info: &DigestInfo,
+    output_length: Option<usize>,
+    key: &[u8],
+    salt: &[u8],
+    personalization: &[u8],
+  ) -> Result<Self, HashError> {
+    let max_output_size = match info.name {
+      "blake2b512" => blake2_params::Blake2bParams::max_output_size(),
+      "blake2s256" => blake2_params::Blake2sParams::max_output_size(),
+      _ => return Err(HashError::SaltUnsupported(info.name.to_string())),
+    };
+    if !super::fips::digest_allowed(info.name) {
+      return Err(HashError::DigestNotFipsApproved(info.name.to_string()));
+    }
+    // Two words of the parameter block each.
+    let max_parameter_size = max_output_size / 4;
+    if salt.len() > max_parameter_size {
+      return Err(HashError::ParameterTooLong(
+        "salt",
+        info.name,
+        max_parameter_size,
+      ));
+    }
+    if personalization.len() > max_parameter_size {
+      return Err(HashError::ParameterTooLong(
+        "personalization",
+        info.name,
+        max_parameter_size,
+      ));
+    }
+    if key.len() > max_output_size {
+      return Err(HashError::InvalidKeyLength(info.name, max_output_size));
+    }
+    let output_size = output_length.unwrap_or(max_output_size);
+    if output_size == 0 || output_size > max_output_size {
+      return Err(HashError::OutputLengthOutOfRange(
+        output_size,
+        info.name,
+        max_output_size,
+      ));
+    }
+    Ok(match info.name {
+      "blake2b512" => {
+        let context = blake2_params::Blake2Params::new(
+          key,
+          salt,
+          personalization,
+          output_size,
+        );
+        // SAFETY: a BLAKE2 core and block buffer, which are plain data.
+        Blake2bParams(unsafe { Wiped::new(context) })
+      }
+      _ => {
+        let context = blake2_params::Blake2Params::new(
+          key,
+          salt,
+          personalization,
+          output_size,
+        );
+        // SAFETY: as above.
+        Blake2sParams(unsafe { Wiped::new(context) })
+      }
+    })
+  }
+
+  /// Hashes `data` and compares the digest with `expected` in constant
+  /// time. Fails for unknown algorithms and for an `expected` of the wrong
+  /// length, which would otherwise leak through the comparison's timing.
+  pub fn verify_oneshot(
+    algorithm_name: &str,
+    data: &[u8],
+    expected: &[u8],
+  ) -> Result<bool, HashError> {
+    let info = DigestInfo::lookup_or_err(algorithm_name)?;
+    if !super::fips::digest_allowed(info.name) {
+      return Err(HashError::DigestNotFipsApproved(info.name.to_string()));
+    }
+    // Keyed-only digests have no one-shot form.
+    let digest =
+      Self::digest_oneshot(algorithm_name, data).ok_or_else(|| {
+        HashError::DigestMethodUnsupported(algorithm_name.to_string())
+      })?;
+    if digest.len() != expected.len() {
+      return Err(HashError::ExpectedDigestLength(
+        digest.len(),
+        expected.len(),
+      ));
+    }
+    Ok(
+      aws_lc_rs::constant_time::verify_slices_are_equal(&digest, expected)
+        .is_ok(),
+    )
+  }
+
+  /// Hashes `data` with a digest that lives on the stack, skipping the
+  /// boxed context `Hash::new` would allocate.
+  pub fn digest_oneshot(
+    algorithm_name: &str,
+    data: &[u8],
+  ) -> Option<Box<[u8]>> {
+    let info = DigestInfo::lookup(algorithm_name)?;
+    if !super::fips::digest_allowed(info.name) {
+      return None;
+    }
+    let ring_algorithm = match info.name {
+      "sha1" => Some(&aws_lc_rs::digest::SHA1_FOR_LEGACY_USE_ONLY),
+      "sha224" => Some(&aws_lc_rs::digest::SHA224),
+      "sha256" => Some(&aws_lc_rs::digest::SHA256),
+      "sha384" => Some(&aws_lc_rs::digest::SHA384),
+      "sha512" => Some(&aws_lc_rs::digest::SHA512),
+      _ => None,
+    };
+    if let Some(algorithm) = ring_algorithm {
+      return Some(Box::from(
+        aws_lc_rs::digest::digest(algorithm, data).as_ref(),
+      ));
+    }
+
+    let new_digest = match info.kind {
+      // The default output lengths align with Node.js
+      DigestKind::Shake128 | DigestKind::CShake128 => {
+        let mut output = [0; 16];
+        sha3::Shake128::digest_xof(data, &mut output);
+        return Some(Box::from(output.as_slice()));
+      }
+      DigestKind::Shake256 | DigestKind::CShake256 => {
+        let mut output = [0; 32];
+        sha3::Shake256::digest_xof(data, &mut output);
+        return Some(Box::from(output.as_slice()));
+      }
+      DigestKind::Blake3 => {
+        return Some(Box::from(blake3::hash(data).as_bytes().as_slice()));
+      }
+      DigestKind::Kmac128
+      | DigestKind::Kmac256
+      | DigestKind::SipHash24
+      | DigestKind::SipHash13 => return None,
+      DigestKind::Resumable(new_hash) => {
+        let mut hash = new_hash();
+        hash.update(data);
+        return Some(hash.digest_and_drop());
+      }
+      DigestKind::Fixed(new_digest) => new_digest,
+    };
+
+    let digest = match_fixed_digest!(
+      info.name,
       fn <D>() {
-        let digest: D = Digest::new();
-        if let Some(length) = output_length {
-          if length != digest.output_size() {
-            return Err(HashError::OutputLengthMismatch);
-          }
-        }
-        FixedSize(Box::new(digest))
+        Box::from(D::digest(data).as_slice())
       },
       _ => {
-        return Err(HashError::DigestMethodUnsupported(algorithm_name.to_string()))
+        let mut digest = new_digest();
+        digest.update(data);
+        digest.finalize()
       }
     );
 
-    Ok(algorithm)
+    Some(digest)
+  }
+
+  /// Creates a keyed (MAC mode) hash. The key is consumed by the BLAKE2
+  /// parameter block or the KMAC prefix, or kept only in the SipHash state,
+  /// which is wiped when dropped.
+  fn new_keyed(
+    info: &DigestInfo,
+    output_length: Option<usize>,
+    key: &[u8],
+  ) -> Result<Self, HashError> {
+    if let DigestKind::SipHash24 | DigestKind::SipHash13 = info.kind {
+      let key = <&[u8; siphash::KEY_SIZE]>::try_from(key).map_err(|_| {
+        HashError::KeyLengthMismatch(info.name, siphash::KEY_SIZE)
+      })?;
+      let output_size = output_length.unwrap_or(8);
+      if output_size != 8 && output_size != 16 {
+        return Err(HashError::SipHashOutputLength(output_size, info.name));
+      }
+      return Ok(match info.kind {
+        DigestKind::SipHash24 => {
+          let context = siphash::SipHash24::new(key, output_size);
+          // SAFETY: SipHash keys, state words and counters, all integers.
+          SipHash24(unsafe { Wiped::new(context) })
+        }
+        _ => {
+          let context = siphash::SipHash13::new(key, output_size);
+          // SAFETY: as above.
+          SipHash13(unsafe { Wiped::new(context) })
+        }
+      });
+    }
+    if info.requires_key() {
+      return Self::new_customized(info, output_length, Some(key), &[], &[]);
+    }
+    let algorithm_name = info.name;
+    match algorithm_name {
+      "blake2b512" => {
+        if output_length.is_some_and(|length| length != 64) {
+          return Err(HashError::OutputLengthMismatch);
+        }
+        let mac = <blake2::Blake2bMac512 as Mac>::new_from_slice(key)
+          .map_err(|_| HashError::InvalidKeyLength("blake2b512", 64))?;
+        // SAFETY: a BLAKE2 core and block buffer, which are plain data.
+        Ok(Blake2bMac(unsafe { Wiped::new(mac) }))
+      }
+      "blake2s256" => {
+        if output_length.is_some_and(|length| length != 32) {
+          return Err(HashError::OutputLengthMismatch);
+        }
+        let mac = <blake2::Blake2sMac256 as Mac>::new_from_slice(key)
+          .map_err(|_| HashError::InvalidKeyLength("blake2s256", 32))?;
+        // SAFETY: as above.
+        Ok(Blake2sMac(unsafe { Wiped::new(mac) }))
+      }
+      _ => Err(HashError::KeyedDigestUnsupported(
+        algorithm_name.to_string(),
+      )),
+    }
   }
 
   pub fn update(&mut self, data: &[u8]) {
     match self {
       FixedSize(context) => DynDigest::update(&mut **context, data),
+      Sha2(context) => context.update(data),
       Shake128(context, _) => Update::update(&mut **context, data),
       Shake256(context, _) => Update::update(&mut **context, data),
+      CShake128(context, _) => Update::update(&mut **context, data),
+      CShake256(context, _) => Update::update(&mut **context, data),
+      Kmac128(context, _) => context.update(data),
+      Kmac256(context, _) => context.update(data),
+      SipHash24(context) => context.update(data),
+      SipHash13(context) => context.update(data),
+      Blake3(context, _) => {
+        if data.len() >= blake3_parallel_threshold() {
+          context.update_rayon(data);
+        } else {
+          context.update(data);
+        }
+      }
+      Blake2bVar(context) => Update::update(&mut **context, data),
+      Blake2sVar(context) => Update::update(&mut **context, data),
+      Blake2bMac(context) => Mac::update(&mut **context, data),
+      Blake2sMac(context) => Mac::update(&mut **context, data),
+      Blake2bParams(context) => context.update(data),
+      Blake2sParams(context) => context.update(data),
     };
   }
 
   pub fn digest_and_drop(self) -> Box<[u8]> {
     match self {
       FixedSize(context) => context.finalize(),
+      Sha2(context) => context.finalize(),
 
       // The default output lengths align with Node.js
       Shake128(context, output_length) => {
@@ -281,6 +1631,36 @@ impl Hash {
       Shake256(context, output_length) => {
         context.finalize_boxed(output_length.unwrap_or(32))
       }
+      CShake128(context, output_length) => {
+        context.finalize_boxed(output_length.unwrap_or(16))
+      }
+      CShake256(context, output_length) => {
+        context.finalize_boxed(output_length.unwrap_or(32))
+      }
+      // The default output lengths align with OpenSSL
+      Kmac128(context, output_length) => {
+        context.finalize(output_length.unwrap_or(32))
+      }
+      Kmac256(context, output_length) => {
+        context.finalize(output_length.unwrap_or(64))
+      }
+      SipHash24(context) => context.finalize(),
+      SipHash13(context) => context.finalize(),
+      Blake3(context, output_length) => {
+        let mut output = vec![0; output_length.unwrap_or(blake3::OUT_LEN)];
+        context.finalize_xof().fill(&mut output);
+        output.into_boxed_slice()
+      }
+      Blake2bVar(context) => context.finalize_boxed(),
+      Blake2sVar(context) => context.finalize_boxed(),
+      Blake2bMac(context) => {
+        Box::from(Mac::finalize(context.clone_inner()).into_bytes().as_slice())
+      }
+      Blake2sMac(context) => {
+        Box::from(Mac::finalize(context.clone_inner()).into_bytes().as_slice())
+      }
+      Blake2bParams(mut context) => context.finalize(),
+      Blake2sParams(mut context) => context.finalize(),
     }
   }
 
@@ -288,140 +1668,1004 @@ impl Hash {
     &self,
     output_length: Option<usize>,
   ) -> Result<Self, HashError> {
+    // Like Node.js, only XOFs take a new output length; everything else
+    // ignores it. Without one, XOFs keep their original output length.
     let hash = match self {
-      FixedSize(context) => {
-        if let Some(length) = output_length {
-          if length != context.output_size() {
-            return Err(HashError::OutputLengthMismatch);
-          }
-        }
-        FixedSize(context.box_clone())
+      FixedSize(context) => FixedSize(context.box_clone_send()),
+      Sha2(context) => Sha2(context.clone()),
+
+      Shake128(context, length) => {
+        Shake128(context.clone(), output_length.or(*length))
+      }
+      Shake256(context, length) => {
+        Shake256(context.clone(), output_length.or(*length))
+      }
+      CShake128(context, length) => {
+        CShake128(context.clone(), output_length.or(*length))
+      }
+      CShake256(context, length) => {
+        CShake256(context.clone(), output_length.or(*length))
+      }
+      Kmac128(context, length) => {
+        Kmac128(context.clone(), output_length.or(*length))
+      }
+      Kmac256(context, length) => {
+        Kmac256(context.clone(), output_length.or(*length))
+      }
+      SipHash24(context) => SipHash24(context.clone()),
+      SipHash13(context) => SipHash13(context.clone()),
+      Blake3(context, length) => {
+        Blake3(context.clone(), output_length.or(*length))
       }
 
-      Shake128(context, _) => Shake128(context.clone(), output_length),
-      Shake256(context, _) => Shake256(context.clone(), output_length),
+      Blake2bVar(context) => Blake2bVar(context.clone()),
+      Blake2sVar(context) => Blake2sVar(context.clone()),
+      Blake2bMac(context) => Blake2bMac(context.clone()),
+      Blake2sMac(context) => Blake2sMac(context.clone()),
+      Blake2bParams(context) => Blake2bParams(context.clone()),
+      Blake2sParams(context) => Blake2sParams(context.clone()),
     };
     Ok(hash)
   }
 
+  /// Serializes the hash so `import_state` can resume it later, possibly in
+  /// another process. The state is prefixed with `STATE_VERSION` and the
+  /// algorithm name.
+  pub fn export_state(&self) -> Result<Vec<u8>, HashError> {
+    let (name, payload) = match self {
+      Sha2(context) => (context.name(), context.export_state()),
+      _ => return Err(HashError::StateNotResumable),
+    };
+    let mut state = vec![STATE_VERSION, name.len() as u8];
+    state.extend_from_slice(name.as_bytes());
+    state.extend_from_slice(&payload);
+    Ok(state)
+  }
+
+  pub fn import_state(
+    state: &[u8],
+  ) -> Result<(Self, &'static DigestInfo), HashError> {
+    let [version, name_length, rest @ ..] = state else {
+      return Err(HashError::InvalidState);
+    };
+    if *version != STATE_VERSION {
+      return Err(HashError::UnsupportedStateVersion(*version));
+    }
+    let name_length = *name_length as usize;
+    if rest.len() < name_length {
+      return Err(HashError::InvalidState);
+    }
+    let (name, payload) = rest.split_at(name_length);
+    let info = std::str::from_utf8(name)
+      .ok()
+      .and_then(DigestInfo::lookup)
+      .ok_or(HashError::InvalidState)?;
+    if !super::fips::digest_allowed(info.name) {
+      return Err(HashError::DigestNotFipsApproved(info.name.to_string()));
+    }
+    let context = resumable::Sha2::import_state(info.name, payload)
+      .ok_or(HashError::InvalidState)?;
+    Ok((Sha2(Box::new(context)), info))
+  }
+
   pub fn get_hashes() -> Vec<&'static str> {
-    vec![
-      "RSA-MD4",
-      "RSA-MD5",
-      "RSA-RIPEMD160",
-      "RSA-SHA1",
-      "RSA-SHA1-2",
-      "RSA-SHA224",
-      "RSA-SHA256",
-      "RSA-SHA3-224",
-      "RSA-SHA3-256",
-      "RSA-SHA3-384",
-      "RSA-SHA3-512",
-      "RSA-SHA384",
-      "RSA-SHA512",
-      "RSA-SHA512/224",
-      "RSA-SHA512/256",
-      "RSA-SM3",
-      "blake2b512",
-      "blake2s256",
-      "id-rsassa-pkcs1-v1_5-with-sha3-224",
-      "id-rsassa-pkcs1-v1_5-with-sha3-256",
-      "id-rsassa-pkcs1-v1_5-with-sha3-384",
-      "id-rsassa-pkcs1-v1_5-with-sha3-512",
-      "md4",
-      "md4WithRSAEncryption",
-      "md5",
-      "md5-sha1",
-      "md5WithRSAEncryption",
-      "ripemd",
-      "ripemd160",
-      "ripemd160WithRSA",
-      "rmd160",
-      "sha1",
-      "sha1WithRSAEncryption",
-      "sha224",
-      "sha224WithRSAEncryption",
-      "sha256",
-      "sha256WithRSAEncryption",
-      "sha3-224",
-      "sha3-256",
-      "sha3-384",
-      "sha3-512",
-      "sha384",
-      "sha384WithRSAEncryption",
-      "sha512",
-      "sha512-224",
-      "sha512-224WithRSAEncryption",
-      "sha512-256",
-      "sha512-256WithRSAEncryption",
-      "sha512WithRSAEncryption",
-      "shake128",
-      "shake256",
-      "sm3",
-      "sm3WithRSAEncryption",
-      "ssl3-md5",
-      "ssl3-sha1",
-    ]
-  }
-
-  pub fn get_size(algorithm_name: &str) -> Option<u8> {
-    match algorithm_name {
-      "RSA-MD4" => Some(16),
-      "RSA-MD5" => Some(16),
-      "RSA-RIPEMD160" => Some(20),
-      "RSA-SHA1" => Some(20),
-      "RSA-SHA1-2" => Some(20),
-      "RSA-SHA224" => Some(28),
-      "RSA-SHA256" => Some(32),
-      "RSA-SHA3-224" => Some(28),
-      "RSA-SHA3-256" => Some(32),
-      "RSA-SHA3-384" => Some(48),
-      "RSA-SHA3-512" => Some(64),
-      "RSA-SHA384" => Some(48),
-      "RSA-SHA512" => Some(64),
-      "RSA-SHA512/224" => Some(28),
-      "RSA-SHA512/256" => Some(32),
-      "RSA-SM3" => Some(32),
-      "blake2b512" => Some(64),
-      "blake2s256" => Some(32),
-      "id-rsassa-pkcs1-v1_5-with-sha3-224" => Some(28),
-      "id-rsassa-pkcs1-v1_5-with-sha3-256" => Some(32),
-      "id-rsassa-pkcs1-v1_5-with-sha3-384" => Some(48),
-      "id-rsassa-pkcs1-v1_5-with-sha3-512" => Some(64),
-      "md4" => Some(16),
-      "md4WithRSAEncryption" => Some(16),
-      "md5" => Some(16),
-      "md5-sha1" => Some(20),
-      "md5WithRSAEncryption" => Some(16),
-      "ripemd" => Some(20),
-      "ripemd160" => Some(20),
-      "ripemd160WithRSA" => Some(20),
-      "rmd160" => Some(20),
-      "sha1" => Some(20),
-      "sha1WithRSAEncryption" => Some(20),
-      "sha224" => Some(28),
-      "sha224WithRSAEncryption" => Some(28),
-      "sha256" => Some(32),
-      "sha256WithRSAEncryption" => Some(32),
-      "sha3-224" => Some(28),
-      "sha3-256" => Some(32),
-      "sha3-384" => Some(48),
-      "sha3-512" => Some(64),
-      "sha384" => Some(48),
-      "sha384WithRSAEncryption" => Some(48),
-      "sha512" => Some(64),
-      "sha512-224" => Some(28),
-      "sha512-224WithRSAEncryption" => Some(28),
-      "sha512-256" => Some(32),
-      "sha512-256WithRSAEncryption" => Some(32),
-      "sha512WithRSAEncryption" => Some(64),
-      "shake128" => None, // Variable length
-      "shake256" => None, // Variable length
-      "sm3" => Some(32),
-      "sm3WithRSAEncryption" => Some(32),
-      "ssl3-md5" => Some(16),
-      "ssl3-sha1" => Some(20),
-      _ => None,
+    // Node.js sorts its list by byte value; keep that order and append the
+    // Deno-only names, sorted the same way.
+    let mut names = DIGESTS
+      .iter()
+      // KMAC and SipHash can't be created from their name alone.
+      .filter(|info| {
+        super::fips::digest_allowed(info.name) && !info.requires_key()
+      })
+      .flat_map(DigestInfo::names)
+      .collect::<Vec<_>>();
+    names.sort_unstable_by_key(|&(name, in_node)| (!in_node, name));
+    names.into_iter().map(|(name, _)| name).collect()
+  }
+
+  pub fn get_size(algorithm_name: &str) -> Option<usize> {
+    DigestInfo::lookup(algorithm_name)?.output_size
+  }
+
+  pub fn get_block_size(algorithm_name: &str) -> Option<usize> {
+    Some(DigestInfo::lookup(algorithm_name)?.block_size)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn hex_digest(algorithm: &str, data: &[u8]) -> String {
+    let mut hash = Hash::new(algorithm, None, None).unwrap();
+    hash.update(data);
+    faster_hex::hex_string(&hash.digest_and_drop())
+  }
+
+  #[test]
+  fn multi_hasher() {
+    let algorithms = ["sha1", "sha256", "sha512"].map(String::from);
+    let hasher = MultiHasher::new(&algorithms).unwrap();
+    assert!(hasher.update(b"a"));
+    assert!(hasher.update(b"bc"));
+    let digests = hasher
+      .digest()
+      .unwrap()
+      .iter()
+      .map(|digest| faster_hex::hex_string(digest))
+      .collect::<Vec<_>>();
+    let expected = algorithms
+      .iter()
+      .map(|algorithm| hex_digest(algorithm, b"abc"))
+      .collect::<Vec<_>>();
+    assert_eq!(digests, expected);
+    assert!(!hasher.update(b"abc"));
+    assert!(hasher.digest().is_none());
+
+    let algorithms = ["sha256", "nope"].map(String::from);
+    assert!(matches!(
+      MultiHasher::new(&algorithms),
+      Err(HashError::DigestMethodUnsupported(_))
+    ));
+  }
+
+  #[test]
+  fn algorithm_name_lookup() {
+    let data = b"abc";
+    for (alias, canonical) in [
+      ("SHA1", "sha1"),
+      ("Sha-1", "sha1"),
+      ("SHA-256", "sha256"),
+      ("Sha-512/256", "sha512-256"),
+      ("SHA-512/224", "sha512-224"),
+      ("SHAKE-128", "shake128"),
+      ("SHAKE256", "shake256"),
+      ("RSA-SHA256", "sha256"),
+      ("RSA-SHA512/256", "sha512-256"),
+      ("sha256WithRSAEncryption", "sha256"),
+      ("RSA-MD5", "md5"),
+    ] {
+      assert_eq!(
+        hex_digest(alias, data),
+        hex_digest(canonical, data),
+        "{alias}"
+      );
+      assert_eq!(
+        Hash::digest_oneshot(alias, data),
+        Hash::digest_oneshot(canonical, data),
+        "{alias}"
+      );
+    }
+    assert!(matches!(
+      normalize_algorithm_name("sha256"),
+      Cow::Borrowed(_)
+    ));
+    assert_eq!(normalize_algorithm_name("sha-3-256"), "sha-3-256");
+    assert!(Hash::new("sha-", None, None).is_err());
+  }
+
+  #[test]
+  fn get_hashes_round_trip() {
+    let hashes = Hash::get_hashes();
+    let unique = hashes
+      .iter()
+      .map(|name| name.to_ascii_lowercase())
+      .collect::<std::collections::HashSet<_>>();
+    assert_eq!(hashes.len(), unique.len(), "duplicate digest names");
+
+    for algorithm in hashes {
+      let info = DigestInfo::lookup(algorithm).unwrap();
+      let mut hash = Hash::new(algorithm, None, None)
+        .unwrap_or_else(|_| panic!("{algorithm} is listed but not accepted"));
+      hash.update(b"abc");
+      let digest = hash.digest_and_drop();
+      if let Some(size) = info.output_size {
+        assert_eq!(digest.len(), size, "{algorithm}");
+        assert_eq!(Hash::get_size(algorithm), Some(size), "{algorithm}");
+      } else {
+        assert_eq!(Hash::get_size(algorithm), None, "{algorithm}");
+      }
+    }
+
+    for algorithm in ["sha", "sha0", "rsa-", "sha3", ""] {
+      assert!(DigestInfo::lookup(algorithm).is_none(), "{algorithm}");
+      assert!(Hash::new(algorithm, None, None).is_err(), "{algorithm}");
+    }
+    #[cfg(not(feature = "legacy_digests"))]
+    assert!(Hash::new("md2", None, None).is_err());
+    assert_eq!(Hash::get_size("md5-sha1"), Some(36));
+  }
+
+  #[test]
+  fn get_hashes_lists_node_names_first() {
+    let hashes = Hash::get_hashes();
+    let in_node = |name: &str| {
+      DIGESTS
+        .iter()
+        .flat_map(DigestInfo::names)
+        .any(|(candidate, in_node)| candidate == name && in_node)
+    };
+    let node_count = hashes.iter().take_while(|name| in_node(name)).count();
+    assert_eq!(node_count, 52);
+    assert!(hashes[node_count..].iter().all(|name| !in_node(name)));
+    assert!(hashes[..node_count].is_sorted());
+    assert!(hashes[node_count..].is_sorted());
+    for name in ["md4", "sha1-2", "shake-128", "blake3", "keccak256"] {
+      assert!(hashes[node_count..].contains(&name), "{name}");
     }
   }
+
+  #[test]
+  fn sizes_resolve_aliases() {
+    for algorithm in ["sha256", "RSA-SHA256", "sha256WithRSAEncryption"] {
+      assert_eq!(Hash::get_size(algorithm), Some(32), "{algorithm}");
+      assert_eq!(Hash::get_block_size(algorithm), Some(64), "{algorithm}");
+    }
+    for algorithm in ["sha512", "RSA-SHA512", "sha512WithRSAEncryption"] {
+      assert_eq!(Hash::get_size(algorithm), Some(64), "{algorithm}");
+      assert_eq!(Hash::get_block_size(algorithm), Some(128), "{algorithm}");
+    }
+    assert_eq!(Hash::get_size("shake256"), None);
+    assert_eq!(Hash::get_block_size("shake256"), Some(136));
+    assert_eq!(Hash::get_size("unknown"), None);
+    assert_eq!(Hash::get_block_size("unknown"), None);
+  }
+
+  #[test]
+  fn export_import_state() {
+    for algorithm in ["sha224", "sha256", "SHA384", "sha512"] {
+      let mut hash = Hash::new(algorithm, None, None).unwrap();
+      hash.update(b"hello ");
+      let state = hash.export_state().unwrap();
+      let (mut resumed, _) = Hash::import_state(&state).unwrap();
+      resumed.update(b"world");
+      assert_eq!(
+        resumed.digest_and_drop(),
+        Hash::digest_oneshot(algorithm, b"hello world").unwrap(),
+        "{algorithm}"
+      );
+    }
+
+    let hash = Hash::new("sha256", None, None).unwrap();
+    let mut state = hash.export_state().unwrap();
+    assert!(matches!(
+      Hash::import_state(&state[..state.len() - 1]),
+      Err(HashError::InvalidState)
+    ));
+    state[0] = STATE_VERSION + 1;
+    assert!(matches!(
+      Hash::import_state(&state),
+      Err(HashError::UnsupportedStateVersion(_))
+    ));
+    assert!(matches!(
+      Hash::new("md5", None, None).unwrap().export_state(),
+      Err(HashError::StateNotResumable)
+    ));
+  }
+
+  // https://csrc.nist.gov/projects/cryptographic-standards-and-guidelines/example-values
+  #[test]
+  fn cshake_nist_samples() {
+    let short = (0..4).collect::<Vec<u8>>();
+    let long = (0..200).collect::<Vec<u8>>();
+    for (algorithm, data, output_length, expected) in [
+      (
+        "cshake128",
+        &short,
+        32,
+        "c1c36925b6409a04f1b504fcbca9d82b4017277cb5ed2b2065fc1d3814d5aaf5",
+      ),
+      (
+        "cshake128",
+        &long,
+        32,
+        "c5221d50e4f822d96a2e8881a961420f294b7b24fe3d2094baed2c6524cc166b",
+      ),
+      (
+        "cshake256",
+        &short,
+        64,
+        "d008828e2b80ac9d2218ffee1d070c48b8e4c87bff32c9699d5b6896eee0edd1\
+         64020e2be0560858d9c00c037e34a96937c561a74c412bb4c746469527281c8c",
+      ),
+      (
+        "cshake256",
+        &long,
+        64,
+        "07dc27b11e51fbac75bc7b3c1d983e8b4b85fb1defaf218912ac864302730917\
+         27f42b17ed1df63e8ec118f04b23633c1dfb1574c8fb55cb45da8e25afb092bb",
+      ),
+    ] {
+      let hasher = Hasher::new_customized(
+        algorithm,
+        Some(output_length),
+        None,
+        &[],
+        b"Email Signature",
+      )
+      .unwrap();
+      hasher.update(data);
+      let copy = hasher.clone_inner(None).unwrap().unwrap();
+      assert_eq!(
+        faster_hex::hex_string(&hasher.digest().unwrap()),
+        expected,
+        "{algorithm}"
+      );
+      // Reset keeps the customization string.
+      hasher.reset().unwrap();
+      hasher.update(data);
+      assert_eq!(hasher.digest(), copy.digest(), "{algorithm}");
+    }
+
+    // Without a function name or customization, cSHAKE is SHAKE.
+    assert_eq!(
+      hex_digest("cshake128", b"abc"),
+      hex_digest("shake128", b"abc")
+    );
+    assert_eq!(
+      hex_digest("cshake256", b"abc"),
+      hex_digest("shake256", b"abc")
+    );
+    assert!(matches!(
+      Hasher::new_customized("sha256", None, None, &[], b"Email Signature"),
+      Err(HashError::CustomizationUnsupported(_))
+    ));
+  }
+
+  #[test]
+  fn legacy_aliases() {
+    assert!(matches!(
+      Hash::new("dss1", None, None),
+      Err(HashError::LegacyAliasDisabled(_, "sha1"))
+    ));
+    assert_eq!(resolve_alias("DSA-SHA1", false), "DSA-SHA1");
+
+    for alias in ["sha", "dss1", "dsaEncryption", "DSA-SHA1", "dsaWithSHA1"] {
+      assert!(DigestInfo::lookup_with(alias, false).is_none(), "{alias}");
+      let info = DigestInfo::lookup_with(alias, true).unwrap();
+      assert_eq!(info.name, "sha1", "{alias}");
+    }
+    assert_eq!(resolve_alias("DSA-SHA1", true), "sha1");
+    assert_eq!(resolve_alias("sha256", true), "sha256");
+  }
+
+  #[test]
+  fn blake2_salt_and_personalization() {
+    let data = b"The quick brown fox jumps over the lazy dog";
+    let digest = |algorithm, output_length, key, salt, personalization| {
+      let hasher = Hasher::new_salted(
+        algorithm,
+        output_length,
+        key,
+        salt,
+        personalization,
+      )?;
+      hasher.update(data);
+      let copy = hasher.clone_inner(None)?.unwrap();
+      let digest = faster_hex::hex_string(&hasher.digest().unwrap());
+      assert_eq!(faster_hex::hex_string(&copy.digest().unwrap()), digest);
+      Ok::<_, HashError>(digest)
+    };
+
+    // From libsodium's crypto_generichash_blake2b_salt_personal.
+    assert_eq!(
+      digest(
+        "blake2b512",
+        None,
+        None,
+        b"0123456789abcdef",
+        b"deno personalize"
+      )
+      .unwrap(),
+      "fa2c5803c5b5f26bea8e0cc5ce670c713c5bb038a03e0dd4b5a29914d6fabb1e0ad1c426b0e0a4effc4ddfc5526a37661f795323b67e55f5c0c894e27a67f65a"
+    );
+    assert_eq!(
+      digest(
+        "blake2b512",
+        Some(32),
+        Some(b"secret key"),
+        b"salt",
+        b"persona"
+      )
+      .unwrap(),
+      "1efb10d3651984c0bd5fd60e14df233208c0ed1b11479b7727a5a49f9ab13a63"
+    );
+    // From Python's hashlib.blake2s.
+    assert_eq!(
+      digest("blake2s256", None, None, b"01234567", b"personal").unwrap(),
+      "e70c1d1f0f91ca0b642b5693ae3bef413c9ade84e817bfa4c22da2602a08597e"
+    );
+    assert_eq!(
+      digest("blake2s256", Some(16), None, b"01234567", b"personal").unwrap(),
+      "549706accf08d87dbe7754ad847fa726"
+    );
+    // Empty parameters are the plain digest.
+    assert_eq!(
+      digest("blake2b512", None, None, b"", b"").unwrap(),
+      hex_digest("blake2b512", data)
+    );
+
+    assert!(matches!(
+      digest("blake2s256", None, None, b"012345678", b""),
+      Err(HashError::ParameterTooLong("salt", "blake2s256", 8))
+    ));
+    assert!(matches!(
+      digest("blake2b512", None, None, b"", &[0; 17]),
+      Err(HashError::ParameterTooLong(
+        "personalization",
+        "blake2b512",
+        16
+      ))
+    ));
+    assert!(matches!(
+      digest("sha256", None, None, b"salt", b""),
+      Err(HashError::SaltUnsupported(_))
+    ));
+  }
+
+  #[test]
+  fn oid_names() {
+    for (oid, name) in DIGEST_OIDS {
+      let Some(info) = DigestInfo::lookup(oid) else {
+        // md2 and md4 are behind the legacy_digests feature.
+        assert!(DigestInfo::lookup(name).is_none(), "{name}");
+        continue;
+      };
+      assert_eq!(info.name, *name);
+      assert_eq!(
+        DigestInfo::lookup(&format!("id-{name}")).unwrap().name,
+        *name
+      );
+    }
+    assert_eq!(
+      hex_digest("2.16.840.1.101.3.4.2.1", b"abc"),
+      hex_digest("sha256", b"abc")
+    );
+    assert_eq!(DigestInfo::lookup("ID-SHA3-256").unwrap().name, "sha3-256");
+    assert_eq!(
+      DigestInfo::lookup("id-rsassa-pkcs1-v1_5-with-sha3-256")
+        .unwrap()
+        .name,
+      "sha3-256"
+    );
+    for unknown in ["2.16.840.1.101.3.4.2.99", "id-blake3", "id-", "1.2"] {
+      assert!(matches!(
+        Hash::new(unknown, None, None),
+        Err(HashError::DigestMethodUnsupported(_))
+      ));
+    }
+    let hashes = Hash::get_hashes();
+    assert!(
+      !hashes
+        .iter()
+        .any(|name| name.starts_with(|c: char| { c.is_ascii_digit() }))
+    );
+  }
+
+  #[test]
+  fn kmac_nist_samples() {
+    let key = (0x40..0x60).collect::<Vec<u8>>();
+    let short = (0..4).collect::<Vec<u8>>();
+    let long = (0..200).collect::<Vec<u8>>();
+    let tag = b"My Tagged Application".as_slice();
+    for (algorithm, data, customization, kmac, kmac_xof) in [
+      (
+        "kmac128",
+        &short,
+        b"".as_slice(),
+        "e5780b0d3ea6f7d3a429c5706aa43a00fadbd7d49628839e3187243f456ee14e",
+        "cd83740bbd92ccc8cf032b1481a0f4460e7ca9dd12b08a0c4031178bacd6ec35",
+      ),
+      (
+        "kmac128",
+        &short,
+        tag,
+        "3b1fba963cd8b0b59e8c1a6d71888b7143651af8ba0a7070c0979e2811324aa5",
+        "31a44527b4ed9f5c6101d11de6d26f0620aa5c341def41299657fe9df1a3b16c",
+      ),
+      (
+        "kmac128",
+        &long,
+        tag,
+        "1f5b4e6cca02209e0dcb5ca635b89a15e271ecc760071dfd805faa38f9729230",
+        "47026c7cd793084aa0283c253ef658490c0db61438b8326fe9bddf281b83ae0f",
+      ),
+      (
+        "kmac256",
+        &short,
+        tag,
+        "20c570c31346f703c9ac36c61c03cb64c3970d0cfc787e9b79599d273a68d2f7\
+         f69d4cc3de9d104a351689f27cf6f5951f0103f33f4f24871024d9c27773a8dd",
+        "1755133f1534752aad0748f2c706fb5c784512cab835cd15676b16c0c6647fa9\
+         6faa7af634a0bf8ff6df39374fa00fad9a39e322a7c92065a64eb1fb0801eb2b",
+      ),
+      (
+        "kmac256",
+        &long,
+        b"".as_slice(),
+        "75358cf39e41494e949707927cee0af20a3ff553904c86b08f21cc414bcfd691\
+         589d27cf5e15369cbbff8b9a4c2eb17800855d0235ff635da82533ec6b759b69",
+        "ff7b171f1e8a2b24683eed37830ee797538ba8dc563f6da1e667391a75edc02c\
+         a633079f81ce12a25f45615ec89972031d18337331d24ceb8f8ca8e6a19fd98b",
+      ),
+      (
+        "kmac256",
+        &long,
+        tag,
+        "b58618f71f92e1d56c1b8c55ddd7cd188b97b4ca4d99831eb2699a837da2e4d9\
+         70fbacfde50033aea585f1a2708510c32d07880801bd182898fe476876fc8965",
+        "d5be731c954ed7732846bb59dbe3a8e30f83e77a4bff4459f2f1c2b4ecebb8ce\
+         67ba01c62e8ab8578d2d499bd1bb276768781190020a306a97de281dcc30305d",
+      ),
+    ] {
+      // The sample output lengths are the defaults.
+      let hasher =
+        Hasher::new_customized(algorithm, None, Some(&key), &[], customization)
+          .unwrap();
+      hasher.update(data);
+      let xof = hasher.clone_inner(None).unwrap().unwrap();
+      assert_eq!(
+        faster_hex::hex_string(&hasher.digest().unwrap()),
+        kmac,
+        "{algorithm}"
+      );
+      let mut output =
+        xof.squeeze(kmac_xof.len() / 4).unwrap().unwrap().to_vec();
+      output.extend(xof.squeeze(kmac_xof.len() / 4).unwrap().unwrap());
+      assert_eq!(faster_hex::hex_string(&output), kmac_xof, "{algorithm}");
+    }
+
+    let hasher = Hasher::new("kmac128", Some(16), Some(&key)).unwrap();
+    hasher.update(&short);
+    let truncated = hasher.digest().unwrap();
+    assert_eq!(truncated.len(), 16);
+    // The output length is bound into KMAC, so this isn't a prefix of the
+    // 32-byte sample.
+    assert_ne!(
+      faster_hex::hex_string(&truncated),
+      "e5780b0d3ea6f7d3a429c5706aa43a00"
+    );
+
+    assert!(matches!(
+      Hasher::new("kmac256", None, None),
+      Err(HashError::KeyRequired(_))
+    ));
+    assert!(matches!(
+      Hasher::new_customized("kmac128", None, Some(&key), b"KMAC", &[]),
+      Err(HashError::FunctionNameUnsupported(_))
+    ));
+    assert!(matches!(
+      Hasher::new_customized("cshake128", None, Some(&key), &[], tag),
+      Err(HashError::KeyedDigestUnsupported(_))
+    ));
+    assert!(
+      !Hash::get_hashes()
+        .iter()
+        .any(|name| name.starts_with("kmac"))
+    );
+  }
+
+  #[test]
+  fn siphash_reference_vectors() {
+    // Key 00..0f and message 00..(n - 1), from vectors.h of the reference
+    // implementation; the 15-byte 2-4 value is the example in Appendix A
+    // of the paper. The reference has no 1-3 vectors, those were checked
+    // against a separate implementation of the paper's description.
+    let key = (0..16).collect::<Vec<u8>>();
+    for (algorithm, length, output_size, expected) in [
+      ("siphash24", 0, 8, "310e0edd47db6f72"),
+      ("siphash24", 1, 8, "fd67dc93c539f874"),
+      ("siphash24", 8, 8, "6224939a79f5f593"),
+      ("siphash24", 15, 8, "e545be4961ca29a1"),
+      ("siphash24", 63, 8, "724506eb4c328a95"),
+      ("siphash24", 0, 16, "a3817f04ba25a8e66df67214c7550293"),
+      ("siphash24", 15, 16, "5493e99933b0a8117e08ec0f97cfc3d9"),
+      ("siphash24", 63, 16, "5150d1772f50834a503e069a973fbd7c"),
+      ("siphash13", 0, 8, "dcc40f055801acab"),
+      ("siphash13", 15, 8, "5699512a6dd820d3"),
+      ("siphash13", 63, 8, "a8b3bbb76290199d"),
+      ("siphash13", 0, 16, "e77ebcb22788a5befd62db6add303001"),
+      ("siphash13", 63, 16, "4c5800e34efe426f079f6b0aa75260ad"),
+    ] {
+      let data = (0..length).collect::<Vec<u8>>();
+      let hasher =
+        Hasher::new(algorithm, Some(output_size), Some(&key)).unwrap();
+      // Split mid-word so updates that don't fill the tail are covered.
+      let (head, tail) = data.split_at(length as usize / 3);
+      hasher.update(head);
+      let copy = hasher.clone_inner(None).unwrap().unwrap();
+      hasher.update(tail);
+      assert_eq!(
+        faster_hex::hex_string(&hasher.digest().unwrap()),
+        expected,
+        "{algorithm} {length} {output_size}"
+      );
+      copy.update(tail);
+      assert_eq!(faster_hex::hex_string(&copy.digest().unwrap()), expected);
+    }
+
+    let hasher = Hasher::new("SipHash-2-4", None, Some(&key)).unwrap();
+    hasher.update(&(0..15).collect::<Vec<u8>>());
+    assert_eq!(
+      faster_hex::hex_string(&hasher.digest().unwrap()),
+      "e545be4961ca29a1"
+    );
+
+    assert!(matches!(
+      Hasher::new("siphash24", None, None),
+      Err(HashError::KeyRequired(_))
+    ));
+    assert!(matches!(
+      Hasher::new("siphash13", None, Some(&key[..15])),
+      Err(HashError::KeyLengthMismatch("siphash13", 16))
+    ));
+    assert!(matches!(
+      Hasher::new("siphash24", Some(32), Some(&key)),
+      Err(HashError::SipHashOutputLength(32, "siphash24"))
+    ));
+    assert!(
+      !Hash::get_hashes()
+        .iter()
+        .any(|name| name.starts_with("siphash"))
+    );
+    assert!(Hash::digest_oneshot("siphash24", b"abc").is_none());
+  }
+
+  #[test]
+  fn hmac_block_sizes() {
+    for (algorithm, block_size) in [
+      ("md5", 64),
+      ("sha1", 64),
+      ("sha224", 64),
+      ("sha384", 128),
+      ("sha512-256", 128),
+      ("sha3-224", 144),
+      ("sha3-256", 136),
+      ("sha3-384", 104),
+      ("sha3-512", 72),
+      ("blake2b512", 128),
+      ("blake2s256", 64),
+      ("sm3", 64),
+    ] {
+      assert_eq!(
+        Hash::get_block_size(algorithm),
+        Some(block_size),
+        "{algorithm}"
+      );
+    }
+  }
+
+  #[test]
+  fn digest_oneshot_matches_streaming() {
+    let data = b"The quick brown fox jumps over the lazy dog";
+    for algorithm in Hash::get_hashes() {
+      let algorithm = algorithm.to_lowercase();
+      let Some(oneshot) = Hash::digest_oneshot(&algorithm, data) else {
+        panic!("{algorithm} is not supported by digest_oneshot");
+      };
+      let mut hash = Hash::new(&algorithm, None, None).unwrap();
+      hash.update(data);
+      assert_eq!(oneshot, hash.digest_and_drop(), "{algorithm}");
+    }
+    assert!(Hash::digest_oneshot("unknown", data).is_none());
+  }
+
+  #[test]
+  fn digest_encoding() {
+    let digest = [0xfb, 0xff, 0x00, 0x7f, 0xe9];
+    let encode =
+      |encoding| DigestEncoding::parse(encoding).unwrap().encode(&digest);
+    assert_eq!(encode("hex"), "fbff007fe9");
+    assert_eq!(encode("base64"), "+/8Af+k=");
+    assert_eq!(encode("base64url"), "-_8Af-k");
+    assert_eq!(encode("latin1"), "\u{fb}\u{ff}\u{0}\u{7f}\u{e9}");
+    assert_eq!(encode("binary"), encode("latin1"));
+    assert!(DigestEncoding::parse("utf8").is_none());
+  }
+
+  #[test]
+  fn digest_decoding() {
+    let decode = |encoding, encoded| {
+      DigestEncoding::parse(encoding).unwrap().decode(encoded)
+    };
+    let digest = [0xfb, 0xff, 0x00, 0x7f, 0xe9];
+    assert_eq!(decode("hex", "FBff007fe9").unwrap(), digest);
+    assert_eq!(decode("base64", "+/8Af+k=").unwrap(), digest);
+    assert_eq!(decode("base64", "+/8Af+k").unwrap(), digest);
+    assert_eq!(decode("base64url", "-_8Af-k").unwrap(), digest);
+    assert_eq!(
+      decode("latin1", "\u{fb}\u{ff}\u{0}\u{7f}\u{e9}").unwrap(),
+      digest
+    );
+    assert!(decode("hex", "fbf").is_none());
+    assert!(decode("hex", "zz").is_none());
+    assert!(decode("base64", "-_8Af-k").is_none());
+    assert!(decode("latin1", "\u{100}").is_none());
+  }
+
+  #[test]
+  fn verify_oneshot() {
+    let expected = Hash::digest_oneshot("sha256", b"abc").unwrap();
+    assert!(Hash::verify_oneshot("sha256", b"abc", &expected).unwrap());
+    assert!(!Hash::verify_oneshot("sha256", b"abd", &expected).unwrap());
+    assert!(matches!(
+      Hash::verify_oneshot("sha256", b"abc", &expected[..31]),
+      Err(HashError::ExpectedDigestLength(32, 31))
+    ));
+    assert!(matches!(
+      Hash::verify_oneshot("nope", b"abc", &expected),
+      Err(HashError::DigestMethodUnsupported(_))
+    ));
+    assert!(matches!(
+      Hash::verify_oneshot("kmac128", b"abc", &expected),
+      Err(HashError::DigestMethodUnsupported(_))
+    ));
+  }
+
+  #[test]
+  fn verify_oneshot_fips() {
+    crate::ops::crypto::fips::set_enabled_on_test_thread(true);
+    let expected = Hash::digest_oneshot("sha256", b"abc").unwrap();
+    assert!(Hash::verify_oneshot("sha256", b"abc", &expected).unwrap());
+    assert!(matches!(
+      Hash::verify_oneshot("md5", b"abc", &expected[..16]),
+      Err(HashError::DigestNotFipsApproved(name)) if name == "md5"
+    ));
+    crate::ops::crypto::fips::set_enabled_on_test_thread(false);
+  }
+
+  #[test]
+  fn digest_encoded_unknown_encoding_keeps_hash() {
+    let hasher = Hasher::new("sha1", None, None).unwrap();
+    hasher.update(b"abc");
+    assert!(hasher.digest_encoded("ucs2").is_none());
+    assert_eq!(
+      hasher.digest_encoded("hex").unwrap(),
+      "a9993e364706816aba3e25717850c26c9cd0d89d"
+    );
+    assert!(hasher.digest_encoded("hex").is_none());
+  }
+
+  #[test]
+  #[allow(clippy::disallowed_methods)]
+  fn hash_file() {
+    let path = std::env::temp_dir()
+      .join(format!("deno_node_hash_file_{}", std::process::id()));
+    let data = (0..200_000).map(|i| i as u8).collect::<Vec<_>>();
+    std::fs::write(&path, &data).unwrap();
+
+    for (algorithm, output_length) in
+      [("sha256", None), ("md5", None), ("shake256", Some(100))]
+    {
+      let mut hash = Hash::new(algorithm, output_length, None).unwrap();
+      hash.update(&data);
+      assert_eq!(
+        hash_file_sync(algorithm, &path, output_length).unwrap(),
+        hash.digest_and_drop(),
+        "{algorithm}"
+      );
+    }
+    std::fs::remove_file(&path).unwrap();
+
+    let err = hash_file_sync("sha256", &path, None).unwrap_err();
+    assert!(matches!(
+      err,
+      HashFileError::Io(err) if err.kind() == std::io::ErrorKind::NotFound
+    ));
+    assert!(matches!(
+      hash_file_sync("sha0", &path, None),
+      Err(HashFileError::Hash(HashError::DigestMethodUnsupported(_)))
+    ));
+  }
+
+  #[test]
+  fn clone_output_length() {
+    let digest_len =
+      |hasher: Option<Hasher>| hasher.unwrap().digest().unwrap().len();
+
+    let sha256 = Hasher::new("sha256", None, None).unwrap();
+    assert_eq!(digest_len(sha256.clone_inner(Some(5)).unwrap()), 32);
+    let copy = sha256.clone_inner(Some(5)).unwrap().unwrap();
+    copy.reset().unwrap();
+    assert_eq!(copy.digest().unwrap().len(), 32);
+
+    let blake2 = Hasher::new("blake2b512", Some(20), None).unwrap();
+    assert_eq!(digest_len(blake2.clone_inner(Some(64)).unwrap()), 20);
+
+    for algorithm in ["shake128", "shake256", "blake3"] {
+      let hasher = Hasher::new(algorithm, Some(5), None).unwrap();
+      assert_eq!(digest_len(hasher.clone_inner(None).unwrap()), 5);
+      assert_eq!(digest_len(hasher.clone_inner(Some(0)).unwrap()), 0);
+      assert_eq!(digest_len(hasher.clone_inner(Some(77)).unwrap()), 77);
+    }
+  }
+
+  // https://homes.esat.kuleuven.be/~bosselae/ripemd160.html
+  #[test]
+  fn ripemd_spec_vectors() {
+    let million_a = vec![b'a'; 1_000_000];
+    for (data, ripemd256, ripemd320) in [
+      (
+        b"".as_slice(),
+        "02ba4c4e5f8ecd1877fc52d64d30e37a2d9774fb1e5d026380ae0168e3c5522d",
+        "22d65d5661536cdc75c1fdf5c6de7b41b9f27325ebc61e8557177d705a0ec880151c3a32a00899b8",
+      ),
+      (
+        b"abc",
+        "afbd6e228b9d8cbbcef5ca2d03e6dba10ac0bc7dcbe4680e1e42d2e975459b65",
+        "de4c01b3054f8930a79d09ae738e92301e5a17085beffdc1b8d116713e74f82fa942d64cdbc4682d",
+      ),
+      (
+        b"message digest",
+        "87e971759a1ce47a514d5c914c392c9018c7c46bc14465554afcdf54a5070c0e",
+        "3a8e28502ed45d422f68844f9dd316e7b98533fa3f2a91d29f84d425c88d6b4eff727df66a7c0197",
+      ),
+      (
+        b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq",
+        "3843045583aac6c8c8d9128573e7a9809afb2a0f34ccc36ea9e72f16f6368e3f",
+        "d034a7950cf722021ba4b84df769a5de2060e259df4c9bb4a4268c0e935bbc7470a969c9d072a1ac",
+      ),
+      (
+        &million_a,
+        "ac953744e10e31514c150d4d8d7b677342e33399788296e43ae4850ce4f97978",
+        "bdee37f4371e20646b8b0d862dda16292ae36f40965e8c8509e63d1dbddecc503e2b63eb9245bb66",
+      ),
+    ] {
+      assert_eq!(hex_digest("ripemd256", data), ripemd256);
+      assert_eq!(hex_digest("RMD320", data), ripemd320);
+    }
+    assert_eq!(Hash::get_size("ripemd256"), Some(32));
+    assert_eq!(Hash::get_size("ripemd320"), Some(40));
+    assert_eq!(Hash::get_block_size("ripemd320"), Some(64));
+  }
+
+  #[test]
+  fn blake3_parallel_update() {
+    let data = (0..3 * 1024 * 1024 + 17)
+      .map(|i| (i * 31 % 251) as u8)
+      .collect::<Vec<_>>();
+    let (head, tail) = data.split_at(1024 * 1024 + 5);
+    let expected = blake3::hash(&data);
+
+    let mut hash = Hash::new("blake3", None, None).unwrap();
+    hash.update(head);
+    let mut copy = hash.clone_hash(Some(64)).unwrap();
+    hash.update(tail);
+    copy.update(tail);
+    assert_eq!(*hash.digest_and_drop(), *expected.as_bytes());
+    let mut reader = blake3::Hasher::new().update(&data).finalize_xof();
+    let mut long = [0; 64];
+    reader.fill(&mut long);
+    assert_eq!(*copy.digest_and_drop(), long);
+  }
+
+  #[test]
+  fn zero_output_length() {
+    for algorithm in ["shake128", "shake256", "cshake256", "blake3"] {
+      let hasher = Hasher::new(algorithm, Some(0), None).unwrap();
+      assert!(hasher.update(b"abc"));
+      assert_eq!(hasher.digest().unwrap().len(), 0, "{algorithm}");
+    }
+    for algorithm in ["sha256", "md5", "sha3-256"] {
+      assert!(matches!(
+        Hasher::new(algorithm, Some(0), None),
+        Err(HashError::OutputLengthMismatch)
+      ));
+    }
+  }
+
+  #[test]
+  fn hasher_reset() {
+    for (algorithm, output_length, key) in [
+      ("sha256", None, None),
+      ("sha512", None, None),
+      ("md5", None, None),
+      ("shake128", Some(50), None),
+      ("shake256", None, None),
+      ("blake3", Some(40), None),
+      ("blake2b512", Some(20), None),
+      ("blake2s256", None, Some(b"key".as_slice())),
+    ] {
+      let expected = {
+        let hasher = Hasher::new(algorithm, output_length, key).unwrap();
+        hasher.update(b"abc");
+        hasher.digest().unwrap()
+      };
+
+      let hasher = Hasher::new(algorithm, output_length, key).unwrap();
+      hasher.update(b"discarded");
+      hasher.reset().unwrap();
+      hasher.update(b"abc");
+      assert_eq!(hasher.digest().unwrap(), expected, "{algorithm}");
+      assert!(hasher.digest().is_none());
+
+      hasher.reset().unwrap();
+      hasher.update(b"abc");
+      assert_eq!(hasher.digest().unwrap(), expected, "{algorithm}");
+    }
+
+    let hasher = Hasher::new("shake128", None, None).unwrap();
+    hasher.squeeze(8).unwrap();
+    hasher.reset().unwrap();
+    assert!(hasher.update(b"abc"));
+  }
+
+  #[test]
+  fn shake_squeeze() {
+    let expected = {
+      let mut hash = Hash::new("shake256", Some(100), None).unwrap();
+      hash.update(b"abc");
+      hash.digest_and_drop()
+    };
+
+    let hasher = Hasher::new("shake256", None, None).unwrap();
+    hasher.update(b"abc");
+    let copy = hasher.clone_inner(Some(100)).unwrap().unwrap();
+    let mut squeezed = Vec::new();
+    for length in [1, 31, 0, 68] {
+      squeezed.extend(hasher.squeeze(length).unwrap().unwrap());
+    }
+    assert_eq!(squeezed, &*expected);
+    assert!(!hasher.update(b"abc"));
+    assert!(hasher.digest().is_none());
+    assert!(hasher.clone_inner(None).unwrap().is_none());
+    assert_eq!(copy.digest().unwrap(), expected);
+
+    let hasher = Hasher::new("sha256", None, None).unwrap();
+    assert!(matches!(
+      hasher.squeeze(32),
+      Err(HashError::SqueezeUnsupported)
+    ));
+    assert!(hasher.digest().is_some());
+  }
+
+  // https://www.rfc-editor.org/rfc/rfc6986#section-10
+  #[test]
+  fn streebog_rfc6986() {
+    const M1: &[u8] =
+      b"012345678901234567890123456789012345678901234567890123456789012";
+    const M2: &[u8] = &[
+      0xd1, 0xe5, 0x20, 0xe2, 0xe5, 0xf2, 0xf0, 0xe8, 0x2c, 0x20, 0xd1, 0xf2,
+      0xf0, 0xe8, 0xe1, 0xee, 0xe6, 0xe8, 0x20, 0xe2, 0xed, 0xf3, 0xf6, 0xe8,
+      0x2c, 0x20, 0xe2, 0xe5, 0xfe, 0xf2, 0xfa, 0x20, 0xf1, 0x20, 0xec, 0xee,
+      0xf0, 0xff, 0x20, 0xf1, 0xf2, 0xf0, 0xe5, 0xeb, 0xe0, 0xec, 0xe8, 0x20,
+      0xed, 0xe0, 0x20, 0xf5, 0xf0, 0xe0, 0xe1, 0xf0, 0xfb, 0xff, 0x20, 0xef,
+      0xeb, 0xfa, 0xea, 0xfb, 0x20, 0xc8, 0xe3, 0xee, 0xf0, 0xe5, 0xe2, 0xfb,
+    ];
+
+    assert_eq!(
+      hex_digest("streebog512", M1),
+      "1b54d01a4af5b9d5cc3d86d68d285462b19abc2475222f35c085122be4ba1ffa\
+       00ad30f8767b3a82384c6574f024c311e2a481332b08ef7f41797891c1646f48"
+    );
+    assert_eq!(
+      hex_digest("md_gost12_256", M1),
+      "9d151eefd8590b89daa6ba6cb74af9275dd051026bb149a452fd84e5e57b5500"
+    );
+    assert_eq!(
+      hex_digest("md_gost12_512", M2),
+      "1e88e62226bfca6f9994f1f2d51569e0daf8475a3b0fe61a5300eee46d961376\
+       035fe83549ada2b8620fcd7c496ce5b33f0cb9dddc2b6460143b03dabac9fb28"
+    );
+    assert_eq!(
+      hex_digest("streebog256", M2),
+      "9dd2fe4e90409e5da87f53976d7405b0c0cac628fc669a741d50063c557e8f50"
+    );
+  }
+
+  #[cfg(feature = "legacy_digests")]
+  #[test]
+  fn md2_rfc1319() {
+    assert_eq!(hex_digest("md2", b""), "8350e5a3e24c153df2275c9f80692773");
+    assert_eq!(
+      hex_digest("RSA-MD2", b"message digest"),
+      "ab4f496bfb2a530b219ff33031fe06b0"
+    );
+    assert_eq!(
+      faster_hex::hex_string(&Hash::digest_oneshot("md2", b"abc").unwrap()),
+      "da853b0d3f88d99b30283a69e6ded6bb"
+    );
+    assert_eq!(Hash::get_size("md2"), Some(16));
+    assert!(Hash::get_hashes().contains(&"md2"));
+  }
 }
@@ -0,0 +1,156 @@
+// Copyright 2018-2025 the Deno authors. MIT license.
+
+//! BLAKE2b and BLAKE2s with a salt and personalization string in the
+//! parameter block, as libsodium's `crypto_generichash_*_salt_personal`
+//! computes them. `blake2::Blake2bMac` takes the same parameters but only
+//! for a fixed output size, and absorbs a key block even for an empty key.
+
+use digest::block_buffer::BlockBuffer;
+use digest::block_buffer::Lazy;
+use digest::core_api::BlockSizeUser;
+use digest::core_api::BufferKindUser;
+use digest::core_api::UpdateCore;
+use digest::core_api::VariableOutputCore;
+use digest::generic_array::GenericArray;
+use digest::typenum::IsLess;
+use digest::typenum::True;
+use digest::typenum::U256;
+use digest::typenum::Unsigned;
+use zeroize::Zeroize;
+
+pub trait Blake2Core:
+  UpdateCore
+  + VariableOutputCore
+  + BufferKindUser<BufferKind = Lazy>
+  + BlockSizeUser<BlockSize: IsLess<U256, Output = True>>
+  + Clone
+{
+  fn new_with_params(
+    salt: &[u8],
+    personalization: &[u8],
+    key_size: usize,
+    output_size: usize,
+  ) -> Self;
+}
+
+impl Blake2Core for blake2::Blake2bVarCore {
+  fn new_with_params(
+    salt: &[u8],
+    personalization: &[u8],
+    key_size: usize,
+    output_size: usize,
+  ) -> Self {
+    Self::new_with_params(salt, personalization, key_size, output_size)
+  }
+}
+
+impl Blake2Core for blake2::Blake2sVarCore {
+  fn new_with_params(
+    salt: &[u8],
+    personalization: &[u8],
+    key_size: usize,
+    output_size: usize,
+  ) -> Self {
+    Self::new_with_params(salt, personalization, key_size, output_size)
+  }
+}
+
+pub struct Blake2Params<Core: Blake2Core> {
+  core: Core,
+  buffer: BlockBuffer<Core::BlockSize, Lazy>,
+  output_size: usize,
+}
+
+pub type Blake2bParams = Blake2Params<blake2::Blake2bVarCore>;
+pub type Blake2sParams = Blake2Params<blake2::Blake2sVarCore>;
+
+impl<Core: Blake2Core> Blake2Params<Core> {
+  /// The caller checks the lengths: `salt` and `personalization` up to a
+  /// quarter of the block size, `key` and `output_size` up to the maximum
+  /// output size, and `output_size` non-zero.
+  pub fn new(
+    key: &[u8],
+    salt: &[u8],
+    personalization: &[u8],
+    output_size: usize,
+  ) -> Self {
+    let mut context = Self {
+      core: Core::new_with_params(
+        salt,
+        personalization,
+        key.len(),
+        output_size,
+      ),
+      buffer: Default::default(),
+      output_size,
+    };
+    // A non-empty key is zero-padded to a full first block.
+    if !key.is_empty() {
+      let mut block = GenericArray::<u8, Core::BlockSize>::default();
+      block[..key.len()].copy_from_slice(key);
+      context.update(&block);
+      block.as_mut_slice().zeroize();
+    }
+    context
+  }
+
+  pub fn update(&mut self, data: &[u8]) {
+    let Self { core, buffer, .. } = self;
+    buffer.digest_blocks(data, |blocks| core.update_blocks(blocks));
+  }
+
+  /// Leaves the state to be wiped by its owner.
+  pub fn finalize(&mut self) -> Box<[u8]> {
+    let mut output = GenericArray::<u8, Core::OutputSize>::default();
+    self
+      .core
+      .finalize_variable_core(&mut self.buffer, &mut output);
+    Box::from(&output[..self.output_size])
+  }
+
+  pub fn max_output_size() -> usize {
+    Core::OutputSize::USIZE
+  }
+}
+
+impl<Core: Blake2Core> Clone for Blake2Params<Core> {
+  fn clone(&self) -> Self {
+    Self {
+      core: self.core.clone(),
+      buffer: self.buffer.clone(),
+      output_size: self.output_size,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use digest::Mac;
+  use digest::VariableOutput;
+
+  use super::*;
+
+  #[test]
+  fn matches_blake2_crate() {
+    let data = b"The quick brown fox jumps over the lazy dog";
+    let mut context = Blake2bParams::new(&[], &[], &[], 40);
+    context.update(data);
+    let mut expected = [0; 40];
+    let mut reference = blake2::Blake2bVar::new(40).unwrap();
+    digest::Update::update(&mut reference, data);
+    reference.finalize_variable(&mut expected).unwrap();
+    assert_eq!(*context.finalize(), expected);
+
+    let mut context = Blake2sParams::new(b"key", b"salt", b"persona", 32);
+    context.update(data);
+    let mut reference = blake2::Blake2sMac256::new_with_salt_and_personal(
+      b"key", b"salt", b"persona",
+    )
+    .unwrap();
+    reference.update(data);
+    assert_eq!(
+      *context.finalize(),
+      *reference.finalize().into_bytes().as_slice()
+    );
+  }
+}
@@ -0,0 +1,99 @@
+// Copyright 2018-2025 the Deno authors. MIT license.
+
+//! KMAC128 and KMAC256 (NIST SP 800-185), built on the cSHAKE contexts from
+//! `sha3`.
+
+use digest::ExtendableOutput;
+use digest::Update;
+
+#[derive(Clone)]
+pub struct Kmac<D> {
+  cshake: D,
+}
+
+pub type Kmac128 = Kmac<sha3::CShake128>;
+pub type Kmac256 = Kmac<sha3::CShake256>;
+
+impl Kmac128 {
+  pub fn new(key: &[u8], customization: &[u8]) -> Self {
+    let core =
+      sha3::CShake128Core::new_with_function_name(b"KMAC", customization);
+    Self::with_key(sha3::CShake128::from_core(core), 168, key)
+  }
+}
+
+impl Kmac256 {
+  pub fn new(key: &[u8], customization: &[u8]) -> Self {
+    let core =
+      sha3::CShake256Core::new_with_function_name(b"KMAC", customization);
+    Self::with_key(sha3::CShake256::from_core(core), 136, key)
+  }
+}
+
+impl<D: Update + ExtendableOutput> Kmac<D> {
+  fn with_key(mut cshake: D, rate: usize, key: &[u8]) -> Self {
+    let mut encoded_key = left_encode(rate as u64);
+    encoded_key.extend_from_slice(&left_encode(key.len() as u64 * 8));
+    encoded_key.extend_from_slice(key);
+    // bytepad() to a multiple of the rate.
+    encoded_key.resize(encoded_key.len().next_multiple_of(rate), 0);
+    cshake.update(&encoded_key);
+    Self { cshake }
+  }
+
+  pub fn update(&mut self, data: &[u8]) {
+    self.cshake.update(data);
+  }
+
+  /// KMAC proper: the output length is bound into the result, so a
+  /// shorter output is not a prefix of a longer one.
+  pub fn finalize(mut self, output_length: usize) -> Box<[u8]> {
+    self.cshake.update(&right_encode(output_length as u64 * 8));
+    self.cshake.finalize_boxed(output_length)
+  }
+
+  /// KMACXOF, for output of arbitrary and not yet known length.
+  pub fn finalize_xof(mut self) -> D::Reader {
+    self.cshake.update(&right_encode(0));
+    self.cshake.finalize_xof()
+  }
+}
+
+fn encoded_bytes(value: u64) -> (usize, [u8; 8]) {
+  let bytes = value.to_be_bytes();
+  // At least one byte, even for zero.
+  let skip = (value.leading_zeros() as usize / 8).min(7);
+  (skip, bytes)
+}
+
+fn left_encode(value: u64) -> Vec<u8> {
+  let (skip, bytes) = encoded_bytes(value);
+  let mut encoded = vec![(8 - skip) as u8];
+  encoded.extend_from_slice(&bytes[skip..]);
+  encoded
+}
+
+fn right_encode(value: u64) -> Vec<u8> {
+  let (skip, bytes) = encoded_bytes(value);
+  let mut encoded = bytes[skip..].to_vec();
+  encoded.push((8 - skip) as u8);
+  encoded
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn integer_encodings() {
+    assert_eq!(left_encode(0), [1, 0]);
+    assert_eq!(left_encode(168), [1, 168]);
+    assert_eq!(left_encode(256), [2, 1, 0]);
+    assert_eq!(right_encode(0), [0, 1]);
+    assert_eq!(right_encode(512), [2, 0, 2]);
+    assert_eq!(
+      right_encode(u64::MAX),
+      [[0xff; 8].as_slice(), &[8]].concat()
+    );
+  }
+}
@@ -0,0 +1,403 @@
+// Copyright 2018-2025 the Deno authors. MIT license.
+
+//! SHA-2 contexts whose state can be exported and restored. They run on
+//! aws-lc like the other SHA-2 digests, but own its context structs, since
+//! the `aws-lc-rs` contexts keep their state private.
+
+use std::os::raw::c_int;
+use std::os::raw::c_void;
+
+use aws_lc_sys::SHA256_CTX;
+use aws_lc_sys::SHA512_CTX;
+use digest::FixedOutput;
+use digest::generic_array::ArrayLength;
+use digest::typenum::Unsigned;
+
+/// The fields shared by the aws-lc SHA-256 and SHA-512 context structs.
+pub trait Sha2Context: Copy + Send + 'static {
+  const WORD_SIZE: usize;
+  const BLOCK_SIZE: usize;
+
+  fn write_words(&self, out: &mut [u8]);
+  fn read_words(&mut self, bytes: &[u8]);
+  /// Number of message bits absorbed so far, buffered ones included.
+  fn bit_length(&self) -> u128;
+  /// Returns false if `bits` doesn't fit the context's counter.
+  fn set_bit_length(&mut self, bits: u128) -> bool;
+  fn buffered(&self) -> &[u8];
+  fn set_buffered(&mut self, data: &[u8]);
+}
+
+impl Sha2Context for SHA256_CTX {
+  const WORD_SIZE: usize = 4;
+  const BLOCK_SIZE: usize = 64;
+
+  fn write_words(&self, out: &mut [u8]) {
+    for (word, out) in self.h.iter().zip(out.chunks_exact_mut(4)) {
+      out.copy_from_slice(&word.to_be_bytes());
+    }
+  }
+
+  fn read_words(&mut self, bytes: &[u8]) {
+    for (word, bytes) in self.h.iter_mut().zip(bytes.chunks_exact(4)) {
+      *word = u32::from_be_bytes(bytes.try_into().unwrap());
+    }
+  }
+
+  fn bit_length(&self) -> u128 {
+    ((self.Nh as u128) << 32) | self.Nl as u128
+  }
+
+  fn set_bit_length(&mut self, bits: u128) -> bool {
+    let Ok(bits) = u64::try_from(bits) else {
+      return false;
+    };
+    self.Nl = bits as u32;
+    self.Nh = (bits >> 32) as u32;
+    true
+  }
+
+  fn buffered(&self) -> &[u8] {
+    &self.data[..self.num as usize]
+  }
+
+  fn set_buffered(&mut self, data: &[u8]) {
+    self.data[..data.len()].copy_from_slice(data);
+    self.num = data.len() as _;
+  }
+}
+
+impl Sha2Context for SHA512_CTX {
+  const WORD_SIZE: usize = 8;
+  const BLOCK_SIZE: usize = 128;
+
+  fn write_words(&self, out: &mut [u8]) {
+    for (word, out) in self.h.iter().zip(out.chunks_exact_mut(8)) {
+      out.copy_from_slice(&word.to_be_bytes());
+    }
+  }
+
+  fn read_words(&mut self, bytes: &[u8]) {
+    for (word, bytes) in self.h.iter_mut().zip(bytes.chunks_exact(8)) {
+      *word = u64::from_be_bytes(bytes.try_into().unwrap());
+    }
+  }
+
+  fn bit_length(&self) -> u128 {
+    ((self.Nh as u128) << 64) | self.Nl as u128
+  }
+
+  fn set_bit_length(&mut self, bits: u128) -> bool {
+    self.Nl = bits as u64;
+    self.Nh = (bits >> 64) as u64;
+    true
+  }
+
+  fn buffered(&self) -> &[u8] {
+    &self.p[..self.num as usize]
+  }
+
+  fn set_buffered(&mut self, data: &[u8]) {
+    self.p[..data.len()].copy_from_slice(data);
+    self.num = data.len() as _;
+  }
+}
+
+pub trait Sha2Algo {
+  type Context: Sha2Context;
+  type OutputSize: ArrayLength<u8> + 'static;
+  type BlockSize: ArrayLength<u8> + 'static;
+
+  const INIT: unsafe extern "C" fn(*mut Self::Context) -> c_int;
+  const UPDATE: unsafe extern "C" fn(
+    *mut Self::Context,
+    *const c_void,
+    usize,
+  ) -> c_int;
+  const FINAL: unsafe extern "C" fn(*mut u8, *mut Self::Context) -> c_int;
+}
+
+pub struct ResumableSha2<Algo: Sha2Algo> {
+  context: Algo::Context,
+}
+
+impl<Algo: Sha2Algo> ResumableSha2<Algo> {
+  const STATE_SIZE: usize = 8 * <Algo::Context as Sha2Context>::WORD_SIZE;
+
+  /// Serializes the context as the eight state words, the number of
+  /// compressed blocks and the buffered tail, all big-endian.
+  pub fn export_state(&self) -> Vec<u8> {
+    let data = self.context.buffered();
+    let blocks = (self.context.bit_length() / 8 - data.len() as u128)
+      / <Algo::Context as Sha2Context>::BLOCK_SIZE as u128;
+    let mut state = vec![0; Self::STATE_SIZE + 9 + data.len()];
+    let (words, rest) = state.split_at_mut(Self::STATE_SIZE);
+    self.context.write_words(words);
+    // Anything hashed in this lifetime is far below 2^64 blocks.
+    rest[..8].copy_from_slice(&(blocks as u64).to_be_bytes());
+    rest[8] = data.len() as u8;
+    rest[9..].copy_from_slice(data);
+    state
+  }
+
+  /// The inverse of `export_state`. Returns `None` if `state` isn't a
+  /// well-formed context for this digest.
+  pub fn import_state(state: &[u8]) -> Option<Self> {
+    if state.len() < Self::STATE_SIZE + 9 {
+      return None;
+    }
+    let (words, rest) = state.split_at(Self::STATE_SIZE);
+    let (blocks, rest) = rest.split_at(8);
+    let (buffered, data) = rest.split_at(1);
+    let block_size = <Algo::Context as Sha2Context>::BLOCK_SIZE;
+    if data.len() != buffered[0] as usize || data.len() >= block_size {
+      return None;
+    }
+
+    let blocks = u64::from_be_bytes(blocks.try_into().ok()?);
+    let bytes = blocks as u128 * block_size as u128 + data.len() as u128;
+    let mut hash = Self::default();
+    if !hash.context.set_bit_length(bytes * 8) {
+      return None;
+    }
+    hash.context.read_words(words);
+    hash.context.set_buffered(data);
+    Some(hash)
+  }
+}
+
+impl<Algo: Sha2Algo> Clone for ResumableSha2<Algo> {
+  fn clone(&self) -> Self {
+    Self {
+      context: self.context,
+    }
+  }
+}
+
+impl<Algo: Sha2Algo> Default for ResumableSha2<Algo> {
+  fn default() -> Self {
+    // SAFETY: the context structs only hold integers and byte arrays, and
+    // the init function sets every field.
+    unsafe {
+      let mut context = std::mem::zeroed();
+      Algo::INIT(&mut context);
+      Self { context }
+    }
+  }
+}
+
+impl<Algo: Sha2Algo> digest::HashMarker for ResumableSha2<Algo> {}
+impl<Algo: Sha2Algo> digest::Reset for ResumableSha2<Algo> {
+  fn reset(&mut self) {
+    *self = Self::default();
+  }
+}
+impl<Algo: Sha2Algo> digest::Update for ResumableSha2<Algo> {
+  fn update(&mut self, data: &[u8]) {
+    // SAFETY: the context was set up by the init function and `data` is
+    // valid for `data.len()` bytes.
+    unsafe {
+      Algo::UPDATE(&mut self.context, data.as_ptr().cast(), data.len());
+    }
+  }
+}
+impl<Algo: Sha2Algo> digest::OutputSizeUser for ResumableSha2<Algo> {
+  type OutputSize = Algo::OutputSize;
+}
+impl<Algo: Sha2Algo> digest::core_api::BlockSizeUser for ResumableSha2<Algo> {
+  type BlockSize = Algo::BlockSize;
+}
+impl<Algo: Sha2Algo> digest::FixedOutput for ResumableSha2<Algo> {
+  fn finalize_into(mut self, out: &mut digest::Output<Self>) {
+    debug_assert_eq!(out.len(), Algo::OutputSize::USIZE);
+    // SAFETY: `out` holds the digest size of the algorithm.
+    unsafe {
+      Algo::FINAL(out.as_mut_ptr(), &mut self.context);
+    }
+  }
+}
+impl<Algo: Sha2Algo> digest::FixedOutputReset for ResumableSha2<Algo> {
+  fn finalize_into_reset(&mut self, out: &mut digest::Output<Self>) {
+    let hash = std::mem::take(self);
+    hash.finalize_into(out);
+  }
+}
+
+macro_rules! sha2_algo {
+  ($algo:ident, $context:ty, $output_size:ident, $block_size:ident, $init:ident, $update:ident, $final:ident) => {
+    pub struct $algo;
+    impl Sha2Algo for $algo {
+      type Context = $context;
+      type OutputSize = digest::typenum::$output_size;
+      type BlockSize = digest::typenum::$block_size;
+
+      const INIT: unsafe extern "C" fn(*mut $context) -> c_int =
+        aws_lc_sys::$init;
+      const UPDATE: unsafe extern "C" fn(
+        *mut $context,
+        *const c_void,
+        usize,
+      ) -> c_int = aws_lc_sys::$update;
+      const FINAL: unsafe extern "C" fn(*mut u8, *mut $context) -> c_int =
+        aws_lc_sys::$final;
+    }
+  };
+}
+
+sha2_algo!(
+  Sha224Algo,
+  SHA256_CTX,
+  U28,
+  U64,
+  SHA224_Init,
+  SHA224_Update,
+  SHA224_Final
+);
+sha2_algo!(
+  Sha256Algo,
+  SHA256_CTX,
+  U32,
+  U64,
+  SHA256_Init,
+  SHA256_Update,
+  SHA256_Final
+);
+sha2_algo!(
+  Sha384Algo,
+  SHA512_CTX,
+  U48,
+  U128,
+  SHA384_Init,
+  SHA384_Update,
+  SHA384_Final
+);
+sha2_algo!(
+  Sha512Algo,
+  SHA512_CTX,
+  U64,
+  U128,
+  SHA512_Init,
+  SHA512_Update,
+  SHA512_Final
+);
+
+pub type ResumableSha224 = ResumableSha2<Sha224Algo>;
+pub type ResumableSha256 = ResumableSha2<Sha256Algo>;
+pub type ResumableSha384 = ResumableSha2<Sha384Algo>;
+pub type ResumableSha512 = ResumableSha2<Sha512Algo>;
+
+/// One of the resumable SHA-2 contexts.
+#[derive(Clone)]
+pub enum Sha2 {
+  Sha224(ResumableSha224),
+  Sha256(ResumableSha256),
+  Sha384(ResumableSha384),
+  Sha512(ResumableSha512),
+}
+
+impl Sha2 {
+  pub fn name(&self) -> &'static str {
+    match self {
+      Sha2::Sha224(_) => "sha224",
+      Sha2::Sha256(_) => "sha256",
+      Sha2::Sha384(_) => "sha384",
+      Sha2::Sha512(_) => "sha512",
+    }
+  }
+
+  pub fn update(&mut self, data: &[u8]) {
+    match self {
+      Sha2::Sha224(context) => digest::Update::update(context, data),
+      Sha2::Sha256(context) => digest::Update::update(context, data),
+      Sha2::Sha384(context) => digest::Update::update(context, data),
+      Sha2::Sha512(context) => digest::Update::update(context, data),
+    }
+  }
+
+  pub fn finalize(self) -> Box<[u8]> {
+    match self {
+      Sha2::Sha224(context) => Box::from(context.finalize_fixed().as_slice()),
+      Sha2::Sha256(context) => Box::from(context.finalize_fixed().as_slice()),
+      Sha2::Sha384(context) => Box::from(context.finalize_fixed().as_slice()),
+      Sha2::Sha512(context) => Box::from(context.finalize_fixed().as_slice()),
+    }
+  }
+
+  pub fn export_state(&self) -> Vec<u8> {
+    match self {
+      Sha2::Sha224(context) => context.export_state(),
+      Sha2::Sha256(context) => context.export_state(),
+      Sha2::Sha384(context) => context.export_state(),
+      Sha2::Sha512(context) => context.export_state(),
+    }
+  }
+
+  /// Restores a context exported by the digest called `name`.
+  pub fn import_state(name: &str, state: &[u8]) -> Option<Self> {
+    match name {
+      "sha224" => ResumableSha224::import_state(state).map(Sha2::Sha224),
+      "sha256" => ResumableSha256::import_state(state).map(Sha2::Sha256),
+      "sha384" => ResumableSha384::import_state(state).map(Sha2::Sha384),
+      "sha512" => ResumableSha512::import_state(state).map(Sha2::Sha512),
+      _ => None,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use digest::Digest;
+  use rand::Rng;
+
+  use super::*;
+
+  fn assert_matches_rust_crypto<Algo: Sha2Algo, D: Digest>() {
+    let mut rng = rand::thread_rng();
+    for _ in 0..64 {
+      let len = rng.gen_range(0..1024);
+      let data = (0..len).map(|_| rng.r#gen()).collect::<Vec<u8>>();
+      let split = rng.gen_range(0..=len);
+
+      let mut context = ResumableSha2::<Algo>::default();
+      Digest::update(&mut context, &data[..split]);
+      let state = context.export_state();
+      let mut context = ResumableSha2::<Algo>::import_state(&state).unwrap();
+      Digest::update(&mut context, &data[split..]);
+
+      assert_eq!(context.finalize().as_slice(), D::digest(&data).as_slice());
+    }
+  }
+
+  #[test]
+  fn matches_rust_crypto() {
+    assert_matches_rust_crypto::<Sha224Algo, sha2::Sha224>();
+    assert_matches_rust_crypto::<Sha256Algo, sha2::Sha256>();
+    assert_matches_rust_crypto::<Sha384Algo, sha2::Sha384>();
+    assert_matches_rust_crypto::<Sha512Algo, sha2::Sha512>();
+  }
+
+  #[test]
+  fn rejects_malformed_state() {
+    let mut context = ResumableSha256::default();
+    Digest::update(&mut context, b"abc");
+    let state = context.export_state();
+    assert!(ResumableSha256::import_state(&state).is_some());
+    assert!(ResumableSha256::import_state(&state[..state.len() - 1]).is_none());
+    assert!(ResumableSha256::import_state(&[]).is_none());
+    // A SHA-256 state is too short to be a SHA-512 state.
+    assert!(ResumableSha512::import_state(&state).is_none());
+
+    let mut state = state;
+    state[8 * 4 + 8] = 64;
+    assert!(ResumableSha256::import_state(&state).is_none());
+    // A full block can't be buffered.
+    let mut state = state[..8 * 4 + 9].to_vec();
+    state[8 * 4 + 8] = 64;
+    state.extend_from_slice(&[0; 64]);
+    assert!(ResumableSha256::import_state(&state).is_none());
+    // The bit counter of SHA-256 is 64 bits wide.
+    let mut state = ResumableSha256::default().export_state();
+    state[8 * 4..8 * 4 + 8].copy_from_slice(&u64::MAX.to_be_bytes());
+    assert!(ResumableSha256::import_state(&state).is_none());
+  }
+}
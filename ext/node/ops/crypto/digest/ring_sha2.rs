@@ -7,6 +7,7 @@ use digest::generic_array::ArrayLength;
 pub trait RingDigestAlgo {
   fn algorithm() -> &'static aws_lc_rs::digest::Algorithm;
   type OutputSize: ArrayLength<u8> + 'static;
+  type BlockSize: ArrayLength<u8> + 'static;
 }
 
 pub struct RingDigest<Algo: RingDigestAlgo> {
@@ -45,6 +46,11 @@ impl<Algo: RingDigestAlgo> digest::Update for RingDigest<Algo> {
 impl<Algo: RingDigestAlgo> digest::OutputSizeUser for RingDigest<Algo> {
   type OutputSize = Algo::OutputSize;
 }
+impl<Algo: RingDigestAlgo> digest::core_api::BlockSizeUser
+  for RingDigest<Algo>
+{
+  type BlockSize = Algo::BlockSize;
+}
 impl<Algo: RingDigestAlgo> digest::FixedOutput for RingDigest<Algo> {
   fn finalize_into(self, out: &mut digest::Output<Self>) {
     let result = self.context.finish();
@@ -61,22 +67,54 @@ impl<Algo: RingDigestAlgo> digest::FixedOutputReset for RingDigest<Algo> {
   }
 }
 
-pub struct RingSha256Algo;
-impl RingDigestAlgo for RingSha256Algo {
+pub struct RingSha1Algo;
+impl RingDigestAlgo for RingSha1Algo {
   fn algorithm() -> &'static aws_lc_rs::digest::Algorithm {
-    &aws_lc_rs::digest::SHA256
+    &aws_lc_rs::digest::SHA1_FOR_LEGACY_USE_ONLY
   }
 
-  type OutputSize = digest::typenum::U32;
+  type OutputSize = digest::typenum::U20;
+  type BlockSize = digest::typenum::U64;
 }
-pub struct RingSha512Algo;
-impl RingDigestAlgo for RingSha512Algo {
-  fn algorithm() -> &'static aws_lc_rs::digest::Algorithm {
-    &aws_lc_rs::digest::SHA512
+
+pub type RingSha1 = RingDigest<RingSha1Algo>;
+
+#[cfg(test)]
+mod tests {
+  use digest::Digest;
+  use digest::DynDigest;
+  use rand::Rng;
+
+  use super::*;
+
+  fn assert_matches_rust_crypto<R, D>()
+  where
+    R: Digest + DynDigest + Clone,
+    D: Digest,
+  {
+    let mut rng = rand::thread_rng();
+    for _ in 0..64 {
+      let len = rng.gen_range(0..1024);
+      let data = (0..len).map(|_| rng.r#gen()).collect::<Vec<u8>>();
+      let split = rng.gen_range(0..=len);
+
+      let mut ring = <R as Digest>::new();
+      Digest::update(&mut ring, &data[..split]);
+      let copy = ring.box_clone();
+      Digest::update(&mut ring, &data[split..]);
+      let expected = D::digest(&data);
+
+      assert_eq!(DynDigest::output_size(&ring), expected.len());
+      assert_eq!(&*Box::new(ring).finalize(), expected.as_slice());
+
+      let mut copy = copy;
+      copy.update(&data[split..]);
+      assert_eq!(&*copy.finalize(), expected.as_slice());
+    }
   }
 
-  type OutputSize = digest::typenum::U64;
+  #[test]
+  fn matches_rust_crypto() {
+    assert_matches_rust_crypto::<RingSha1, sha1::Sha1>();
+  }
 }
-
-pub type RingSha256 = RingDigest<RingSha256Algo>;
-pub type RingSha512 = RingDigest<RingSha512Algo>;
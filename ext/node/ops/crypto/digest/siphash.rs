@@ -0,0 +1,73 @@
+// Copyright 2018-2025 the Deno authors. MIT license.
+
+//! SipHash-2-4 and SipHash-1-3 (Aumasson and Bernstein, "SipHash: a fast
+//! short-input PRF"), with 64- or 128-bit output. The 128-bit variant is
+//! initialized differently, so its output doesn't extend the 64-bit one.
+
+use std::hash::Hasher;
+
+use siphasher::sip;
+use siphasher::sip128;
+use siphasher::sip128::Hasher128;
+
+pub const KEY_SIZE: usize = 16;
+
+pub trait NewWithKey {
+  fn new_with_key(key: &[u8; KEY_SIZE]) -> Self;
+}
+
+macro_rules! impl_new_with_key {
+  ($($type:ty),*) => {
+    $(impl NewWithKey for $type {
+      fn new_with_key(key: &[u8; KEY_SIZE]) -> Self {
+        <$type>::new_with_key(key)
+      }
+    })*
+  };
+}
+
+impl_new_with_key!(
+  sip::SipHasher24,
+  sip::SipHasher13,
+  sip128::SipHasher24,
+  sip128::SipHasher13
+);
+
+#[derive(Clone)]
+pub enum SipHash<Short, Long> {
+  Output64(Short),
+  Output128(Long),
+}
+
+pub type SipHash24 = SipHash<sip::SipHasher24, sip128::SipHasher24>;
+pub type SipHash13 = SipHash<sip::SipHasher13, sip128::SipHasher13>;
+
+impl<Short, Long> SipHash<Short, Long>
+where
+  Short: Hasher + NewWithKey,
+  Long: Hasher + Hasher128 + NewWithKey,
+{
+  /// The caller checks that `output_size` is 8 or 16.
+  pub fn new(key: &[u8; KEY_SIZE], output_size: usize) -> Self {
+    if output_size == 16 {
+      Self::Output128(Long::new_with_key(key))
+    } else {
+      Self::Output64(Short::new_with_key(key))
+    }
+  }
+
+  pub fn update(&mut self, data: &[u8]) {
+    match self {
+      Self::Output64(hasher) => hasher.write(data),
+      Self::Output128(hasher) => hasher.write(data),
+    }
+  }
+
+  /// Both halves are little-endian, as in the reference implementation.
+  pub fn finalize(&self) -> Box<[u8]> {
+    match self {
+      Self::Output64(hasher) => Box::from(hasher.finish().to_le_bytes()),
+      Self::Output128(hasher) => Box::from(hasher.finish128().as_bytes()),
+    }
+  }
+}
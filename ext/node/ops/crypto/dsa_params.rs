@@ -0,0 +1,107 @@
+// Copyright 2018-2025 the Deno authors. MIT license.
+
+//! DSA domain parameters with the probable primes of FIPS 186-4, appendix
+//! A.1.1.2, and the unverifiable generator of appendix A.2.1, as OpenSSL
+//! generates them. `dsa::Components::generate` searches for p and q without
+//! a domain parameter seed.
+
+use num_bigint_dig::BigUint;
+use num_bigint_dig::prime::probably_prime;
+use num_integer::Integer;
+use num_traits::One;
+use num_traits::Zero;
+use rand::RngCore;
+use sha2::Digest;
+use sha2::Sha256;
+
+const OUTLEN: usize = 256;
+
+/// Miller-Rabin rounds for p and q (FIPS 186-4, table C.1).
+fn rounds(l: usize) -> usize {
+  match l {
+    0..=1024 => 40,
+    1025..=2048 => 56,
+    _ => 64,
+  }
+}
+
+fn hash(value: &BigUint, len: usize) -> BigUint {
+  let bytes = value.to_bytes_be();
+  let mut input = vec![0; len.saturating_sub(bytes.len())];
+  input.extend_from_slice(&bytes);
+  BigUint::from_bytes_be(&Sha256::digest(&input))
+}
+
+/// Returns p, q and g for an L-bit p and an N-bit q. N must be at most the
+/// 256 bits of SHA-256.
+pub fn generate(l: usize, n: usize) -> (BigUint, BigUint, BigUint) {
+  debug_assert!(n <= OUTLEN && n % 8 == 0 && l > n);
+  let mut rng = rand::thread_rng();
+  let seedlen = n / 8;
+  let seed_modulus = BigUint::one() << (seedlen * 8);
+  let blocks = l.div_ceil(OUTLEN) - 1;
+  let rounds = rounds(l);
+
+  let (p, q) = 'seed: loop {
+    let mut seed = vec![0; seedlen];
+    rng.fill_bytes(&mut seed);
+    let seed = BigUint::from_bytes_be(&seed);
+
+    let u = hash(&seed, seedlen) % (BigUint::one() << (n - 1));
+    let mut q = (BigUint::one() << (n - 1)) + &u;
+    if q.is_even() {
+      q += 1u32;
+    }
+    if !probably_prime(&q, rounds) {
+      continue;
+    }
+
+    let two_q = &q << 1;
+    let mut offset = BigUint::one();
+    for _counter in 0..4 * l {
+      let mut w = BigUint::zero();
+      for j in 0..=blocks {
+        let v = (&seed + &offset + BigUint::from(j)) % &seed_modulus;
+        w += hash(&v, seedlen) << (j * OUTLEN);
+      }
+      // Equivalent to taking the last block mod 2^b.
+      w %= BigUint::one() << (l - 1);
+      let x = w + (BigUint::one() << (l - 1));
+      let c = &x % &two_q;
+      let p = x + 1u32 - c;
+      if p.bits() == l && probably_prime(&p, rounds) {
+        break 'seed (p, q);
+      }
+      offset += BigUint::from(blocks + 1);
+    }
+  };
+
+  let e = (&p - 1u32) / &q;
+  let mut h = BigUint::from(2u32);
+  let g = loop {
+    let g = h.modpow(&e, &p);
+    if !g.is_one() {
+      break g;
+    }
+    h += 1u32;
+  };
+
+  (p, q, g)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn domain_parameters() {
+    for (l, n) in [(1024, 160), (2048, 224)] {
+      let (p, q, g) = generate(l, n);
+      assert_eq!(p.bits(), l);
+      assert_eq!(q.bits(), n);
+      assert!(((&p - 1u32) % &q).is_zero());
+      assert!(g > BigUint::one() && g < p);
+      assert!(g.modpow(&q, &p).is_one());
+    }
+  }
+}
@@ -0,0 +1,263 @@
+// Copyright 2018-2025 the Deno authors. MIT license.
+
+//! Ed25519ph and Ed25519ctx (RFC 8032, section 5.1), which prefix both
+//! hashes with `dom2(phflag, context)`. Ed25519ph goes through
+//! `ed25519-dalek`'s prehashed API. Its `dom2` always has the prehash flag
+//! set, so Ed25519ctx is written out over `curve25519-dalek` here. Both
+//! reject a small-order `R` or public key, like `verify_strict`.
+
+use curve25519_dalek::EdwardsPoint;
+use curve25519_dalek::Scalar;
+use curve25519_dalek::edwards::CompressedEdwardsY;
+use curve25519_dalek::scalar::clamp_integer;
+use digest::FixedOutput;
+use digest::HashMarker;
+use digest::Output;
+use digest::OutputSizeUser;
+use digest::Update;
+use digest::consts::U64;
+use ed25519_dalek::Signature;
+use ed25519_dalek::SigningKey;
+use ed25519_dalek::VerifyingKey;
+use sha2::Digest as _;
+use sha2::Sha512;
+use zeroize::Zeroizing;
+
+pub const MAX_CONTEXT_LENGTH: usize = 255;
+/// Ed25519ph signs the SHA-512 digest of the message.
+pub const PREHASH_LENGTH: usize = 64;
+pub const SIGNATURE_LENGTH: usize = 64;
+
+/// The RFC 8032 variant: `dom2(1, context)` when the message is a
+/// prehash, `dom2(0, context)` otherwise. Callers keep the context within
+/// `MAX_CONTEXT_LENGTH` bytes.
+#[derive(Clone, Copy)]
+pub struct Dom2<'a> {
+  pub prehashed: bool,
+  pub context: &'a [u8],
+}
+
+impl Dom2<'_> {
+  fn hash(&self, parts: &[&[u8]]) -> Scalar {
+    let mut hasher = Sha512::new()
+      .chain_update(b"SigEd25519 no Ed25519 collisions")
+      .chain_update([self.prehashed as u8, self.context.len() as u8])
+      .chain_update(self.context);
+    for part in parts {
+      Update::update(&mut hasher, part);
+    }
+    let hash = Zeroizing::new(<[u8; 64]>::from(hasher.finalize()));
+    Scalar::from_bytes_mod_order_wide(&hash)
+  }
+}
+
+/// A digest that outputs the prehash written into it. `ed25519-dalek`
+/// finalizes the SHA-512 state itself, but the op gets the digest.
+#[derive(Clone, Default)]
+struct Prehash(Vec<u8>);
+
+impl HashMarker for Prehash {}
+
+impl OutputSizeUser for Prehash {
+  type OutputSize = U64;
+}
+
+impl Update for Prehash {
+  fn update(&mut self, data: &[u8]) {
+    self.0.extend_from_slice(data);
+  }
+}
+
+impl FixedOutput for Prehash {
+  fn finalize_into(self, out: &mut Output<Self>) {
+    out.copy_from_slice(&self.0);
+  }
+}
+
+/// With `dom2.prehashed`, the message is the `PREHASH_LENGTH`-byte
+/// SHA-512 digest.
+pub fn sign(
+  key: &SigningKey,
+  dom2: Dom2,
+  message: &[u8],
+) -> [u8; SIGNATURE_LENGTH] {
+  if dom2.prehashed {
+    // The only error is a context over `MAX_CONTEXT_LENGTH` bytes.
+    return key
+      .sign_prehashed(Prehash::new_with_prefix(message), Some(dom2.context))
+      .unwrap()
+      .to_bytes();
+  }
+
+  let hash = Zeroizing::new(<[u8; 64]>::from(Sha512::digest(key.as_bytes())));
+  let mut scalar = Zeroizing::new([0; 32]);
+  scalar.copy_from_slice(&hash[..32]);
+  let s = Zeroizing::new(Scalar::from_bytes_mod_order(clamp_integer(*scalar)));
+  let public = key.verifying_key().to_bytes();
+
+  let r = Zeroizing::new(dom2.hash(&[&hash[32..], message]));
+  let r_bytes = EdwardsPoint::mul_base(&r).compress().to_bytes();
+  let k = dom2.hash(&[&r_bytes, &public, message]);
+  let signature_s = *r + k * *s;
+
+  let mut signature = [0; SIGNATURE_LENGTH];
+  signature[..32].copy_from_slice(&r_bytes);
+  signature[32..].copy_from_slice(signature_s.as_bytes());
+  signature
+}
+
+/// Returns false for a signature or prehash of the wrong length rather
+/// than an error, as for plain Ed25519.
+pub fn verify(
+  key: &VerifyingKey,
+  dom2: Dom2,
+  message: &[u8],
+  signature: &[u8],
+) -> bool {
+  let Ok(signature) = Signature::from_slice(signature) else {
+    return false;
+  };
+  if dom2.prehashed {
+    return message.len() == PREHASH_LENGTH
+      && key
+        .verify_prehashed_strict(
+          Prehash::new_with_prefix(message),
+          Some(dom2.context),
+          &signature,
+        )
+        .is_ok();
+  }
+
+  let r_bytes = signature.r_bytes();
+  // S must be below the group order.
+  let Some(s) =
+    Option::from(Scalar::from_canonical_bytes(*signature.s_bytes()))
+  else {
+    return false;
+  };
+  let Some(r) = CompressedEdwardsY(*r_bytes).decompress() else {
+    return false;
+  };
+  let Some(a) = CompressedEdwardsY(key.to_bytes()).decompress() else {
+    return false;
+  };
+  if r.is_small_order() || a.is_small_order() {
+    return false;
+  }
+  let k = dom2.hash(&[r_bytes, key.as_bytes(), message]);
+  // The cofactorless equation [S]B - [k]A = R, as in `ed25519-dalek`.
+  EdwardsPoint::vartime_double_scalar_mul_basepoint(&k, &-a, &s)
+    .compress()
+    .as_bytes()
+    == r_bytes
+}
+
+#[cfg(test)]
+mod tests {
+  use curve25519_dalek::traits::Identity as _;
+
+  use super::*;
+
+  fn unhex(hex: &str) -> Vec<u8> {
+    let mut bytes = vec![0; hex.len() / 2];
+    faster_hex::hex_decode(hex.as_bytes(), &mut bytes).unwrap();
+    bytes
+  }
+
+  // RFC 8032, section 7.2 (Ed25519ctx) and section 7.3 (Ed25519ph).
+  #[test]
+  fn rfc8032_vectors() {
+    let vectors = [
+      (
+        "0305334e381af78f141cb666f6199f57bc3495335a256a95bd2a55bf546663f6",
+        false,
+        "666f6f",
+        "f726936d19c800494e3fdaff20b276a8".to_string(),
+        "55a4cc2f70a54e04288c5f4cd1e45a7bb520b36292911876cada7323198dd87a8b36950b95130022907a7fb7c4e9b2d5f6cca685a587b4b21f4b888e4e7edb0d",
+      ),
+      (
+        "0305334e381af78f141cb666f6199f57bc3495335a256a95bd2a55bf546663f6",
+        false,
+        "626172",
+        "f726936d19c800494e3fdaff20b276a8".to_string(),
+        "fc60d5872fc46b3aa69f8b5b4351d5808f92bcc044606db097abab6dbcb1aee3216c48e8b3b66431b5b186d1d28f8ee15a5ca2df6668346291c2043d4eb3e90d",
+      ),
+      (
+        "0305334e381af78f141cb666f6199f57bc3495335a256a95bd2a55bf546663f6",
+        false,
+        "666f6f",
+        "508e9e6882b979fea900f62adceaca35".to_string(),
+        "8b70c1cc8310e1de20ac53ce28ae6e7207f33c3295e03bb5c0732a1d20dc64908922a8b052cf99b7c4fe107a5abb5b2c4085ae75890d02df26269d8945f84b0b",
+      ),
+      (
+        "ab9c2853ce297ddab85c993b3ae14bcad39b2c682beabc27d6d4eb20711d6560",
+        false,
+        "666f6f",
+        "f726936d19c800494e3fdaff20b276a8".to_string(),
+        "21655b5f1aa965996b3f97b3c849eafba922a0a62992f73b3d1b73106a84ad85e9b86a7b6005ea868337ff2d20a7f5fbd4cd10b0be49a68da2b2e0dc0ad8960f",
+      ),
+      (
+        "833fe62409237b9d62ec77587520911e9a759cec1d19755b7da901b96dca3d42",
+        true,
+        "",
+        faster_hex::hex_string(&Sha512::digest(b"abc")),
+        "98a70222f0b8121aa9d30f813d683f809e462b469c7ff87639499bb94e6dae4131f85042463c2a355a2003d062adf5aaa10b8c61e636062aaad11c2a26083406",
+      ),
+    ];
+    for (secret, prehashed, context, message, signature) in vectors {
+      let key = SigningKey::from_bytes(&unhex(secret).try_into().unwrap());
+      let context = unhex(context);
+      let dom2 = Dom2 {
+        prehashed,
+        context: &context,
+      };
+      let message = unhex(&message);
+      let signed = sign(&key, dom2, &message);
+      assert_eq!(faster_hex::hex_string(&signed), signature);
+
+      let public = key.verifying_key();
+      assert!(verify(&public, dom2, &message, &signed));
+      assert!(!verify(&public, dom2, &[0; PREHASH_LENGTH], &signed));
+      assert!(!verify(&public, dom2, &message, &signed[..63]));
+      let other = Dom2 {
+        prehashed: !prehashed,
+        ..dom2
+      };
+      assert!(!verify(&public, other, &message, &signed));
+      let other = Dom2 {
+        context: b"baz",
+        ..dom2
+      };
+      assert!(!verify(&public, other, &message, &signed));
+      let mut tampered = signed;
+      tampered[63] |= 0x80;
+      assert!(!verify(&public, dom2, &message, &tampered));
+    }
+  }
+
+  // Both signatures satisfy the cofactorless equation, so only the
+  // small-order checks reject them.
+  #[test]
+  fn rejects_small_order_points() {
+    let identity = CompressedEdwardsY::identity().to_bytes();
+    let message = [0x61; PREHASH_LENGTH];
+    for prehashed in [false, true] {
+      let dom2 = Dom2 {
+        prehashed,
+        context: b"foo",
+      };
+
+      // With R the identity, S = k * a gives [S]B - [k]A = R.
+      let key = SigningKey::from_bytes(&[7; 32]);
+      let public = key.verifying_key();
+      let k = dom2.hash(&[&identity, public.as_bytes(), &message]);
+      let signature = [identity, (k * key.to_scalar()).to_bytes()].concat();
+      assert!(!verify(&public, dom2, &message, &signature));
+
+      // With A and R the identity, S = 0 verifies any message.
+      let weak = VerifyingKey::from_bytes(&identity).unwrap();
+      let signature = [identity, [0; 32]].concat();
+      assert!(!verify(&weak, dom2, &message, &signature));
+    }
+  }
+}
@@ -0,0 +1,408 @@
+// Copyright 2018-2025 the Deno authors. MIT license.
+
+//! Ed448 signatures (RFC 8032, section 5.2). Keys sign in the pure form
+//! with an empty context, as Node.js does. `ed448-goldilocks` provides the
+//! curve and scalar arithmetic but no EdDSA.
+
+use ed448_goldilocks::Scalar;
+use ed448_goldilocks::curve::ExtendedPoint;
+use ed448_goldilocks::curve::edwards::CompressedEdwardsY;
+use sha3::Shake256;
+use sha3::digest::ExtendableOutput;
+use sha3::digest::Update;
+use sha3::digest::XofReader;
+use zeroize::Zeroize;
+use zeroize::Zeroizing;
+
+use super::wipe::wipe;
+
+pub const KEY_LENGTH: usize = 57;
+pub const SIGNATURE_LENGTH: usize = 114;
+
+/// `dom4(phflag, context)`, which prefixes both hashes. Ed448ph signs the
+/// 64-byte SHAKE256 digest of the message.
+#[derive(Clone, Copy)]
+struct Dom4<'a> {
+  prehashed: bool,
+  context: &'a [u8],
+}
+
+impl Dom4<'_> {
+  /// No prehashing and an empty context.
+  const PURE: Dom4<'static> = Dom4 {
+    prehashed: false,
+    context: &[],
+  };
+
+  fn hash(&self, parts: &[&[u8]]) -> Scalar {
+    let prefix = [self.prehashed as u8, self.context.len() as u8];
+    let mut all = vec![&b"SigEd448"[..], &prefix, self.context];
+    all.extend_from_slice(parts);
+    reduce(&shake256(&all))
+  }
+}
+
+fn shake256(parts: &[&[u8]]) -> Zeroizing<[u8; 114]> {
+  let mut hasher = Shake256::default();
+  for part in parts {
+    hasher.update(part);
+  }
+  let mut output = Zeroizing::new([0; 114]);
+  hasher.finalize_xof().read(&mut *output);
+  output
+}
+
+/// Reduces a little-endian 912-bit integer modulo the group order.
+fn reduce(bytes: &[u8; 114]) -> Scalar {
+  // `from_bytes` doesn't reduce, but multiplying by one does. The result
+  // is `lo + mid * 2^448 + hi * 2^896`.
+  let mut lo = [0; 56];
+  let mut mid = [0; 56];
+  let mut hi = [0; 56];
+  lo.copy_from_slice(&bytes[..56]);
+  mid.copy_from_slice(&bytes[56..112]);
+  hi[..2].copy_from_slice(&bytes[112..]);
+  let mut two_224 = [0; 56];
+  two_224[28] = 1;
+  let two_224 = Scalar::from_bytes(two_224);
+  let two_448 = two_224 * two_224;
+  let result = Scalar::from_bytes(lo) * Scalar::one()
+    + Scalar::from_bytes(mid) * two_448
+    + Scalar::from_bytes(hi) * two_448 * two_448;
+  lo.zeroize();
+  mid.zeroize();
+  hi.zeroize();
+  result
+}
+
+/// RFC 8032 encodes the sign of x in the top bit of the last byte, where
+/// `compress` puts it in the lowest bit.
+fn encode_point(point: &ExtendedPoint) -> [u8; KEY_LENGTH] {
+  let mut bytes = point.compress().0;
+  bytes[KEY_LENGTH - 1] <<= 7;
+  bytes
+}
+
+/// Only accepts the canonical encoding of a point on the curve.
+/// `decompress` doesn't pick the root of x by its sign, so the sign is
+/// fixed up here.
+fn decode_point(bytes: &[u8; KEY_LENGTH]) -> Option<ExtendedPoint> {
+  let mut point = CompressedEdwardsY(*bytes).decompress()?;
+  if encode_point(&point)[KEY_LENGTH - 1] != bytes[KEY_LENGTH - 1] {
+    point = point.negate();
+  }
+  (encode_point(&point) == *bytes).then_some(point)
+}
+
+#[derive(Clone)]
+pub struct VerifyingKey {
+  bytes: [u8; KEY_LENGTH],
+  point: ExtendedPoint,
+}
+
+impl VerifyingKey {
+  pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+    let bytes: [u8; KEY_LENGTH] = bytes.try_into().ok()?;
+    let point = decode_point(&bytes)?;
+    Some(Self { bytes, point })
+  }
+
+  pub fn as_bytes(&self) -> &[u8; KEY_LENGTH] {
+    &self.bytes
+  }
+
+  /// Returns false for a signature of the wrong length rather than an
+  /// error, as OpenSSL does.
+  pub fn verify(&self, message: &[u8], signature: &[u8]) -> bool {
+    self.verify_dom4(Dom4::PURE, message, signature)
+  }
+
+  fn verify_dom4(&self, dom4: Dom4, message: &[u8], signature: &[u8]) -> bool {
+    if signature.len() != SIGNATURE_LENGTH {
+      return false;
+    }
+    let (r_bytes, s_bytes) = signature.split_at(KEY_LENGTH);
+    let Some(r) = decode_point(r_bytes.try_into().unwrap()) else {
+      return false;
+    };
+    // S must be below the group order.
+    if s_bytes[KEY_LENGTH - 1] != 0 {
+      return false;
+    }
+    let mut s_array = [0; 56];
+    s_array.copy_from_slice(&s_bytes[..56]);
+    let s = Scalar::from_bytes(s_array) * Scalar::one();
+    if s.to_bytes() != s_array {
+      return false;
+    }
+    let k = dom4.hash(&[r_bytes, &self.bytes, message]);
+    // The cofactored equation: [4][S]B = [4]R + [4][k]A.
+    let lhs = ExtendedPoint::generator().scalar_mul(&s);
+    let rhs = r.add(&self.point.scalar_mul(&k));
+    lhs.double().double() == rhs.double().double()
+  }
+}
+
+#[derive(Clone)]
+pub struct SigningKey {
+  seed: Zeroizing<[u8; KEY_LENGTH]>,
+  public: VerifyingKey,
+}
+
+impl SigningKey {
+  pub fn from_bytes(seed: &[u8]) -> Option<Self> {
+    let seed = Zeroizing::new(<[u8; KEY_LENGTH]>::try_from(seed).ok()?);
+    let (mut scalar, _) = expand(&seed);
+    let point = ExtendedPoint::generator().scalar_mul(&scalar);
+    // SAFETY: a scalar is an array of integers.
+    unsafe { wipe(&mut scalar) };
+    let public = VerifyingKey {
+      bytes: encode_point(&point),
+      point,
+    };
+    Some(Self { seed, public })
+  }
+
+  pub fn generate() -> Self {
+    let mut seed = Zeroizing::new([0; KEY_LENGTH]);
+    rand::RngCore::fill_bytes(&mut rand::thread_rng(), &mut *seed);
+    Self::from_bytes(&*seed).unwrap()
+  }
+
+  pub fn as_bytes(&self) -> &[u8; KEY_LENGTH] {
+    &self.seed
+  }
+
+  pub fn verifying_key(&self) -> &VerifyingKey {
+    &self.public
+  }
+
+  pub fn sign(&self, message: &[u8]) -> [u8; SIGNATURE_LENGTH] {
+    self.sign_dom4(Dom4::PURE, message)
+  }
+
+  fn sign_dom4(&self, dom4: Dom4, message: &[u8]) -> [u8; SIGNATURE_LENGTH] {
+    let (mut s, prefix) = expand(&self.seed);
+    let mut r = dom4.hash(&[&prefix[..], message]);
+    let r_bytes = encode_point(&ExtendedPoint::generator().scalar_mul(&r));
+    let k = dom4.hash(&[&r_bytes, &self.public.bytes, message]);
+    let signature_s = r + k * s;
+    // SAFETY: scalars are arrays of integers.
+    unsafe {
+      wipe(&mut s);
+      wipe(&mut r);
+    }
+    let mut signature = [0; SIGNATURE_LENGTH];
+    signature[..KEY_LENGTH].copy_from_slice(&r_bytes);
+    signature[KEY_LENGTH..SIGNATURE_LENGTH - 1]
+      .copy_from_slice(&signature_s.to_bytes());
+    signature
+  }
+}
+
+/// Splits the hash of the seed into the clamped secret scalar and the
+/// prefix for the nonce.
+fn expand(seed: &[u8; KEY_LENGTH]) -> (Scalar, Zeroizing<[u8; KEY_LENGTH]>) {
+  let hash = shake256(&[seed]);
+  let mut scalar = [0; 56];
+  scalar.copy_from_slice(&hash[..56]);
+  scalar[0] &= 0xfc;
+  scalar[55] |= 0x80;
+  let result = Scalar::from_bytes(scalar) * Scalar::one();
+  scalar.zeroize();
+  let mut prefix = Zeroizing::new([0; KEY_LENGTH]);
+  prefix.copy_from_slice(&hash[KEY_LENGTH..]);
+  (result, prefix)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  struct Vector {
+    secret: &'static str,
+    public: &'static str,
+    prehashed: bool,
+    context: &'static str,
+    message: &'static str,
+    signature: &'static str,
+  }
+
+  // RFC 8032, section 7.4 ("Blank", "1 octet", "1 octet (with context)"
+  // and "11 octets") and section 7.5 ("abc" with and without a context).
+  const VECTORS: [Vector; 6] = [
+    Vector {
+      secret: "6c82a562cb808d10d632be89c8513ebf6c929f34ddfa8c9f63c9960ef6e348a3528c8a3fcc2f044e39a3fc5b94492f8f032e7549a20098f95b",
+      public: "5fd7449b59b461fd2ce787ec616ad46a1da1342485a70e1f8a0ea75d80e96778edf124769b46c7061bd6783df1e50f6cd1fa1abeafe8256180",
+      prehashed: false,
+      context: "",
+      message: "",
+      signature: "533a37f6bbe457251f023c0d88f976ae2dfb504a843e34d2074fd823d41a591f2b233f034f628281f2fd7a22ddd47d7828c59bd0a21bfd3980ff0d2028d4b18a9df63e006c5d1c2d345b925d8dc00b4104852db99ac5c7cdda8530a113a0f4dbb61149f05a7363268c71d95808ff2e652600",
+    },
+    Vector {
+      secret: "c4eab05d357007c632f3dbb48489924d552b08fe0c353a0d4a1f00acda2c463afbea67c5e8d2877c5e3bc397a659949ef8021e954e0a12274e",
+      public: "43ba28f430cdff456ae531545f7ecd0ac834a55d9358c0372bfa0c6c6798c0866aea01eb00742802b8438ea4cb82169c235160627b4c3a9480",
+      prehashed: false,
+      context: "",
+      message: "03",
+      signature: "26b8f91727bd62897af15e41eb43c377efb9c610d48f2335cb0bd0087810f4352541b143c4b981b7e18f62de8ccdf633fc1bf037ab7cd779805e0dbcc0aae1cbcee1afb2e027df36bc04dcecbf154336c19f0af7e0a6472905e799f1953d2a0ff3348ab21aa4adafd1d234441cf807c03a00",
+    },
+    Vector {
+      secret: "c4eab05d357007c632f3dbb48489924d552b08fe0c353a0d4a1f00acda2c463afbea67c5e8d2877c5e3bc397a659949ef8021e954e0a12274e",
+      public: "43ba28f430cdff456ae531545f7ecd0ac834a55d9358c0372bfa0c6c6798c0866aea01eb00742802b8438ea4cb82169c235160627b4c3a9480",
+      prehashed: false,
+      context: "666f6f",
+      message: "03",
+      signature: "d4f8f6131770dd46f40867d6fd5d5055de43541f8c5e35abbcd001b32a89f7d2151f7647f11d8ca2ae279fb842d607217fce6e042f6815ea000c85741de5c8da1144a6a1aba7f96de42505d7a7298524fda538fccbbb754f578c1cad10d54d0d5428407e85dcbc98a49155c13764e66c3c00",
+    },
+    Vector {
+      secret: "cd23d24f714274e744343237b93290f511f6425f98e64459ff203e8985083ffdf60500553abc0e05cd02184bdb89c4ccd67e187951267eb328",
+      public: "dcea9e78f35a1bf3499a831b10b86c90aac01cd84b67a0109b55a36e9328b1e365fce161d71ce7131a543ea4cb5f7e9f1d8b00696447001400",
+      prehashed: false,
+      context: "",
+      message: "0c3e544074ec63b0265e0c",
+      signature: "1f0a8888ce25e8d458a21130879b840a9089d999aaba039eaf3e3afa090a09d389dba82c4ff2ae8ac5cdfb7c55e94d5d961a29fe0109941e00b8dbdeea6d3b051068df7254c0cdc129cbe62db2dc957dbb47b51fd3f213fb8698f064774250a5028961c9bf8ffd973fe5d5c206492b140e00",
+    },
+    Vector {
+      secret: "833fe62409237b9d62ec77587520911e9a759cec1d19755b7da901b96dca3d42ef7822e0d5104127dc05d6dbefde69e3ab2cec7c867c6e2c49",
+      public: "259b71c19f83ef77a7abd26524cbdb3161b590a48f7d17de3ee0ba9c52beb743c09428a131d6b1b57303d90d8132c276d5ed3d5d01c0f53880",
+      prehashed: true,
+      context: "",
+      message: "616263",
+      signature: "822f6901f7480f3d5f562c592994d9693602875614483256505600bbc281ae381f54d6bce2ea911574932f52a4e6cadd78769375ec3ffd1b801a0d9b3f4030cd433964b6457ea39476511214f97469b57dd32dbc560a9a94d00bff07620464a3ad203df7dc7ce360c3cd3696d9d9fab90f00",
+    },
+    Vector {
+      secret: "833fe62409237b9d62ec77587520911e9a759cec1d19755b7da901b96dca3d42ef7822e0d5104127dc05d6dbefde69e3ab2cec7c867c6e2c49",
+      public: "259b71c19f83ef77a7abd26524cbdb3161b590a48f7d17de3ee0ba9c52beb743c09428a131d6b1b57303d90d8132c276d5ed3d5d01c0f53880",
+      prehashed: true,
+      context: "666f6f",
+      message: "616263",
+      signature: "c32299d46ec8ff02b54540982814dce9a05812f81962b649d528095916a2aa481065b1580423ef927ecf0af5888f90da0f6a9a85ad5dc3f280d91224ba9911a3653d00e484e2ce232521481c8658df304bb7745a73514cdb9bf3e15784ab71284f8d0704a608c54a6b62d97beb511d132100",
+    },
+  ];
+
+  /// The group order, little-endian.
+  const ORDER: &str = "f34458ab92c27823558fc58d72c26c219036d6ae49db4ec4e923ca7cffffffffffffffffffffffffffffffffffffffffffffffffffffff3f";
+
+  fn unhex(hex: &str) -> Vec<u8> {
+    let mut bytes = vec![0; hex.len() / 2];
+    faster_hex::hex_decode(hex.as_bytes(), &mut bytes).unwrap();
+    bytes
+  }
+
+  #[test]
+  fn rfc8032_vectors() {
+    for vector in VECTORS {
+      let key = SigningKey::from_bytes(&unhex(vector.secret)).unwrap();
+      assert_eq!(
+        faster_hex::hex_string(key.verifying_key().as_bytes()),
+        vector.public
+      );
+      let context = unhex(vector.context);
+      let dom4 = Dom4 {
+        prehashed: vector.prehashed,
+        context: &context,
+      };
+      let mut message = unhex(vector.message);
+      if vector.prehashed {
+        let mut hasher = Shake256::default();
+        hasher.update(&message);
+        message = vec![0; 64];
+        hasher.finalize_xof().read(&mut message);
+      }
+      let signed = key.sign_dom4(dom4, &message);
+      assert_eq!(faster_hex::hex_string(&signed), vector.signature);
+
+      let public = VerifyingKey::from_bytes(&unhex(vector.public)).unwrap();
+      assert!(public.verify_dom4(dom4, &message, &signed));
+      assert_eq!(
+        public.verify(&message, &signed),
+        !vector.prehashed && context.is_empty()
+      );
+      assert!(!public.verify_dom4(dom4, b"other", &signed));
+      assert!(!public.verify_dom4(dom4, &message, &signed[..113]));
+      let other = Dom4 {
+        prehashed: !vector.prehashed,
+        ..dom4
+      };
+      assert!(!public.verify_dom4(other, &message, &signed));
+      let other = Dom4 {
+        context: b"bar",
+        ..dom4
+      };
+      assert!(!public.verify_dom4(other, &message, &signed));
+      let mut tampered = signed;
+      tampered[113] = 1;
+      assert!(!public.verify_dom4(dom4, &message, &tampered));
+    }
+  }
+
+  /// Adds the group order to a little-endian S, which still fits in 57
+  /// bytes.
+  fn add_order(s: &[u8]) -> Vec<u8> {
+    let order = unhex(ORDER);
+    let mut sum = vec![0; KEY_LENGTH];
+    let mut carry = 0;
+    for i in 0..56 {
+      let total = s[i] as u16 + order[i] as u16 + carry;
+      sum[i] = total as u8;
+      carry = total >> 8;
+    }
+    sum[56] = s[56] + carry as u8;
+    sum
+  }
+
+  #[test]
+  fn rejects_non_canonical_s() {
+    let vector = &VECTORS[1];
+    let public = VerifyingKey::from_bytes(&unhex(vector.public)).unwrap();
+    let message = unhex(vector.message);
+    let signature = unhex(vector.signature);
+    assert!(public.verify(&message, &signature));
+
+    // S + L is the same scalar, but only S < L is accepted.
+    let mut malleated = signature.clone();
+    malleated[KEY_LENGTH..]
+      .copy_from_slice(&add_order(&signature[KEY_LENGTH..]));
+    assert_ne!(malleated, signature);
+    assert!(!public.verify(&message, &malleated));
+    let mut order = signature.clone();
+    order[KEY_LENGTH..SIGNATURE_LENGTH - 1].copy_from_slice(&unhex(ORDER));
+    assert!(!public.verify(&message, &order));
+  }
+
+  #[test]
+  fn rejects_non_canonical_points() {
+    // A point with y = 3, small enough that y + p still fits in 56 bytes.
+    let mut canonical = [0; KEY_LENGTH];
+    canonical[0] = 3;
+    assert!(VerifyingKey::from_bytes(&canonical).is_some());
+    let mut y_plus_p = [0; KEY_LENGTH];
+    y_plus_p[0] = 2;
+    y_plus_p[28..56].fill(0xff);
+    assert!(VerifyingKey::from_bytes(&y_plus_p).is_none());
+    // Only the top bit of the last byte, the sign of x, may be set.
+    let mut unused_bit = canonical;
+    unused_bit[KEY_LENGTH - 1] = 1;
+    assert!(VerifyingKey::from_bytes(&unused_bit).is_none());
+
+    let vector = &VECTORS[1];
+    let mut public = unhex(vector.public);
+    let message = unhex(vector.message);
+    let mut signature = unhex(vector.signature);
+    signature[KEY_LENGTH - 1] |= 1;
+    let key = VerifyingKey::from_bytes(&public).unwrap();
+    assert!(!key.verify(&message, &signature));
+    public[KEY_LENGTH - 1] |= 1;
+    assert!(VerifyingKey::from_bytes(&public).is_none());
+  }
+
+  #[test]
+  fn rejects_invalid_public_keys() {
+    assert!(VerifyingKey::from_bytes(&[0; 56]).is_none());
+    // y = 2 isn't on the curve.
+    let mut bytes = [0; KEY_LENGTH];
+    bytes[0] = 2;
+    assert!(VerifyingKey::from_bytes(&bytes).is_none());
+  }
+}
@@ -0,0 +1,89 @@
+// Copyright 2018-2025 the Deno authors. MIT license.
+
+//! Runtime FIPS mode, toggled by `crypto.setFips()`. Like OpenSSL's, it is
+//! process-wide.
+
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+
+static FIPS_ENABLED: AtomicBool = AtomicBool::new(false);
+
+#[cfg(test)]
+thread_local! {
+  static TEST_THREAD_ENABLED: std::cell::Cell<bool> =
+    const { std::cell::Cell::new(false) };
+}
+
+pub fn enabled() -> bool {
+  #[cfg(test)]
+  if TEST_THREAD_ENABLED.get() {
+    return true;
+  }
+  FIPS_ENABLED.load(Ordering::Relaxed)
+}
+
+/// Turns FIPS mode on for the calling thread only, so a test can use it
+/// without affecting the tests running next to it.
+#[cfg(test)]
+pub fn set_enabled_on_test_thread(enabled: bool) {
+  TEST_THREAD_ENABLED.set(enabled);
+}
+
+pub fn set_enabled(enabled: bool) {
+  FIPS_ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Digests approved by FIPS 180-4 and FIPS 202, by canonical name.
+pub fn is_digest_approved(name: &str) -> bool {
+  matches!(
+    name,
+    "sha1"
+      | "sha224"
+      | "sha256"
+      | "sha384"
+      | "sha512"
+      | "sha512-224"
+      | "sha512-256"
+      | "sha3-224"
+      | "sha3-256"
+      | "sha3-384"
+      | "sha3-512"
+      | "shake128"
+      | "shake256"
+      | "cshake128"
+      | "cshake256"
+      | "kmac128"
+      | "kmac256"
+  )
+}
+
+/// Every cipher implemented so far is an AES mode, all of which are approved.
+pub fn is_cipher_approved(name: &str) -> bool {
+  name.starts_with("aes")
+}
+
+pub fn digest_allowed(name: &str) -> bool {
+  !enabled() || is_digest_approved(name)
+}
+
+pub fn cipher_allowed(name: &str) -> bool {
+  !enabled() || is_cipher_approved(name)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn approved_algorithms() {
+    for name in ["sha1", "sha256", "sha512-256", "sha3-384", "shake256"] {
+      assert!(is_digest_approved(name), "{name}");
+    }
+    for name in ["md4", "md5", "md5-sha1", "ripemd160", "sm3", "blake2b512"] {
+      assert!(!is_digest_approved(name), "{name}");
+    }
+    assert!(is_cipher_approved("aes-128-gcm"));
+    assert!(is_cipher_approved("aes256"));
+    assert!(!is_cipher_approved("des-ede3-cbc"));
+  }
+}
@@ -0,0 +1,860 @@
+// Copyright 2018-2025 the Deno authors. MIT license.
+
+//! HMAC (RFC 2104) over any fixed-output digest `createHash()` supports.
+
+use std::cell::RefCell;
+
+use deno_core::GarbageCollected;
+use deno_core::ToJsBuffer;
+use deno_core::cppgc;
+use deno_core::op2;
+use deno_core::v8;
+use deno_error::JsErrorBox;
+use hmac::Mac;
+use hmac::SimpleHmac;
+use rayon::prelude::*;
+use zeroize::Zeroizing;
+
+use super::digest::DigestEncoding;
+use super::digest::DigestInfo;
+use super::digest::HashError;
+use super::digest::match_fixed_digest;
+use super::keys::KeyObjectHandle;
+use crate::ops::buffer::decode_base64_lenient;
+use crate::ops::buffer::decode_hex_lenient;
+
+trait DynMac {
+  fn update(&mut self, data: &[u8]);
+  /// Leaves the state in place, to be wiped by the caller.
+  fn finalize(&self) -> Box<[u8]>;
+  fn box_clone(&self) -> Box<dyn DynMac>;
+  /// Zeroizes the state, which holds the key XORed into the HMAC pads.
+  fn wipe(&mut self);
+}
+
+impl<D> DynMac for SimpleHmac<D>
+where
+  D: digest::Digest + digest::core_api::BlockSizeUser + Clone + 'static,
+{
+  fn update(&mut self, data: &[u8]) {
+    Mac::update(self, data);
+  }
+
+  fn finalize(&self) -> Box<[u8]> {
+    Box::from(Mac::finalize(self.clone()).into_bytes().as_slice())
+  }
+
+  fn box_clone(&self) -> Box<dyn DynMac> {
+    Box::new(self.clone())
+  }
+
+  fn wipe(&mut self) {
+    // SAFETY: `SimpleHmac` holds the inner and outer digest contexts, and
+    // the digests `match_fixed_digest!` names are plain state arrays, block
+    // buffers and counters.
+    unsafe { super::wipe::wipe(self) };
+  }
+}
+
+/// A MAC context that is wiped when it's dropped, whether finalized or not.
+struct MacState(Box<dyn DynMac>);
+
+impl Drop for MacState {
+  fn drop(&mut self) {
+    self.0.wipe();
+  }
+}
+
+/// Resolves `algorithm` to the canonical name of a digest HMAC can use.
+/// XOFs are rejected: OpenSSL doesn't define HMAC over them, and KMAC is
+/// the MAC built for them.
+fn hmac_digest_name(algorithm: &str) -> Result<&'static str, HashError> {
+  let info = DigestInfo::lookup_or_err(algorithm)?;
+  if info.output_size.is_none() {
+    return Err(HashError::HmacOverXof(info.name.to_string()));
+  }
+  // SipHash is keyed itself; HMAC needs an unkeyed hash.
+  if info.requires_key() {
+    return Err(HashError::DigestMethodUnsupported(info.name.to_string()));
+  }
+  if !super::fips::digest_allowed(info.name) {
+    return Err(HashError::DigestNotFipsApproved(info.name.to_string()));
+  }
+  Ok(info.name)
+}
+
+/// Computes an HMAC in a single call with the state on the stack, for
+/// short messages where creating an `Hmac` object would dominate.
+pub fn hmac_oneshot(
+  algorithm: &str,
+  key: &[u8],
+  data: &[u8],
+) -> Result<Box<[u8]>, HashError> {
+  let name = hmac_digest_name(algorithm)?;
+  match_fixed_digest!(
+    name,
+    fn <D>() {
+      let mut mac = <SimpleHmac<D> as Mac>::new_from_slice(key).unwrap();
+      Mac::update(&mut mac, data);
+      let output = DynMac::finalize(&mac);
+      mac.wipe();
+      Ok(output)
+    },
+    _ => {
+      Err(HashError::DigestMethodUnsupported(name.to_string()))
+    }
+  )
+}
+
+/// Batches of at least this many bytes are MACed on the rayon pool.
+const BATCH_PARALLEL_BYTES: usize = 1024 * 1024;
+
+/// Computes the MAC of `prefix || chunk` for each pair, in order. The key
+/// is absorbed once and the keyed state copied for each pair.
+pub fn hmac_batch(
+  algorithm: &str,
+  key: &[u8],
+  pairs: &[[&[u8]; 2]],
+) -> Result<Vec<Box<[u8]>>, HashError> {
+  let name = hmac_digest_name(algorithm)?;
+  match_fixed_digest!(
+    name,
+    fn <D>() {
+      let mut keyed = <SimpleHmac<D> as Mac>::new_from_slice(key).unwrap();
+      let mac_pair = |[prefix, chunk]: &[&[u8]; 2]| {
+        let mut mac = keyed.clone();
+        Mac::update(&mut mac, prefix);
+        Mac::update(&mut mac, chunk);
+        let output = DynMac::finalize(&mac);
+        mac.wipe();
+        output
+      };
+      let total = pairs
+        .iter()
+        .map(|[prefix, chunk]| prefix.len() + chunk.len())
+        .sum::<usize>();
+      let macs = if pairs.len() > 1 && total >= BATCH_PARALLEL_BYTES {
+        pairs.par_iter().map(mac_pair).collect()
+      } else {
+        pairs.iter().map(mac_pair).collect()
+      };
+      keyed.wipe();
+      Ok(macs)
+    },
+    _ => {
+      Err(HashError::DigestMethodUnsupported(name.to_string()))
+    }
+  )
+}
+
+/// Computes the MAC of `data` and compares it with `expected` in constant
+/// time. The computed tag is zeroized and never leaves this function. An
+/// expected tag of the wrong length (or one that didn't decode, passed as
+/// `None`) doesn't match.
+pub fn hmac_verify(
+  algorithm: &str,
+  key: &[u8],
+  data: &[u8],
+  expected: Option<&[u8]>,
+) -> Result<bool, HashError> {
+  let mac = Zeroizing::new(hmac_oneshot(algorithm, key, data)?);
+  let Some(expected) = expected else {
+    return Ok(false);
+  };
+  Ok(aws_lc_rs::constant_time::verify_slices_are_equal(&mac, expected).is_ok())
+}
+
+fn decode_expected(
+  expected: &str,
+  encoding: &str,
+) -> Result<Option<Vec<u8>>, HashError> {
+  let encoding = DigestEncoding::parse(encoding)
+    .ok_or_else(|| HashError::UnsupportedEncoding(encoding.to_string()))?;
+  Ok(encoding.decode(expected))
+}
+
+fn secret_key(key: &KeyObjectHandle) -> Result<&[u8], JsErrorBox> {
+  key
+    .as_secret_key()
+    .ok_or_else(|| JsErrorBox::type_error("key is not a secret key"))
+}
+
+pub struct Hmac {
+  mac: RefCell<Option<MacState>>,
+}
+
+impl GarbageCollected for Hmac {
+  fn get_name(&self) -> &'static std::ffi::CStr {
+    c"Hmac"
+  }
+}
+
+// Make prototype available for JavaScript
+#[op2]
+impl Hmac {
+  #[constructor]
+  #[cppgc]
+  fn create(_: bool) -> Hmac {
+    unreachable!()
+  }
+}
+
+impl Hmac {
+  /// Keys longer than the digest's block size are hashed first, as RFC
+  /// 2104 specifies.
+  pub fn new(algorithm: &str, key: &[u8]) -> Result<Self, HashError> {
+    let name = hmac_digest_name(algorithm)?;
+    let mac = MacState(match_fixed_digest!(
+      name,
+      fn <D>() {
+        // Any key length is valid for HMAC.
+        Box::new(<SimpleHmac<D> as Mac>::new_from_slice(key).unwrap())
+      },
+      _ => {
+        return Err(HashError::DigestMethodUnsupported(name.to_string()));
+      }
+    ));
+    Ok(Self {
+      mac: RefCell::new(Some(mac)),
+    })
+  }
+
+  /// Returns false once the MAC has been finalized.
+  pub fn update(&self, data: &[u8]) -> bool {
+    match self.mac.borrow_mut().as_mut() {
+      Some(mac) => {
+        mac.0.update(data);
+        true
+      }
+      None => false,
+    }
+  }
+
+  pub fn digest(&self) -> Option<Box<[u8]>> {
+    // The state is wiped as it's dropped.
+    Some(self.mac.borrow_mut().take()?.0.finalize())
+  }
+
+  /// Snapshots the MAC state into an independent `Hmac`. Returns `None` once
+  /// the MAC has been finalized.
+  pub fn clone_inner(&self) -> Option<Self> {
+    let mac = MacState(self.mac.borrow().as_ref()?.0.box_clone());
+    Some(Self {
+      mac: RefCell::new(Some(mac)),
+    })
+  }
+}
+
+/// Reads an HMAC key passed as a view, an ArrayBuffer, or a string in
+/// `key_encoding`. Strings are decoded as `Buffer.from(key, key_encoding)`
+/// would decode them, but without allocating a `Buffer` in JS. A lone
+/// surrogate becomes U+FFFD in UTF-8, and Latin-1 keeps the low byte of
+/// each code unit.
+fn read_key(
+  scope: &mut v8::HandleScope,
+  key: v8::Local<v8::Value>,
+  key_encoding: &str,
+) -> Result<Zeroizing<Vec<u8>>, JsErrorBox> {
+  if let Ok(key) = v8::Local::<v8::String>::try_from(key) {
+    let bytes = match key_encoding {
+      "utf8" => key.to_rust_string_lossy(scope).into_bytes(),
+      "latin1" => {
+        let mut bytes = vec![0; key.length()];
+        key.write_one_byte_v2(scope, 0, &mut bytes, v8::WriteFlags::empty());
+        bytes
+      }
+      "hex" => {
+        let key = Zeroizing::new(key.to_rust_string_lossy(scope));
+        decode_hex_lenient(key.as_bytes())
+      }
+      "base64" | "base64url" => {
+        let key = Zeroizing::new(key.to_rust_string_lossy(scope));
+        decode_base64_lenient(key.as_bytes())
+      }
+      _ => {
+        return Err(JsErrorBox::type_error(format!(
+          "Unsupported encoding: {key_encoding}"
+        )));
+      }
+    };
+    return Ok(Zeroizing::new(bytes));
+  }
+  if let Ok(view) = v8::Local::<v8::ArrayBufferView>::try_from(key) {
+    let mut storage = [0; v8::TYPED_ARRAY_MAX_SIZE_IN_HEAP];
+    return Ok(Zeroizing::new(view.get_contents(&mut storage).to_vec()));
+  }
+  if let Ok(buffer) = v8::Local::<v8::ArrayBuffer>::try_from(key) {
+    let store = buffer.get_backing_store();
+    return Ok(Zeroizing::new(
+      store.iter().map(|byte| byte.get()).collect(),
+    ));
+  }
+  Err(JsErrorBox::type_error(
+    "key must be a string, an ArrayBuffer or an ArrayBufferView",
+  ))
+}
+
+/// `key_encoding` only applies to string keys; see `read_key`.
+#[op2]
+#[cppgc]
+pub fn op_node_create_hmac(
+  scope: &mut v8::HandleScope,
+  #[string] algorithm: &str,
+  key: v8::Local<v8::Value>,
+  #[string] key_encoding: &str,
+) -> Result<Hmac, JsErrorBox> {
+  let key = read_key(scope, key, key_encoding)?;
+  Hmac::new(algorithm, &key).map_err(JsErrorBox::from_err)
+}
+
+/// Like `op_node_create_hmac`, reading the key out of a secret KeyObject.
+#[op2]
+#[cppgc]
+pub fn op_node_create_hmac_from_key(
+  #[string] algorithm: &str,
+  #[cppgc] key: &KeyObjectHandle,
+) -> Result<Hmac, JsErrorBox> {
+  Hmac::new(algorithm, secret_key(key)?).map_err(JsErrorBox::from_err)
+}
+
+/// Checks that `algorithm` can be used for HMAC, so `createHmac()` can
+/// throw before it has a message for `op_node_hmac_oneshot`.
+#[op2(fast)]
+pub fn op_node_hmac_validate(
+  #[string] algorithm: &str,
+) -> Result<(), HashError> {
+  hmac_digest_name(algorithm).map(|_| ())
+}
+
+/// Takes the key as `op_node_create_hmac` does.
+#[op2]
+#[buffer]
+pub fn op_node_hmac_oneshot(
+  scope: &mut v8::HandleScope,
+  #[string] algorithm: &str,
+  key: v8::Local<v8::Value>,
+  #[string] key_encoding: &str,
+  #[anybuffer] data: &[u8],
+) -> Result<Box<[u8]>, JsErrorBox> {
+  let key = read_key(scope, key, key_encoding)?;
+  hmac_oneshot(algorithm, &key, data).map_err(JsErrorBox::from_err)
+}
+
+/// Like `op_node_hmac_oneshot`, with the MAC encoded as by
+/// `op_node_hash_digest_encoded`. Returns `None` without computing anything
+/// if `encoding` isn't handled here.
+#[op2]
+#[string]
+pub fn op_node_hmac_oneshot_encoded(
+  scope: &mut v8::HandleScope,
+  #[string] algorithm: &str,
+  key: v8::Local<v8::Value>,
+  #[string] key_encoding: &str,
+  #[anybuffer] data: &[u8],
+  #[string] encoding: &str,
+) -> Result<Option<String>, JsErrorBox> {
+  let Some(encoding) = DigestEncoding::parse(encoding) else {
+    return Ok(None);
+  };
+  let key = read_key(scope, key, key_encoding)?;
+  let mac =
+    hmac_oneshot(algorithm, &key, data).map_err(JsErrorBox::from_err)?;
+  Ok(Some(encoding.encode(&mac)))
+}
+
+#[op2(fast)]
+pub fn op_node_hmac_update(
+  #[cppgc] hmac: &Hmac,
+  #[buffer] data: &[u8],
+) -> bool {
+  hmac.update(data)
+}
+
+#[op2(fast)]
+pub fn op_node_hmac_update_str(
+  #[cppgc] hmac: &Hmac,
+  #[string] data: &str,
+) -> bool {
+  hmac.update(data.as_bytes())
+}
+
+/// Returns `None` if the MAC was already finalized.
+#[op2]
+#[buffer]
+pub fn op_node_hmac_digest(#[cppgc] hmac: &Hmac) -> Option<Box<[u8]>> {
+  hmac.digest()
+}
+
+/// Checks an HMAC tag, as for webhook signatures, without handing the
+/// computed tag to JS.
+#[op2]
+pub fn op_node_hmac_verify(
+  #[string] algorithm: &str,
+  #[anybuffer] key: &[u8],
+  #[anybuffer] data: &[u8],
+  #[anybuffer] expected: &[u8],
+) -> Result<bool, HashError> {
+  hmac_verify(algorithm, key, data, Some(expected))
+}
+
+/// Like `op_node_hmac_verify`, with the expected tag encoded as a string.
+#[op2]
+pub fn op_node_hmac_verify_str(
+  #[string] algorithm: &str,
+  #[anybuffer] key: &[u8],
+  #[anybuffer] data: &[u8],
+  #[string] expected: &str,
+  #[string] encoding: &str,
+) -> Result<bool, HashError> {
+  let expected = decode_expected(expected, encoding)?;
+  hmac_verify(algorithm, key, data, expected.as_deref())
+}
+
+#[op2]
+pub fn op_node_hmac_verify_with_key(
+  #[string] algorithm: &str,
+  #[cppgc] key: &KeyObjectHandle,
+  #[anybuffer] data: &[u8],
+  #[anybuffer] expected: &[u8],
+) -> Result<bool, JsErrorBox> {
+  hmac_verify(algorithm, secret_key(key)?, data, Some(expected))
+    .map_err(JsErrorBox::from_err)
+}
+
+#[op2]
+pub fn op_node_hmac_verify_with_key_str(
+  #[string] algorithm: &str,
+  #[cppgc] key: &KeyObjectHandle,
+  #[anybuffer] data: &[u8],
+  #[string] expected: &str,
+  #[string] encoding: &str,
+) -> Result<bool, JsErrorBox> {
+  let expected =
+    decode_expected(expected, encoding).map_err(JsErrorBox::from_err)?;
+  hmac_verify(algorithm, secret_key(key)?, data, expected.as_deref())
+    .map_err(JsErrorBox::from_err)
+}
+
+/// The key of `op_node_hmac_batch`, which is only read once the pairs are.
+enum BatchKey<'s> {
+  View(v8::Local<'s, v8::ArrayBufferView>),
+  Object(cppgc::Ptr<KeyObjectHandle>),
+}
+
+/// MACs each `[prefix, chunk]` pair of views in `pairs` with the same key,
+/// which is a view or a secret KeyObject handle, in a single op call. The
+/// whole batch fails, naming the index, if any pair is malformed or backed by
+/// a detached buffer.
+///
+/// Reading `pairs` can run getters, which could detach or resize any view
+/// seen so far, so no contents are read until every pair is collected.
+#[op2]
+#[serde]
+pub fn op_node_hmac_batch(
+  scope: &mut v8::HandleScope,
+  #[string] algorithm: &str,
+  key: v8::Local<v8::Value>,
+  pairs: v8::Local<v8::Array>,
+) -> Result<Vec<ToJsBuffer>, JsErrorBox> {
+  let key = if let Ok(view) = v8::Local::<v8::ArrayBufferView>::try_from(key) {
+    BatchKey::View(view)
+  } else if let Some(handle) =
+    cppgc::try_unwrap_cppgc_object::<KeyObjectHandle>(scope, key)
+  {
+    BatchKey::Object(handle)
+  } else {
+    return Err(JsErrorBox::type_error(
+      "key must be an ArrayBufferView or a secret KeyObject",
+    ));
+  };
+
+  let mut views = Vec::with_capacity(pairs.length() as usize);
+  for i in 0..pairs.length() {
+    let pair = pairs
+      .get_index(scope, i)
+      .and_then(|pair| v8::Local::<v8::Array>::try_from(pair).ok())
+      .filter(|pair| pair.length() == 2)
+      .and_then(|pair| {
+        let prefix = pair.get_index(scope, 0)?.try_into().ok()?;
+        let chunk = pair.get_index(scope, 1)?.try_into().ok()?;
+        Some([prefix, chunk])
+      })
+      .ok_or_else(|| {
+        JsErrorBox::type_error(format!(
+          "pairs[{i}] is not a [prefix, chunk] pair of ArrayBufferViews"
+        ))
+      })?;
+    views.push(pair);
+  }
+
+  let mut detached = |view: &v8::Local<v8::ArrayBufferView>| {
+    view
+      .buffer(scope)
+      .is_some_and(|buffer| buffer.was_detached())
+  };
+  if matches!(&key, BatchKey::View(view) if detached(view)) {
+    return Err(JsErrorBox::type_error(
+      "key is backed by a detached ArrayBuffer",
+    ));
+  }
+  if let Some(i) = views.iter().position(|pair| pair.iter().any(&mut detached))
+  {
+    return Err(JsErrorBox::type_error(format!(
+      "pairs[{i}] is backed by a detached ArrayBuffer"
+    )));
+  }
+
+  let mut key_storage = [0; v8::TYPED_ARRAY_MAX_SIZE_IN_HEAP];
+  let key = match &key {
+    BatchKey::View(view) => view.get_contents(&mut key_storage),
+    BatchKey::Object(handle) => secret_key(handle)?,
+  };
+  let mut storage =
+    vec![[0; v8::TYPED_ARRAY_MAX_SIZE_IN_HEAP]; 2 * views.len()];
+  let slices = views
+    .iter()
+    .zip(storage.chunks_exact_mut(2))
+    .map(|([prefix, chunk], storage)| {
+      let [prefix_storage, chunk_storage] = storage else {
+        unreachable!()
+      };
+      [
+        prefix.get_contents(prefix_storage),
+        chunk.get_contents(chunk_storage),
+      ]
+    })
+    .collect::<Vec<_>>();
+  let macs =
+    hmac_batch(algorithm, key, &slices).map_err(JsErrorBox::from_err)?;
+  Ok(macs.into_iter().map(Into::into).collect())
+}
+
+#[op2]
+#[cppgc]
+pub fn op_node_hmac_clone(#[cppgc] hmac: &Hmac) -> Option<Hmac> {
+  hmac.clone_inner()
+}
+
+#[cfg(test)]
+mod tests {
+  use std::cell::Cell;
+  use std::rc::Rc;
+
+  use base64::Engine;
+  use base64::prelude::BASE64_STANDARD;
+  use base64::prelude::BASE64_URL_SAFE_NO_PAD;
+
+  use super::*;
+
+  fn hex_mac(algorithm: &str, key: &[u8], data: &[u8]) -> String {
+    let hmac = Hmac::new(algorithm, key).unwrap();
+    assert!(hmac.update(data));
+    faster_hex::hex_string(&hmac.digest().unwrap())
+  }
+
+  fn long_key_data() -> &'static [u8] {
+    b"Test Using Larger Than Block-Size Key - Hash Key First"
+  }
+
+  // https://www.rfc-editor.org/rfc/rfc4231#section-4
+  #[test]
+  fn rfc4231() {
+    let cases: [(&[u8], &[u8], [&str; 4]); 4] = [
+      (
+        &[0x0b; 20],
+        b"Hi There",
+        [
+          "896fb1128abbdf196832107cd49df33f47b4b1169912ba4f53684b22",
+          "b0344c61d8db38535ca8afceaf0bf12b881dc200c9833da726e9376c2e32cff7",
+          "afd03944d84895626b0825f4ab46907f15f9dadbe4101ec682aa034c7cebc59cfaea9ea9076ede7f4af152e8b2fa9cb6",
+          "87aa7cdea5ef619d4ff0b4241a1d6cb02379f4e2ce4ec2787ad0b30545e17cdedaa833b7d6b8a702038b274eaea3f4e4be9d914eeb61f1702e696c203a126854",
+        ],
+      ),
+      (
+        b"Jefe",
+        b"what do ya want for nothing?",
+        [
+          "a30e01098bc6dbbf45690f3a7e9e6d0f8bbea2a39e6148008fd05e44",
+          "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843",
+          "af45d2e376484031617f78d2b58a6b1b9c7ef464f5a01b47e42ec3736322445e8e2240ca5e69e2c78b3239ecfab21649",
+          "164b7a7bfcf819e2e395fbe73b56e0a387bd64222e831fd610270cd7ea2505549758bf75c05a994a6d034f65f8f0e6fdcaeab1a34d4a6b4b636e070a38bce737",
+        ],
+      ),
+      // A key larger than the block size, hashed first.
+      (
+        &[0xaa; 131],
+        b"Test Using Larger Than Block-Size Key - Hash Key First",
+        [
+          "95e9a0db962095adaebe9b2d6f0dbce2d499f112f2d2b7273fa6870e",
+          "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54",
+          "4ece084485813e9088d2c63a041bc5b44f9ef1012a2b588f3cd11f05033ac4c60c2ef6ab4030fe8296248df163f44952",
+          "80b24263c7c1a3ebb71493c1dd7be8b49b46d1f41b4aeec1121b013783f8f3526b56d037e05f2598bd0fd2215d6a1e5295e64f73f63f0aec8b915a985d786598",
+        ],
+      ),
+      (
+        &[0xaa; 131],
+        b"This is a test using a larger than block-size key and a larger than block-size data. The key needs to be hashed before being used by the HMAC algorithm.",
+        [
+          "3a854166ac5d9f023f54d517d0b39dbd946770db9c2b95c9f6f565d1",
+          "9b09ffa71b942fcb27635fbcd5b0e944bfdc63644f0713938a7f51535c3a35e2",
+          "6617178e941f020d351e2f254e8fd32c602420feb0b8fb9adccebb82461e99c5a678cc31e799176d3860e6110c46523e",
+          "e37b6a775dc87dbaa4dfa9f96e5e3ffddebd71f8867289865df5a32d20cdc944b6022cac3c4982b10d5eeb55c3e4de15134676fb6de0446065c97440fa8c6a58",
+        ],
+      ),
+    ];
+    for (key, data, expected) in cases {
+      for (algorithm, expected) in ["sha224", "sha256", "sha384", "sha512"]
+        .iter()
+        .zip(expected)
+      {
+        assert_eq!(hex_mac(algorithm, key, data), expected, "{algorithm}");
+      }
+    }
+  }
+
+  // https://www.rfc-editor.org/rfc/rfc2202
+  #[test]
+  fn rfc2202() {
+    let long_key_data: [&[u8]; 2] = [
+      b"Test Using Larger Than Block-Size Key - Hash Key First",
+      b"Test Using Larger Than Block-Size Key and Larger Than One Block-Size Data",
+    ];
+    let jefe: (&[u8], &[u8]) = (b"Jefe", b"what do ya want for nothing?");
+    for (algorithm, short_key, expected) in [
+      (
+        "md5",
+        16,
+        [
+          "9294727a3638bb1c13f48ef8158bfc9d",
+          "750c783e6ab0b503eaa86e310a5db738",
+          "6b1ab7fe4bd7bf8f0b62e6ce61b9d0cd",
+          "6f630fad67cda0ee1fb1f562db3aa53e",
+        ],
+      ),
+      (
+        "sha1",
+        20,
+        [
+          "b617318655057264e28bc0b6fb378c8ef146be00",
+          "effcdf6ae5eb2fa2d27416d5f184df9c259a7c79",
+          "aa4ae5e15272d00e95705637ce8a3b55ed402112",
+          "e8e99d0f45237d786d6bbaa7965c7808bbff1a91",
+        ],
+      ),
+    ] {
+      let short_key = vec![0x0b; short_key];
+      let cases = [
+        (short_key.as_slice(), b"Hi There".as_slice()),
+        jefe,
+        (&[0xaa; 80], long_key_data[0]),
+        (&[0xaa; 80], long_key_data[1]),
+      ];
+      for ((key, data), expected) in cases.into_iter().zip(expected) {
+        assert_eq!(hex_mac(algorithm, key, data), expected, "{algorithm}");
+      }
+    }
+  }
+
+  // The GB/T 32905 SM3 messages, with the 32-byte key 0x01..=0x20.
+  #[test]
+  fn sm3() {
+    let key = (1..=32).collect::<Vec<u8>>();
+    assert_eq!(
+      hex_mac("sm3", &key, b"abc"),
+      "44809d3ff5336f44071be1917887380730ae145f940a53819279801784c65e17"
+    );
+    assert_eq!(
+      hex_mac("sm3", &key, &b"abcd".repeat(16)),
+      "d27f13a69be209923ca693683e382b3c4d87a8d54ed9a52bea35494f8284a93d"
+    );
+    assert_eq!(
+      hex_mac("sm3", &[0xaa; 131], long_key_data()),
+      "b4fd844e13342002f0b2e0690ea7741f1497d993a70494cea601e657bedf67a0"
+    );
+  }
+
+  // https://csrc.nist.gov/csrc/media/projects/cryptographic-standards-and-guidelines/documents/examples/hmac_sha3-256.pdf
+  #[test]
+  fn nist_sha3_256() {
+    for (key_length, data, expected) in [
+      (
+        32,
+        "Sample message for keylen<blocklen",
+        "4fe8e202c4f058e8dddc23d8c34e467343e23555e24fc2f025d598f558f67205",
+      ),
+      (
+        136,
+        "Sample message for keylen=blocklen",
+        "68b94e2e538a9be4103bebb5aa016d47961d4d1aa906061313b557f8af2c3faa",
+      ),
+      (
+        168,
+        "Sample message for keylen>blocklen",
+        "9bcf2c238e235c3ce88404e813bd2f3a97185ac6f238c63d6229a00b07974258",
+      ),
+    ] {
+      let key = (0..key_length).map(|i| i as u8).collect::<Vec<_>>();
+      assert_eq!(hex_mac("sha3-256", &key, data.as_bytes()), expected);
+    }
+  }
+
+  // BLAKE2b has a 128-byte block, so a 200-byte key is hashed first.
+  #[test]
+  fn blake2b_long_key() {
+    assert_eq!(
+      hex_mac("blake2b512", &[0xaa; 200], long_key_data()),
+      "2f5f2d35b23f886565d4ef590d7226d15750f0971a39346f2859f38e87f0b09b871ff0176dfd41c30f2d99e53309e215601088c0299f83f8acd804b32ccea0ae"
+    );
+  }
+
+  #[test]
+  fn oneshot_matches_streaming() {
+    let data = b"The quick brown fox jumps over the lazy dog";
+    for algorithm in ["md5", "sha1", "sha256", "sha3-512", "blake2b512", "sm3"]
+    {
+      let oneshot = hmac_oneshot(algorithm, b"key", data).unwrap();
+      assert_eq!(
+        faster_hex::hex_string(&oneshot),
+        hex_mac(algorithm, b"key", data)
+      );
+    }
+    assert!(matches!(
+      hmac_oneshot("shake256", b"key", data),
+      Err(HashError::HmacOverXof(_))
+    ));
+  }
+
+  #[test]
+  fn clone() {
+    let data = b"The quick brown fox jumps over the lazy dog";
+    let hmac = Hmac::new("sha256", b"key").unwrap();
+    assert!(hmac.update(&data[..9]));
+    let copy = hmac.clone_inner().unwrap();
+    assert!(copy.update(b"something else"));
+    assert!(hmac.update(&data[9..]));
+    assert_eq!(
+      faster_hex::hex_string(&hmac.digest().unwrap()),
+      hex_mac("sha256", b"key", data)
+    );
+    assert!(hmac.clone_inner().is_none());
+
+    assert!(copy.update(&data[9..]));
+    let mut expected = data[..9].to_vec();
+    expected.extend_from_slice(b"something else");
+    expected.extend_from_slice(&data[9..]);
+    assert_eq!(
+      faster_hex::hex_string(&copy.digest().unwrap()),
+      hex_mac("sha256", b"key", &expected)
+    );
+  }
+
+  #[test]
+  fn verify() {
+    let tag = hmac_oneshot("sha256", b"key", b"payload").unwrap();
+    assert!(hmac_verify("sha256", b"key", b"payload", Some(&tag)).unwrap());
+    assert!(!hmac_verify("sha256", b"key", b"payloaf", Some(&tag)).unwrap());
+    assert!(!hmac_verify("sha256", b"yek", b"payload", Some(&tag)).unwrap());
+    assert!(
+      !hmac_verify("sha256", b"key", b"payload", Some(&tag[..31])).unwrap()
+    );
+    assert!(!hmac_verify("sha256", b"key", b"payload", None).unwrap());
+    assert!(matches!(
+      hmac_verify("nope", b"key", b"payload", None),
+      Err(HashError::DigestMethodUnsupported(_))
+    ));
+
+    let hex = faster_hex::hex_string(&tag);
+    for (expected, encoding) in [
+      (hex.as_str(), "hex"),
+      (&BASE64_STANDARD.encode(&tag), "base64"),
+      (&BASE64_URL_SAFE_NO_PAD.encode(&tag), "base64url"),
+    ] {
+      let expected = decode_expected(expected, encoding).unwrap();
+      assert!(
+        hmac_verify("sha256", b"key", b"payload", expected.as_deref()).unwrap()
+      );
+    }
+    assert_eq!(decode_expected("zz", "hex").unwrap(), None);
+    assert!(matches!(
+      decode_expected(&hex, "ucs2"),
+      Err(HashError::UnsupportedEncoding(_))
+    ));
+  }
+
+  #[test]
+  fn wiped_on_drop() {
+    struct Recorder(Rc<Cell<bool>>);
+    impl DynMac for Recorder {
+      fn update(&mut self, _: &[u8]) {}
+      fn finalize(&self) -> Box<[u8]> {
+        assert!(!self.0.get());
+        Box::new([])
+      }
+      fn box_clone(&self) -> Box<dyn DynMac> {
+        Box::new(Recorder(self.0.clone()))
+      }
+      fn wipe(&mut self) {
+        self.0.set(true);
+      }
+    }
+    let hmac = |wiped: &Rc<Cell<bool>>| Hmac {
+      mac: RefCell::new(Some(MacState(Box::new(Recorder(wiped.clone()))))),
+    };
+
+    let wiped = Rc::new(Cell::new(false));
+    drop(hmac(&wiped));
+    assert!(wiped.get());
+
+    let wiped = Rc::new(Cell::new(false));
+    let finalized = hmac(&wiped);
+    finalized.digest().unwrap();
+    assert!(wiped.get());
+  }
+
+  #[test]
+  fn batch() {
+    let chunk = vec![0x5a; 4096];
+    for count in [0, 1, 3, 300] {
+      let prefixes =
+        (0..count).map(|i: u32| i.to_be_bytes()).collect::<Vec<_>>();
+      let pairs = prefixes
+        .iter()
+        .map(|prefix| [prefix.as_slice(), chunk.as_slice()])
+        .collect::<Vec<_>>();
+      let macs = hmac_batch("sha256", b"key", &pairs).unwrap();
+      assert_eq!(macs.len(), count as usize);
+      for (mac, prefix) in macs.iter().zip(&prefixes) {
+        let data = [prefix.as_slice(), &chunk].concat();
+        assert_eq!(*mac, hmac_oneshot("sha256", b"key", &data).unwrap());
+      }
+    }
+    assert!(matches!(
+      hmac_batch("shake128", b"key", &[]),
+      Err(HashError::HmacOverXof(_))
+    ));
+  }
+
+  #[test]
+  fn finalized() {
+    let hmac = Hmac::new("sha256", b"key").unwrap();
+    assert!(hmac.digest().is_some());
+    assert!(!hmac.update(b"data"));
+    assert!(hmac.digest().is_none());
+  }
+
+  #[test]
+  fn rejects_xofs() {
+    for algorithm in ["shake128", "shake256", "blake3", "cshake128"] {
+      assert!(matches!(
+        Hmac::new(algorithm, b"key"),
+        Err(HashError::HmacOverXof(_))
+      ));
+    }
+    for algorithm in ["nope", "siphash24"] {
+      assert!(matches!(
+        Hmac::new(algorithm, b"key"),
+        Err(HashError::DigestMethodUnsupported(_))
+      ));
+    }
+  }
+}
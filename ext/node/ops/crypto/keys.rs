@@ -2,16 +2,25 @@
 
 use std::borrow::Cow;
 use std::cell::RefCell;
+use std::rc::Rc;
 
+use aes::cipher::BlockDecryptMut as _;
+use aes::cipher::BlockEncryptMut as _;
+use aes::cipher::KeyIvInit as _;
+use aes::cipher::block_padding::Pkcs7;
 use base64::Engine;
 use deno_core::GarbageCollected;
+use deno_core::OpState;
 use deno_core::ToJsBuffer;
 use deno_core::op2;
 use deno_core::serde_v8::BigInt as V8BigInt;
 use deno_core::unsync::spawn_blocking;
+use deno_core::v8;
 use deno_error::JsErrorBox;
+use deno_features::FeatureChecker;
 use ed25519_dalek::pkcs8::BitStringRef;
 use elliptic_curve::JwkEcKey;
+use md5::Digest as _;
 use num_bigint::BigInt;
 use num_traits::FromPrimitive as _;
 use pkcs8::DecodePrivateKey as _;
@@ -32,7 +41,6 @@ use rsa::traits::PrivateKeyParts;
 use rsa::traits::PublicKeyParts;
 use sec1::DecodeEcPrivateKey as _;
 use sec1::LineEnding;
-use sec1::der::Tag;
 use sec1::der::Writer as _;
 use sec1::pem::PemLabel as _;
 use spki::DecodePublicKey as _;
@@ -48,12 +56,20 @@ use spki::der::asn1::OctetStringRef;
 use x509_parser::error::X509Error;
 use x509_parser::x509;
 
+use super::des;
 use super::dh;
 use super::dh::DiffieHellmanGroup;
 use super::digest::match_fixed_digest_with_oid;
+use super::dsa_params;
+use super::ed448;
+use super::ml_dsa;
+use super::ml_kem;
 use super::pkcs3;
 use super::pkcs3::DhParameter;
 use super::primes::Prime;
+use super::slh_dsa;
+use super::sm2;
+use super::x448;
 
 #[derive(Clone)]
 pub enum KeyObjectHandle {
@@ -75,7 +91,12 @@ pub enum AsymmetricPrivateKey {
   Dsa(dsa::SigningKey),
   Ec(EcPrivateKey),
   X25519(x25519_dalek::StaticSecret),
+  X448(x448::StaticSecret),
   Ed25519(ed25519_dalek::SigningKey),
+  Ed448(ed448::SigningKey),
+  MlDsa(ml_dsa::SigningKey),
+  MlKem(ml_kem::DecapsulationKey),
+  SlhDsa(slh_dsa::SigningKey),
   Dh(DhPrivateKey),
 }
 
@@ -85,13 +106,70 @@ pub struct RsaPssPrivateKey {
   pub details: Option<RsaPssDetails>,
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, PartialEq, Eq)]
 pub struct RsaPssDetails {
   pub hash_algorithm: RsaPssHashAlgorithm,
   pub mf1_hash_algorithm: RsaPssHashAlgorithm,
   pub salt_length: u32,
 }
 
+impl RsaPssDetails {
+  /// The JWA `alg` of a key restricted to these parameters, if there is one.
+  /// PS256, PS384 and PS512 use the same hash for MGF1 and a salt the size
+  /// of the digest (RFC 7518, section 3.5).
+  fn jwk_alg(&self) -> Option<&'static str> {
+    if self.mf1_hash_algorithm != self.hash_algorithm
+      || self.salt_length != self.hash_algorithm.salt_length()
+    {
+      return None;
+    }
+    match self.hash_algorithm {
+      RsaPssHashAlgorithm::Sha256 => Some("PS256"),
+      RsaPssHashAlgorithm::Sha384 => Some("PS384"),
+      RsaPssHashAlgorithm::Sha512 => Some("PS512"),
+      _ => None,
+    }
+  }
+
+  fn from_jwk_alg(alg: &str) -> Option<Self> {
+    let hash_algorithm = match alg {
+      "PS256" => RsaPssHashAlgorithm::Sha256,
+      "PS384" => RsaPssHashAlgorithm::Sha384,
+      "PS512" => RsaPssHashAlgorithm::Sha512,
+      _ => return None,
+    };
+    Some(RsaPssDetails {
+      hash_algorithm,
+      mf1_hash_algorithm: hash_algorithm,
+      salt_length: hash_algorithm.salt_length(),
+    })
+  }
+
+  /// The DER of the RSASSA-PSS-params for the key's AlgorithmIdentifier.
+  fn to_der(self) -> der::Result<Vec<u8>> {
+    let hash =
+      |algorithm: RsaPssHashAlgorithm| rsa::pkcs8::AlgorithmIdentifierRef {
+        oid: algorithm.oid(),
+        parameters: Some(AnyRef::NULL),
+      };
+    let mgf1_hash = hash(self.mf1_hash_algorithm).to_der()?;
+    let mask_gen_algorithm = match self.mf1_hash_algorithm {
+      RsaPssHashAlgorithm::Sha1 => None,
+      _ => Some(rsa::pkcs8::AlgorithmIdentifierRef {
+        oid: ID_MFG1,
+        parameters: Some(AnyRef::from_der(&mgf1_hash)?),
+      }),
+    };
+    RsaPssParametersEncoding {
+      hash_algorithm: (self.hash_algorithm != RsaPssHashAlgorithm::Sha1)
+        .then(|| hash(self.hash_algorithm)),
+      mask_gen_algorithm,
+      salt_length: (self.salt_length != 20).then_some(self.salt_length),
+    }
+    .to_der()
+  }
+}
+
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub enum RsaPssHashAlgorithm {
   Sha1,
@@ -116,6 +194,18 @@ impl RsaPssHashAlgorithm {
     }
   }
 
+  fn oid(&self) -> rsa::pkcs8::ObjectIdentifier {
+    match self {
+      RsaPssHashAlgorithm::Sha1 => ID_SHA1_OID,
+      RsaPssHashAlgorithm::Sha224 => ID_SHA224_OID,
+      RsaPssHashAlgorithm::Sha256 => ID_SHA256_OID,
+      RsaPssHashAlgorithm::Sha384 => ID_SHA384_OID,
+      RsaPssHashAlgorithm::Sha512 => ID_SHA512_OID,
+      RsaPssHashAlgorithm::Sha512_224 => ID_SHA512_224_OID,
+      RsaPssHashAlgorithm::Sha512_256 => ID_SHA512_256_OID,
+    }
+  }
+
   pub fn salt_length(&self) -> u32 {
     match self {
       RsaPssHashAlgorithm::Sha1 => 20,
@@ -132,6 +222,9 @@ pub enum EcPrivateKey {
   P224(p224::SecretKey),
   P256(p256::SecretKey),
   P384(p384::SecretKey),
+  P521(p521::SecretKey),
+  Secp256k1(k256::SecretKey),
+  Sm2(sm2::SecretKey),
 }
 
 #[derive(Clone)]
@@ -140,6 +233,22 @@ pub struct DhPrivateKey {
   pub params: DhParameter,
 }
 
+impl DhPrivateKey {
+  /// Derives the public key, `g^x mod p`.
+  pub fn to_public_key(&self) -> DhPublicKey {
+    let uint = |value: &asn1::Uint| {
+      num_bigint_dig::BigUint::from_bytes_be(value.as_bytes())
+    };
+    DhPublicKey {
+      key: self.key.compute_public_key(
+        &uint(&self.params.base),
+        &uint(&self.params.prime),
+      ),
+      params: self.params.clone(),
+    }
+  }
+}
+
 #[derive(Clone)]
 pub enum AsymmetricPublicKey {
   Rsa(rsa::RsaPublicKey),
@@ -147,7 +256,12 @@ pub enum AsymmetricPublicKey {
   Dsa(dsa::VerifyingKey),
   Ec(EcPublicKey),
   X25519(x25519_dalek::PublicKey),
+  X448(x448::PublicKey),
   Ed25519(ed25519_dalek::VerifyingKey),
+  Ed448(ed448::VerifyingKey),
+  MlDsa(ml_dsa::VerifyingKey),
+  MlKem(ml_kem::EncapsulationKey),
+  SlhDsa(slh_dsa::VerifyingKey),
   Dh(DhPublicKey),
 }
 
@@ -162,6 +276,9 @@ pub enum EcPublicKey {
   P224(p224::PublicKey),
   P256(p256::PublicKey),
   P384(p384::PublicKey),
+  P521(p521::PublicKey),
+  Secp256k1(k256::PublicKey),
+  Sm2(sm2::PublicKey),
 }
 
 #[derive(Clone)]
@@ -198,6 +315,38 @@ impl KeyObjectHandle {
       _ => None,
     }
   }
+
+  /// Compares the canonical encodings of two keys in constant time: the
+  /// bytes of a secret key, SPKI for a public key and PKCS#8 for a private
+  /// key.
+  pub fn equals(&self, other: &KeyObjectHandle) -> bool {
+    use aws_lc_rs::constant_time::verify_slices_are_equal;
+
+    let (a, b) = match (self, other) {
+      (KeyObjectHandle::Secret(a), KeyObjectHandle::Secret(b)) => {
+        return verify_slices_are_equal(a, b).is_ok();
+      }
+      (
+        KeyObjectHandle::AsymmetricPublic(a),
+        KeyObjectHandle::AsymmetricPublic(b),
+      ) => (a.export_der("spki").ok(), b.export_der("spki").ok()),
+      (
+        KeyObjectHandle::AsymmetricPrivate(a),
+        KeyObjectHandle::AsymmetricPrivate(b),
+      ) => (
+        a.export_der("pkcs8", None, None).ok(),
+        b.export_der("pkcs8", None, None).ok(),
+      ),
+      _ => return false,
+    };
+    let (Some(a), Some(b)) = (
+      a.map(zeroize::Zeroizing::new),
+      b.map(zeroize::Zeroizing::new),
+    ) else {
+      return false;
+    };
+    verify_slices_are_equal(&a, &b).is_ok()
+  }
 }
 
 impl AsymmetricPrivateKey {
@@ -219,11 +368,26 @@ impl AsymmetricPrivateKey {
       AsymmetricPrivateKey::X25519(key) => {
         AsymmetricPublicKey::X25519(x25519_dalek::PublicKey::from(key))
       }
+      AsymmetricPrivateKey::X448(key) => {
+        AsymmetricPublicKey::X448(key.public_key().clone())
+      }
       AsymmetricPrivateKey::Ed25519(key) => {
         AsymmetricPublicKey::Ed25519(key.verifying_key())
       }
-      AsymmetricPrivateKey::Dh(_) => {
-        panic!("cannot derive public key from DH private key")
+      AsymmetricPrivateKey::Ed448(key) => {
+        AsymmetricPublicKey::Ed448(key.verifying_key().clone())
+      }
+      AsymmetricPrivateKey::MlDsa(key) => {
+        AsymmetricPublicKey::MlDsa(key.verifying_key().clone())
+      }
+      AsymmetricPrivateKey::MlKem(key) => {
+        AsymmetricPublicKey::MlKem(key.encapsulation_key().clone())
+      }
+      AsymmetricPrivateKey::SlhDsa(key) => {
+        AsymmetricPublicKey::SlhDsa(key.verifying_key())
+      }
+      AsymmetricPrivateKey::Dh(key) => {
+        AsymmetricPublicKey::Dh(key.to_public_key())
       }
     }
   }
@@ -247,6 +411,11 @@ impl EcPublicKey {
       }
       EcPublicKey::P256(key) => Ok(key.to_jwk()),
       EcPublicKey::P384(key) => Ok(key.to_jwk()),
+      EcPublicKey::P521(key) => Ok(key.to_jwk()),
+      EcPublicKey::Secp256k1(key) => Ok(key.to_jwk()),
+      EcPublicKey::Sm2(_) => {
+        Err(AsymmetricPublicKeyJwkError::UnsupportedJwkEcCurveSm2)
+      }
     }
   }
 }
@@ -258,6 +427,9 @@ impl EcPrivateKey {
       EcPrivateKey::P224(key) => EcPublicKey::P224(key.public_key()),
       EcPrivateKey::P256(key) => EcPublicKey::P256(key.public_key()),
       EcPrivateKey::P384(key) => EcPublicKey::P384(key.public_key()),
+      EcPrivateKey::P521(key) => EcPublicKey::P521(key.public_key()),
+      EcPrivateKey::Secp256k1(key) => EcPublicKey::Secp256k1(key.public_key()),
+      EcPrivateKey::Sm2(key) => EcPublicKey::Sm2(key.public_key().clone()),
     }
   }
 
@@ -268,6 +440,11 @@ impl EcPrivateKey {
       }
       EcPrivateKey::P256(key) => Ok(key.to_jwk()),
       EcPrivateKey::P384(key) => Ok(key.to_jwk()),
+      EcPrivateKey::P521(key) => Ok(key.to_jwk()),
+      EcPrivateKey::Secp256k1(key) => Ok(key.to_jwk()),
+      EcPrivateKey::Sm2(_) => {
+        Err(AsymmetricPrivateKeyJwkError::UnsupportedJwkEcCurveSm2)
+      }
     }
   }
 }
@@ -296,6 +473,12 @@ pub const ID_SECP256R1_OID: const_oid::ObjectIdentifier =
   const_oid::ObjectIdentifier::new_unwrap("1.2.840.10045.3.1.7");
 pub const ID_SECP384R1_OID: const_oid::ObjectIdentifier =
   const_oid::ObjectIdentifier::new_unwrap("1.3.132.0.34");
+pub const ID_SECP521R1_OID: const_oid::ObjectIdentifier =
+  const_oid::ObjectIdentifier::new_unwrap("1.3.132.0.35");
+pub const ID_SECP256K1_OID: const_oid::ObjectIdentifier =
+  const_oid::ObjectIdentifier::new_unwrap("1.3.132.0.10");
+pub const ID_SM2P256V1_OID: const_oid::ObjectIdentifier =
+  const_oid::ObjectIdentifier::new_unwrap("1.2.156.10197.1.301");
 
 pub const RSA_ENCRYPTION_OID: const_oid::ObjectIdentifier =
   const_oid::ObjectIdentifier::new_unwrap("1.2.840.113549.1.1.1");
@@ -307,10 +490,22 @@ pub const EC_OID: const_oid::ObjectIdentifier =
   const_oid::ObjectIdentifier::new_unwrap("1.2.840.10045.2.1");
 pub const X25519_OID: const_oid::ObjectIdentifier =
   const_oid::ObjectIdentifier::new_unwrap("1.3.101.110");
+pub const X448_OID: const_oid::ObjectIdentifier =
+  const_oid::ObjectIdentifier::new_unwrap("1.3.101.111");
 pub const ED25519_OID: const_oid::ObjectIdentifier =
   const_oid::ObjectIdentifier::new_unwrap("1.3.101.112");
+pub const ED448_OID: const_oid::ObjectIdentifier =
+  const_oid::ObjectIdentifier::new_unwrap("1.3.101.113");
 pub const DH_KEY_AGREEMENT_OID: const_oid::ObjectIdentifier =
   const_oid::ObjectIdentifier::new_unwrap("1.2.840.113549.1.3.1");
+const PBE_WITH_MD5_AND_DES_CBC_OID: const_oid::ObjectIdentifier =
+  const_oid::ObjectIdentifier::new_unwrap("1.2.840.113549.1.5.3");
+const PBE_WITH_SHA1_AND_DES_CBC_OID: const_oid::ObjectIdentifier =
+  const_oid::ObjectIdentifier::new_unwrap("1.2.840.113549.1.5.10");
+const PBE_WITH_SHA1_AND_3_KEY_TRIPLE_DES_CBC_OID: const_oid::ObjectIdentifier =
+  const_oid::ObjectIdentifier::new_unwrap("1.2.840.113549.1.12.1.3");
+const PBE_WITH_SHA1_AND_2_KEY_TRIPLE_DES_CBC_OID: const_oid::ObjectIdentifier =
+  const_oid::ObjectIdentifier::new_unwrap("1.2.840.113549.1.12.1.4");
 
 // The parameters field associated with OID id-RSASSA-PSS
 // Defined in RFC 3447, section A.2.3
@@ -379,6 +574,513 @@ impl<'a> TryFrom<rsa::pkcs8::der::asn1::AnyRef<'a>> for RsaPssParameters<'a> {
   }
 }
 
+// RSASSA-PSS-params as OpenSSL writes them: without the fields that have
+// their default values, and with NULL parameters for the hash algorithms.
+#[derive(der::Sequence)]
+struct RsaPssParametersEncoding<'a> {
+  #[asn1(context_specific = "0", tag_mode = "EXPLICIT", optional = "true")]
+  hash_algorithm: Option<rsa::pkcs8::AlgorithmIdentifierRef<'a>>,
+  #[asn1(context_specific = "1", tag_mode = "EXPLICIT", optional = "true")]
+  mask_gen_algorithm: Option<rsa::pkcs8::AlgorithmIdentifierRef<'a>>,
+  #[asn1(context_specific = "2", tag_mode = "EXPLICIT", optional = "true")]
+  salt_length: Option<u32>,
+}
+
+// OpenSSL's traditional "DSA PRIVATE KEY" format, which has no standard
+// definition but the one in its `dsa_asn1.c`.
+//
+// DSAPrivateKey ::= SEQUENCE {
+//   version INTEGER, -- 0
+//   p INTEGER,
+//   q INTEGER,
+//   g INTEGER,
+//   pub_key INTEGER,
+//   priv_key INTEGER
+// }
+#[derive(der::Sequence)]
+struct DsaTraditionalPrivateKey<'a> {
+  version: u8,
+  p: asn1::UintRef<'a>,
+  q: asn1::UintRef<'a>,
+  g: asn1::UintRef<'a>,
+  pub_key: asn1::UintRef<'a>,
+  priv_key: asn1::UintRef<'a>,
+}
+
+const DSA_PRIVATE_KEY_PEM_LABEL: &str = "DSA PRIVATE KEY";
+/// OpenSSL's label for SEC1 keys on the SM2 curve.
+const SM2_PRIVATE_KEY_PEM_LABEL: &str = "SM2 PRIVATE KEY";
+const CERTIFICATE_PEM_LABEL: &str = "CERTIFICATE";
+
+/// The subjectPublicKeyInfo of a DER certificate. Its validity period
+/// isn't checked, as in Node.js.
+fn certificate_spki(der: &[u8]) -> Result<Document, AsymmetricPublicKeyError> {
+  if let Err(err) = AnyRef::from_der(der) {
+    if let spki::der::ErrorKind::Incomplete { .. } = err.kind() {
+      return Err(AsymmetricPublicKeyError::X509CertificateTooLong);
+    }
+  }
+  let (_, certificate) = x509_parser::parse_x509_certificate(der)
+    .map_err(|_| AsymmetricPublicKeyError::InvalidX509Certificate)?;
+  Document::try_from(certificate.tbs_certificate.subject_pki.raw)
+    .map_err(|_| AsymmetricPublicKeyError::InvalidX509Certificate)
+}
+
+/// Decrypts a PKCS#8 `EncryptedPrivateKeyInfo`. A wrong passphrase is
+/// reported as a bad decrypt, as in OpenSSL.
+fn decrypt_pkcs8(
+  info: EncryptedPrivateKeyInfo,
+  passphrase: Option<&[u8]>,
+) -> Result<SecretDocument, AsymmetricPrivateKeyError> {
+  let passphrase = passphrase.ok_or(
+    AsymmetricPrivateKeyError::EncryptedPrivateKeyRequiresPassphraseToDecrypt,
+  )?;
+  let algorithm = info.encryption_algorithm.oid();
+  info.decrypt(passphrase).map_err(|err| match err {
+    // pkcs5 reports bad padding as a failure to encrypt.
+    pkcs8::Error::EncryptedPrivateKey(
+      pkcs8::pkcs5::Error::DecryptFailed | pkcs8::pkcs5::Error::EncryptFailed,
+    )
+    | pkcs8::Error::Asn1(_) => AsymmetricPrivateKeyError::BadDecrypt,
+    _ => AsymmetricPrivateKeyError::UnsupportedPrivateKeyEncryption(
+      algorithm.to_string(),
+    ),
+  })
+}
+
+// An `EncryptedPrivateKeyInfo` whose algorithm is left undecoded, for the
+// PKCS#5 v1.5 and PKCS#12 schemes that `pkcs8` doesn't decrypt. Both define
+// their parameters as
+//
+// PBEParameter ::= SEQUENCE {
+//   salt OCTET STRING,
+//   iterationCount INTEGER
+// }
+#[derive(der::Sequence)]
+struct LegacyEncryptedPrivateKeyInfo<'a> {
+  encryption_algorithm: rsa::pkcs8::AlgorithmIdentifierRef<'a>,
+  encrypted_data: OctetStringRef<'a>,
+}
+
+#[derive(der::Sequence)]
+struct PbeParameter<'a> {
+  salt: OctetStringRef<'a>,
+  iteration_count: u32,
+}
+
+/// Decrypts a PKCS#8 private key encrypted with one of the PKCS#12 triple
+/// DES schemes, or with a PKCS#5 v1.5 DES scheme if the legacy provider is
+/// enabled, as in OpenSSL 3. `None` for any other encryption, which is left
+/// to `decrypt_pkcs8`.
+fn decrypt_legacy_pkcs8(
+  der: &[u8],
+  passphrase: Option<&[u8]>,
+) -> Result<Option<SecretDocument>, AsymmetricPrivateKeyError> {
+  let Ok(info) = LegacyEncryptedPrivateKeyInfo::from_der(der) else {
+    return Ok(None);
+  };
+  let algorithm = info.encryption_algorithm.oid;
+  let key_len = match algorithm {
+    PBE_WITH_SHA1_AND_3_KEY_TRIPLE_DES_CBC_OID => 24,
+    PBE_WITH_SHA1_AND_2_KEY_TRIPLE_DES_CBC_OID => 16,
+    PBE_WITH_MD5_AND_DES_CBC_OID | PBE_WITH_SHA1_AND_DES_CBC_OID => {
+      if !super::legacy_provider::enabled() {
+        return Err(
+          AsymmetricPrivateKeyError::UnsupportedPrivateKeyEncryption(
+            algorithm.to_string(),
+          ),
+        );
+      }
+      8
+    }
+    _ => return Ok(None),
+  };
+  let params = info
+    .encryption_algorithm
+    .parameters
+    .and_then(|params| params.decode_as::<PbeParameter>().ok())
+    .ok_or(AsymmetricPrivateKeyError::InvalidPkcs8PrivateKey)?;
+  let salt = params.salt.as_bytes();
+  // OpenSSL treats a zero iteration count as one.
+  let iterations = params.iteration_count.max(1);
+
+  let passphrase = passphrase.ok_or(
+    AsymmetricPrivateKeyError::EncryptedPrivateKeyRequiresPassphraseToDecrypt,
+  )?;
+  let derived = match algorithm {
+    PBE_WITH_MD5_AND_DES_CBC_OID => {
+      pbkdf1::<md5::Md5>(passphrase, salt, iterations)
+    }
+    PBE_WITH_SHA1_AND_DES_CBC_OID => {
+      pbkdf1::<sha1::Sha1>(passphrase, salt, iterations)
+    }
+    _ => {
+      let password = pkcs12_password(passphrase);
+      let mut derived = pkcs12_kdf(&password, salt, iterations, 1, key_len);
+      derived.extend_from_slice(&pkcs12_kdf(&password, salt, iterations, 2, 8));
+      derived
+    }
+  };
+  let (key, iv) = derived.split_at(key_len);
+  let der = des::decrypt_cbc(key, iv, info.encrypted_data.as_bytes())
+    .map(zeroize::Zeroizing::new)
+    .ok_or(AsymmetricPrivateKeyError::BadDecrypt)?;
+  SecretDocument::try_from(der.as_slice())
+    .map(Some)
+    .map_err(|_| AsymmetricPrivateKeyError::BadDecrypt)
+}
+
+/// PBKDF1 of PKCS#5 v1.5, producing the 8 byte DES key followed by the IV.
+fn pbkdf1<D: md5::Digest>(
+  passphrase: &[u8],
+  salt: &[u8],
+  iterations: u32,
+) -> zeroize::Zeroizing<Vec<u8>> {
+  let mut derived = D::new()
+    .chain_update(passphrase)
+    .chain_update(salt)
+    .finalize();
+  for _ in 1..iterations {
+    derived = D::digest(derived);
+  }
+  zeroize::Zeroizing::new(derived[..16].to_vec())
+}
+
+/// Encodes a passphrase as the null-terminated BMPString that PKCS#12 key
+/// derivation expects. Like OpenSSL, a passphrase that isn't UTF-8 is taken
+/// to be Latin-1.
+fn pkcs12_password(passphrase: &[u8]) -> zeroize::Zeroizing<Vec<u8>> {
+  let units: zeroize::Zeroizing<Vec<u16>> =
+    zeroize::Zeroizing::new(match std::str::from_utf8(passphrase) {
+      Ok(passphrase) => passphrase.encode_utf16().collect(),
+      Err(_) => passphrase.iter().map(|&byte| byte as u16).collect(),
+    });
+  zeroize::Zeroizing::new(
+    units
+      .iter()
+      .chain(&[0])
+      .flat_map(|unit| unit.to_be_bytes())
+      .collect(),
+  )
+}
+
+/// The PKCS#12 key derivation of RFC 7292, appendix B.2, with SHA-1. `id`
+/// is 1 for the key and 2 for the IV.
+fn pkcs12_kdf(
+  password: &[u8],
+  salt: &[u8],
+  iterations: u32,
+  id: u8,
+  len: usize,
+) -> zeroize::Zeroizing<Vec<u8>> {
+  // The block size of SHA-1.
+  const V: usize = 64;
+  let fill = |data: &[u8]| {
+    data
+      .iter()
+      .cycle()
+      .take(data.len().div_ceil(V) * V)
+      .copied()
+      .collect::<Vec<_>>()
+  };
+  let mut input = zeroize::Zeroizing::new(fill(salt));
+  input.extend(fill(password));
+
+  let mut output = zeroize::Zeroizing::new(Vec::with_capacity(len + 20));
+  while output.len() < len {
+    let mut block = sha1::Sha1::new()
+      .chain_update([id; V])
+      .chain_update(input.as_slice())
+      .finalize();
+    for _ in 1..iterations {
+      block = sha1::Sha1::digest(block);
+    }
+    output.extend_from_slice(&block);
+    // Adds the block, repeated to V bytes, plus one to each V byte chunk of
+    // the input.
+    for chunk in input.chunks_mut(V) {
+      let mut carry = 1u16;
+      for (i, byte) in chunk.iter_mut().enumerate().rev() {
+        carry += *byte as u16 + block[i % block.len()] as u16;
+        *byte = carry as u8;
+        carry >>= 8;
+      }
+    }
+  }
+  output.truncate(len);
+  output
+}
+
+/// Encrypts a PKCS#8 private key with PBES2 as OpenSSL's `PKCS8_encrypt`
+/// does by default: PBKDF2 with HMAC-SHA256, 2048 iterations and a 16 byte
+/// salt.
+fn encrypt_pkcs8(
+  der: &[u8],
+  cipher: &str,
+  passphrase: &[u8],
+) -> Result<Box<[u8]>, AsymmetricPrivateKeyDerError> {
+  use pkcs8::pkcs5::pbes2;
+
+  let key_len = export_cipher_key_len(cipher)?;
+  let mut salt = [0; 16];
+  let mut iv = [0; 16];
+  thread_rng().fill_bytes(&mut salt);
+  thread_rng().fill_bytes(&mut iv);
+  let kdf = pbes2::Pbkdf2Params::hmac_with_sha256(2048, &salt)
+    .map_err(|_| AsymmetricPrivateKeyDerError::EncryptionFailed)?;
+  let encryption = match key_len {
+    16 => pbes2::EncryptionScheme::Aes128Cbc { iv: &iv },
+    24 => pbes2::EncryptionScheme::Aes192Cbc { iv: &iv },
+    _ => pbes2::EncryptionScheme::Aes256Cbc { iv: &iv },
+  };
+  let params = pbes2::Parameters {
+    kdf: pbes2::Kdf::Pbkdf2(kdf),
+    encryption,
+  };
+  PrivateKeyInfo::try_from(der)
+    .and_then(|info| info.encrypt_with_params(params, passphrase))
+    .map(|document| document.as_bytes().into())
+    .map_err(|_| AsymmetricPrivateKeyDerError::EncryptionFailed)
+}
+
+/// Encrypts a PKCS#1 or SEC1 private key for OpenSSL's traditional PEM
+/// format. Returns the `DEK-Info` header and the ciphertext.
+fn encrypt_traditional(
+  der: &[u8],
+  cipher: &str,
+  passphrase: &[u8],
+) -> Result<(String, Vec<u8>), AsymmetricPrivateKeyDerError> {
+  let key_len = export_cipher_key_len(cipher)?;
+  let mut iv = [0; 16];
+  thread_rng().fill_bytes(&mut iv);
+  let key = evp_bytes_to_key(passphrase, &iv[..8], key_len);
+  let ciphertext = match key_len {
+    16 => cbc::Encryptor::<aes::Aes128>::new_from_slices(&key, &iv)
+      .map(|cipher| cipher.encrypt_padded_vec_mut::<Pkcs7>(der)),
+    24 => cbc::Encryptor::<aes::Aes192>::new_from_slices(&key, &iv)
+      .map(|cipher| cipher.encrypt_padded_vec_mut::<Pkcs7>(der)),
+    _ => cbc::Encryptor::<aes::Aes256>::new_from_slices(&key, &iv)
+      .map(|cipher| cipher.encrypt_padded_vec_mut::<Pkcs7>(der)),
+  }
+  .map_err(|_| AsymmetricPrivateKeyDerError::EncryptionFailed)?;
+  let dek_info = format!(
+    "AES-{}-CBC,{}",
+    key_len * 8,
+    faster_hex::hex_string_upper(&iv)
+  );
+  Ok((dek_info, ciphertext))
+}
+
+/// The key length of a cipher that private keys can be exported with.
+fn export_cipher_key_len(
+  cipher: &str,
+) -> Result<usize, AsymmetricPrivateKeyDerError> {
+  match cipher.to_ascii_lowercase().as_str() {
+    "aes-128-cbc" | "aes128" => Ok(16),
+    "aes-192-cbc" | "aes192" => Ok(24),
+    "aes-256-cbc" | "aes256" => Ok(32),
+    _ => Err(AsymmetricPrivateKeyDerError::UnknownCipher),
+  }
+}
+
+/// OpenSSL's `EVP_BytesToKey` with MD5 and one round, which derives the key
+/// of a traditional encrypted PEM.
+fn evp_bytes_to_key(
+  passphrase: &[u8],
+  salt: &[u8],
+  key_len: usize,
+) -> zeroize::Zeroizing<Vec<u8>> {
+  let mut key = zeroize::Zeroizing::new(Vec::with_capacity(key_len + 16));
+  while key.len() < key_len {
+    let mut hasher = md5::Md5::new();
+    hasher.update(&key[key.len().saturating_sub(16)..]);
+    hasher.update(passphrase);
+    hasher.update(salt);
+    key.extend_from_slice(&hasher.finalize());
+  }
+  key.truncate(key_len);
+  key
+}
+
+/// Decrypts a private key PEM in OpenSSL's traditional format, with
+/// `Proc-Type` and `DEK-Info` headers. The key is derived by
+/// `evp_bytes_to_key`, salted with the first 8 bytes of the IV. `None` for a
+/// PEM without these headers.
+fn decrypt_traditional_pem<'a>(
+  pem: &'a str,
+  passphrase: Option<&[u8]>,
+) -> Result<Option<(&'a str, SecretDocument)>, AsymmetricPrivateKeyError> {
+  let mut lines = pem.trim_start().lines().map(str::trim_end);
+  let Some(label) = lines
+    .next()
+    .and_then(|line| line.strip_prefix("-----BEGIN "))
+    .and_then(|line| line.strip_suffix("-----"))
+  else {
+    return Ok(None);
+  };
+  if lines.next() != Some("Proc-Type: 4,ENCRYPTED") {
+    return Ok(None);
+  }
+  let (cipher, iv) = lines
+    .next()
+    .and_then(|line| line.strip_prefix("DEK-Info: "))
+    .and_then(|info| info.split_once(','))
+    .ok_or(AsymmetricPrivateKeyError::InvalidEncryptedPemPrivateKey)?;
+  let (key_len, iv_len) = match cipher {
+    "AES-128-CBC" => (16, 16),
+    "AES-192-CBC" => (24, 16),
+    "AES-256-CBC" => (32, 16),
+    "DES-EDE3-CBC" => (24, 8),
+    // As in OpenSSL 3, single DES needs the legacy provider.
+    "DES-CBC" if super::legacy_provider::enabled() => (8, 8),
+    _ => {
+      return Err(AsymmetricPrivateKeyError::UnsupportedPrivateKeyEncryption(
+        cipher.to_string(),
+      ));
+    }
+  };
+  let mut iv_bytes = [0; 16];
+  if iv.len() != 2 * iv_len
+    || faster_hex::hex_decode(iv.as_bytes(), &mut iv_bytes[..iv_len]).is_err()
+  {
+    return Err(AsymmetricPrivateKeyError::InvalidEncryptedPemPrivateKey);
+  }
+  let iv_bytes = &iv_bytes[..iv_len];
+  let end = format!("-----END {label}-----");
+  let body = lines
+    .take_while(|line| *line != end)
+    .collect::<Vec<_>>()
+    .concat();
+  let ciphertext = base64::engine::general_purpose::STANDARD
+    .decode(body.trim())
+    .map_err(|_| AsymmetricPrivateKeyError::InvalidEncryptedPemPrivateKey)?;
+
+  let passphrase = passphrase.ok_or(
+    AsymmetricPrivateKeyError::EncryptedPrivateKeyRequiresPassphraseToDecrypt,
+  )?;
+  let key = evp_bytes_to_key(passphrase, &iv_bytes[..8], key_len);
+  let der = match cipher {
+    "AES-128-CBC" => {
+      cbc::Decryptor::<aes::Aes128>::new_from_slices(&key, iv_bytes)
+        .map(|cipher| cipher.decrypt_padded_vec_mut::<Pkcs7>(&ciphertext))
+    }
+    "AES-192-CBC" => {
+      cbc::Decryptor::<aes::Aes192>::new_from_slices(&key, iv_bytes)
+        .map(|cipher| cipher.decrypt_padded_vec_mut::<Pkcs7>(&ciphertext))
+    }
+    "AES-256-CBC" => {
+      cbc::Decryptor::<aes::Aes256>::new_from_slices(&key, iv_bytes)
+        .map(|cipher| cipher.decrypt_padded_vec_mut::<Pkcs7>(&ciphertext))
+    }
+    _ => Ok(
+      des::decrypt_cbc(&key, iv_bytes, &ciphertext)
+        .ok_or(aes::cipher::block_padding::UnpadError),
+    ),
+  }
+  .map_err(|_| AsymmetricPrivateKeyError::InvalidEncryptedPemPrivateKey)?
+  .map(zeroize::Zeroizing::new)
+  .map_err(|_| AsymmetricPrivateKeyError::BadDecrypt)?;
+  let doc = SecretDocument::try_from(der.as_slice())
+    .map_err(|_| AsymmetricPrivateKeyError::BadDecrypt)?;
+  Ok(Some((label, doc)))
+}
+
+/// Re-encodes a traditional DSA private key as PKCS#8.
+fn dsa_traditional_to_pkcs8(
+  der: &[u8],
+) -> Result<SecretDocument, AsymmetricPrivateKeyError> {
+  let key = DsaTraditionalPrivateKey::from_der(der)
+    .map_err(|_| AsymmetricPrivateKeyError::InvalidDsaPrivateKey)?;
+  if key.version != 0 {
+    return Err(AsymmetricPrivateKeyError::InvalidDsaPrivateKey);
+  }
+  let uint =
+    |value: asn1::UintRef| dsa::BigUint::from_bytes_be(value.as_bytes());
+  let components =
+    dsa::Components::from_components(uint(key.p), uint(key.q), uint(key.g))
+      .map_err(|_| AsymmetricPrivateKeyError::InvalidDsaPrivateKey)?;
+  let verifying_key =
+    dsa::VerifyingKey::from_components(components, uint(key.pub_key))
+      .map_err(|_| AsymmetricPrivateKeyError::InvalidDsaPrivateKey)?;
+  let signing_key =
+    dsa::SigningKey::from_components(verifying_key, uint(key.priv_key))
+      .map_err(|_| AsymmetricPrivateKeyError::InvalidDsaPrivateKey)?;
+  signing_key
+    .to_pkcs8_der()
+    .map_err(|_| AsymmetricPrivateKeyError::InvalidDsaPrivateKey)
+}
+
+/// Re-encodes a SEC1 EC private key as PKCS#8. As in OpenSSL, the key must
+/// name its curve, but may leave out the public key.
+fn sec1_to_pkcs8(
+  der: &[u8],
+) -> Result<SecretDocument, AsymmetricPrivateKeyError> {
+  let key = sec1::EcPrivateKey::from_der(der)
+    .map_err(|_| AsymmetricPrivateKeyError::InvalidSec1PrivateKey)?;
+  if key.parameters.is_none() {
+    return Err(
+      AsymmetricPrivateKeyError::MalformedOrMissingNamedCurveInEcParameters,
+    );
+  }
+  SecretDocument::from_sec1_der(der)
+    .map_err(|_| AsymmetricPrivateKeyError::InvalidSec1PrivateKey)
+}
+
+/// SEC1 `ECPrivateKey` for an SM2 key. OpenSSL leaves out the curve when
+/// the key is wrapped in PKCS#8, which already names it.
+fn sm2_to_sec1_der(
+  key: &sm2::SecretKey,
+  with_parameters: bool,
+) -> Result<zeroize::Zeroizing<Vec<u8>>, spki::der::Error> {
+  let private_key = key.to_bytes();
+  let public_key = key.public_key().to_sec1_bytes();
+  sec1::EcPrivateKey {
+    private_key: &*private_key,
+    parameters: with_parameters
+      .then_some(sec1::EcParameters::NamedCurve(ID_SM2P256V1_OID)),
+    public_key: Some(&public_key),
+  }
+  .to_der()
+  .map(zeroize::Zeroizing::new)
+}
+
+/// SEC1 `ECPrivateKey`. Unlike `to_sec1_der`, it names the curve, as
+/// OpenSSL does.
+fn ec_to_sec1_der<C>(
+  key: &elliptic_curve::SecretKey<C>,
+  named_curve: const_oid::ObjectIdentifier,
+) -> Result<zeroize::Zeroizing<Vec<u8>>, spki::der::Error>
+where
+  C: elliptic_curve::CurveArithmetic + elliptic_curve::point::PointCompression,
+  elliptic_curve::AffinePoint<C>: elliptic_curve::sec1::FromEncodedPoint<C>
+    + elliptic_curve::sec1::ToEncodedPoint<C>,
+  elliptic_curve::FieldBytesSize<C>: elliptic_curve::sec1::ModulusSize,
+{
+  let private_key = key.to_bytes();
+  let public_key = key.public_key().to_sec1_bytes();
+  sec1::EcPrivateKey {
+    private_key: &private_key,
+    parameters: Some(sec1::EcParameters::NamedCurve(named_curve)),
+    public_key: Some(&public_key),
+  }
+  .to_der()
+  .map(zeroize::Zeroizing::new)
+}
+
+fn sm2_to_pkcs8_der(
+  key: &sm2::SecretKey,
+) -> Result<SecretDocument, spki::der::Error> {
+  let sec1 = sm2_to_sec1_der(key, false)?;
+  SecretDocument::encode_msg(&PrivateKeyInfo {
+    algorithm: rsa::pkcs8::AlgorithmIdentifierRef {
+      oid: EC_OID,
+      parameters: Some(asn1::AnyRef::from(&ID_SM2P256V1_OID)),
+    },
+    private_key: &sec1,
+    public_key: None,
+  })
+}
+
 #[derive(Debug, thiserror::Error, deno_error::JsError)]
 pub enum X509PublicKeyError {
   #[class(generic)]
@@ -407,42 +1109,118 @@ pub enum X509PublicKeyError {
   UnsupportedX509KeyType,
 }
 
+/// JWK members are unpadded base64url, but padding is tolerated as in
+/// Node.js.
+const BASE64_URL_SAFE_FORGIVING:
+  base64::engine::general_purpose::GeneralPurpose =
+  base64::engine::general_purpose::GeneralPurpose::new(
+    &base64::alphabet::URL_SAFE,
+    base64::engine::general_purpose::GeneralPurposeConfig::new()
+      .with_decode_allow_trailing_bits(true)
+      .with_decode_padding_mode(base64::engine::DecodePaddingMode::Indifferent),
+  );
+
 #[derive(Debug, thiserror::Error, deno_error::JsError)]
+#[class(type)]
+#[property("code" = "ERR_CRYPTO_INVALID_JWK")]
 pub enum RsaJwkError {
-  #[class(generic)]
-  #[error(transparent)]
+  #[error("Invalid JWK RSA key")]
   Base64(#[from] base64::DecodeError),
-  #[class(generic)]
-  #[error(transparent)]
+  #[error("Invalid JWK RSA key")]
   Rsa(#[from] rsa::Error),
-  #[class(type)]
   #[error("missing RSA private component")]
   MissingRsaPrivateComponent,
 }
 
 #[derive(Debug, thiserror::Error, deno_error::JsError)]
+#[class(type)]
+#[property("code" = "ERR_CRYPTO_INVALID_JWK")]
 pub enum EcJwkError {
-  #[class(generic)]
-  #[error(transparent)]
+  #[error("Invalid JWK EC key")]
   Ec(#[from] elliptic_curve::Error),
-  #[class(type)]
   #[error("unsupported curve: {0}")]
   UnsupportedCurve(String),
 }
 
 #[derive(Debug, thiserror::Error, deno_error::JsError)]
+#[class(type)]
+#[property("code" = "ERR_CRYPTO_INVALID_JWK")]
 pub enum EdRawError {
-  #[class(generic)]
-  #[error(transparent)]
+  #[error("invalid Ed25519 key")]
   Ed25519Signature(#[from] ed25519_dalek::SignatureError),
-  #[class(type)]
   #[error("invalid Ed25519 key")]
   InvalidEd25519Key,
-  #[class(type)]
+  #[error("invalid Ed448 key")]
+  InvalidEd448Key,
+  #[error("invalid X448 key")]
+  InvalidX448Key,
   #[error("unsupported curve")]
   UnsupportedCurve,
 }
 
+#[derive(Debug, thiserror::Error, deno_error::JsError)]
+#[class(type)]
+#[property("code" = "ERR_CRYPTO_INVALID_JWK")]
+pub enum OkpJwkError {
+  #[error("Invalid JWK OKP key")]
+  Base64(#[from] base64::DecodeError),
+  #[error(transparent)]
+  EdRaw(#[from] EdRawError),
+  #[error("Invalid JWK OKP key")]
+  InvalidKeyLength,
+  #[error("missing OKP private component")]
+  MissingOkpPrivateComponent,
+  #[error("JWK OKP public key does not match the private key")]
+  PublicKeyMismatch,
+}
+
+#[derive(Debug, thiserror::Error, deno_error::JsError)]
+#[class(type)]
+pub enum MlDsaRawError {
+  #[error("unsupported key type: {0}")]
+  UnsupportedKeyType(String),
+  #[error("unsupported key format: {0}")]
+  UnsupportedKeyFormat(String),
+  #[error("invalid ML-DSA public key")]
+  InvalidMlDsaPublicKey,
+  #[error("invalid ML-DSA seed")]
+  InvalidMlDsaSeed,
+  #[error("ML-DSA private keys can only be imported from their seed")]
+  MlDsaPrivateKeyRequiresSeed,
+}
+
+#[derive(Debug, thiserror::Error, deno_error::JsError)]
+#[class(type)]
+pub enum MlKemRawError {
+  #[error("unsupported key type: {0}")]
+  UnsupportedKeyType(String),
+  #[error("unsupported key format: {0}")]
+  UnsupportedKeyFormat(String),
+  #[error("invalid ML-KEM public key")]
+  InvalidMlKemPublicKey,
+  #[error("invalid ML-KEM seed")]
+  InvalidMlKemSeed,
+  #[error("ML-KEM private keys can only be imported from their seed")]
+  MlKemPrivateKeyRequiresSeed,
+}
+
+#[derive(Debug, thiserror::Error, deno_error::JsError)]
+#[class(type)]
+pub enum CryptoKeyDataError {
+  #[error("unsupported CryptoKey algorithm: {0}")]
+  UnsupportedAlgorithm(String),
+  #[error("unsupported named curve: {0}")]
+  UnsupportedNamedCurve(String),
+  #[error("key type cannot be converted to a CryptoKey")]
+  UnsupportedKeyType,
+  #[error("invalid RSA key")]
+  InvalidRsaKey,
+  #[error("invalid EC key")]
+  InvalidEcKey,
+  #[error("invalid {0} key")]
+  InvalidOkpKey(String),
+}
+
 #[derive(Debug, thiserror::Error, deno_error::JsError)]
 #[class(type)]
 pub enum AsymmetricPrivateKeyError {
@@ -467,10 +1245,16 @@ pub enum AsymmetricPrivateKeyError {
     #[inherit]
     RsaPssParamsParseError,
   ),
-  #[error("invalid encrypted PKCS#8 private key")]
-  InvalidEncryptedPkcs8PrivateKey,
   #[error("invalid PKCS#8 private key")]
   InvalidPkcs8PrivateKey,
+  #[class(generic)]
+  #[property("code" = "ERR_OSSL_BAD_DECRYPT")]
+  #[error("bad decrypt")]
+  BadDecrypt,
+  #[class(generic)]
+  #[property("code" = "ERR_OSSL_EVP_UNSUPPORTED")]
+  #[error("unsupported private key encryption: {0}")]
+  UnsupportedPrivateKeyEncryption(String),
   #[error("PKCS#1 private key does not support encryption with passphrase")]
   Pkcs1PrivateKeyDoesNotSupportEncryptionWithPassphrase,
   #[error("SEC1 private key does not support encryption with passphrase")]
@@ -491,10 +1275,22 @@ pub enum AsymmetricPrivateKeyError {
   InvalidX25519PrivateKey,
   #[error("x25519 private key is the wrong length")]
   X25519PrivateKeyIsWrongLength,
+  #[error("invalid x448 private key")]
+  InvalidX448PrivateKey,
   #[error("invalid Ed25519 private key")]
   InvalidEd25519PrivateKey,
+  #[error("invalid Ed448 private key")]
+  InvalidEd448PrivateKey,
+  #[error("invalid ML-DSA private key")]
+  InvalidMlDsaPrivateKey,
+  #[error("invalid ML-KEM private key")]
+  InvalidMlKemPrivateKey,
+  #[error("invalid SLH-DSA private key")]
+  InvalidSlhDsaPrivateKey,
   #[error("missing dh parameters")]
   MissingDhParameters,
+  #[error("invalid DH private key")]
+  InvalidDhPrivateKey,
   #[error("unsupported private key oid")]
   UnsupportedPrivateKeyOid,
 }
@@ -517,12 +1313,14 @@ pub enum AsymmetricPublicKeyError {
     #[inherit]
     AsymmetricPrivateKeyError,
   ),
-  #[class(type)]
+  #[class(generic)]
+  #[property("code" = "ERR_OSSL_ASN1_WRONG_TAG")]
   #[error("invalid x509 certificate")]
   InvalidX509Certificate,
   #[class(generic)]
-  #[error(transparent)]
-  X509(#[from] x509_parser::nom::Err<X509Error>),
+  #[property("code" = "ERR_OSSL_ASN1_TOO_LONG")]
+  #[error("invalid x509 certificate: too long")]
+  X509CertificateTooLong,
   #[class(inherit)]
   #[error(transparent)]
   X509PublicKey(
@@ -577,9 +1375,24 @@ pub enum AsymmetricPublicKeyError {
   #[error("x25519 public key is too short")]
   X25519PublicKeyIsTooShort,
   #[class(type)]
+  #[error("invalid x448 public key")]
+  InvalidX448PublicKey,
+  #[class(type)]
   #[error("invalid Ed25519 public key")]
   InvalidEd25519PublicKey,
   #[class(type)]
+  #[error("invalid Ed448 public key")]
+  InvalidEd448PublicKey,
+  #[class(type)]
+  #[error("invalid ML-DSA public key")]
+  InvalidMlDsaPublicKey,
+  #[class(type)]
+  #[error("invalid ML-KEM public key")]
+  InvalidMlKemPublicKey,
+  #[class(type)]
+  #[error("invalid SLH-DSA public key")]
+  InvalidSlhDsaPublicKey,
+  #[class(type)]
   #[error("missing dh parameters")]
   MissingDhParameters,
   #[class(type)]
@@ -608,47 +1421,53 @@ impl KeyObjectHandle {
           )
         })?;
 
-        if let Some(passphrase) = passphrase {
-          SecretDocument::from_pkcs8_encrypted_pem(pem, passphrase).map_err(
-            |_| AsymmetricPrivateKeyError::InvalidEncryptedPemPrivateKey,
-          )?
-        } else {
-          let (label, doc) = SecretDocument::from_pem(pem)
-            .map_err(|_| AsymmetricPrivateKeyError::InvalidPemPrivateKey)?;
+        // As in Node.js, a passphrase is ignored for an unencrypted key.
+        let (label, doc) = match decrypt_traditional_pem(pem, passphrase)? {
+          Some(decrypted) => decrypted,
+          None => SecretDocument::from_pem(pem)
+            .map_err(|_| AsymmetricPrivateKeyError::InvalidPemPrivateKey)?,
+        };
 
-          match label {
-            EncryptedPrivateKeyInfo::PEM_LABEL => {
-              return Err(AsymmetricPrivateKeyError::EncryptedPrivateKeyRequiresPassphraseToDecrypt);
-            }
-            PrivateKeyInfo::PEM_LABEL => doc,
-            rsa::pkcs1::RsaPrivateKey::PEM_LABEL => {
-              SecretDocument::from_pkcs1_der(doc.as_bytes()).map_err(|_| {
-                AsymmetricPrivateKeyError::InvalidPkcs1PrivateKey
-              })?
-            }
-            sec1::EcPrivateKey::PEM_LABEL => {
-              SecretDocument::from_sec1_der(doc.as_bytes())
-                .map_err(|_| AsymmetricPrivateKeyError::InvalidSec1PrivateKey)?
-            }
-            _ => {
-              return Err(AsymmetricPrivateKeyError::UnsupportedPemLabel(
-                label.to_string(),
-              ));
+        match label {
+          EncryptedPrivateKeyInfo::PEM_LABEL => {
+            match decrypt_legacy_pkcs8(doc.as_bytes(), passphrase)? {
+              Some(doc) => doc,
+              None => {
+                let info = EncryptedPrivateKeyInfo::try_from(doc.as_bytes())
+                  .map_err(|_| {
+                    AsymmetricPrivateKeyError::InvalidEncryptedPemPrivateKey
+                  })?;
+                decrypt_pkcs8(info, passphrase)?
+              }
             }
           }
+          PrivateKeyInfo::PEM_LABEL => doc,
+          rsa::pkcs1::RsaPrivateKey::PEM_LABEL => {
+            SecretDocument::from_pkcs1_der(doc.as_bytes())
+              .map_err(|_| AsymmetricPrivateKeyError::InvalidPkcs1PrivateKey)?
+          }
+          sec1::EcPrivateKey::PEM_LABEL | SM2_PRIVATE_KEY_PEM_LABEL => {
+            sec1_to_pkcs8(doc.as_bytes())?
+          }
+          DSA_PRIVATE_KEY_PEM_LABEL => {
+            dsa_traditional_to_pkcs8(doc.as_bytes())?
+          }
+          _ => {
+            return Err(AsymmetricPrivateKeyError::UnsupportedPemLabel(
+              label.to_string(),
+            ));
+          }
         }
       }
       "der" => match typ {
-        "pkcs8" => {
-          if let Some(passphrase) = passphrase {
-            SecretDocument::from_pkcs8_encrypted_der(key, passphrase).map_err(
-              |_| AsymmetricPrivateKeyError::InvalidEncryptedPkcs8PrivateKey,
-            )?
-          } else {
-            SecretDocument::from_pkcs8_der(key)
-              .map_err(|_| AsymmetricPrivateKeyError::InvalidPkcs8PrivateKey)?
-          }
-        }
+        "pkcs8" => match decrypt_legacy_pkcs8(key, passphrase)? {
+          Some(doc) => doc,
+          None => match EncryptedPrivateKeyInfo::try_from(key) {
+            Ok(info) => decrypt_pkcs8(info, passphrase)?,
+            Err(_) => SecretDocument::from_pkcs8_der(key)
+              .map_err(|_| AsymmetricPrivateKeyError::InvalidPkcs8PrivateKey)?,
+          },
+        },
         "pkcs1" => {
           if passphrase.is_some() {
             return Err(AsymmetricPrivateKeyError::Pkcs1PrivateKeyDoesNotSupportEncryptionWithPassphrase);
@@ -660,8 +1479,7 @@ impl KeyObjectHandle {
           if passphrase.is_some() {
             return Err(AsymmetricPrivateKeyError::Sec1PrivateKeyDoesNotSupportEncryptionWithPassphrase);
           }
-          SecretDocument::from_sec1_der(key)
-            .map_err(|_| AsymmetricPrivateKeyError::InvalidSec1PrivateKey)?
+          sec1_to_pkcs8(key)?
         }
         _ => {
           return Err(AsymmetricPrivateKeyError::UnsupportedKeyType(
@@ -728,6 +1546,27 @@ impl KeyObjectHandle {
             .map_err(|_| AsymmetricPrivateKeyError::InvalidSec1PrivateKey)?;
             AsymmetricPrivateKey::Ec(EcPrivateKey::P384(secret_key))
           }
+          ID_SECP521R1_OID => {
+            let secret_key = p521::SecretKey::from_sec1_der(
+              pk_info.private_key,
+            )
+            .map_err(|_| AsymmetricPrivateKeyError::InvalidSec1PrivateKey)?;
+            AsymmetricPrivateKey::Ec(EcPrivateKey::P521(secret_key))
+          }
+          ID_SECP256K1_OID => {
+            let secret_key = k256::SecretKey::from_sec1_der(
+              pk_info.private_key,
+            )
+            .map_err(|_| AsymmetricPrivateKeyError::InvalidSec1PrivateKey)?;
+            AsymmetricPrivateKey::Ec(EcPrivateKey::Secp256k1(secret_key))
+          }
+          ID_SM2P256V1_OID => {
+            let secret_key = sec1::EcPrivateKey::from_der(pk_info.private_key)
+              .ok()
+              .and_then(|key| sm2::SecretKey::from_bytes(key.private_key).ok())
+              .ok_or(AsymmetricPrivateKeyError::InvalidSec1PrivateKey)?;
+            AsymmetricPrivateKey::Ec(EcPrivateKey::Sm2(secret_key))
+          }
           _ => return Err(AsymmetricPrivateKeyError::UnsupportedEcNamedCurve),
         }
       }
@@ -741,11 +1580,47 @@ impl KeyObjectHandle {
         bytes.copy_from_slice(string_ref.as_bytes());
         AsymmetricPrivateKey::X25519(x25519_dalek::StaticSecret::from(bytes))
       }
+      X448_OID => {
+        let secret = OctetStringRef::from_der(pk_info.private_key)
+          .ok()
+          .and_then(|key| x448::StaticSecret::from_bytes(key.as_bytes()))
+          .ok_or(AsymmetricPrivateKeyError::InvalidX448PrivateKey)?;
+        AsymmetricPrivateKey::X448(secret)
+      }
       ED25519_OID => {
         let signing_key = ed25519_dalek::SigningKey::try_from(pk_info)
           .map_err(|_| AsymmetricPrivateKeyError::InvalidEd25519PrivateKey)?;
         AsymmetricPrivateKey::Ed25519(signing_key)
       }
+      ED448_OID => {
+        let signing_key = OctetStringRef::from_der(pk_info.private_key)
+          .ok()
+          .and_then(|seed| ed448::SigningKey::from_bytes(seed.as_bytes()))
+          .ok_or(AsymmetricPrivateKeyError::InvalidEd448PrivateKey)?;
+        AsymmetricPrivateKey::Ed448(signing_key)
+      }
+      ml_dsa::ID_ML_DSA_44_OID
+      | ml_dsa::ID_ML_DSA_65_OID
+      | ml_dsa::ID_ML_DSA_87_OID => {
+        let variant = ml_dsa::Variant::from_oid(alg).unwrap();
+        let signing_key =
+          ml_dsa::SigningKey::from_pkcs8(variant, pk_info.private_key)
+            .ok_or(AsymmetricPrivateKeyError::InvalidMlDsaPrivateKey)?;
+        AsymmetricPrivateKey::MlDsa(signing_key)
+      }
+      ml_kem::ID_ML_KEM_768_OID => {
+        let decapsulation_key =
+          ml_kem::DecapsulationKey::from_pkcs8(pk_info.private_key)
+            .ok_or(AsymmetricPrivateKeyError::InvalidMlKemPrivateKey)?;
+        AsymmetricPrivateKey::MlKem(decapsulation_key)
+      }
+      alg if slh_dsa::Variant::from_oid(alg).is_some() => {
+        let variant = slh_dsa::Variant::from_oid(alg).unwrap();
+        let signing_key =
+          slh_dsa::SigningKey::from_bytes(variant, pk_info.private_key)
+            .ok_or(AsymmetricPrivateKeyError::InvalidSlhDsaPrivateKey)?;
+        AsymmetricPrivateKey::SlhDsa(signing_key)
+      }
       DH_KEY_AGREEMENT_OID => {
         let params = pk_info
           .algorithm
@@ -753,8 +1628,10 @@ impl KeyObjectHandle {
           .ok_or(AsymmetricPrivateKeyError::MissingDhParameters)?;
         let params = pkcs3::DhParameter::from_der(&params.to_der().unwrap())
           .map_err(|_| AsymmetricPrivateKeyError::MissingDhParameters)?;
+        let private_key = asn1::UintRef::from_der(pk_info.private_key)
+          .map_err(|_| AsymmetricPrivateKeyError::InvalidDhPrivateKey)?;
         AsymmetricPrivateKey::Dh(DhPrivateKey {
-          key: dh::PrivateKey::from_bytes(pk_info.private_key),
+          key: dh::PrivateKey::from_bytes(private_key.as_bytes()),
           params,
         })
       }
@@ -785,6 +1662,9 @@ impl KeyObjectHandle {
           const ID_SECP224R1: &[u8] = &oid!(raw 1.3.132.0.33);
           const ID_SECP256R1: &[u8] = &oid!(raw 1.2.840.10045.3.1.7);
           const ID_SECP384R1: &[u8] = &oid!(raw 1.3.132.0.34);
+          const ID_SECP521R1: &[u8] = &oid!(raw 1.3.132.0.35);
+          const ID_SECP256K1: &[u8] = &oid!(raw 1.3.132.0.10);
+          const ID_SM2P256V1: &[u8] = &oid!(raw 1.2.156.10197.1.301);
 
           match curve_oid.as_bytes() {
             ID_SECP224R1 => {
@@ -799,6 +1679,18 @@ impl KeyObjectHandle {
               let public_key = p384::PublicKey::from_sec1_bytes(data)?;
               AsymmetricPublicKey::Ec(EcPublicKey::P384(public_key))
             }
+            ID_SECP521R1 => {
+              let public_key = p521::PublicKey::from_sec1_bytes(data)?;
+              AsymmetricPublicKey::Ec(EcPublicKey::P521(public_key))
+            }
+            ID_SECP256K1 => {
+              let public_key = k256::PublicKey::from_sec1_bytes(data)?;
+              AsymmetricPublicKey::Ec(EcPublicKey::Secp256k1(public_key))
+            }
+            ID_SM2P256V1 => {
+              let public_key = sm2::PublicKey::from_sec1_bytes(data)?;
+              AsymmetricPublicKey::Ec(EcPublicKey::Sm2(public_key))
+            }
             _ => return Err(X509PublicKeyError::UnsupportedEcNamedCurve),
           }
         } else {
@@ -810,7 +1702,14 @@ impl KeyObjectHandle {
           .map_err(|_| X509PublicKeyError::MalformedDssPublicKey)?;
         AsymmetricPublicKey::Dsa(verifying_key)
       }
-      _ => return Err(X509PublicKeyError::UnsupportedX509KeyType),
+      // x509-parser doesn't parse RSA-PSS, Ed25519, Ed448, X25519 or X448
+      // keys, which are read like any other SPKI.
+      _ => {
+        return KeyObjectHandle::new_asymmetric_public_key_from_js(
+          spki.raw, "der", "spki", None,
+        )
+        .map_err(|_| X509PublicKeyError::UnsupportedX509KeyType);
+      }
     };
 
     Ok(KeyObjectHandle::AsymmetricPublic(key))
@@ -820,10 +1719,10 @@ impl KeyObjectHandle {
     jwk: RsaJwkKey,
     is_public: bool,
   ) -> Result<KeyObjectHandle, RsaJwkError> {
-    use base64::prelude::BASE64_URL_SAFE_NO_PAD;
-
-    let n = BASE64_URL_SAFE_NO_PAD.decode(jwk.n.as_bytes())?;
-    let e = BASE64_URL_SAFE_NO_PAD.decode(jwk.e.as_bytes())?;
+    let n = BASE64_URL_SAFE_FORGIVING.decode(jwk.n.as_bytes())?;
+    let e = BASE64_URL_SAFE_FORGIVING.decode(jwk.e.as_bytes())?;
+    // An RSA-PSS key exported as JWK keeps its restrictions in `alg`.
+    let pss_details = jwk.alg.as_deref().and_then(RsaPssDetails::from_jwk_alg);
 
     if is_public {
       let public_key = RsaPublicKey::new(
@@ -831,28 +1730,30 @@ impl KeyObjectHandle {
         rsa::BigUint::from_bytes_be(&e),
       )?;
 
-      Ok(KeyObjectHandle::AsymmetricPublic(AsymmetricPublicKey::Rsa(
-        public_key,
-      )))
+      let key = match pss_details {
+        Some(details) => AsymmetricPublicKey::RsaPss(RsaPssPublicKey {
+          key: public_key,
+          details: Some(details),
+        }),
+        None => AsymmetricPublicKey::Rsa(public_key),
+      };
+      Ok(KeyObjectHandle::AsymmetricPublic(key))
     } else {
-      let d = BASE64_URL_SAFE_NO_PAD.decode(
-        jwk
-          .d
-          .ok_or(RsaJwkError::MissingRsaPrivateComponent)?
-          .as_bytes(),
-      )?;
-      let p = BASE64_URL_SAFE_NO_PAD.decode(
-        jwk
-          .p
-          .ok_or(RsaJwkError::MissingRsaPrivateComponent)?
-          .as_bytes(),
-      )?;
-      let q = BASE64_URL_SAFE_NO_PAD.decode(
-        jwk
-          .q
-          .ok_or(RsaJwkError::MissingRsaPrivateComponent)?
-          .as_bytes(),
-      )?;
+      let decode = |component: Option<String>| {
+        let component =
+          component.ok_or(RsaJwkError::MissingRsaPrivateComponent)?;
+        Ok::<_, RsaJwkError>(
+          BASE64_URL_SAFE_FORGIVING.decode(component.as_bytes())?,
+        )
+      };
+      let d = decode(jwk.d)?;
+      let p = decode(jwk.p)?;
+      let q = decode(jwk.q)?;
+      // The CRT parameters are recomputed from the primes, but malformed
+      // ones still make the key invalid.
+      decode(jwk.dp)?;
+      decode(jwk.dq)?;
+      decode(jwk.qi)?;
 
       let mut private_key = RsaPrivateKey::from_components(
         rsa::BigUint::from_bytes_be(&n),
@@ -863,11 +1764,17 @@ impl KeyObjectHandle {
           rsa::BigUint::from_bytes_be(&q),
         ],
       )?;
+      private_key.validate()?;
       private_key.precompute()?; // precompute CRT params
 
-      Ok(KeyObjectHandle::AsymmetricPrivate(
-        AsymmetricPrivateKey::Rsa(private_key),
-      ))
+      let key = match pss_details {
+        Some(details) => AsymmetricPrivateKey::RsaPss(RsaPssPrivateKey {
+          key: private_key,
+          details: Some(details),
+        }),
+        None => AsymmetricPrivateKey::Rsa(private_key),
+      };
+      Ok(KeyObjectHandle::AsymmetricPrivate(key))
     }
   }
 
@@ -893,6 +1800,24 @@ impl KeyObjectHandle {
       "P-384" => KeyObjectHandle::AsymmetricPrivate(AsymmetricPrivateKey::Ec(
         EcPrivateKey::P384(p384::SecretKey::from_jwk(jwk)?),
       )),
+      "P-521" if is_public => {
+        KeyObjectHandle::AsymmetricPublic(AsymmetricPublicKey::Ec(
+          EcPublicKey::P521(p521::PublicKey::from_jwk(jwk)?),
+        ))
+      }
+      "P-521" => KeyObjectHandle::AsymmetricPrivate(AsymmetricPrivateKey::Ec(
+        EcPrivateKey::P521(p521::SecretKey::from_jwk(jwk)?),
+      )),
+      "secp256k1" if is_public => {
+        KeyObjectHandle::AsymmetricPublic(AsymmetricPublicKey::Ec(
+          EcPublicKey::Secp256k1(k256::PublicKey::from_jwk(jwk)?),
+        ))
+      }
+      "secp256k1" => {
+        KeyObjectHandle::AsymmetricPrivate(AsymmetricPrivateKey::Ec(
+          EcPrivateKey::Secp256k1(k256::SecretKey::from_jwk(jwk)?),
+        ))
+      }
       _ => {
         return Err(EcJwkError::UnsupportedCurve(jwk.crv().to_string()));
       }
@@ -924,6 +1849,21 @@ impl KeyObjectHandle {
           ))
         }
       }
+      "Ed448" => {
+        if !is_public {
+          let key = ed448::SigningKey::from_bytes(data)
+            .ok_or(EdRawError::InvalidEd448Key)?;
+          Ok(KeyObjectHandle::AsymmetricPrivate(
+            AsymmetricPrivateKey::Ed448(key),
+          ))
+        } else {
+          let key = ed448::VerifyingKey::from_bytes(data)
+            .ok_or(EdRawError::InvalidEd448Key)?;
+          Ok(KeyObjectHandle::AsymmetricPublic(
+            AsymmetricPublicKey::Ed448(key),
+          ))
+        }
+      }
       "X25519" => {
         let data: [u8; 32] =
           data.try_into().map_err(|_| EdRawError::InvalidEd25519Key)?;
@@ -939,11 +1879,291 @@ impl KeyObjectHandle {
           ))
         }
       }
+      "X448" => {
+        if !is_public {
+          let key = x448::StaticSecret::from_bytes(data)
+            .ok_or(EdRawError::InvalidX448Key)?;
+          Ok(KeyObjectHandle::AsymmetricPrivate(
+            AsymmetricPrivateKey::X448(key),
+          ))
+        } else {
+          let key = x448::PublicKey::from_bytes(data)
+            .ok_or(EdRawError::InvalidX448Key)?;
+          Ok(KeyObjectHandle::AsymmetricPublic(
+            AsymmetricPublicKey::X448(key),
+          ))
+        }
+      }
       _ => Err(EdRawError::UnsupportedCurve),
     }
   }
 
-  pub fn new_asymmetric_public_key_from_js(
+  pub fn new_okp_jwk(
+    jwk: OkpJwkKey,
+    is_public: bool,
+  ) -> Result<KeyObjectHandle, OkpJwkError> {
+    let key_length = match jwk.crv.as_str() {
+      "Ed25519" | "X25519" => 32,
+      "Ed448" => ed448::KEY_LENGTH,
+      "X448" => x448::KEY_LENGTH,
+      _ => return Err(EdRawError::UnsupportedCurve.into()),
+    };
+    // RFC 8037, section 2: `x` and `d` are unpadded base64url of exactly
+    // the key length.
+    let decode = |component: &str| {
+      let bytes = base64::engine::general_purpose::URL_SAFE_NO_PAD
+        .decode(component.as_bytes())?;
+      if bytes.len() != key_length {
+        return Err(OkpJwkError::InvalidKeyLength);
+      }
+      Ok(bytes)
+    };
+
+    let x = decode(&jwk.x)?;
+    let public = Self::new_ed_raw(&jwk.crv, &x, true)?;
+    if is_public {
+      return Ok(public);
+    }
+
+    let d = decode(
+      jwk
+        .d
+        .as_deref()
+        .ok_or(OkpJwkError::MissingOkpPrivateComponent)?,
+    )?;
+    let private = Self::new_ed_raw(&jwk.crv, &d, false)?;
+    let KeyObjectHandle::AsymmetricPrivate(private_key) = &private else {
+      unreachable!();
+    };
+    let derived = match private_key.to_public_key() {
+      AsymmetricPublicKey::Ed25519(key) => key.to_bytes().to_vec(),
+      AsymmetricPublicKey::X25519(key) => key.as_bytes().to_vec(),
+      AsymmetricPublicKey::Ed448(key) => key.as_bytes().to_vec(),
+      AsymmetricPublicKey::X448(key) => key.as_bytes().to_vec(),
+      _ => unreachable!(),
+    };
+    if derived != x {
+      return Err(OkpJwkError::PublicKeyMismatch);
+    }
+    Ok(private)
+  }
+
+  /// Imports the FIPS 204 public key (`raw-public`) or the seed
+  /// (`raw-seed`) of an ML-DSA key.
+  pub fn new_ml_dsa_raw(
+    typ: &str,
+    format: &str,
+    data: &[u8],
+  ) -> Result<KeyObjectHandle, MlDsaRawError> {
+    let variant = ml_dsa::Variant::from_name(typ)
+      .ok_or_else(|| MlDsaRawError::UnsupportedKeyType(typ.to_string()))?;
+    match format {
+      "raw-public" => {
+        let key = ml_dsa::VerifyingKey::from_bytes(variant, data)
+          .ok_or(MlDsaRawError::InvalidMlDsaPublicKey)?;
+        Ok(KeyObjectHandle::AsymmetricPublic(
+          AsymmetricPublicKey::MlDsa(key),
+        ))
+      }
+      "raw-seed" => {
+        let key = ml_dsa::SigningKey::from_seed(variant, data)
+          .ok_or(MlDsaRawError::InvalidMlDsaSeed)?;
+        Ok(KeyObjectHandle::AsymmetricPrivate(
+          AsymmetricPrivateKey::MlDsa(key),
+        ))
+      }
+      "raw-private" => Err(MlDsaRawError::MlDsaPrivateKeyRequiresSeed),
+      _ => Err(MlDsaRawError::UnsupportedKeyFormat(format.to_string())),
+    }
+  }
+
+  /// Imports the FIPS 203 encapsulation key (`raw-public`) or the 64-byte
+  /// seed (`raw-seed`) of an ML-KEM key.
+  pub fn new_ml_kem_raw(
+    typ: &str,
+    format: &str,
+    data: &[u8],
+  ) -> Result<KeyObjectHandle, MlKemRawError> {
+    if typ != ml_kem::NAME {
+      return Err(MlKemRawError::UnsupportedKeyType(typ.to_string()));
+    }
+    match format {
+      "raw-public" => {
+        let key = ml_kem::EncapsulationKey::from_bytes(data)
+          .ok_or(MlKemRawError::InvalidMlKemPublicKey)?;
+        Ok(KeyObjectHandle::AsymmetricPublic(
+          AsymmetricPublicKey::MlKem(key),
+        ))
+      }
+      "raw-seed" => {
+        let key = ml_kem::DecapsulationKey::from_seed(data)
+          .ok_or(MlKemRawError::InvalidMlKemSeed)?;
+        Ok(KeyObjectHandle::AsymmetricPrivate(
+          AsymmetricPrivateKey::MlKem(key),
+        ))
+      }
+      "raw-private" => Err(MlKemRawError::MlKemPrivateKeyRequiresSeed),
+      _ => Err(MlKemRawError::UnsupportedKeyFormat(format.to_string())),
+    }
+  }
+
+  /// Imports the key material that ext/crypto keeps for a `CryptoKey`:
+  /// PKCS#1 for RSA, PKCS#8 or an uncompressed point for EC and the raw
+  /// key for the CFRG curves. The public key of a generated RSA or EC key
+  /// pair shares the material of the private key.
+  pub fn new_crypto_key_data(
+    algorithm: &str,
+    named_curve: &str,
+    data: &[u8],
+    is_private_data: bool,
+    is_public: bool,
+  ) -> Result<KeyObjectHandle, CryptoKeyDataError> {
+    let private_key = match (algorithm, is_private_data) {
+      ("rsa", true) => AsymmetricPrivateKey::Rsa(
+        RsaPrivateKey::from_pkcs1_der(data)
+          .map_err(|_| CryptoKeyDataError::InvalidRsaKey)?,
+      ),
+      ("rsa", false) => {
+        let key = RsaPublicKey::from_pkcs1_der(data)
+          .map_err(|_| CryptoKeyDataError::InvalidRsaKey)?;
+        return Ok(KeyObjectHandle::AsymmetricPublic(
+          AsymmetricPublicKey::Rsa(key),
+        ));
+      }
+      ("ec", true) => {
+        let invalid = |_| CryptoKeyDataError::InvalidEcKey;
+        let key = match named_curve {
+          "P-256" => EcPrivateKey::P256(
+            p256::SecretKey::from_pkcs8_der(data).map_err(invalid)?,
+          ),
+          "P-384" => EcPrivateKey::P384(
+            p384::SecretKey::from_pkcs8_der(data).map_err(invalid)?,
+          ),
+          // ext/crypto keeps a generated P-521 key as its bare scalar.
+          "P-521" => EcPrivateKey::P521(
+            p521::SecretKey::from_pkcs8_der(data)
+              .or_else(|_| p521::SecretKey::from_slice(data))
+              .map_err(|_| CryptoKeyDataError::InvalidEcKey)?,
+          ),
+          _ => {
+            return Err(CryptoKeyDataError::UnsupportedNamedCurve(
+              named_curve.to_string(),
+            ));
+          }
+        };
+        AsymmetricPrivateKey::Ec(key)
+      }
+      ("ec", false) => {
+        let invalid = |_| CryptoKeyDataError::InvalidEcKey;
+        let key = match named_curve {
+          "P-256" => EcPublicKey::P256(
+            p256::PublicKey::from_sec1_bytes(data).map_err(invalid)?,
+          ),
+          "P-384" => EcPublicKey::P384(
+            p384::PublicKey::from_sec1_bytes(data).map_err(invalid)?,
+          ),
+          "P-521" => EcPublicKey::P521(
+            p521::PublicKey::from_sec1_bytes(data).map_err(invalid)?,
+          ),
+          _ => {
+            return Err(CryptoKeyDataError::UnsupportedNamedCurve(
+              named_curve.to_string(),
+            ));
+          }
+        };
+        return Ok(KeyObjectHandle::AsymmetricPublic(AsymmetricPublicKey::Ec(
+          key,
+        )));
+      }
+      // The key pairs of the CFRG curves keep separate public keys.
+      ("Ed25519" | "X25519" | "X448", _) => {
+        return Self::new_ed_raw(algorithm, data, is_public).map_err(|_| {
+          CryptoKeyDataError::InvalidOkpKey(algorithm.to_string())
+        });
+      }
+      _ => {
+        return Err(CryptoKeyDataError::UnsupportedAlgorithm(
+          algorithm.to_string(),
+        ));
+      }
+    };
+
+    Ok(if is_public {
+      KeyObjectHandle::AsymmetricPublic(private_key.to_public_key())
+    } else {
+      KeyObjectHandle::AsymmetricPrivate(private_key)
+    })
+  }
+
+  /// Encodes an asymmetric key the way `new_crypto_key_data` reads it.
+  pub fn export_crypto_key_data(
+    &self,
+  ) -> Result<Box<[u8]>, CryptoKeyDataError> {
+    let data = match self {
+      KeyObjectHandle::AsymmetricPrivate(key) => match key {
+        AsymmetricPrivateKey::Rsa(key) => key
+          .to_pkcs1_der()
+          .map_err(|_| CryptoKeyDataError::InvalidRsaKey)?
+          .as_bytes()
+          .to_vec(),
+        AsymmetricPrivateKey::Ec(key) => match key {
+          EcPrivateKey::P256(key) => key.to_pkcs8_der(),
+          EcPrivateKey::P384(key) => key.to_pkcs8_der(),
+          EcPrivateKey::P521(key) => key.to_pkcs8_der(),
+          _ => return Err(CryptoKeyDataError::UnsupportedKeyType),
+        }
+        .map_err(|_| CryptoKeyDataError::InvalidEcKey)?
+        .as_bytes()
+        .to_vec(),
+        AsymmetricPrivateKey::Ed25519(key) => key.to_bytes().to_vec(),
+        AsymmetricPrivateKey::X25519(key) => key.to_bytes().to_vec(),
+        AsymmetricPrivateKey::X448(key) => key.as_bytes().to_vec(),
+        _ => return Err(CryptoKeyDataError::UnsupportedKeyType),
+      },
+      KeyObjectHandle::AsymmetricPublic(key) => match key {
+        AsymmetricPublicKey::Rsa(key) => key
+          .to_pkcs1_der()
+          .map_err(|_| CryptoKeyDataError::InvalidRsaKey)?
+          .into_vec(),
+        AsymmetricPublicKey::Ec(key) => match key {
+          EcPublicKey::P256(key) => key.to_sec1_bytes(),
+          EcPublicKey::P384(key) => key.to_sec1_bytes(),
+          EcPublicKey::P521(key) => key.to_sec1_bytes(),
+          _ => return Err(CryptoKeyDataError::UnsupportedKeyType),
+        }
+        .into_vec(),
+        AsymmetricPublicKey::Ed25519(key) => key.to_bytes().to_vec(),
+        AsymmetricPublicKey::X25519(key) => key.as_bytes().to_vec(),
+        AsymmetricPublicKey::X448(key) => key.as_bytes().to_vec(),
+        _ => return Err(CryptoKeyDataError::UnsupportedKeyType),
+      },
+      KeyObjectHandle::Secret(_) => {
+        return Err(CryptoKeyDataError::UnsupportedKeyType);
+      }
+    };
+    Ok(data.into_boxed_slice())
+  }
+
+  /// The name of the algorithm if it is behind `--unstable-pqc`.
+  fn unstable_pqc_api_name(&self) -> Option<&'static str> {
+    match self {
+      KeyObjectHandle::AsymmetricPrivate(AsymmetricPrivateKey::MlDsa(_))
+      | KeyObjectHandle::AsymmetricPublic(AsymmetricPublicKey::MlDsa(_)) => {
+        Some("ML-DSA")
+      }
+      KeyObjectHandle::AsymmetricPrivate(AsymmetricPrivateKey::MlKem(_))
+      | KeyObjectHandle::AsymmetricPublic(AsymmetricPublicKey::MlKem(_)) => {
+        Some("ML-KEM")
+      }
+      KeyObjectHandle::AsymmetricPrivate(AsymmetricPrivateKey::SlhDsa(_))
+      | KeyObjectHandle::AsymmetricPublic(AsymmetricPublicKey::SlhDsa(_)) => {
+        Some("SLH-DSA")
+      }
+      _ => None,
+    }
+  }
+
+  pub fn new_asymmetric_public_key_from_js(
     key: &[u8],
     format: &str,
     typ: &str,
@@ -957,19 +2177,21 @@ impl KeyObjectHandle {
           )
         })?;
 
-        let (label, document) = Document::from_pem(pem)
-          .map_err(|_| AsymmetricPublicKeyError::InvalidPemPublicKey)?;
-
-        match label {
-          SubjectPublicKeyInfoRef::PEM_LABEL => document,
-          rsa::pkcs1::RsaPublicKey::PEM_LABEL => {
-            Document::from_pkcs1_der(document.as_bytes())
-              .map_err(|_| AsymmetricPublicKeyError::InvalidPkcs1PublicKey)?
+        // `Document` would reject a malformed certificate or an encrypted
+        // PEM's headers before its label is known, so both are decoded
+        // separately.
+        match spki::der::pem::decode_label(pem.as_bytes()) {
+          Ok(CERTIFICATE_PEM_LABEL) => {
+            let (_, der) = spki::der::pem::decode_vec(pem.as_bytes())
+              .map_err(|_| AsymmetricPublicKeyError::InvalidPemPublicKey)?;
+            certificate_spki(&der)?
           }
-          EncryptedPrivateKeyInfo::PEM_LABEL
-          | PrivateKeyInfo::PEM_LABEL
-          | sec1::EcPrivateKey::PEM_LABEL
-          | rsa::pkcs1::RsaPrivateKey::PEM_LABEL => {
+          Ok(
+            EncryptedPrivateKeyInfo::PEM_LABEL
+            | PrivateKeyInfo::PEM_LABEL
+            | sec1::EcPrivateKey::PEM_LABEL
+            | rsa::pkcs1::RsaPrivateKey::PEM_LABEL,
+          ) => {
             let handle = KeyObjectHandle::new_asymmetric_private_key_from_js(
               key, format, typ, passphrase,
             )?;
@@ -983,24 +2205,39 @@ impl KeyObjectHandle {
               | KeyObjectHandle::Secret(_) => unreachable!(),
             }
           }
-          "CERTIFICATE" => {
-            let (_, pem) = x509_parser::pem::parse_x509_pem(pem.as_bytes())
-              .map_err(|_| AsymmetricPublicKeyError::InvalidX509Certificate)?;
-
-            let cert = pem.parse_x509()?;
-            let public_key = cert.tbs_certificate.subject_pki;
-
-            return KeyObjectHandle::new_x509_public_key(&public_key)
-              .map_err(Into::into);
-          }
           _ => {
-            return Err(AsymmetricPublicKeyError::UnsupportedPemLabel(
-              label.to_string(),
-            ));
+            let (label, document) = Document::from_pem(pem)
+              .map_err(|_| AsymmetricPublicKeyError::InvalidPemPublicKey)?;
+
+            match label {
+              SubjectPublicKeyInfoRef::PEM_LABEL => document,
+              rsa::pkcs1::RsaPublicKey::PEM_LABEL => {
+                Document::from_pkcs1_der(document.as_bytes()).map_err(|_| {
+                  AsymmetricPublicKeyError::InvalidPkcs1PublicKey
+                })?
+              }
+              _ => {
+                return Err(AsymmetricPublicKeyError::UnsupportedPemLabel(
+                  label.to_string(),
+                ));
+              }
+            }
           }
         }
       }
       "der" => match typ {
+        // As in Node.js, an RSAPrivateKey is accepted too.
+        "pkcs1" if rsa::pkcs1::RsaPrivateKey::from_der(key).is_ok() => {
+          let handle = KeyObjectHandle::new_asymmetric_private_key_from_js(
+            key, format, typ, passphrase,
+          )?;
+          let KeyObjectHandle::AsymmetricPrivate(private) = handle else {
+            unreachable!();
+          };
+          return Ok(KeyObjectHandle::AsymmetricPublic(
+            private.to_public_key(),
+          ));
+        }
         "pkcs1" => Document::from_pkcs1_der(key)
           .map_err(|_| AsymmetricPublicKeyError::InvalidPkcs1PublicKey)?,
         "spki" => Document::from_public_key_der(key)
@@ -1063,6 +2300,18 @@ impl KeyObjectHandle {
             let public_key = p384::PublicKey::from_sec1_bytes(data)?;
             AsymmetricPublicKey::Ec(EcPublicKey::P384(public_key))
           }
+          ID_SECP521R1_OID => {
+            let public_key = p521::PublicKey::from_sec1_bytes(data)?;
+            AsymmetricPublicKey::Ec(EcPublicKey::P521(public_key))
+          }
+          ID_SECP256K1_OID => {
+            let public_key = k256::PublicKey::from_sec1_bytes(data)?;
+            AsymmetricPublicKey::Ec(EcPublicKey::Secp256k1(public_key))
+          }
+          ID_SM2P256V1_OID => {
+            let public_key = sm2::PublicKey::from_sec1_bytes(data)?;
+            AsymmetricPublicKey::Ec(EcPublicKey::Sm2(public_key))
+          }
           _ => return Err(AsymmetricPublicKeyError::UnsupportedEcNamedCurve),
         }
       }
@@ -1077,11 +2326,55 @@ impl KeyObjectHandle {
         bytes.copy_from_slice(&data[0..32]);
         AsymmetricPublicKey::X25519(x25519_dalek::PublicKey::from(bytes))
       }
+      X448_OID => {
+        let public_key = spki
+          .subject_public_key
+          .as_bytes()
+          .and_then(x448::PublicKey::from_bytes)
+          .ok_or(AsymmetricPublicKeyError::InvalidX448PublicKey)?;
+        AsymmetricPublicKey::X448(public_key)
+      }
       ED25519_OID => {
         let verifying_key = ed25519_dalek::VerifyingKey::try_from(spki)
           .map_err(|_| AsymmetricPublicKeyError::InvalidEd25519PublicKey)?;
         AsymmetricPublicKey::Ed25519(verifying_key)
       }
+      ED448_OID => {
+        let verifying_key = spki
+          .subject_public_key
+          .as_bytes()
+          .and_then(ed448::VerifyingKey::from_bytes)
+          .ok_or(AsymmetricPublicKeyError::InvalidEd448PublicKey)?;
+        AsymmetricPublicKey::Ed448(verifying_key)
+      }
+      ml_dsa::ID_ML_DSA_44_OID
+      | ml_dsa::ID_ML_DSA_65_OID
+      | ml_dsa::ID_ML_DSA_87_OID => {
+        let variant = ml_dsa::Variant::from_oid(spki.algorithm.oid).unwrap();
+        let verifying_key = spki
+          .subject_public_key
+          .as_bytes()
+          .and_then(|bytes| ml_dsa::VerifyingKey::from_bytes(variant, bytes))
+          .ok_or(AsymmetricPublicKeyError::InvalidMlDsaPublicKey)?;
+        AsymmetricPublicKey::MlDsa(verifying_key)
+      }
+      ml_kem::ID_ML_KEM_768_OID => {
+        let encapsulation_key = spki
+          .subject_public_key
+          .as_bytes()
+          .and_then(ml_kem::EncapsulationKey::from_bytes)
+          .ok_or(AsymmetricPublicKeyError::InvalidMlKemPublicKey)?;
+        AsymmetricPublicKey::MlKem(encapsulation_key)
+      }
+      oid if slh_dsa::Variant::from_oid(oid).is_some() => {
+        let variant = slh_dsa::Variant::from_oid(oid).unwrap();
+        let verifying_key = spki
+          .subject_public_key
+          .as_bytes()
+          .and_then(|bytes| slh_dsa::VerifyingKey::from_bytes(variant, bytes))
+          .ok_or(AsymmetricPublicKeyError::InvalidSlhDsaPublicKey)?;
+        AsymmetricPublicKey::SlhDsa(verifying_key)
+      }
       DH_KEY_AGREEMENT_OID => {
         let params = spki
           .algorithm
@@ -1089,14 +2382,17 @@ impl KeyObjectHandle {
           .ok_or(AsymmetricPublicKeyError::MissingDhParameters)?;
         let params = pkcs3::DhParameter::from_der(&params.to_der().unwrap())
           .map_err(|_| AsymmetricPublicKeyError::MalformedDhParameters)?;
-        let Some(subject_public_key) = spki.subject_public_key.as_bytes()
+        let Some(subject_public_key) = spki
+          .subject_public_key
+          .as_bytes()
+          .and_then(|bytes| asn1::UintRef::from_der(bytes).ok())
         else {
           return Err(
             AsymmetricPublicKeyError::MalformedOrMissingPublicKeyInDhSpki,
           );
         };
         AsymmetricPublicKey::Dh(DhPublicKey {
-          key: dh::PublicKey::from_bytes(subject_public_key),
+          key: dh::PublicKey::from_bytes(subject_public_key.as_bytes()),
           params,
         })
       }
@@ -1120,7 +2416,7 @@ pub enum RsaPssParamsParseError {
   MalformedOrMissingPssMaskGenAlgorithm,
 }
 
-fn parse_rsa_pss_params(
+pub(crate) fn parse_rsa_pss_params(
   parameters: Option<AnyRef<'_>>,
 ) -> Result<Option<RsaPssDetails>, RsaPssParamsParseError> {
   let details = if let Some(parameters) = parameters {
@@ -1160,7 +2456,8 @@ fn parse_rsa_pss_params(
           }
         }
       }
-      None => hash_algorithm,
+      // RFC 4055 has mgf1SHA1 as the default, whatever the hash.
+      None => RsaPssHashAlgorithm::Sha1,
     };
 
     let salt_length = params
@@ -1190,6 +2487,8 @@ pub enum AsymmetricPrivateKeyJwkError {
   KeyIsNotAsymmetricPrivateKey,
   #[error("Unsupported JWK EC curve: P224")]
   UnsupportedJwkEcCurveP224,
+  #[error("Unsupported JWK EC curve: SM2")]
+  UnsupportedJwkEcCurveSm2,
   #[error("jwk export not implemented for this key type")]
   JwkExportNotImplementedForKeyType,
 }
@@ -1201,6 +2500,8 @@ pub enum AsymmetricPublicKeyJwkError {
   KeyIsNotAsymmetricPublicKey,
   #[error("Unsupported JWK EC curve: P224")]
   UnsupportedJwkEcCurveP224,
+  #[error("Unsupported JWK EC curve: SM2")]
+  UnsupportedJwkEcCurveSm2,
   #[error("jwk export not implemented for this key type")]
   JwkExportNotImplementedForKeyType,
 }
@@ -1216,14 +2517,22 @@ pub enum AsymmetricPublicKeyDerError {
   ExportingNonRsaPublicKeyAsPkcs1Unsupported,
   #[error("invalid EC public key")]
   InvalidEcPublicKey,
-  #[error("exporting RSA-PSS public key as SPKI is not supported yet")]
-  ExportingNonRsaPssPublicKeyAsSpkiUnsupported,
   #[error("invalid DSA public key")]
   InvalidDsaPublicKey,
   #[error("invalid X25519 public key")]
   InvalidX25519PublicKey,
+  #[error("invalid X448 public key")]
+  InvalidX448PublicKey,
   #[error("invalid Ed25519 public key")]
   InvalidEd25519PublicKey,
+  #[error("invalid Ed448 public key")]
+  InvalidEd448PublicKey,
+  #[error("invalid ML-DSA public key")]
+  InvalidMlDsaPublicKey,
+  #[error("invalid ML-KEM public key")]
+  InvalidMlKemPublicKey,
+  #[error("invalid SLH-DSA public key")]
+  InvalidSlhDsaPublicKey,
   #[error("invalid DH public key")]
   InvalidDhPublicKey,
   #[error("unsupported key type: {0}")]
@@ -1257,6 +2566,22 @@ impl AsymmetricPublicKey {
         });
         Ok(jwk)
       }
+      AsymmetricPublicKey::Ed448(key) => {
+        let jwk = deno_core::serde_json::json!({
+            "kty": "OKP",
+            "crv": "Ed448",
+            "x": bytes_to_b64(key.as_bytes()),
+        });
+        Ok(jwk)
+      }
+      AsymmetricPublicKey::X448(key) => {
+        let jwk = deno_core::serde_json::json!({
+            "kty": "OKP",
+            "crv": "X448",
+            "x": bytes_to_b64(key.as_bytes()),
+        });
+        Ok(jwk)
+      }
       AsymmetricPublicKey::Rsa(key) => {
         let n = key.n();
         let e = key.e();
@@ -1272,11 +2597,14 @@ impl AsymmetricPublicKey {
         let n = key.key.n();
         let e = key.key.e();
 
-        let jwk = deno_core::serde_json::json!({
+        let mut jwk = deno_core::serde_json::json!({
             "kty": "RSA",
             "n": bytes_to_b64(&n.to_bytes_be()),
             "e": bytes_to_b64(&e.to_bytes_be()),
         });
+        if let Some(alg) = key.details.and_then(|details| details.jwk_alg()) {
+          jwk["alg"] = alg.into();
+        }
         Ok(jwk)
       }
       _ => Err(AsymmetricPublicKeyJwkError::JwkExportNotImplementedForKeyType),
@@ -1306,8 +2634,33 @@ impl AsymmetricPublicKey {
             .map_err(|_| AsymmetricPublicKeyDerError::InvalidRsaPublicKey)?
             .into_vec()
             .into_boxed_slice(),
-          AsymmetricPublicKey::RsaPss(_key) => {
-            return Err(AsymmetricPublicKeyDerError::ExportingNonRsaPssPublicKeyAsSpkiUnsupported)
+          AsymmetricPublicKey::RsaPss(key) => {
+            let public_key = key
+              .key
+              .to_pkcs1_der()
+              .map_err(|_| AsymmetricPublicKeyDerError::InvalidRsaPublicKey)?;
+            let params = key
+              .details
+              .map(RsaPssDetails::to_der)
+              .transpose()
+              .map_err(|_| AsymmetricPublicKeyDerError::InvalidRsaPublicKey)?;
+            let spki = SubjectPublicKeyInfoRef {
+              algorithm: rsa::pkcs8::AlgorithmIdentifierRef {
+                oid: RSASSA_PSS_OID,
+                parameters: params
+                  .as_deref()
+                  .map(AnyRef::from_der)
+                  .transpose()
+                  .map_err(|_| AsymmetricPublicKeyDerError::InvalidRsaPublicKey)?,
+              },
+              subject_public_key: BitStringRef::from_bytes(public_key.as_bytes())
+                .map_err(|_| AsymmetricPublicKeyDerError::InvalidRsaPublicKey)?,
+            };
+
+            spki
+              .to_der()
+              .map_err(|_| AsymmetricPublicKeyDerError::InvalidRsaPublicKey)?
+              .into_boxed_slice()
           }
           AsymmetricPublicKey::Dsa(key) => key
             .to_public_key_der()
@@ -1319,6 +2672,11 @@ impl AsymmetricPublicKey {
               EcPublicKey::P224(key) => (key.to_sec1_bytes(), ID_SECP224R1_OID),
               EcPublicKey::P256(key) => (key.to_sec1_bytes(), ID_SECP256R1_OID),
               EcPublicKey::P384(key) => (key.to_sec1_bytes(), ID_SECP384R1_OID),
+              EcPublicKey::P521(key) => (key.to_sec1_bytes(), ID_SECP521R1_OID),
+              EcPublicKey::Secp256k1(key) => {
+                (key.to_sec1_bytes(), ID_SECP256K1_OID)
+              }
+              EcPublicKey::Sm2(key) => (key.to_sec1_bytes(), ID_SM2P256V1_OID),
             };
 
             let spki = SubjectPublicKeyInfoRef {
@@ -1350,6 +2708,21 @@ impl AsymmetricPublicKey {
               .map_err(|_| AsymmetricPublicKeyDerError::InvalidX25519PublicKey)?
               .into_boxed_slice()
           }
+          AsymmetricPublicKey::X448(key) => {
+            let spki = SubjectPublicKeyInfoRef {
+              algorithm: rsa::pkcs8::AlgorithmIdentifierRef {
+                oid: X448_OID,
+                parameters: None,
+              },
+              subject_public_key: BitStringRef::from_bytes(key.as_bytes())
+                .map_err(|_| AsymmetricPublicKeyDerError::InvalidX448PublicKey)?,
+            };
+
+            spki
+              .to_der()
+              .map_err(|_| AsymmetricPublicKeyDerError::InvalidX448PublicKey)?
+              .into_boxed_slice()
+          }
           AsymmetricPublicKey::Ed25519(key) => {
             let spki = SubjectPublicKeyInfoRef {
               algorithm: rsa::pkcs8::AlgorithmIdentifierRef {
@@ -1365,13 +2738,80 @@ impl AsymmetricPublicKey {
               .map_err(|_| AsymmetricPublicKeyDerError::InvalidEd25519PublicKey)?
               .into_boxed_slice()
           }
+          AsymmetricPublicKey::Ed448(key) => {
+            let spki = SubjectPublicKeyInfoRef {
+              algorithm: rsa::pkcs8::AlgorithmIdentifierRef {
+                oid: ED448_OID,
+                parameters: None,
+              },
+              subject_public_key: BitStringRef::from_bytes(key.as_bytes())
+                .map_err(|_| AsymmetricPublicKeyDerError::InvalidEd448PublicKey)?,
+            };
+
+            spki
+              .to_der()
+              .map_err(|_| AsymmetricPublicKeyDerError::InvalidEd448PublicKey)?
+              .into_boxed_slice()
+          }
+          AsymmetricPublicKey::MlDsa(key) => {
+            let bytes = key.to_bytes();
+            let spki = SubjectPublicKeyInfoRef {
+              algorithm: rsa::pkcs8::AlgorithmIdentifierRef {
+                oid: key.variant().oid(),
+                parameters: None,
+              },
+              subject_public_key: BitStringRef::from_bytes(&bytes)
+                .map_err(|_| AsymmetricPublicKeyDerError::InvalidMlDsaPublicKey)?,
+            };
+
+            spki
+              .to_der()
+              .map_err(|_| AsymmetricPublicKeyDerError::InvalidMlDsaPublicKey)?
+              .into_boxed_slice()
+          }
+          AsymmetricPublicKey::MlKem(key) => {
+            let bytes = key.to_bytes();
+            let spki = SubjectPublicKeyInfoRef {
+              algorithm: rsa::pkcs8::AlgorithmIdentifierRef {
+                oid: ml_kem::ID_ML_KEM_768_OID,
+                parameters: None,
+              },
+              subject_public_key: BitStringRef::from_bytes(&bytes)
+                .map_err(|_| AsymmetricPublicKeyDerError::InvalidMlKemPublicKey)?,
+            };
+
+            spki
+              .to_der()
+              .map_err(|_| AsymmetricPublicKeyDerError::InvalidMlKemPublicKey)?
+              .into_boxed_slice()
+          }
+          AsymmetricPublicKey::SlhDsa(key) => {
+            let bytes = key.to_bytes();
+            let spki = SubjectPublicKeyInfoRef {
+              algorithm: rsa::pkcs8::AlgorithmIdentifierRef {
+                oid: key.variant().oid(),
+                parameters: None,
+              },
+              subject_public_key: BitStringRef::from_bytes(&bytes).map_err(
+                |_| AsymmetricPublicKeyDerError::InvalidSlhDsaPublicKey,
+              )?,
+            };
+
+            spki
+              .to_der()
+              .map_err(|_| AsymmetricPublicKeyDerError::InvalidSlhDsaPublicKey)?
+              .into_boxed_slice()
+          }
           AsymmetricPublicKey::Dh(key) => {
-            let public_key_bytes = key.key.clone().into_vec();
+            // The public key is an INTEGER in the BIT STRING.
+            let public_key_bytes = asn1::Uint::new(&key.key.clone().into_vec())
+              .and_then(|key| key.to_der())
+              .map_err(|_| AsymmetricPublicKeyDerError::InvalidDhPublicKey)?;
             let params = key.params.to_der().unwrap();
             let spki = SubjectPublicKeyInfoRef {
               algorithm: rsa::pkcs8::AlgorithmIdentifierRef {
                 oid: DH_KEY_AGREEMENT_OID,
-                parameters: Some(AnyRef::new(Tag::Sequence, &params).unwrap()),
+                parameters: Some(AnyRef::from_der(&params).unwrap()),
               },
               subject_public_key: BitStringRef::from_bytes(&public_key_bytes)
                 .map_err(|_| {
@@ -1404,19 +2844,33 @@ pub enum AsymmetricPrivateKeyDerError {
   InvalidEcPrivateKey,
   #[error("exporting non-EC private key as SEC1 is not supported")]
   ExportingNonEcPrivateKeyAsSec1Unsupported,
-  #[class(type)]
-  #[error("exporting RSA-PSS private key as PKCS#8 is not supported yet")]
-  ExportingNonRsaPssPrivateKeyAsPkcs8Unsupported,
   #[error("invalid DSA private key")]
   InvalidDsaPrivateKey,
   #[error("invalid X25519 private key")]
   InvalidX25519PrivateKey,
+  #[error("invalid X448 private key")]
+  InvalidX448PrivateKey,
   #[error("invalid Ed25519 private key")]
   InvalidEd25519PrivateKey,
+  #[error("invalid Ed448 private key")]
+  InvalidEd448PrivateKey,
+  #[error("invalid ML-DSA private key")]
+  InvalidMlDsaPrivateKey,
+  #[error("invalid ML-KEM private key")]
+  InvalidMlKemPrivateKey,
+  #[error("invalid SLH-DSA private key")]
+  InvalidSlhDsaPrivateKey,
   #[error("invalid DH private key")]
   InvalidDhPrivateKey,
   #[error("unsupported key type: {0}")]
   UnsupportedKeyType(String),
+  #[class(generic)]
+  #[property("code" = "ERR_CRYPTO_UNKNOWN_CIPHER")]
+  #[error("Unknown cipher")]
+  UnknownCipher,
+  #[class(generic)]
+  #[error("failed to encrypt private key")]
+  EncryptionFailed,
 }
 
 // https://datatracker.ietf.org/doc/html/rfc7518#section-6.3.2
@@ -1458,7 +2912,13 @@ impl AsymmetricPrivateKey {
   ) -> Result<deno_core::serde_json::Value, AsymmetricPrivateKeyJwkError> {
     match self {
       AsymmetricPrivateKey::Rsa(key) => Ok(rsa_private_to_jwk(key)),
-      AsymmetricPrivateKey::RsaPss(key) => Ok(rsa_private_to_jwk(&key.key)),
+      AsymmetricPrivateKey::RsaPss(key) => {
+        let mut jwk = rsa_private_to_jwk(&key.key);
+        if let Some(alg) = key.details.and_then(|details| details.jwk_alg()) {
+          jwk["alg"] = alg.into();
+        }
+        Ok(jwk)
+      }
       AsymmetricPrivateKey::Ec(key) => {
         let jwk = key.to_jwk()?;
         Ok(deno_core::serde_json::json!(jwk))
@@ -1490,15 +2950,29 @@ impl AsymmetricPrivateKey {
             "kty": "OKP",
         }))
       }
+      AsymmetricPrivateKey::Ed448(key) => Ok(deno_core::serde_json::json!({
+          "crv": "Ed448",
+          "x": bytes_to_b64(key.verifying_key().as_bytes()),
+          "d": bytes_to_b64(key.as_bytes()),
+          "kty": "OKP",
+      })),
+      AsymmetricPrivateKey::X448(key) => Ok(deno_core::serde_json::json!({
+          "crv": "X448",
+          "x": bytes_to_b64(key.public_key().as_bytes()),
+          "d": bytes_to_b64(key.as_bytes()),
+          "kty": "OKP",
+      })),
       _ => Err(AsymmetricPrivateKeyJwkError::JwkExportNotImplementedForKeyType),
     }
   }
 
+  /// Exports the key as DER. A PKCS#8 key is encrypted if `cipher` is set;
+  /// PKCS#1 and SEC1 keys can only be encrypted as PEM.
   fn export_der(
     &self,
     typ: &str,
-    // cipher: Option<&str>,
-    // passphrase: Option<&str>,
+    cipher: Option<&str>,
+    passphrase: Option<&[u8]>,
   ) -> Result<Box<[u8]>, AsymmetricPrivateKeyDerError> {
     match typ {
       "pkcs1" => match self {
@@ -1516,9 +2990,14 @@ impl AsymmetricPrivateKey {
       "sec1" => match self {
         AsymmetricPrivateKey::Ec(key) => {
           let sec1 = match key {
-            EcPrivateKey::P224(key) => key.to_sec1_der(),
-            EcPrivateKey::P256(key) => key.to_sec1_der(),
-            EcPrivateKey::P384(key) => key.to_sec1_der(),
+            EcPrivateKey::P224(key) => ec_to_sec1_der(key, ID_SECP224R1_OID),
+            EcPrivateKey::P256(key) => ec_to_sec1_der(key, ID_SECP256R1_OID),
+            EcPrivateKey::P384(key) => ec_to_sec1_der(key, ID_SECP384R1_OID),
+            EcPrivateKey::P521(key) => ec_to_sec1_der(key, ID_SECP521R1_OID),
+            EcPrivateKey::Secp256k1(key) => {
+              ec_to_sec1_der(key, ID_SECP256K1_OID)
+            }
+            EcPrivateKey::Sm2(key) => sm2_to_sec1_der(key, true),
           }
           .map_err(|_| AsymmetricPrivateKeyDerError::InvalidEcPrivateKey)?;
           Ok(sec1.to_vec().into_boxed_slice())
@@ -1533,8 +3012,33 @@ impl AsymmetricPrivateKey {
               .map_err(|_| AsymmetricPrivateKeyDerError::InvalidRsaPrivateKey)?;
             document.to_bytes().to_vec().into_boxed_slice()
           }
-          AsymmetricPrivateKey::RsaPss(_key) => {
-            return Err(AsymmetricPrivateKeyDerError::ExportingNonRsaPssPrivateKeyAsPkcs8Unsupported)
+          AsymmetricPrivateKey::RsaPss(key) => {
+            let private_key = key
+              .key
+              .to_pkcs1_der()
+              .map_err(|_| AsymmetricPrivateKeyDerError::InvalidRsaPrivateKey)?;
+            let params = key
+              .details
+              .map(RsaPssDetails::to_der)
+              .transpose()
+              .map_err(|_| AsymmetricPrivateKeyDerError::InvalidRsaPrivateKey)?;
+            let private_key = PrivateKeyInfo {
+              algorithm: rsa::pkcs8::AlgorithmIdentifierRef {
+                oid: RSASSA_PSS_OID,
+                parameters: params
+                  .as_deref()
+                  .map(AnyRef::from_der)
+                  .transpose()
+                  .map_err(|_| AsymmetricPrivateKeyDerError::InvalidRsaPrivateKey)?,
+              },
+              private_key: private_key.as_bytes(),
+              public_key: None,
+            };
+
+            private_key
+              .to_der()
+              .map_err(|_| AsymmetricPrivateKeyDerError::InvalidRsaPrivateKey)?
+              .into_boxed_slice()
           }
           AsymmetricPrivateKey::Dsa(key) => {
             let document = key
@@ -1547,6 +3051,9 @@ impl AsymmetricPrivateKey {
               EcPrivateKey::P224(key) => key.to_pkcs8_der(),
               EcPrivateKey::P256(key) => key.to_pkcs8_der(),
               EcPrivateKey::P384(key) => key.to_pkcs8_der(),
+              EcPrivateKey::P521(key) => key.to_pkcs8_der(),
+              EcPrivateKey::Secp256k1(key) => key.to_pkcs8_der(),
+              EcPrivateKey::Sm2(key) => sm2_to_pkcs8_der(key).map_err(Into::into),
             }
             .map_err(|_| AsymmetricPrivateKeyDerError::InvalidEcPrivateKey)?;
             document.to_bytes().to_vec().into_boxed_slice()
@@ -1559,28 +3066,115 @@ impl AsymmetricPrivateKey {
 
             let private_key = PrivateKeyInfo {
               algorithm: rsa::pkcs8::AlgorithmIdentifierRef {
-                oid: X25519_OID,
+                oid: X25519_OID,
+                parameters: None,
+              },
+              private_key: &private_key,
+              public_key: None,
+            };
+
+            private_key
+              .to_der()
+              .map_err(|_| AsymmetricPrivateKeyDerError::InvalidX25519PrivateKey)?
+              .into_boxed_slice()
+          }
+          AsymmetricPrivateKey::X448(key) => {
+            let private_key = OctetStringRef::new(key.as_bytes())
+              .map_err(|_| AsymmetricPrivateKeyDerError::InvalidX448PrivateKey)?
+              .to_der()
+              .map_err(|_| AsymmetricPrivateKeyDerError::InvalidX448PrivateKey)?;
+
+            let private_key = PrivateKeyInfo {
+              algorithm: rsa::pkcs8::AlgorithmIdentifierRef {
+                oid: X448_OID,
+                parameters: None,
+              },
+              private_key: &private_key,
+              public_key: None,
+            };
+
+            private_key
+              .to_der()
+              .map_err(|_| AsymmetricPrivateKeyDerError::InvalidX448PrivateKey)?
+              .into_boxed_slice()
+          }
+          AsymmetricPrivateKey::Ed25519(key) => {
+            let private_key = OctetStringRef::new(key.as_bytes())
+              .map_err(|_| AsymmetricPrivateKeyDerError::InvalidEd25519PrivateKey)?
+              .to_der()
+              .map_err(|_| AsymmetricPrivateKeyDerError::InvalidEd25519PrivateKey)?;
+
+            let private_key = PrivateKeyInfo {
+              algorithm: rsa::pkcs8::AlgorithmIdentifierRef {
+                oid: ED25519_OID,
+                parameters: None,
+              },
+              private_key: &private_key,
+              public_key: None,
+            };
+
+            private_key
+              .to_der()
+              .map_err(|_| AsymmetricPrivateKeyDerError::InvalidEd25519PrivateKey)?
+              .into_boxed_slice()
+          }
+          AsymmetricPrivateKey::Ed448(key) => {
+            let private_key = OctetStringRef::new(key.as_bytes())
+              .map_err(|_| AsymmetricPrivateKeyDerError::InvalidEd448PrivateKey)?
+              .to_der()
+              .map_err(|_| AsymmetricPrivateKeyDerError::InvalidEd448PrivateKey)?;
+
+            let private_key = PrivateKeyInfo {
+              algorithm: rsa::pkcs8::AlgorithmIdentifierRef {
+                oid: ED448_OID,
+                parameters: None,
+              },
+              private_key: &private_key,
+              public_key: None,
+            };
+
+            private_key
+              .to_der()
+              .map_err(|_| AsymmetricPrivateKeyDerError::InvalidEd448PrivateKey)?
+              .into_boxed_slice()
+          }
+          AsymmetricPrivateKey::MlDsa(key) => {
+            let private_key = key.to_pkcs8();
+            let private_key = PrivateKeyInfo {
+              algorithm: rsa::pkcs8::AlgorithmIdentifierRef {
+                oid: key.variant().oid(),
+                parameters: None,
+              },
+              private_key: &private_key,
+              public_key: None,
+            };
+
+            private_key
+              .to_der()
+              .map_err(|_| AsymmetricPrivateKeyDerError::InvalidMlDsaPrivateKey)?
+              .into_boxed_slice()
+          }
+          AsymmetricPrivateKey::MlKem(key) => {
+            let private_key = key.to_pkcs8();
+            let private_key = PrivateKeyInfo {
+              algorithm: rsa::pkcs8::AlgorithmIdentifierRef {
+                oid: ml_kem::ID_ML_KEM_768_OID,
                 parameters: None,
               },
               private_key: &private_key,
               public_key: None,
             };
 
-            let der = private_key
+            private_key
               .to_der()
-              .map_err(|_| AsymmetricPrivateKeyDerError::InvalidX25519PrivateKey)?
-              .into_boxed_slice();
-            return Ok(der);
+              .map_err(|_| AsymmetricPrivateKeyDerError::InvalidMlKemPrivateKey)?
+              .into_boxed_slice()
           }
-          AsymmetricPrivateKey::Ed25519(key) => {
-            let private_key = OctetStringRef::new(key.as_bytes())
-              .map_err(|_| AsymmetricPrivateKeyDerError::InvalidEd25519PrivateKey)?
-              .to_der()
-              .map_err(|_| AsymmetricPrivateKeyDerError::InvalidEd25519PrivateKey)?;
-
+          AsymmetricPrivateKey::SlhDsa(key) => {
+            let private_key = key.to_bytes();
             let private_key = PrivateKeyInfo {
               algorithm: rsa::pkcs8::AlgorithmIdentifierRef {
-                oid: ED25519_OID,
+                oid: key.variant().oid(),
                 parameters: None,
               },
               private_key: &private_key,
@@ -1589,16 +3183,20 @@ impl AsymmetricPrivateKey {
 
             private_key
               .to_der()
-              .map_err(|_| AsymmetricPrivateKeyDerError::InvalidEd25519PrivateKey)?
+              .map_err(|_| AsymmetricPrivateKeyDerError::InvalidSlhDsaPrivateKey)?
               .into_boxed_slice()
           }
           AsymmetricPrivateKey::Dh(key) => {
-            let private_key = key.key.clone().into_vec();
+            let private_key = zeroize::Zeroizing::new(key.key.clone().into_vec());
+            let private_key = asn1::Uint::new(&private_key)
+              .and_then(|key| key.to_der())
+              .map(zeroize::Zeroizing::new)
+              .map_err(|_| AsymmetricPrivateKeyDerError::InvalidDhPrivateKey)?;
             let params = key.params.to_der().unwrap();
             let private_key = PrivateKeyInfo {
               algorithm: rsa::pkcs8::AlgorithmIdentifierRef {
                 oid: DH_KEY_AGREEMENT_OID,
-                parameters: Some(AnyRef::new(Tag::Sequence, &params).unwrap()),
+                parameters: Some(AnyRef::from_der(&params).unwrap()),
               },
               private_key: &private_key,
               public_key: None,
@@ -1611,34 +3209,85 @@ impl AsymmetricPrivateKey {
           }
         };
 
-        Ok(der)
+        match cipher {
+          Some(cipher) => {
+            encrypt_pkcs8(&der, cipher, passphrase.unwrap_or_default())
+          }
+          None => Ok(der),
+        }
       }
       _ => Err(AsymmetricPrivateKeyDerError::UnsupportedKeyType(typ.to_string())),
     }
   }
 }
 
+/// ML-DSA, ML-KEM and SLH-DSA are behind `--unstable-pqc`. Keys can only be
+/// generated or imported through the checked ops, so using them needs no
+/// check.
+// The runtime puts the checker in the op state in a `std` `Arc`.
+#[allow(clippy::disallowed_types)]
+fn check_unstable_pqc(state: &OpState, api_name: &str) {
+  state
+    .borrow::<std::sync::Arc<FeatureChecker>>()
+    .check_or_exit("pqc", api_name);
+}
+
 #[op2]
 #[cppgc]
 pub fn op_node_create_private_key(
+  state: &mut OpState,
   #[buffer] key: &[u8],
   #[string] format: &str,
   #[string] typ: &str,
   #[buffer] passphrase: Option<&[u8]>,
 ) -> Result<KeyObjectHandle, AsymmetricPrivateKeyError> {
-  KeyObjectHandle::new_asymmetric_private_key_from_js(
+  let handle = KeyObjectHandle::new_asymmetric_private_key_from_js(
     key, format, typ, passphrase,
-  )
+  )?;
+  if let Some(api_name) = handle.unstable_pqc_api_name() {
+    check_unstable_pqc(state, api_name);
+  }
+  Ok(handle)
+}
+
+#[derive(serde::Deserialize)]
+pub struct OkpJwkKey {
+  crv: String,
+  x: String,
+  d: Option<String>,
 }
 
 #[op2]
 #[cppgc]
-pub fn op_node_create_ed_raw(
-  #[string] curve: &str,
-  #[buffer] key: &[u8],
+pub fn op_node_create_okp_jwk(
+  #[serde] jwk: OkpJwkKey,
   is_public: bool,
-) -> Result<KeyObjectHandle, EdRawError> {
-  KeyObjectHandle::new_ed_raw(curve, key, is_public)
+) -> Result<KeyObjectHandle, OkpJwkError> {
+  KeyObjectHandle::new_okp_jwk(jwk, is_public)
+}
+
+#[op2]
+#[cppgc]
+pub fn op_node_create_ml_dsa_raw(
+  state: &mut OpState,
+  #[string] typ: &str,
+  #[string] format: &str,
+  #[buffer] key: &[u8],
+) -> Result<KeyObjectHandle, MlDsaRawError> {
+  check_unstable_pqc(state, "ML-DSA");
+  KeyObjectHandle::new_ml_dsa_raw(typ, format, key)
+}
+
+#[op2]
+#[cppgc]
+pub fn op_node_create_ml_kem_raw(
+  state: &mut OpState,
+  #[string] typ: &str,
+  #[string] format: &str,
+  #[buffer] key: &[u8],
+) -> Result<KeyObjectHandle, MlKemRawError> {
+  check_unstable_pqc(state, "ML-KEM");
+  KeyObjectHandle::new_ml_kem_raw(typ, format, key)
 }
 
 #[derive(serde::Deserialize)]
@@ -1648,6 +3297,10 @@ pub struct RsaJwkKey {
   d: Option<String>,
   p: Option<String>,
   q: Option<String>,
+  dp: Option<String>,
+  dq: Option<String>,
+  qi: Option<String>,
+  alg: Option<String>,
 }
 
 #[op2]
@@ -1671,13 +3324,36 @@ pub fn op_node_create_ec_jwk(
 #[op2]
 #[cppgc]
 pub fn op_node_create_public_key(
+  state: &mut OpState,
   #[buffer] key: &[u8],
   #[string] format: &str,
   #[string] typ: &str,
   #[buffer] passphrase: Option<&[u8]>,
 ) -> Result<KeyObjectHandle, AsymmetricPublicKeyError> {
-  KeyObjectHandle::new_asymmetric_public_key_from_js(
+  let handle = KeyObjectHandle::new_asymmetric_public_key_from_js(
     key, format, typ, passphrase,
+  )?;
+  if let Some(api_name) = handle.unstable_pqc_api_name() {
+    check_unstable_pqc(state, api_name);
+  }
+  Ok(handle)
+}
+
+#[op2]
+#[cppgc]
+pub fn op_node_create_key_object_from_crypto_key(
+  #[string] algorithm: &str,
+  #[string] named_curve: &str,
+  #[buffer] data: &[u8],
+  is_private_data: bool,
+  is_public: bool,
+) -> Result<KeyObjectHandle, CryptoKeyDataError> {
+  KeyObjectHandle::new_crypto_key_data(
+    algorithm,
+    named_curve,
+    data,
+    is_private_data,
+    is_public,
   )
 }
 
@@ -1689,6 +3365,78 @@ pub fn op_node_create_secret_key(
   KeyObjectHandle::Secret(key)
 }
 
+#[derive(Debug, thiserror::Error, deno_error::JsError)]
+#[class(type)]
+#[error("Unknown encoding: {0}")]
+#[property("code" = "ERR_UNKNOWN_ENCODING")]
+pub struct UnknownEncodingError(String);
+
+/// Decodes `key` the way `Buffer.from(key, encoding)` does and keeps the
+/// bytes in the handle, so they never show up in a JS `Buffer`. Malformed
+/// hex and base64 aren't errors: hex stops at the first invalid pair, and
+/// base64 skips unknown characters and ends at the first `=`. Takes the
+/// names `normalizeEncoding()` returns.
+#[op2]
+#[cppgc]
+pub fn op_node_create_secret_key_from_string(
+  scope: &mut v8::HandleScope,
+  key: v8::Local<v8::String>,
+  #[string] encoding: &str,
+) -> Result<KeyObjectHandle, UnknownEncodingError> {
+  let key = match encoding {
+    "utf8" => key.to_rust_string_lossy(scope).into_bytes(),
+    "latin1" => {
+      let mut bytes = vec![0; key.length()];
+      key.write_one_byte_v2(scope, 0, &mut bytes, v8::WriteFlags::empty());
+      bytes
+    }
+    "hex" => decode_hex_lenient(key.to_rust_string_lossy(scope).as_bytes()),
+    "base64" | "base64url" => {
+      decode_base64_lenient(key.to_rust_string_lossy(scope).as_bytes())
+    }
+    _ => return Err(UnknownEncodingError(encoding.to_string())),
+  };
+  Ok(KeyObjectHandle::Secret(key.into_boxed_slice()))
+}
+
+fn decode_hex_lenient(encoded: &[u8]) -> Vec<u8> {
+  let nibble = |c: u8| (c as char).to_digit(16).map(|digit| digit as u8);
+  encoded
+    .chunks_exact(2)
+    .map_while(|pair| Some(nibble(pair[0])? << 4 | nibble(pair[1])?))
+    .collect()
+}
+
+/// Both alphabets are accepted whichever of base64 and base64url was asked
+/// for, and leftover bits that don't fill a byte are dropped.
+fn decode_base64_lenient(encoded: &[u8]) -> Vec<u8> {
+  let sextet = |c: u8| match c {
+    b'A'..=b'Z' => Some(c - b'A'),
+    b'a'..=b'z' => Some(c - b'a' + 26),
+    b'0'..=b'9' => Some(c - b'0' + 52),
+    b'+' | b'-' => Some(62),
+    b'/' | b'_' => Some(63),
+    _ => None,
+  };
+  let mut decoded = Vec::with_capacity(encoded.len() * 3 / 4);
+  let mut bits = 0u32;
+  let mut len = 0;
+  for value in encoded
+    .iter()
+    .take_while(|&&c| c != b'=')
+    .filter_map(|&c| sextet(c))
+  {
+    bits = bits << 6 | value as u32;
+    len += 6;
+    if len >= 8 {
+      len -= 8;
+      decoded.push((bits >> len) as u8);
+      bits &= (1 << len) - 1;
+    }
+  }
+  decoded
+}
+
 #[op2]
 #[string]
 pub fn op_node_get_asymmetric_key_type(
@@ -1713,10 +3461,34 @@ pub fn op_node_get_asymmetric_key_type(
     | KeyObjectHandle::AsymmetricPublic(AsymmetricPublicKey::X25519(_)) => {
       Ok("x25519")
     }
+    KeyObjectHandle::AsymmetricPrivate(AsymmetricPrivateKey::X448(_))
+    | KeyObjectHandle::AsymmetricPublic(AsymmetricPublicKey::X448(_)) => {
+      Ok("x448")
+    }
     KeyObjectHandle::AsymmetricPrivate(AsymmetricPrivateKey::Ed25519(_))
     | KeyObjectHandle::AsymmetricPublic(AsymmetricPublicKey::Ed25519(_)) => {
       Ok("ed25519")
     }
+    KeyObjectHandle::AsymmetricPrivate(AsymmetricPrivateKey::Ed448(_))
+    | KeyObjectHandle::AsymmetricPublic(AsymmetricPublicKey::Ed448(_)) => {
+      Ok("ed448")
+    }
+    KeyObjectHandle::AsymmetricPrivate(AsymmetricPrivateKey::MlDsa(key)) => {
+      Ok(key.variant().name())
+    }
+    KeyObjectHandle::AsymmetricPublic(AsymmetricPublicKey::MlDsa(key)) => {
+      Ok(key.variant().name())
+    }
+    KeyObjectHandle::AsymmetricPrivate(AsymmetricPrivateKey::MlKem(_))
+    | KeyObjectHandle::AsymmetricPublic(AsymmetricPublicKey::MlKem(_)) => {
+      Ok(ml_kem::NAME)
+    }
+    KeyObjectHandle::AsymmetricPrivate(AsymmetricPrivateKey::SlhDsa(key)) => {
+      Ok(key.variant().name())
+    }
+    KeyObjectHandle::AsymmetricPublic(AsymmetricPublicKey::SlhDsa(key)) => {
+      Ok(key.variant().name())
+    }
     KeyObjectHandle::AsymmetricPrivate(AsymmetricPrivateKey::Dh(_))
     | KeyObjectHandle::AsymmetricPublic(AsymmetricPublicKey::Dh(_)) => Ok("dh"),
     KeyObjectHandle::Secret(_) => Err(JsErrorBox::type_error(
@@ -1756,8 +3528,32 @@ pub enum AsymmetricKeyDetails {
     named_curve: &'static str,
   },
   X25519,
+  X448,
   Ed25519,
-  Dh,
+  Ed448,
+  MlDsa,
+  MlKem,
+  SlhDsa,
+  #[serde(rename_all = "camelCase")]
+  Dh {
+    prime_length: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    generator: Option<u32>,
+  },
+}
+
+impl AsymmetricKeyDetails {
+  fn dh(params: &DhParameter) -> Self {
+    use num_traits::ToPrimitive as _;
+
+    let uint = |value: &asn1::Uint| {
+      num_bigint_dig::BigUint::from_bytes_be(value.as_bytes())
+    };
+    AsymmetricKeyDetails::Dh {
+      prime_length: uint(&params.prime).bits(),
+      generator: uint(&params.base).to_u32(),
+    }
+  }
 }
 
 #[op2]
@@ -1812,12 +3608,22 @@ pub fn op_node_get_asymmetric_key_details(
           EcPrivateKey::P224(_) => "p224",
           EcPrivateKey::P256(_) => "p256",
           EcPrivateKey::P384(_) => "p384",
+          EcPrivateKey::P521(_) => "p521",
+          EcPrivateKey::Secp256k1(_) => "secp256k1",
+          EcPrivateKey::Sm2(_) => "SM2",
         };
         Ok(AsymmetricKeyDetails::Ec { named_curve })
       }
       AsymmetricPrivateKey::X25519(_) => Ok(AsymmetricKeyDetails::X25519),
+      AsymmetricPrivateKey::X448(_) => Ok(AsymmetricKeyDetails::X448),
       AsymmetricPrivateKey::Ed25519(_) => Ok(AsymmetricKeyDetails::Ed25519),
-      AsymmetricPrivateKey::Dh(_) => Ok(AsymmetricKeyDetails::Dh),
+      AsymmetricPrivateKey::Ed448(_) => Ok(AsymmetricKeyDetails::Ed448),
+      AsymmetricPrivateKey::MlDsa(_) => Ok(AsymmetricKeyDetails::MlDsa),
+      AsymmetricPrivateKey::MlKem(_) => Ok(AsymmetricKeyDetails::MlKem),
+      AsymmetricPrivateKey::SlhDsa(_) => Ok(AsymmetricKeyDetails::SlhDsa),
+      AsymmetricPrivateKey::Dh(key) => {
+        Ok(AsymmetricKeyDetails::dh(&key.params))
+      }
     },
     KeyObjectHandle::AsymmetricPublic(public_key) => match public_key {
       AsymmetricPublicKey::Rsa(key) => {
@@ -1865,12 +3671,20 @@ pub fn op_node_get_asymmetric_key_details(
           EcPublicKey::P224(_) => "p224",
           EcPublicKey::P256(_) => "p256",
           EcPublicKey::P384(_) => "p384",
+          EcPublicKey::P521(_) => "p521",
+          EcPublicKey::Secp256k1(_) => "secp256k1",
+          EcPublicKey::Sm2(_) => "SM2",
         };
         Ok(AsymmetricKeyDetails::Ec { named_curve })
       }
       AsymmetricPublicKey::X25519(_) => Ok(AsymmetricKeyDetails::X25519),
+      AsymmetricPublicKey::X448(_) => Ok(AsymmetricKeyDetails::X448),
       AsymmetricPublicKey::Ed25519(_) => Ok(AsymmetricKeyDetails::Ed25519),
-      AsymmetricPublicKey::Dh(_) => Ok(AsymmetricKeyDetails::Dh),
+      AsymmetricPublicKey::Ed448(_) => Ok(AsymmetricKeyDetails::Ed448),
+      AsymmetricPublicKey::MlDsa(_) => Ok(AsymmetricKeyDetails::MlDsa),
+      AsymmetricPublicKey::MlKem(_) => Ok(AsymmetricKeyDetails::MlKem),
+      AsymmetricPublicKey::SlhDsa(_) => Ok(AsymmetricKeyDetails::SlhDsa),
+      AsymmetricPublicKey::Dh(key) => Ok(AsymmetricKeyDetails::dh(&key.params)),
     },
     KeyObjectHandle::Secret(_) => Err(JsErrorBox::type_error(
       "symmetric key is not an asymmetric key",
@@ -2102,22 +3916,18 @@ fn dsa_generate(
 ) -> Result<KeyObjectHandlePair, JsErrorBox> {
   let mut rng = rand::thread_rng();
   use dsa::Components;
-  use dsa::KeySize;
   use dsa::SigningKey;
 
-  let key_size = match (modulus_length, divisor_length) {
-    #[allow(deprecated)]
-    (1024, 160) => KeySize::DSA_1024_160,
-    (2048, 224) => KeySize::DSA_2048_224,
-    (2048, 256) => KeySize::DSA_2048_256,
-    (3072, 256) => KeySize::DSA_3072_256,
-    _ => {
-      return Err(JsErrorBox::type_error(
-        "Invalid modulusLength+divisorLength combination",
-      ));
-    }
-  };
-  let components = Components::generate(&mut rng, key_size);
+  if !matches!(
+    (modulus_length, divisor_length),
+    (1024, 160) | (2048, 224) | (2048, 256) | (3072, 256)
+  ) {
+    return Err(JsErrorBox::type_error(
+      "Invalid modulusLength+divisorLength combination",
+    ));
+  }
+  let (p, q, g) = dsa_params::generate(modulus_length, divisor_length);
+  let components = Components::from_components(p, q, g).unwrap();
   let signing_key = SigningKey::generate(&mut rng, components);
   let private_key = AsymmetricPrivateKey::Dsa(signing_key);
   let public_key = private_key.to_public_key();
@@ -2162,6 +3972,17 @@ fn ec_generate(named_curve: &str) -> Result<KeyObjectHandlePair, JsErrorBox> {
       let key = p384::SecretKey::random(&mut rng);
       AsymmetricPrivateKey::Ec(EcPrivateKey::P384(key))
     }
+    "P-521" | "secp521r1" => {
+      let key = p521::SecretKey::random(&mut rng);
+      AsymmetricPrivateKey::Ec(EcPrivateKey::P521(key))
+    }
+    "secp256k1" => {
+      let key = k256::SecretKey::random(&mut rng);
+      AsymmetricPrivateKey::Ec(EcPrivateKey::Secp256k1(key))
+    }
+    "SM2" | "sm2p256v1" => {
+      AsymmetricPrivateKey::Ec(EcPrivateKey::Sm2(sm2::SecretKey::random()))
+    }
     _ => {
       return Err(JsErrorBox::type_error(format!(
         "unsupported named curve: {}",
@@ -2210,6 +4031,24 @@ pub async fn op_node_generate_x25519_key_async() -> KeyObjectHandlePair {
   spawn_blocking(x25519_generate).await.unwrap()
 }
 
+fn x448_generate() -> KeyObjectHandlePair {
+  let private_key = AsymmetricPrivateKey::X448(x448::StaticSecret::generate());
+  let public_key = private_key.to_public_key();
+  KeyObjectHandlePair::new(private_key, public_key)
+}
+
+#[op2]
+#[cppgc]
+pub fn op_node_generate_x448_key() -> KeyObjectHandlePair {
+  x448_generate()
+}
+
+#[op2(async)]
+#[cppgc]
+pub async fn op_node_generate_x448_key_async() -> KeyObjectHandlePair {
+  spawn_blocking(x448_generate).await.unwrap()
+}
+
 fn ed25519_generate() -> KeyObjectHandlePair {
   let keypair = ed25519_dalek::SigningKey::generate(&mut thread_rng());
   let private_key = AsymmetricPrivateKey::Ed25519(keypair);
@@ -2229,58 +4068,152 @@ pub async fn op_node_generate_ed25519_key_async() -> KeyObjectHandlePair {
   spawn_blocking(ed25519_generate).await.unwrap()
 }
 
-fn u32_slice_to_u8_slice(slice: &[u32]) -> &[u8] {
-  // SAFETY: just reinterpreting the slice as u8
-  unsafe {
-    std::slice::from_raw_parts(
-      slice.as_ptr() as *const u8,
-      std::mem::size_of_val(slice),
-    )
+fn ed448_generate() -> KeyObjectHandlePair {
+  let private_key = AsymmetricPrivateKey::Ed448(ed448::SigningKey::generate());
+  let public_key = private_key.to_public_key();
+  KeyObjectHandlePair::new(private_key, public_key)
+}
+
+#[op2]
+#[cppgc]
+pub fn op_node_generate_ed448_key() -> KeyObjectHandlePair {
+  ed448_generate()
+}
+
+#[op2(async)]
+#[cppgc]
+pub async fn op_node_generate_ed448_key_async() -> KeyObjectHandlePair {
+  spawn_blocking(ed448_generate).await.unwrap()
+}
+
+fn ml_dsa_variant(
+  state: &OpState,
+  typ: &str,
+) -> Result<ml_dsa::Variant, JsErrorBox> {
+  check_unstable_pqc(state, "ML-DSA");
+  ml_dsa::Variant::from_name(typ).ok_or_else(|| {
+    JsErrorBox::type_error(format!("unsupported ML-DSA parameter set: {typ}"))
+  })
+}
+
+fn ml_dsa_generate(variant: ml_dsa::Variant) -> KeyObjectHandlePair {
+  let private_key =
+    AsymmetricPrivateKey::MlDsa(ml_dsa::SigningKey::generate(variant));
+  let public_key = private_key.to_public_key();
+  KeyObjectHandlePair::new(private_key, public_key)
+}
+
+#[op2]
+#[cppgc]
+pub fn op_node_generate_ml_dsa_key(
+  state: &mut OpState,
+  #[string] typ: &str,
+) -> Result<KeyObjectHandlePair, JsErrorBox> {
+  Ok(ml_dsa_generate(ml_dsa_variant(state, typ)?))
+}
+
+#[op2(async)]
+#[cppgc]
+pub async fn op_node_generate_ml_dsa_key_async(
+  state: Rc<RefCell<OpState>>,
+  #[string] typ: String,
+) -> Result<KeyObjectHandlePair, JsErrorBox> {
+  let variant = ml_dsa_variant(&state.borrow(), &typ)?;
+  Ok(
+    spawn_blocking(move || ml_dsa_generate(variant))
+      .await
+      .unwrap(),
+  )
+}
+
+fn ml_kem_check(state: &OpState, typ: &str) -> Result<(), JsErrorBox> {
+  check_unstable_pqc(state, "ML-KEM");
+  if typ != ml_kem::NAME {
+    return Err(JsErrorBox::type_error(format!(
+      "unsupported ML-KEM parameter set: {typ}"
+    )));
   }
+  Ok(())
 }
 
-fn dh_group_generate(
-  group_name: &str,
+fn ml_kem_generate() -> KeyObjectHandlePair {
+  let private_key =
+    AsymmetricPrivateKey::MlKem(ml_kem::DecapsulationKey::generate());
+  let public_key = private_key.to_public_key();
+  KeyObjectHandlePair::new(private_key, public_key)
+}
+
+#[op2]
+#[cppgc]
+pub fn op_node_generate_ml_kem_key(
+  state: &mut OpState,
+  #[string] typ: &str,
 ) -> Result<KeyObjectHandlePair, JsErrorBox> {
-  let (dh, prime, generator) = match group_name {
-    "modp5" => (
-      dh::DiffieHellman::group::<dh::Modp1536>(),
-      dh::Modp1536::MODULUS,
-      dh::Modp1536::GENERATOR,
-    ),
-    "modp14" => (
-      dh::DiffieHellman::group::<dh::Modp2048>(),
-      dh::Modp2048::MODULUS,
-      dh::Modp2048::GENERATOR,
-    ),
-    "modp15" => (
-      dh::DiffieHellman::group::<dh::Modp3072>(),
-      dh::Modp3072::MODULUS,
-      dh::Modp3072::GENERATOR,
-    ),
-    "modp16" => (
-      dh::DiffieHellman::group::<dh::Modp4096>(),
-      dh::Modp4096::MODULUS,
-      dh::Modp4096::GENERATOR,
-    ),
-    "modp17" => (
-      dh::DiffieHellman::group::<dh::Modp6144>(),
-      dh::Modp6144::MODULUS,
-      dh::Modp6144::GENERATOR,
-    ),
-    "modp18" => (
-      dh::DiffieHellman::group::<dh::Modp8192>(),
-      dh::Modp8192::MODULUS,
-      dh::Modp8192::GENERATOR,
-    ),
-    _ => return Err(JsErrorBox::type_error("Unsupported group name")),
-  };
+  ml_kem_check(state, typ)?;
+  Ok(ml_kem_generate())
+}
+
+#[op2(async)]
+#[cppgc]
+pub async fn op_node_generate_ml_kem_key_async(
+  state: Rc<RefCell<OpState>>,
+  #[string] typ: String,
+) -> Result<KeyObjectHandlePair, JsErrorBox> {
+  ml_kem_check(&state.borrow(), &typ)?;
+  Ok(spawn_blocking(ml_kem_generate).await.unwrap())
+}
+
+fn slh_dsa_variant(
+  state: &OpState,
+  typ: &str,
+) -> Result<slh_dsa::Variant, JsErrorBox> {
+  check_unstable_pqc(state, "SLH-DSA");
+  slh_dsa::Variant::from_name(typ).ok_or_else(|| {
+    JsErrorBox::type_error(format!("unsupported SLH-DSA parameter set: {typ}"))
+  })
+}
+
+fn slh_dsa_generate(variant: slh_dsa::Variant) -> KeyObjectHandlePair {
+  let private_key =
+    AsymmetricPrivateKey::SlhDsa(slh_dsa::SigningKey::generate(variant));
+  let public_key = private_key.to_public_key();
+  KeyObjectHandlePair::new(private_key, public_key)
+}
+
+#[op2]
+#[cppgc]
+pub fn op_node_generate_slh_dsa_key(
+  state: &mut OpState,
+  #[string] typ: &str,
+) -> Result<KeyObjectHandlePair, JsErrorBox> {
+  Ok(slh_dsa_generate(slh_dsa_variant(state, typ)?))
+}
+
+#[op2(async)]
+#[cppgc]
+pub async fn op_node_generate_slh_dsa_key_async(
+  state: Rc<RefCell<OpState>>,
+  #[string] typ: String,
+) -> Result<KeyObjectHandlePair, JsErrorBox> {
+  let variant = slh_dsa_variant(&state.borrow(), &typ)?;
+  Ok(
+    spawn_blocking(move || slh_dsa_generate(variant))
+      .await
+      .unwrap(),
+  )
+}
+
+fn dh_key_pair(
+  dh: dh::DiffieHellman,
+  prime: &[u8],
+  generator: usize,
+) -> KeyObjectHandlePair {
   let params = DhParameter {
-    prime: asn1::Int::new(u32_slice_to_u8_slice(prime)).unwrap(),
-    base: asn1::Int::new(generator.to_be_bytes().as_slice()).unwrap(),
+    prime: asn1::Uint::new(prime).unwrap(),
+    base: asn1::Uint::new(&generator.to_be_bytes()).unwrap(),
     private_value_length: None,
   };
-  Ok(KeyObjectHandlePair::new(
+  KeyObjectHandlePair::new(
     AsymmetricPrivateKey::Dh(DhPrivateKey {
       key: dh.private_key,
       params: params.clone(),
@@ -2289,7 +4222,29 @@ fn dh_group_generate(
       key: dh.public_key,
       params,
     }),
-  ))
+  )
+}
+
+fn dh_group_key_pair<G: DiffieHellmanGroup>() -> KeyObjectHandlePair {
+  dh_key_pair(
+    dh::DiffieHellman::group::<G>(),
+    &G::modulus().to_bytes_be(),
+    G::GENERATOR,
+  )
+}
+
+fn dh_group_generate(
+  group_name: &str,
+) -> Result<KeyObjectHandlePair, JsErrorBox> {
+  Ok(match group_name {
+    "modp5" => dh_group_key_pair::<dh::Modp1536>(),
+    "modp14" => dh_group_key_pair::<dh::Modp2048>(),
+    "modp15" => dh_group_key_pair::<dh::Modp3072>(),
+    "modp16" => dh_group_key_pair::<dh::Modp4096>(),
+    "modp17" => dh_group_key_pair::<dh::Modp6144>(),
+    "modp18" => dh_group_key_pair::<dh::Modp8192>(),
+    _ => return Err(JsErrorBox::type_error("Unsupported group name")),
+  })
 }
 
 #[op2]
@@ -2318,22 +4273,9 @@ fn dh_generate(
   let prime = prime
     .map(|p| p.into())
     .unwrap_or_else(|| Prime::generate(prime_len));
-  let dh = dh::DiffieHellman::new(prime.clone(), generator);
-  let params = DhParameter {
-    prime: asn1::Int::new(&prime.0.to_bytes_be()).unwrap(),
-    base: asn1::Int::new(generator.to_be_bytes().as_slice()).unwrap(),
-    private_value_length: None,
-  };
-  KeyObjectHandlePair::new(
-    AsymmetricPrivateKey::Dh(DhPrivateKey {
-      key: dh.private_key,
-      params: params.clone(),
-    }),
-    AsymmetricPublicKey::Dh(DhPublicKey {
-      key: dh.public_key,
-      params,
-    }),
-  )
+  let prime_bytes = prime.to_bytes_be();
+  let dh = dh::DiffieHellman::new(prime, generator);
+  dh_key_pair(dh, &prime_bytes, generator)
 }
 
 #[op2]
@@ -2487,24 +4429,47 @@ pub enum ExportPrivateKeyPemError {
 pub fn op_node_export_private_key_pem(
   #[cppgc] handle: &KeyObjectHandle,
   #[string] typ: &str,
+  #[string] cipher: Option<String>,
+  #[buffer] passphrase: Option<&[u8]>,
 ) -> Result<String, ExportPrivateKeyPemError> {
   let private_key = handle
     .as_private_key()
     .ok_or(AsymmetricPrivateKeyDerError::KeyIsNotAsymmetricPrivateKey)?;
-  let data = private_key.export_der(typ)?;
+  let (pkcs8_cipher, traditional_cipher) = match typ {
+    "pkcs8" => (cipher.as_deref(), None),
+    _ => (None, cipher.as_deref()),
+  };
+  let data = private_key.export_der(typ, pkcs8_cipher, passphrase)?;
 
   let label = match typ {
     "pkcs1" => "RSA PRIVATE KEY",
+    "pkcs8" if pkcs8_cipher.is_some() => "ENCRYPTED PRIVATE KEY",
     "pkcs8" => "PRIVATE KEY",
     "sec1" => "EC PRIVATE KEY",
     _ => unreachable!("export_der would have errored"),
   };
 
+  let Some(cipher) = traditional_cipher else {
+    return encode_pem(label, &data);
+  };
+  let (dek_info, data) =
+    encrypt_traditional(&data, cipher, passphrase.unwrap_or_default())?;
+  let pem = encode_pem(label, &data)?;
+  let (begin, rest) = pem.split_once('\n').expect("pem has a begin line");
+  Ok(format!(
+    "{begin}\nProc-Type: 4,ENCRYPTED\nDEK-Info: {dek_info}\n\n{rest}"
+  ))
+}
+
+fn encode_pem(
+  label: &'static str,
+  data: &[u8],
+) -> Result<String, ExportPrivateKeyPemError> {
   let pem_len = der::pem::encapsulated_len(label, LineEnding::LF, data.len())
     .map_err(|_| ExportPrivateKeyPemError::VeryLargeData)?;
   let mut out = vec![0; pem_len];
   let mut writer = PemWriter::new(label, LineEnding::LF, &mut out)?;
-  writer.write(&data)?;
+  writer.write(data)?;
   let len = writer.finish()?;
   out.truncate(len);
 
@@ -2541,11 +4506,84 @@ pub fn op_node_export_private_key_jwk(
 pub fn op_node_export_private_key_der(
   #[cppgc] handle: &KeyObjectHandle,
   #[string] typ: &str,
+  #[string] cipher: Option<String>,
+  #[buffer] passphrase: Option<&[u8]>,
 ) -> Result<Box<[u8]>, AsymmetricPrivateKeyDerError> {
   let private_key = handle
     .as_private_key()
     .ok_or(AsymmetricPrivateKeyDerError::KeyIsNotAsymmetricPrivateKey)?;
-  private_key.export_der(typ)
+  private_key.export_der(typ, cipher.as_deref(), passphrase)
+}
+
+#[derive(Debug, thiserror::Error, deno_error::JsError)]
+#[class(type)]
+pub enum ExportRawKeyError {
+  #[error("key is not an asymmetric public key")]
+  KeyIsNotAsymmetricPublicKey,
+  #[error("key is not an asymmetric private key")]
+  KeyIsNotAsymmetricPrivateKey,
+  #[error("raw export not implemented for this key type")]
+  RawExportNotImplementedForKeyType,
+  #[error("unsupported key format: {0}")]
+  UnsupportedKeyFormat(String),
+}
+
+#[op2]
+#[buffer]
+pub fn op_node_export_public_key_raw(
+  #[cppgc] handle: &KeyObjectHandle,
+) -> Result<Box<[u8]>, ExportRawKeyError> {
+  let public_key = handle
+    .as_public_key()
+    .ok_or(ExportRawKeyError::KeyIsNotAsymmetricPublicKey)?;
+  match &*public_key {
+    AsymmetricPublicKey::MlDsa(key) => Ok(key.to_bytes().into_boxed_slice()),
+    AsymmetricPublicKey::MlKem(key) => Ok(key.to_bytes().into_boxed_slice()),
+    _ => Err(ExportRawKeyError::RawExportNotImplementedForKeyType),
+  }
+}
+
+/// `raw-seed` is the seed that the key was expanded from and `raw-private`
+/// the FIPS 204 or FIPS 203 encoding of the expanded key.
+#[op2]
+#[buffer]
+pub fn op_node_export_private_key_raw(
+  #[cppgc] handle: &KeyObjectHandle,
+  #[string] format: &str,
+) -> Result<Box<[u8]>, ExportRawKeyError> {
+  let private_key = handle
+    .as_private_key()
+    .ok_or(ExportRawKeyError::KeyIsNotAsymmetricPrivateKey)?;
+  let (seed, expanded) = match private_key {
+    AsymmetricPrivateKey::MlDsa(key) => {
+      (key.seed().as_slice(), key.to_expanded().to_vec())
+    }
+    AsymmetricPrivateKey::MlKem(key) => {
+      (key.seed().as_slice(), key.to_expanded().to_vec())
+    }
+    _ => return Err(ExportRawKeyError::RawExportNotImplementedForKeyType),
+  };
+  match format {
+    "raw-seed" => Ok(seed.to_vec().into_boxed_slice()),
+    "raw-private" => Ok(expanded.into_boxed_slice()),
+    _ => Err(ExportRawKeyError::UnsupportedKeyFormat(format.to_string())),
+  }
+}
+
+#[op2]
+#[buffer]
+pub fn op_node_export_crypto_key_data(
+  #[cppgc] handle: &KeyObjectHandle,
+) -> Result<Box<[u8]>, CryptoKeyDataError> {
+  handle.export_crypto_key_data()
+}
+
+#[op2(fast)]
+pub fn op_node_key_equals(
+  #[cppgc] handle: &KeyObjectHandle,
+  #[cppgc] other: &KeyObjectHandle,
+) -> bool {
+  handle.equals(other)
 }
 
 #[op2]
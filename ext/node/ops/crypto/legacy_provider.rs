@@ -0,0 +1,17 @@
+// Copyright 2018-2025 the Deno authors. MIT license.
+
+//! Emulates OpenSSL 3's legacy provider, loaded in Node.js with
+//! `--openssl-legacy-provider`. Like OpenSSL's, it is process-wide.
+
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+
+static LEGACY_PROVIDER_ENABLED: AtomicBool = AtomicBool::new(false);
+
+pub fn enabled() -> bool {
+  LEGACY_PROVIDER_ENABLED.load(Ordering::Relaxed)
+}
+
+pub fn set_enabled(enabled: bool) {
+  LEGACY_PROVIDER_ENABLED.store(enabled, Ordering::Relaxed);
+}
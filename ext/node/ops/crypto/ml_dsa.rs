@@ -0,0 +1,410 @@
+// Copyright 2018-2025 the Deno authors. MIT license.
+
+//! ML-DSA signatures (FIPS 204) with ML-DSA-44, ML-DSA-65 and ML-DSA-87,
+//! and the private key encodings of RFC 9881. `ml-dsa` provides the
+//! algorithm, but its signing keys can't give back the seed they were
+//! expanded from, so the seed and the public key are kept alongside.
+
+use ml_dsa::B32;
+use ml_dsa::EncodedSignature;
+use ml_dsa::EncodedVerifyingKey;
+use ml_dsa::KeyGen as _;
+use ml_dsa::MlDsa44;
+use ml_dsa::MlDsa65;
+use ml_dsa::MlDsa87;
+use ml_dsa::MlDsaParams;
+use ml_dsa::Signature;
+use spki::der::Decode as _;
+use spki::der::Reader as _;
+use spki::der::SliceReader;
+use spki::der::asn1::OctetStringRef;
+use zeroize::Zeroizing;
+
+pub const SEED_LENGTH: usize = 32;
+pub const MAX_CONTEXT_LENGTH: usize = 255;
+
+/// `seed [0] IMPLICIT OCTET STRING (SIZE (32))` in the `ML-DSA-PrivateKey`
+/// CHOICE.
+const SEED_TAG: u8 = 0x80;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Variant {
+  MlDsa44,
+  MlDsa65,
+  MlDsa87,
+}
+
+impl Variant {
+  pub fn from_name(name: &str) -> Option<Self> {
+    match name {
+      "ml-dsa-44" => Some(Variant::MlDsa44),
+      "ml-dsa-65" => Some(Variant::MlDsa65),
+      "ml-dsa-87" => Some(Variant::MlDsa87),
+      _ => None,
+    }
+  }
+
+  pub fn name(&self) -> &'static str {
+    match self {
+      Variant::MlDsa44 => "ml-dsa-44",
+      Variant::MlDsa65 => "ml-dsa-65",
+      Variant::MlDsa87 => "ml-dsa-87",
+    }
+  }
+
+  pub fn from_oid(oid: const_oid::ObjectIdentifier) -> Option<Self> {
+    [Variant::MlDsa44, Variant::MlDsa65, Variant::MlDsa87]
+      .into_iter()
+      .find(|variant| variant.oid() == oid)
+  }
+
+  pub fn oid(&self) -> const_oid::ObjectIdentifier {
+    match self {
+      Variant::MlDsa44 => ID_ML_DSA_44_OID,
+      Variant::MlDsa65 => ID_ML_DSA_65_OID,
+      Variant::MlDsa87 => ID_ML_DSA_87_OID,
+    }
+  }
+
+  pub fn signature_length(&self) -> usize {
+    match self {
+      Variant::MlDsa44 => 2420,
+      Variant::MlDsa65 => 3309,
+      Variant::MlDsa87 => 4627,
+    }
+  }
+}
+
+pub const ID_ML_DSA_44_OID: const_oid::ObjectIdentifier =
+  const_oid::ObjectIdentifier::new_unwrap("2.16.840.1.101.3.4.3.17");
+pub const ID_ML_DSA_65_OID: const_oid::ObjectIdentifier =
+  const_oid::ObjectIdentifier::new_unwrap("2.16.840.1.101.3.4.3.18");
+pub const ID_ML_DSA_87_OID: const_oid::ObjectIdentifier =
+  const_oid::ObjectIdentifier::new_unwrap("2.16.840.1.101.3.4.3.19");
+
+// The keys are several kilobytes, so they are boxed to keep the key enums
+// small.
+#[derive(Clone)]
+pub enum VerifyingKey {
+  MlDsa44(Box<ml_dsa::VerifyingKey<MlDsa44>>),
+  MlDsa65(Box<ml_dsa::VerifyingKey<MlDsa65>>),
+  MlDsa87(Box<ml_dsa::VerifyingKey<MlDsa87>>),
+}
+
+#[derive(Clone)]
+enum ExpandedKey {
+  MlDsa44(Box<ml_dsa::SigningKey<MlDsa44>>),
+  MlDsa65(Box<ml_dsa::SigningKey<MlDsa65>>),
+  MlDsa87(Box<ml_dsa::SigningKey<MlDsa87>>),
+}
+
+impl VerifyingKey {
+  /// Any string of the right length is a valid public key encoding.
+  pub fn from_bytes(variant: Variant, bytes: &[u8]) -> Option<Self> {
+    fn decode<P: MlDsaParams>(
+      bytes: &[u8],
+    ) -> Option<Box<ml_dsa::VerifyingKey<P>>> {
+      let encoded = EncodedVerifyingKey::<P>::try_from(bytes).ok()?;
+      Some(Box::new(ml_dsa::VerifyingKey::decode(&encoded)))
+    }
+
+    Some(match variant {
+      Variant::MlDsa44 => VerifyingKey::MlDsa44(decode(bytes)?),
+      Variant::MlDsa65 => VerifyingKey::MlDsa65(decode(bytes)?),
+      Variant::MlDsa87 => VerifyingKey::MlDsa87(decode(bytes)?),
+    })
+  }
+
+  pub fn variant(&self) -> Variant {
+    match self {
+      VerifyingKey::MlDsa44(_) => Variant::MlDsa44,
+      VerifyingKey::MlDsa65(_) => Variant::MlDsa65,
+      VerifyingKey::MlDsa87(_) => Variant::MlDsa87,
+    }
+  }
+
+  pub fn to_bytes(&self) -> Vec<u8> {
+    match self {
+      VerifyingKey::MlDsa44(key) => key.encode().to_vec(),
+      VerifyingKey::MlDsa65(key) => key.encode().to_vec(),
+      VerifyingKey::MlDsa87(key) => key.encode().to_vec(),
+    }
+  }
+
+  /// Returns false for a malformed signature or an overlong context
+  /// rather than an error, as OpenSSL does.
+  pub fn verify(
+    &self,
+    message: &[u8],
+    context: &[u8],
+    signature: &[u8],
+  ) -> bool {
+    fn verify<P: MlDsaParams>(
+      key: &ml_dsa::VerifyingKey<P>,
+      message: &[u8],
+      context: &[u8],
+      signature: &[u8],
+    ) -> bool {
+      let Ok(encoded) = EncodedSignature::<P>::try_from(signature) else {
+        return false;
+      };
+      Signature::decode(&encoded).is_some_and(|signature| {
+        key.verify_with_context(message, context, &signature)
+      })
+    }
+
+    match self {
+      VerifyingKey::MlDsa44(key) => verify(key, message, context, signature),
+      VerifyingKey::MlDsa65(key) => verify(key, message, context, signature),
+      VerifyingKey::MlDsa87(key) => verify(key, message, context, signature),
+    }
+  }
+}
+
+#[derive(Clone)]
+pub struct SigningKey {
+  seed: Zeroizing<[u8; SEED_LENGTH]>,
+  expanded: ExpandedKey,
+  public: VerifyingKey,
+}
+
+impl SigningKey {
+  pub fn from_seed(variant: Variant, seed: &[u8]) -> Option<Self> {
+    fn key_gen<P: MlDsaParams>(
+      xi: &B32,
+    ) -> (Box<ml_dsa::SigningKey<P>>, Box<ml_dsa::VerifyingKey<P>>) {
+      let pair = P::key_gen_internal(xi);
+      (
+        Box::new(pair.signing_key().clone()),
+        Box::new(pair.verifying_key().clone()),
+      )
+    }
+
+    let seed = Zeroizing::new(<[u8; SEED_LENGTH]>::try_from(seed).ok()?);
+    let xi = Zeroizing::new(B32::from(*seed));
+    let (expanded, public) = match variant {
+      Variant::MlDsa44 => {
+        let (expanded, public) = key_gen::<MlDsa44>(&xi);
+        (
+          ExpandedKey::MlDsa44(expanded),
+          VerifyingKey::MlDsa44(public),
+        )
+      }
+      Variant::MlDsa65 => {
+        let (expanded, public) = key_gen::<MlDsa65>(&xi);
+        (
+          ExpandedKey::MlDsa65(expanded),
+          VerifyingKey::MlDsa65(public),
+        )
+      }
+      Variant::MlDsa87 => {
+        let (expanded, public) = key_gen::<MlDsa87>(&xi);
+        (
+          ExpandedKey::MlDsa87(expanded),
+          VerifyingKey::MlDsa87(public),
+        )
+      }
+    };
+    Some(Self {
+      seed,
+      expanded,
+      public,
+    })
+  }
+
+  pub fn generate(variant: Variant) -> Self {
+    let mut seed = Zeroizing::new([0; SEED_LENGTH]);
+    rand::RngCore::fill_bytes(&mut rand::thread_rng(), &mut *seed);
+    Self::from_seed(variant, &*seed).unwrap()
+  }
+
+  /// Decodes the `privateKey` of a PKCS#8 `PrivateKeyInfo`: the seed, or
+  /// both the seed and the expanded key, which must match. The expanded
+  /// key alone is rejected, as the public key can't be recovered from it
+  /// here.
+  pub fn from_pkcs8(variant: Variant, private_key: &[u8]) -> Option<Self> {
+    match private_key {
+      [SEED_TAG, len, seed @ ..] if *len as usize == SEED_LENGTH => {
+        Self::from_seed(variant, seed)
+      }
+      _ => {
+        let mut reader = SliceReader::new(private_key).ok()?;
+        let both = reader
+          .sequence(|reader| {
+            let seed = OctetStringRef::decode(reader)?;
+            let expanded = OctetStringRef::decode(reader)?;
+            Ok((seed, expanded))
+          })
+          .ok()?;
+        let (seed, expanded) = reader.finish(both).ok()?;
+        let key = Self::from_seed(variant, seed.as_bytes())?;
+        (*key.to_expanded() == expanded.as_bytes()).then_some(key)
+      }
+    }
+  }
+
+  /// The `seed` choice of the PKCS#8 `privateKey`, which RFC 9881
+  /// recommends.
+  pub fn to_pkcs8(&self) -> Zeroizing<Vec<u8>> {
+    let mut der = Zeroizing::new(Vec::with_capacity(2 + SEED_LENGTH));
+    der.extend_from_slice(&[SEED_TAG, SEED_LENGTH as u8]);
+    der.extend_from_slice(&*self.seed);
+    der
+  }
+
+  pub fn variant(&self) -> Variant {
+    self.public.variant()
+  }
+
+  pub fn seed(&self) -> &[u8; SEED_LENGTH] {
+    &self.seed
+  }
+
+  /// The FIPS 204 encoding of the private key.
+  pub fn to_expanded(&self) -> Zeroizing<Vec<u8>> {
+    Zeroizing::new(match &self.expanded {
+      ExpandedKey::MlDsa44(key) => key.encode().to_vec(),
+      ExpandedKey::MlDsa65(key) => key.encode().to_vec(),
+      ExpandedKey::MlDsa87(key) => key.encode().to_vec(),
+    })
+  }
+
+  pub fn verifying_key(&self) -> &VerifyingKey {
+    &self.public
+  }
+
+  /// Signs with fresh randomness, the hedged variant of FIPS 204. Returns
+  /// `None` if the context is over `MAX_CONTEXT_LENGTH` bytes.
+  pub fn sign(&self, message: &[u8], context: &[u8]) -> Option<Vec<u8>> {
+    fn sign<P: MlDsaParams>(
+      key: &ml_dsa::SigningKey<P>,
+      message: &[u8],
+      context: &[u8],
+    ) -> Option<Vec<u8>> {
+      let signature = key
+        .sign_randomized(message, context, &mut rand::thread_rng())
+        .ok()?;
+      Some(signature.encode().to_vec())
+    }
+
+    match &self.expanded {
+      ExpandedKey::MlDsa44(key) => sign(key, message, context),
+      ExpandedKey::MlDsa65(key) => sign(key, message, context),
+      ExpandedKey::MlDsa87(key) => sign(key, message, context),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use sha2::Digest as _;
+  use sha2::Sha256;
+
+  use super::*;
+
+  fn unhex(hex: &str) -> Vec<u8> {
+    let mut bytes = vec![0; hex.len() / 2];
+    faster_hex::hex_decode(hex.as_bytes(), &mut bytes).unwrap();
+    bytes
+  }
+
+  fn sha256_hex(bytes: &[u8]) -> String {
+    faster_hex::hex_string(&Sha256::digest(bytes))
+  }
+
+  // The first test case of each group in NIST's ACVP ML-DSA-keyGen-FIPS204
+  // vectors, with the SHA-256 digests of the public and private keys.
+  #[test]
+  fn acvp_key_gen() {
+    let vectors = [
+      (
+        Variant::MlDsa44,
+        "93ef2e6ef1fb08999d142abe0295482370d3f43bdb254a78e2b0d5168eca065f",
+        "6995b20ecd5cde41719035028a712ccf35b1adf53b913030423d9d6fa188d673",
+        "16a35d4b59f932aeada987dc689b075add0df57b4815bb103be7443ee3c1c561",
+      ),
+      (
+        Variant::MlDsa65,
+        "70cefb9aed5b68e018b079da8284b9d5cad5499ed9c265ff73588005d85c225c",
+        "646b26b8d09dbc9e865b6a006c693a3127b065e62fab5fbe8b159c416462feb6",
+        "3894dc56a4553781d68ff0d1b6fcf1b4876085ea602fb6f8738def50ed7d4c75",
+      ),
+      (
+        Variant::MlDsa87,
+        "38359fbcd79582cffe609e137ee2efe8a8dbcbad18ba92bb433ab4f09b49299d",
+        "ea374a09356e5f89be784f28f4ef938e8976cb5c4db00fbacb257663491748d4",
+        "a0cc3d4f703057c09b9261336ba45563d2c781d173f7fc634910698e95eee375",
+      ),
+    ];
+    for (variant, seed, public, private) in vectors {
+      let key = SigningKey::from_seed(variant, &unhex(seed)).unwrap();
+      assert_eq!(sha256_hex(&key.verifying_key().to_bytes()), public);
+      assert_eq!(sha256_hex(&key.to_expanded()), private);
+    }
+  }
+
+  #[test]
+  fn pkcs8_private_key() {
+    let seed = [7; SEED_LENGTH];
+    let key = SigningKey::from_seed(Variant::MlDsa65, &seed).unwrap();
+    let der = key.to_pkcs8();
+    assert_eq!(der[..2], [0x80, 0x20]);
+    assert_eq!(der[2..], seed);
+
+    let decoded = SigningKey::from_pkcs8(Variant::MlDsa65, &der).unwrap();
+    assert_eq!(decoded.seed(), &seed);
+    assert!(SigningKey::from_pkcs8(Variant::MlDsa65, &der[..33]).is_none());
+
+    // The `both` choice: SEQUENCE { OCTET STRING, OCTET STRING }.
+    let expanded = key.to_expanded();
+    let both = |seed: &[u8], expanded: &[u8]| {
+      let mut der = vec![0x30, 0x82, 0x0f, 0xe6, 0x04, 0x20];
+      der.extend_from_slice(seed);
+      der.extend_from_slice(&[0x04, 0x82, 0x0f, 0xc0]);
+      der.extend_from_slice(expanded);
+      der
+    };
+    assert_eq!(expanded.len(), 4032);
+    let decoded =
+      SigningKey::from_pkcs8(Variant::MlDsa65, &both(&seed, &expanded))
+        .unwrap();
+    assert_eq!(decoded.seed(), &seed);
+    let other = SigningKey::from_seed(Variant::MlDsa65, &[8; SEED_LENGTH])
+      .unwrap()
+      .to_expanded();
+    assert!(
+      SigningKey::from_pkcs8(Variant::MlDsa65, &both(&seed, &other)).is_none()
+    );
+
+    // The `expandedKey` choice.
+    let mut der = vec![0x04, 0x82, 0x0f, 0xc0];
+    der.extend_from_slice(&expanded);
+    assert!(SigningKey::from_pkcs8(Variant::MlDsa65, &der).is_none());
+  }
+
+  #[test]
+  fn sign_and_verify() {
+    for variant in [Variant::MlDsa44, Variant::MlDsa65, Variant::MlDsa87] {
+      let key = SigningKey::generate(variant);
+      let public = key.verifying_key();
+      let signature = key.sign(b"message", b"context").unwrap();
+      assert_eq!(signature.len(), variant.signature_length());
+      assert!(public.verify(b"message", b"context", &signature));
+      assert!(!public.verify(b"message", b"", &signature));
+      assert!(!public.verify(b"other", b"context", &signature));
+      assert!(!public.verify(b"message", b"context", &signature[1..]));
+      let mut tampered = signature.clone();
+      tampered[0] ^= 1;
+      assert!(!public.verify(b"message", b"context", &tampered));
+
+      // Hedged signing gives a different signature each time.
+      assert_ne!(key.sign(b"message", b"context").unwrap(), signature);
+
+      let public = VerifyingKey::from_bytes(variant, &public.to_bytes());
+      assert!(public.unwrap().verify(b"message", b"context", &signature));
+
+      let context = [0; MAX_CONTEXT_LENGTH + 1];
+      assert!(key.sign(b"message", &context).is_none());
+    }
+  }
+}
@@ -0,0 +1,354 @@
+// Copyright 2018-2025 the Deno authors. MIT license.
+
+//! ML-KEM-768 key encapsulation (FIPS 203) and the private key encodings of
+//! draft-ietf-lamps-kyber-certificates. `ml-kem` provides the algorithm,
+//! but its decapsulation keys can't give back the seed they were generated
+//! from, so the seed and the encapsulation key are kept alongside.
+
+use ml_kem::B32;
+use ml_kem::EncapsulateDeterministic as _;
+use ml_kem::EncodedSizeUser as _;
+use ml_kem::KemCore as _;
+use ml_kem::MlKem768;
+use ml_kem::MlKem768Params;
+use ml_kem::kem::Decapsulate as _;
+use spki::der::Decode as _;
+use spki::der::Reader as _;
+use spki::der::SliceReader;
+use spki::der::asn1::OctetStringRef;
+use zeroize::Zeroize as _;
+use zeroize::Zeroizing;
+
+pub const NAME: &str = "ml-kem-768";
+
+/// `d || z`, the two 32-byte seeds of `ML-KEM.KeyGen_internal`.
+pub const SEED_LENGTH: usize = 64;
+
+pub const ID_ML_KEM_768_OID: const_oid::ObjectIdentifier =
+  const_oid::ObjectIdentifier::new_unwrap("2.16.840.1.101.3.4.4.2");
+
+/// `seed [0] IMPLICIT OCTET STRING (SIZE (64))` in the `ML-KEM-PrivateKey`
+/// CHOICE.
+const SEED_TAG: u8 = 0x80;
+
+// The keys are kilobytes, so they are boxed to keep the key enums small.
+#[derive(Clone)]
+pub struct EncapsulationKey(Box<ml_kem::kem::EncapsulationKey<MlKem768Params>>);
+
+impl EncapsulationKey {
+  /// Checks that the key decodes to coefficients below q, as FIPS 203,
+  /// section 7.2 requires. `ml-kem` reduces them instead, so the key must
+  /// encode back to the same bytes.
+  pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+    let encoded = bytes.try_into().ok()?;
+    let key = ml_kem::kem::EncapsulationKey::from_bytes(encoded);
+    (key.as_bytes() == *encoded).then(|| Self(Box::new(key)))
+  }
+
+  pub fn to_bytes(&self) -> Vec<u8> {
+    self.0.as_bytes().to_vec()
+  }
+
+  /// Returns the shared secret and the ciphertext.
+  pub fn encapsulate(&self) -> (Zeroizing<[u8; 32]>, Vec<u8>) {
+    let mut m = Zeroizing::new([0; 32]);
+    rand::RngCore::fill_bytes(&mut rand::thread_rng(), &mut *m);
+    self.encapsulate_deterministic(&m)
+  }
+
+  fn encapsulate_deterministic(
+    &self,
+    m: &[u8; 32],
+  ) -> (Zeroizing<[u8; 32]>, Vec<u8>) {
+    let mut m = B32::from(*m);
+    // Encapsulation can't fail.
+    let (ciphertext, shared_secret) =
+      self.0.encapsulate_deterministic(&m).unwrap();
+    m.as_mut_slice().zeroize();
+    (Zeroizing::new(shared_secret.into()), ciphertext.to_vec())
+  }
+}
+
+#[derive(Clone)]
+pub struct DecapsulationKey {
+  seed: Zeroizing<[u8; SEED_LENGTH]>,
+  expanded: Box<ml_kem::kem::DecapsulationKey<MlKem768Params>>,
+  public: EncapsulationKey,
+}
+
+impl DecapsulationKey {
+  /// `ML-KEM.KeyGen_internal` with `d` and `z` from the seed.
+  pub fn from_seed(seed: &[u8]) -> Option<Self> {
+    let seed = Zeroizing::new(<[u8; SEED_LENGTH]>::try_from(seed).ok()?);
+    // `hybrid-array` doesn't implement `Zeroize` here, so the seeds are
+    // cleared by hand.
+    let mut d = B32::try_from(&seed[..32]).unwrap();
+    let mut z = B32::try_from(&seed[32..]).unwrap();
+    let (expanded, public) = MlKem768::generate_deterministic(&d, &z);
+    d.as_mut_slice().zeroize();
+    z.as_mut_slice().zeroize();
+    Some(Self {
+      seed,
+      expanded: Box::new(expanded),
+      public: EncapsulationKey(Box::new(public)),
+    })
+  }
+
+  pub fn generate() -> Self {
+    let mut seed = Zeroizing::new([0; SEED_LENGTH]);
+    rand::RngCore::fill_bytes(&mut rand::thread_rng(), &mut *seed);
+    Self::from_seed(&*seed).unwrap()
+  }
+
+  /// Decodes the `privateKey` of a PKCS#8 `PrivateKeyInfo`: the seed, or
+  /// both the seed and the expanded key, which must match. The expanded
+  /// key alone is rejected, as the key couldn't be exported in its seed
+  /// form.
+  pub fn from_pkcs8(private_key: &[u8]) -> Option<Self> {
+    match private_key {
+      [SEED_TAG, len, seed @ ..] if *len as usize == SEED_LENGTH => {
+        Self::from_seed(seed)
+      }
+      _ => {
+        let mut reader = SliceReader::new(private_key).ok()?;
+        let both = reader
+          .sequence(|reader| {
+            let seed = OctetStringRef::decode(reader)?;
+            let expanded = OctetStringRef::decode(reader)?;
+            Ok((seed, expanded))
+          })
+          .ok()?;
+        let (seed, expanded) = reader.finish(both).ok()?;
+        let key = Self::from_seed(seed.as_bytes())?;
+        (*key.to_expanded() == expanded.as_bytes()).then_some(key)
+      }
+    }
+  }
+
+  /// The `seed` choice of the PKCS#8 `privateKey`.
+  pub fn to_pkcs8(&self) -> Zeroizing<Vec<u8>> {
+    let mut der = Zeroizing::new(Vec::with_capacity(2 + SEED_LENGTH));
+    der.extend_from_slice(&[SEED_TAG, SEED_LENGTH as u8]);
+    der.extend_from_slice(&*self.seed);
+    der
+  }
+
+  pub fn seed(&self) -> &[u8; SEED_LENGTH] {
+    &self.seed
+  }
+
+  /// The FIPS 203 encoding of the decapsulation key.
+  pub fn to_expanded(&self) -> Zeroizing<Vec<u8>> {
+    Zeroizing::new(self.expanded.as_bytes().to_vec())
+  }
+
+  pub fn encapsulation_key(&self) -> &EncapsulationKey {
+    &self.public
+  }
+
+  /// Returns `None` only for a ciphertext of the wrong length: any other
+  /// ciphertext that doesn't re-encrypt to itself yields the implicit
+  /// rejection secret `J(z || c)` instead of an error.
+  pub fn decapsulate(&self, c: &[u8]) -> Option<Zeroizing<[u8; 32]>> {
+    let c = c.try_into().ok()?;
+    // Decapsulation can't fail.
+    let shared_secret = self.expanded.decapsulate(c).unwrap();
+    Some(Zeroizing::new(shared_secret.into()))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use sha2::Digest as _;
+  use sha2::Sha256;
+
+  use super::*;
+
+  fn unhex(hex: &str) -> Vec<u8> {
+    let mut bytes = vec![0; hex.len() / 2];
+    faster_hex::hex_decode(hex.as_bytes(), &mut bytes).unwrap();
+    bytes
+  }
+
+  fn sha256_hex(bytes: &[u8]) -> String {
+    faster_hex::hex_string(&Sha256::digest(bytes))
+  }
+
+  fn seed() -> Vec<u8> {
+    (0..SEED_LENGTH as u8).collect()
+  }
+
+  // The first ML-KEM-768 test case in NIST's ACVP ML-KEM-keyGen-FIPS203
+  // vectors, with the seed `d || z` and the SHA-256 digests of the keys.
+  #[test]
+  fn acvp_key_gen() {
+    let key = DecapsulationKey::from_seed(&unhex(
+      "e34a701c4c87582f42264ee422d3c684d97611f2523efe0c998af05056d693dca85768f3486bd32a01bf9a8f21ea938e648eae4e5448c34c3eb88820b159eedd",
+    ))
+    .unwrap();
+    assert_eq!(
+      sha256_hex(&key.encapsulation_key().to_bytes()),
+      "7799c9d8eef172aa78c073514f2f039c240de8c5cb61bca82ba0bc46041ce279"
+    );
+    assert_eq!(
+      sha256_hex(&key.to_expanded()),
+      "104b3444c3de2b81143788d27e17648f45c80f617f906156db2258da96dead40"
+    );
+  }
+
+  // The first ML-KEM-768 encapsulation test case in NIST's ACVP
+  // ML-KEM-encapDecap-FIPS203 vectors, with the SHA-256 digest of the
+  // ciphertext.
+  #[test]
+  fn acvp_encapsulation() {
+    let public = EncapsulationKey::from_bytes(include_bytes!(
+      "testdata/ml_kem_768_encap_26_ek.bin"
+    ))
+    .unwrap();
+    let m =
+      unhex("2ce74ad291133518fe60c7df5d251b9d82add48462ff505c6e547e949e6b6bf7");
+    let (shared_secret, ciphertext) =
+      public.encapsulate_deterministic(&m.try_into().unwrap());
+    assert_eq!(
+      sha256_hex(&ciphertext),
+      "ac57163b80ead205b8323e1402b8ca66bece40d8df9994b12d43bbb4f6e19bf4"
+    );
+    assert_eq!(
+      faster_hex::hex_string(&*shared_secret),
+      "2696d28e9c61c2a01ce9b1608dcb9d292785a0cd58efb7fe13b1de95f0db55b3"
+    );
+  }
+
+  // The ML-KEM-768 decapsulation group of the same vectors, with a valid
+  // ciphertext and a modified one that gets the implicit rejection secret.
+  // Its decapsulation key comes without a seed, so the key is built from
+  // the expanded form here.
+  #[test]
+  fn acvp_decapsulation() {
+    let encoded = include_bytes!("testdata/ml_kem_768_decap_dk.bin");
+    let expanded = ml_kem::kem::DecapsulationKey::from_bytes(encoded.into());
+    let key = DecapsulationKey {
+      seed: Zeroizing::new([0; SEED_LENGTH]),
+      public: EncapsulationKey(Box::new(expanded.encapsulation_key().clone())),
+      expanded: Box::new(expanded),
+    };
+    assert_eq!(*key.to_expanded(), encoded);
+
+    for (ciphertext, shared_secret) in [
+      (
+        &include_bytes!("testdata/ml_kem_768_decap_88_c.bin")[..],
+        "dc5b8888bc1eba5c1969c21164ea43e22e7ac0cd012a2f26cb8c487e69ef7ce4",
+      ),
+      (
+        &include_bytes!("testdata/ml_kem_768_decap_86_c.bin")[..],
+        "3d23b10df232a180786f61261e85278251746580bebca6acbad60aef6952be69",
+      ),
+    ] {
+      assert_eq!(
+        faster_hex::hex_string(&*key.decapsulate(ciphertext).unwrap()),
+        shared_secret
+      );
+    }
+  }
+
+  // Known answers for the seed 00 01 .. 3f and the message 64 65 .. 83,
+  // which OpenSSL 3.5 reproduces with `-pkeyopt hexseed:` and
+  // `-pkeyopt hexikme:`, with the SHA-256 digests of the long values.
+  #[test]
+  fn known_answers() {
+    let key = DecapsulationKey::from_seed(&seed()).unwrap();
+    let public = key.encapsulation_key();
+    assert_eq!(
+      sha256_hex(&public.to_bytes()),
+      "0b7934c83125c788995e2ba6bd761e33046b3e40571be53e023309a29f398cc9"
+    );
+    assert_eq!(
+      sha256_hex(&key.to_expanded()),
+      "dac268bde6a8dd238e9887117d6b664e7a7a9350ad6b7c08a948e504809572a5"
+    );
+
+    let m: [u8; 32] = std::array::from_fn(|i| 100 + i as u8);
+    let (shared_secret, ciphertext) = public.encapsulate_deterministic(&m);
+    assert_eq!(ciphertext.len(), 1088);
+    assert_eq!(
+      sha256_hex(&ciphertext),
+      "57fe559432dbb3c5547c73f155820622f7efdd532e4330360a36ebf7d2ddec55"
+    );
+    assert_eq!(
+      faster_hex::hex_string(&*shared_secret),
+      "c5a74110c158acbaf9c01deb86fa6cc10c14533feda54bec1fdd000d61f07e4e"
+    );
+    assert_eq!(*key.decapsulate(&ciphertext).unwrap(), *shared_secret);
+
+    // Implicit rejection: `J(z || c)` for the corrupted ciphertext.
+    let mut corrupted = ciphertext;
+    corrupted[0] ^= 1;
+    assert_eq!(
+      faster_hex::hex_string(&*key.decapsulate(&corrupted).unwrap()),
+      "bb28c25ed3222c13ce49d65f663f1c9f148565a664747e142f1abe06f33f4826"
+    );
+    assert!(key.decapsulate(&corrupted[1..]).is_none());
+  }
+
+  #[test]
+  fn encapsulate_and_decapsulate() {
+    let key = DecapsulationKey::generate();
+    let public =
+      EncapsulationKey::from_bytes(&key.encapsulation_key().to_bytes())
+        .unwrap();
+    let (shared_secret, ciphertext) = public.encapsulate();
+    assert_eq!(*key.decapsulate(&ciphertext).unwrap(), *shared_secret);
+
+    // Fresh randomness gives a different ciphertext each time.
+    assert_ne!(public.encapsulate().1, ciphertext);
+  }
+
+  #[test]
+  fn rejects_invalid_encapsulation_keys() {
+    let key = DecapsulationKey::from_seed(&seed()).unwrap();
+    let mut bytes = key.encapsulation_key().to_bytes();
+    assert!(EncapsulationKey::from_bytes(&bytes[1..]).is_none());
+    // A first coefficient of 0xfff, which is not below q.
+    bytes[0] = 0xff;
+    bytes[1] |= 0x0f;
+    assert!(EncapsulationKey::from_bytes(&bytes).is_none());
+  }
+
+  #[test]
+  fn pkcs8_private_key() {
+    let seed = seed();
+    let key = DecapsulationKey::from_seed(&seed).unwrap();
+    let der = key.to_pkcs8();
+    assert_eq!(der[..2], [0x80, 0x40]);
+    assert_eq!(der[2..], seed);
+
+    let decoded = DecapsulationKey::from_pkcs8(&der).unwrap();
+    assert_eq!(decoded.seed()[..], seed);
+    assert!(DecapsulationKey::from_pkcs8(&der[..65]).is_none());
+
+    // The `both` choice that OpenSSL writes by default:
+    // SEQUENCE { OCTET STRING, OCTET STRING }.
+    let both = |seed: &[u8], expanded: &[u8]| {
+      let mut der = vec![0x30, 0x82, 0x09, 0xa6, 0x04, 0x40];
+      der.extend_from_slice(seed);
+      der.extend_from_slice(&[0x04, 0x82, 0x09, 0x60]);
+      der.extend_from_slice(expanded);
+      der
+    };
+    let expanded = key.to_expanded();
+    assert_eq!(expanded.len(), 2400);
+    let decoded =
+      DecapsulationKey::from_pkcs8(&both(&seed, &expanded)).unwrap();
+    assert_eq!(decoded.seed()[..], seed);
+    let other = DecapsulationKey::generate();
+    assert!(
+      DecapsulationKey::from_pkcs8(&both(&seed, &other.to_expanded()))
+        .is_none()
+    );
+
+    // The `expandedKey` choice.
+    let mut der = vec![0x04, 0x82, 0x09, 0x60];
+    der.extend_from_slice(&expanded);
+    assert!(DecapsulationKey::from_pkcs8(&der).is_none());
+  }
+}
@@ -1,14 +1,17 @@
 // Copyright 2018-2025 the Deno authors. MIT license.
+use std::cell::RefCell;
 use std::future::Future;
 use std::rc::Rc;
 
 use aws_lc_rs::signature::Ed25519KeyPair;
 use deno_core::JsBuffer;
 use deno_core::OpState;
+use deno_core::ResourceId;
 use deno_core::StringOrBuffer;
 use deno_core::ToJsBuffer;
 use deno_core::op2;
 use deno_core::unsync::spawn_blocking;
+use deno_core::v8;
 use deno_error::JsErrorBox;
 use elliptic_curve::sec1::ToEncodedPoint;
 use hkdf::Hkdf;
@@ -31,15 +34,30 @@ use rsa::RsaPrivateKey;
 use rsa::RsaPublicKey;
 use rsa::pkcs8::DecodePrivateKey;
 use rsa::pkcs8::DecodePublicKey;
+use rsa::traits::PublicKeyParts;
 
 pub mod cipher;
+mod des;
 mod dh;
 pub mod digest;
+mod dsa_params;
+mod ed25519;
+mod ed448;
+mod fips;
+pub mod hmac;
 pub mod keys;
+mod legacy_provider;
 mod md5_sha1;
+mod ml_dsa;
+mod ml_kem;
 mod pkcs3;
 mod primes;
+mod rsa_pss;
 pub mod sign;
+mod slh_dsa;
+mod sm2;
+mod wipe;
+mod x448;
 pub mod x509;
 
 use self::digest::match_fixed_digest_with_eager_block_buffer;
@@ -88,8 +106,84 @@ pub fn op_node_check_prime_bytes_async(
 pub fn op_node_create_hash(
   #[string] algorithm: &str,
   output_length: Option<u32>,
+  #[buffer] key: Option<&[u8]>,
+  #[buffer] function_name: Option<&[u8]>,
+  #[buffer] customization: Option<&[u8]>,
+  #[buffer] salt: Option<&[u8]>,
+  #[buffer] personalization: Option<&[u8]>,
 ) -> Result<digest::Hasher, digest::HashError> {
-  digest::Hasher::new(algorithm, output_length.map(|l| l as usize))
+  let output_length = output_length.map(|l| l as usize);
+  if salt.is_some() || personalization.is_some() {
+    if function_name.is_some() || customization.is_some() {
+      return Err(digest::HashError::SaltUnsupported(algorithm.to_string()));
+    }
+    return digest::Hasher::new_salted(
+      algorithm,
+      output_length,
+      key,
+      salt.unwrap_or_default(),
+      personalization.unwrap_or_default(),
+    );
+  }
+  if function_name.is_none() && customization.is_none() {
+    return digest::Hasher::new(algorithm, output_length, key);
+  }
+  digest::Hasher::new_customized(
+    algorithm,
+    output_length,
+    key,
+    function_name.unwrap_or_default(),
+    customization.unwrap_or_default(),
+  )
+}
+
+#[op2]
+#[cppgc]
+pub fn op_node_create_multi_hash(
+  #[serde] algorithms: Vec<String>,
+) -> Result<digest::MultiHasher, digest::HashError> {
+  digest::MultiHasher::new(&algorithms)
+}
+
+#[op2(fast)]
+pub fn op_node_multi_hash_update(
+  #[cppgc] hasher: &digest::MultiHasher,
+  #[buffer] data: &[u8],
+) -> bool {
+  hasher.update(data)
+}
+
+#[op2(fast)]
+pub fn op_node_multi_hash_update_str(
+  #[cppgc] hasher: &digest::MultiHasher,
+  #[string] data: &str,
+) -> bool {
+  hasher.update(data.as_bytes())
+}
+
+/// Returns `None` if the hashes were already finalized.
+#[op2]
+#[serde]
+pub fn op_node_multi_hash_digest(
+  #[cppgc] hasher: &digest::MultiHasher,
+) -> Option<Vec<ToJsBuffer>> {
+  let digests = hasher.digest()?;
+  Some(digests.into_iter().map(ToJsBuffer::from).collect())
+}
+
+#[op2(fast)]
+pub fn op_node_get_fips() -> bool {
+  fips::enabled()
+}
+
+#[op2(fast)]
+pub fn op_node_set_fips(enabled: bool) {
+  fips::set_enabled(enabled);
+}
+
+#[op2(fast)]
+pub fn op_node_set_legacy_provider(enabled: bool) {
+  legacy_provider::set_enabled(enabled);
 }
 
 #[op2]
@@ -99,8 +193,13 @@ pub fn op_node_get_hashes() -> Vec<&'static str> {
 }
 
 #[op2]
-pub fn op_node_get_hash_size(#[string] algorithm: &str) -> Option<u8> {
-  digest::Hash::get_size(algorithm)
+pub fn op_node_get_hash_size(#[string] algorithm: &str) -> Option<u32> {
+  digest::Hash::get_size(algorithm).map(|size| size as u32)
+}
+
+#[op2]
+pub fn op_node_get_hash_block_size(#[string] algorithm: &str) -> Option<u32> {
+  digest::Hash::get_block_size(algorithm).map(|size| size as u32)
 }
 
 #[op2(fast)]
@@ -119,6 +218,156 @@ pub fn op_node_hash_update_str(
   hasher.update(data.as_bytes())
 }
 
+/// Like `op_node_hash_update_str`, but for any `StringEncoding`. The bytes
+/// are read straight out of the V8 string a chunk at a time, so no `Buffer`
+/// is allocated in JS. Lone surrogates become U+FFFD in UTF-8, and Latin-1
+/// keeps the low byte of each code unit, both as `Buffer.from` does.
+#[op2(fast)]
+pub fn op_node_hash_update_str_encoded(
+  scope: &mut v8::HandleScope,
+  #[cppgc] hasher: &digest::Hasher,
+  data: v8::Local<v8::String>,
+  #[string] encoding: &str,
+) -> Result<bool, JsErrorBox> {
+  const CHUNK_LEN: usize = 4096;
+
+  let encoding = digest::StringEncoding::parse(encoding).ok_or_else(|| {
+    JsErrorBox::type_error(format!("Unsupported encoding: {encoding}"))
+  })?;
+  let mut hash = hasher.hash.borrow_mut();
+  let Some(hash) = hash.as_mut() else {
+    return Ok(false);
+  };
+
+  let length = data.length();
+  let chunks = (0..length)
+    .step_by(CHUNK_LEN)
+    .map(|offset| (offset as u32, (length - offset).min(CHUNK_LEN)));
+  match encoding {
+    digest::StringEncoding::Utf8 => {
+      let mut storage = [std::mem::MaybeUninit::uninit(); 1024];
+      hash.update(data.to_rust_cow_lossy(scope, &mut storage).as_bytes());
+    }
+    digest::StringEncoding::Latin1 => {
+      let mut bytes = [0; CHUNK_LEN];
+      for (offset, len) in chunks {
+        let bytes = &mut bytes[..len];
+        data.write_one_byte_v2(scope, offset, bytes, v8::WriteFlags::empty());
+        hash.update(bytes);
+      }
+    }
+    digest::StringEncoding::Utf16Le if data.is_onebyte() => {
+      let mut latin1 = [0; CHUNK_LEN];
+      let mut bytes = [0; CHUNK_LEN * 2];
+      for (offset, len) in chunks {
+        let latin1 = &mut latin1[..len];
+        data.write_one_byte_v2(scope, offset, latin1, v8::WriteFlags::empty());
+        for (out, byte) in bytes.chunks_exact_mut(2).zip(latin1.iter()) {
+          out[0] = *byte;
+        }
+        hash.update(&bytes[..len * 2]);
+      }
+    }
+    digest::StringEncoding::Utf16Le => {
+      let mut units = [0; CHUNK_LEN];
+      let mut bytes = [0; CHUNK_LEN * 2];
+      for (offset, len) in chunks {
+        let units = &mut units[..len];
+        data.write_v2(scope, offset, units, v8::WriteFlags::empty());
+        for (out, unit) in bytes.chunks_exact_mut(2).zip(units.iter()) {
+          out.copy_from_slice(&unit.to_le_bytes());
+        }
+        hash.update(&bytes[..len * 2]);
+      }
+    }
+  }
+  Ok(true)
+}
+
+#[op2(async)]
+pub fn op_node_hash_update_async(
+  #[cppgc] hasher: &digest::Hasher,
+  #[anybuffer] data: JsBuffer,
+) -> impl Future<Output = Result<bool, tokio::task::JoinError>> + use<> {
+  hasher.update_async(data)
+}
+
+/// Reads the resource `rid` to EOF in 64 KiB chunks and feeds each chunk to
+/// the hash, without a round trip through JS. Resolves to the number of
+/// bytes hashed. Rejects with `ERR_CRYPTO_HASH_FINALIZED` if the hash was
+/// already finalized, and stops early, keeping what was hashed so far, if it
+/// is finalized or the resource is closed while reading.
+#[op2(async)]
+#[number]
+pub fn op_node_hash_read_resource(
+  state: Rc<RefCell<OpState>>,
+  #[cppgc] hasher: &digest::Hasher,
+  #[smi] rid: ResourceId,
+) -> impl Future<Output = Result<u64, JsErrorBox>> + use<> {
+  const CHUNK_SIZE: usize = 64 * 1024;
+
+  let slot = hasher.hash.clone();
+  async move {
+    if slot.borrow().is_none() {
+      return Err(JsErrorBox::from_err(digest::HashError::Finalized));
+    }
+    let resource = state
+      .borrow()
+      .resource_table
+      .get_any(rid)
+      .map_err(JsErrorBox::from_err)?;
+    let mut total = 0;
+    while slot.borrow().is_some() {
+      let chunk = match resource.clone().read(CHUNK_SIZE).await {
+        Ok(chunk) => chunk,
+        Err(_) if !state.borrow().resource_table.has(rid) => break,
+        Err(err) => return Err(err),
+      };
+      if chunk.is_empty() {
+        break;
+      }
+      let mut hash = slot.borrow_mut();
+      let Some(hash) = hash.as_mut() else {
+        break;
+      };
+      hash.update(&chunk);
+      total += chunk.len() as u64;
+    }
+    Ok(total)
+  }
+}
+
+/// Feeds each view in `chunks` to the hash in order, in a single op call.
+/// Returns false if the hash was already finalized, like
+/// `op_node_hash_update`.
+#[op2(fast)]
+pub fn op_node_hash_update_vectored(
+  scope: &mut v8::HandleScope,
+  #[cppgc] hasher: &digest::Hasher,
+  chunks: v8::Local<v8::Array>,
+) -> Result<bool, JsErrorBox> {
+  let mut views = Vec::with_capacity(chunks.length() as usize);
+  for i in 0..chunks.length() {
+    let view = chunks
+      .get_index(scope, i)
+      .and_then(|chunk| v8::Local::<v8::ArrayBufferView>::try_from(chunk).ok())
+      .ok_or_else(|| {
+        JsErrorBox::type_error(format!("chunks[{i}] is not an ArrayBufferView"))
+      })?;
+    views.push(view);
+  }
+
+  let mut hash = hasher.hash.borrow_mut();
+  let Some(hash) = hash.as_mut() else {
+    return Ok(false);
+  };
+  let mut storage = [0; v8::TYPED_ARRAY_MAX_SIZE_IN_HEAP];
+  for view in views {
+    hash.update(view.get_contents(&mut storage));
+  }
+  Ok(true)
+}
+
 #[op2]
 #[buffer]
 pub fn op_node_hash_digest(
@@ -136,6 +385,31 @@ pub fn op_node_hash_digest_hex(
   Some(faster_hex::hex_string(&digest))
 }
 
+#[op2]
+#[string]
+pub fn op_node_hash_digest_encoded(
+  #[cppgc] hasher: &digest::Hasher,
+  #[string] encoding: &str,
+) -> Option<String> {
+  hasher.digest_encoded(encoding)
+}
+
+#[op2]
+#[buffer]
+pub fn op_node_hash_squeeze(
+  #[cppgc] hasher: &digest::Hasher,
+  length: u32,
+) -> Result<Option<Box<[u8]>>, digest::HashError> {
+  hasher.squeeze(length as usize)
+}
+
+#[op2(fast)]
+pub fn op_node_hash_reset(
+  #[cppgc] hasher: &digest::Hasher,
+) -> Result<(), digest::HashError> {
+  hasher.reset()
+}
+
 #[op2]
 #[cppgc]
 pub fn op_node_hash_clone(
@@ -145,6 +419,22 @@ pub fn op_node_hash_clone(
   hasher.clone_inner(output_length.map(|l| l as usize))
 }
 
+#[op2]
+#[buffer]
+pub fn op_node_hash_export_state(
+  #[cppgc] hasher: &digest::Hasher,
+) -> Result<Option<Vec<u8>>, digest::HashError> {
+  hasher.export_state()
+}
+
+#[op2]
+#[cppgc]
+pub fn op_node_hash_import_state(
+  #[anybuffer] state: &[u8],
+) -> Result<digest::Hasher, digest::HashError> {
+  digest::Hasher::import_state(state)
+}
+
 #[derive(Debug, thiserror::Error, deno_error::JsError)]
 pub enum PrivateEncryptDecryptError {
   #[class(generic)]
@@ -159,9 +449,80 @@ pub enum PrivateEncryptDecryptError {
   #[class(generic)]
   #[error(transparent)]
   Rsa(#[from] rsa::Error),
-  #[class(type)]
-  #[error("Unknown padding")]
+  #[class(generic)]
+  #[error("unknown padding type")]
+  #[property("code" = "ERR_OSSL_RSA_UNKNOWN_PADDING_TYPE")]
   UnknownPadding,
+  #[class(generic)]
+  #[error("illegal or unsupported padding mode")]
+  #[property("code" = "ERR_OSSL_ILLEGAL_OR_UNSUPPORTED_PADDING_MODE")]
+  IllegalOrUnsupportedPaddingMode,
+  #[class(generic)]
+  #[error("invalid x931 digest")]
+  #[property("code" = "ERR_OSSL_INVALID_X931_DIGEST")]
+  InvalidX931Digest,
+  #[class(generic)]
+  #[error("invalid padding")]
+  #[property("code" = "ERR_OSSL_RSA_INVALID_PADDING")]
+  InvalidPadding,
+  #[class(generic)]
+  #[error("data too small for key size")]
+  #[property("code" = "ERR_OSSL_RSA_DATA_TOO_SMALL_FOR_KEY_SIZE")]
+  DataTooSmallForKeySize,
+  #[class(generic)]
+  #[error("data too large for key size")]
+  #[property("code" = "ERR_OSSL_RSA_DATA_TOO_LARGE_FOR_KEY_SIZE")]
+  DataTooLargeForKeySize,
+  #[class(generic)]
+  #[error("data greater than mod len")]
+  #[property("code" = "ERR_OSSL_RSA_DATA_GREATER_THAN_MOD_LEN")]
+  DataGreaterThanModLen,
+  #[class(generic)]
+  #[error("data too large for modulus")]
+  #[property("code" = "ERR_OSSL_RSA_DATA_TOO_LARGE_FOR_MODULUS")]
+  DataTooLargeForModulus,
+}
+
+const RSA_PKCS1_PADDING: u32 = 1;
+const RSA_NO_PADDING: u32 = 3;
+const RSA_PKCS1_OAEP_PADDING: u32 = 4;
+const RSA_X931_PADDING: u32 = 5;
+
+/// Applies an unpadded RSA operation to `msg`, as OpenSSL does for
+/// `RSA_NO_PADDING`. Encryption takes exactly one modulus-size block, while
+/// decryption also accepts shorter input.
+fn rsa_raw(
+  key: &impl PublicKeyParts,
+  msg: &[u8],
+  encrypt: bool,
+  op: impl FnOnce(&BigUint) -> Result<BigUint, rsa::Error>,
+) -> Result<Vec<u8>, PrivateEncryptDecryptError> {
+  let size = key.size();
+  if msg.len() > size {
+    return Err(if encrypt {
+      PrivateEncryptDecryptError::DataTooLargeForKeySize
+    } else {
+      PrivateEncryptDecryptError::DataGreaterThanModLen
+    });
+  }
+  if encrypt && msg.len() < size {
+    return Err(PrivateEncryptDecryptError::DataTooSmallForKeySize);
+  }
+  let m = BigUint::from_bytes_be(msg);
+  if &m >= key.n() {
+    return Err(PrivateEncryptDecryptError::DataTooLargeForModulus);
+  }
+  let out = op(&m)?.to_bytes_be();
+  let mut block = vec![0; size - out.len()];
+  block.extend_from_slice(&out);
+  Ok(block)
+}
+
+/// Strips PKCS #1 v1.5 block type 1 padding, `00 01 FF.. 00 data`.
+fn rsa_pkcs1_type1_unpad(block: &[u8]) -> Option<&[u8]> {
+  let rest = block.strip_prefix(&[0, 1])?;
+  let pad = rest.iter().position(|&b| b != 0xff)?;
+  (pad >= 8 && rest[pad] == 0).then(|| &rest[pad + 1..])
 }
 
 #[op2]
@@ -175,19 +536,26 @@ pub fn op_node_private_encrypt(
 
   let mut rng = rand::thread_rng();
   match padding {
-    1 => Ok(
+    RSA_PKCS1_PADDING => Ok(
       key
         .as_ref()
         .encrypt(&mut rng, Pkcs1v15Encrypt, &msg)?
         .into(),
     ),
-    4 => Ok(
+    RSA_NO_PADDING => Ok(
+      rsa_raw(&key, &msg, true, |m| {
+        rsa::hazmat::rsa_decrypt_and_check(&key, Some(&mut rng), m)
+      })?
+      .into(),
+    ),
+    RSA_PKCS1_OAEP_PADDING => Ok(
       key
         .as_ref()
         .encrypt(&mut rng, Oaep::new::<sha1::Sha1>(), &msg)?
         .into(),
     ),
-    _ => Err(PrivateEncryptDecryptError::UnknownPadding),
+    RSA_X931_PADDING => Err(PrivateEncryptDecryptError::InvalidX931Digest),
+    _ => Err(PrivateEncryptDecryptError::IllegalOrUnsupportedPaddingMode),
   }
 }
 
@@ -201,8 +569,20 @@ pub fn op_node_private_decrypt(
   let key = RsaPrivateKey::from_pkcs8_pem((&key).try_into()?)?;
 
   match padding {
-    1 => Ok(key.decrypt(Pkcs1v15Encrypt, &msg)?.into()),
-    4 => Ok(key.decrypt(Oaep::new::<sha1::Sha1>(), &msg)?.into()),
+    RSA_PKCS1_PADDING => Ok(key.decrypt(Pkcs1v15Encrypt, &msg)?.into()),
+    RSA_NO_PADDING => Ok(
+      rsa_raw(&key, &msg, false, |m| {
+        rsa::hazmat::rsa_decrypt_and_check(
+          &key,
+          Some(&mut rand::thread_rng()),
+          m,
+        )
+      })?
+      .into(),
+    ),
+    RSA_PKCS1_OAEP_PADDING => {
+      Ok(key.decrypt(Oaep::new::<sha1::Sha1>(), &msg)?.into())
+    }
     _ => Err(PrivateEncryptDecryptError::UnknownPadding),
   }
 }
@@ -218,8 +598,13 @@ pub fn op_node_public_encrypt(
 
   let mut rng = rand::thread_rng();
   match padding {
-    1 => Ok(key.encrypt(&mut rng, Pkcs1v15Encrypt, &msg)?.into()),
-    4 => Ok(
+    RSA_PKCS1_PADDING => {
+      Ok(key.encrypt(&mut rng, Pkcs1v15Encrypt, &msg)?.into())
+    }
+    RSA_NO_PADDING => Ok(
+      rsa_raw(&key, &msg, true, |m| rsa::hazmat::rsa_encrypt(&key, m))?.into(),
+    ),
+    RSA_PKCS1_OAEP_PADDING => Ok(
       key
         .encrypt(&mut rng, Oaep::new::<sha1::Sha1>(), &msg)?
         .into(),
@@ -228,6 +613,36 @@ pub fn op_node_public_encrypt(
   }
 }
 
+#[op2]
+#[serde]
+pub fn op_node_public_decrypt(
+  #[serde] key: StringOrBuffer,
+  #[serde] msg: StringOrBuffer,
+  #[smi] padding: u32,
+) -> Result<ToJsBuffer, PrivateEncryptDecryptError> {
+  let pem = (&key).try_into()?;
+  // Like OpenSSL, a private key can stand in for its public half.
+  let key = match RsaPublicKey::from_public_key_pem(pem) {
+    Ok(key) => key,
+    Err(_) => RsaPrivateKey::from_pkcs8_pem(pem)?.to_public_key(),
+  };
+
+  match padding {
+    RSA_PKCS1_PADDING => {
+      let block =
+        rsa_raw(&key, &msg, false, |m| rsa::hazmat::rsa_encrypt(&key, m))?;
+      rsa_pkcs1_type1_unpad(&block)
+        .map(|data| data.to_vec().into())
+        .ok_or(PrivateEncryptDecryptError::InvalidPadding)
+    }
+    RSA_NO_PADDING => Ok(
+      rsa_raw(&key, &msg, false, |m| rsa::hazmat::rsa_encrypt(&key, m))?.into(),
+    ),
+    RSA_X931_PADDING => Err(PrivateEncryptDecryptError::InvalidX931Digest),
+    _ => Err(PrivateEncryptDecryptError::IllegalOrUnsupportedPaddingMode),
+  }
+}
+
 #[op2(fast)]
 #[smi]
 pub fn op_node_create_cipheriv(
@@ -376,13 +791,15 @@ pub fn op_node_sign(
   #[cppgc] handle: &KeyObjectHandle,
   #[buffer] digest: &[u8],
   #[string] digest_type: &str,
-  #[smi] pss_salt_length: Option<u32>,
+  #[smi] pss_salt_length: Option<i32>,
+  #[string] pss_mgf1_hash: Option<String>,
   #[smi] dsa_signature_encoding: u32,
 ) -> Result<Box<[u8]>, sign::KeyObjectHandlePrehashedSignAndVerifyError> {
   handle.sign_prehashed(
     digest_type,
     digest,
     pss_salt_length,
+    pss_mgf1_hash.as_deref(),
     dsa_signature_encoding,
   )
 }
@@ -393,7 +810,8 @@ pub fn op_node_verify(
   #[buffer] digest: &[u8],
   #[string] digest_type: &str,
   #[buffer] signature: &[u8],
-  #[smi] pss_salt_length: Option<u32>,
+  #[smi] pss_salt_length: Option<i32>,
+  #[string] pss_mgf1_hash: Option<String>,
   #[smi] dsa_signature_encoding: u32,
 ) -> Result<bool, sign::KeyObjectHandlePrehashedSignAndVerifyError> {
   handle.verify_prehashed(
@@ -401,6 +819,58 @@ pub fn op_node_verify(
     digest,
     signature,
     pss_salt_length,
+    pss_mgf1_hash.as_deref(),
+    dsa_signature_encoding,
+  )
+}
+
+#[op2]
+#[buffer]
+pub fn op_node_sign_sm2(
+  #[cppgc] handle: &KeyObjectHandle,
+  #[buffer] data: &[u8],
+  #[string] digest_type: &str,
+  #[buffer] id: &[u8],
+  #[smi] dsa_signature_encoding: u32,
+) -> Result<Box<[u8]>, sign::KeyObjectHandlePrehashedSignAndVerifyError> {
+  handle.sign_sm2(digest_type, data, id, dsa_signature_encoding)
+}
+
+#[op2(fast)]
+pub fn op_node_verify_sm2(
+  #[cppgc] handle: &KeyObjectHandle,
+  #[buffer] data: &[u8],
+  #[string] digest_type: &str,
+  #[buffer] signature: &[u8],
+  #[buffer] id: &[u8],
+  #[smi] dsa_signature_encoding: u32,
+) -> Result<bool, sign::KeyObjectHandlePrehashedSignAndVerifyError> {
+  handle.verify_sm2(digest_type, data, signature, id, dsa_signature_encoding)
+}
+
+#[op2]
+#[buffer]
+pub fn op_node_sign_recoverable(
+  #[cppgc] handle: &KeyObjectHandle,
+  #[buffer] digest: &[u8],
+  low_s: bool,
+  #[smi] dsa_signature_encoding: u32,
+) -> Result<Box<[u8]>, sign::KeyObjectHandlePrehashedSignAndVerifyError> {
+  handle.sign_recoverable(digest, low_s, dsa_signature_encoding)
+}
+
+#[op2]
+#[buffer]
+pub fn op_node_recover_public_key(
+  #[buffer] digest: &[u8],
+  #[buffer] signature: &[u8],
+  #[smi] recovery_id: u8,
+  #[smi] dsa_signature_encoding: u32,
+) -> Result<Box<[u8]>, sign::KeyObjectHandlePrehashedSignAndVerifyError> {
+  sign::recover_secp256k1_public_key(
+    digest,
+    signature,
+    recovery_id,
     dsa_signature_encoding,
   )
 }
@@ -561,9 +1031,13 @@ fn hkdf_sync(
   match_fixed_digest_with_eager_block_buffer!(
     digest_algorithm,
     fn <D>() {
-      let hk = Hkdf::<D>::new(Some(salt), ikm);
-      hk.expand(info, okm)
-        .map_err(|_| HkdfError::HkdfExpandFailed)
+      let mut hk = Hkdf::<D>::new(Some(salt), ikm);
+      let result = hk.expand(info, okm)
+        .map_err(|_| HkdfError::HkdfExpandFailed);
+      // SAFETY: `Hkdf` holds the PRK as the key of an HMAC context over a
+      // plain digest context.
+      unsafe { wipe::wipe(&mut hk) };
+      result
     },
     _ => {
       Err(HkdfError::UnsupportedDigest(digest_algorithm.to_string()))
@@ -626,6 +1100,26 @@ pub fn op_node_random_int(#[number] min: i64, #[number] max: i64) -> i64 {
   dist.sample(&mut rng)
 }
 
+#[derive(Debug, thiserror::Error, deno_error::JsError)]
+#[class(range)]
+#[error("Input buffers must have the same byte length")]
+#[property("code" = "ERR_CRYPTO_TIMING_SAFE_EQUAL_LENGTH")]
+pub struct TimingSafeEqualLengthError;
+
+/// Compares the bytes of two buffers in constant time. Views are compared
+/// by their byte ranges, whatever their element type, and views over shared
+/// memory are read in place.
+#[op2]
+pub fn op_node_timing_safe_equal(
+  #[anybuffer] a: &[u8],
+  #[anybuffer] b: &[u8],
+) -> Result<bool, TimingSafeEqualLengthError> {
+  if a.len() != b.len() {
+    return Err(TimingSafeEqualLengthError);
+  }
+  Ok(aws_lc_rs::constant_time::verify_slices_are_equal(a, b).is_ok())
+}
+
 #[allow(clippy::too_many_arguments)]
 fn scrypt(
   password: StringOrBuffer,
@@ -992,6 +1486,9 @@ pub enum DiffieHellmanError {
   DhParametersMismatch,
   #[error("Unsupported key type for diffie hellman, or key type mismatch")]
   UnsupportedKeyTypeForDiffieHellmanOrKeyTypeMismatch,
+  #[class(generic)]
+  #[error("diffieHellman failed")]
+  DiffieHellmanFailed,
 }
 
 #[op2]
@@ -1039,6 +1536,26 @@ pub fn op_node_diffie_hellman(
       .raw_secret_bytes()
       .to_vec()
       .into_boxed_slice(),
+      (
+        AsymmetricPrivateKey::Ec(EcPrivateKey::P521(private)),
+        AsymmetricPublicKey::Ec(EcPublicKey::P521(public)),
+      ) => elliptic_curve::ecdh::diffie_hellman(
+        private.to_nonzero_scalar(),
+        public.as_affine(),
+      )
+      .raw_secret_bytes()
+      .to_vec()
+      .into_boxed_slice(),
+      (
+        AsymmetricPrivateKey::Ec(EcPrivateKey::Secp256k1(private)),
+        AsymmetricPublicKey::Ec(EcPublicKey::Secp256k1(public)),
+      ) => elliptic_curve::ecdh::diffie_hellman(
+        private.to_nonzero_scalar(),
+        public.as_affine(),
+      )
+      .raw_secret_bytes()
+      .to_vec()
+      .into_boxed_slice(),
       (
         AsymmetricPrivateKey::X25519(private),
         AsymmetricPublicKey::X25519(public),
@@ -1047,6 +1564,15 @@ pub fn op_node_diffie_hellman(
         .to_bytes()
         .into_iter()
         .collect(),
+      (
+        AsymmetricPrivateKey::X448(private),
+        AsymmetricPublicKey::X448(public),
+      ) => {
+        let secret = private
+          .diffie_hellman(public)
+          .ok_or(DiffieHellmanError::DiffieHellmanFailed)?;
+        secret.to_vec().into_boxed_slice()
+      }
       (AsymmetricPrivateKey::Dh(private), AsymmetricPublicKey::Dh(public)) => {
         if private.params.prime != public.params.prime
           || private.params.base != public.params.base
@@ -1064,7 +1590,12 @@ pub fn op_node_diffie_hellman(
         let private_key = BigUint::from_bytes_be(&private_key);
         let shared_secret = pubkey.modpow(&private_key, &prime);
 
-        shared_secret.to_bytes_be().into()
+        // Node.js pads the secret to the size of the prime.
+        let shared_secret = shared_secret.to_bytes_be();
+        let mut padded = vec![0; prime.bits().div_ceil(8)];
+        let offset = padded.len() - shared_secret.len();
+        padded[offset..].copy_from_slice(&shared_secret);
+        padded.into_boxed_slice()
       }
       _ => return Err(
         DiffieHellmanError::UnsupportedKeyTypeForDiffieHellmanOrKeyTypeMismatch,
@@ -1083,6 +1614,37 @@ pub enum SignEd25519Error {
   ExpectedEd25519PrivateKey,
   #[error("Invalid Ed25519 private key")]
   InvalidEd25519PrivateKey,
+  #[error(transparent)]
+  Dom2(#[from] Ed25519Dom2Error),
+}
+
+#[derive(Debug, thiserror::Error, deno_error::JsError)]
+#[class(type)]
+pub enum Ed25519Dom2Error {
+  #[error("Ed25519 context must be at most 255 bytes")]
+  ContextTooLong,
+  #[error("Ed25519ph expects a 64-byte SHA-512 digest")]
+  InvalidPrehash,
+}
+
+/// Picks Ed25519ph or Ed25519ctx over plain Ed25519, which is `None`.
+fn ed25519_dom2<'a>(
+  prehashed: bool,
+  context: &'a [u8],
+  data: &[u8],
+) -> Result<Option<ed25519::Dom2<'a>>, Ed25519Dom2Error> {
+  if context.len() > ed25519::MAX_CONTEXT_LENGTH {
+    return Err(Ed25519Dom2Error::ContextTooLong);
+  }
+  if prehashed && data.len() != ed25519::PREHASH_LENGTH {
+    return Err(Ed25519Dom2Error::InvalidPrehash);
+  }
+  // RFC 8032 calls for a context with Ed25519ctx, so without one the
+  // signature is a plain Ed25519 one.
+  Ok(
+    (prehashed || !context.is_empty())
+      .then_some(ed25519::Dom2 { prehashed, context }),
+  )
 }
 
 #[op2(fast)]
@@ -1090,6 +1652,8 @@ pub fn op_node_sign_ed25519(
   #[cppgc] key: &KeyObjectHandle,
   #[buffer] data: &[u8],
   #[buffer] signature: &mut [u8],
+  prehashed: bool,
+  #[buffer] context: &[u8],
 ) -> Result<(), SignEd25519Error> {
   let private = key
     .as_private_key()
@@ -1100,6 +1664,11 @@ pub fn op_node_sign_ed25519(
     _ => return Err(SignEd25519Error::ExpectedEd25519PrivateKey),
   };
 
+  if let Some(dom2) = ed25519_dom2(prehashed, context, data)? {
+    signature.copy_from_slice(&ed25519::sign(ed25519, dom2, data));
+    return Ok(());
+  }
+
   let pair = Ed25519KeyPair::from_seed_unchecked(ed25519.as_bytes().as_slice())
     .map_err(|_| SignEd25519Error::InvalidEd25519PrivateKey)?;
   signature.copy_from_slice(pair.sign(data).as_ref());
@@ -1114,6 +1683,8 @@ pub enum VerifyEd25519Error {
   ExpectedPublicKey,
   #[error("Expected Ed25519 public key")]
   ExpectedEd25519PublicKey,
+  #[error(transparent)]
+  Dom2(#[from] Ed25519Dom2Error),
 }
 
 #[op2(fast)]
@@ -1121,6 +1692,8 @@ pub fn op_node_verify_ed25519(
   #[cppgc] key: &KeyObjectHandle,
   #[buffer] data: &[u8],
   #[buffer] signature: &[u8],
+  prehashed: bool,
+  #[buffer] context: &[u8],
 ) -> Result<bool, VerifyEd25519Error> {
   let public = key
     .as_public_key()
@@ -1131,6 +1704,10 @@ pub fn op_node_verify_ed25519(
     _ => return Err(VerifyEd25519Error::ExpectedEd25519PublicKey),
   };
 
+  if let Some(dom2) = ed25519_dom2(prehashed, context, data)? {
+    return Ok(ed25519::verify(ed25519, dom2, data, signature));
+  }
+
   let verified = aws_lc_rs::signature::UnparsedPublicKey::new(
     &aws_lc_rs::signature::ED25519,
     ed25519.as_bytes().as_slice(),
@@ -1141,6 +1718,265 @@ pub fn op_node_verify_ed25519(
   Ok(verified)
 }
 
+#[derive(Debug, thiserror::Error, deno_error::JsError)]
+#[class(type)]
+pub enum SignEd448Error {
+  #[error("Expected private key")]
+  ExpectedPrivateKey,
+  #[error("Expected Ed448 private key")]
+  ExpectedEd448PrivateKey,
+}
+
+#[op2(fast)]
+pub fn op_node_sign_ed448(
+  #[cppgc] key: &KeyObjectHandle,
+  #[buffer] data: &[u8],
+  #[buffer] signature: &mut [u8],
+) -> Result<(), SignEd448Error> {
+  let private = key
+    .as_private_key()
+    .ok_or(SignEd448Error::ExpectedPrivateKey)?;
+
+  let ed448 = match private {
+    AsymmetricPrivateKey::Ed448(private) => private,
+    _ => return Err(SignEd448Error::ExpectedEd448PrivateKey),
+  };
+
+  signature.copy_from_slice(&ed448.sign(data));
+
+  Ok(())
+}
+
+#[derive(Debug, thiserror::Error, deno_error::JsError)]
+#[class(type)]
+pub enum VerifyEd448Error {
+  #[error("Expected public key")]
+  ExpectedPublicKey,
+  #[error("Expected Ed448 public key")]
+  ExpectedEd448PublicKey,
+}
+
+#[op2(fast)]
+pub fn op_node_verify_ed448(
+  #[cppgc] key: &KeyObjectHandle,
+  #[buffer] data: &[u8],
+  #[buffer] signature: &[u8],
+) -> Result<bool, VerifyEd448Error> {
+  let public = key
+    .as_public_key()
+    .ok_or(VerifyEd448Error::ExpectedPublicKey)?;
+
+  let ed448 = match &*public {
+    AsymmetricPublicKey::Ed448(public) => public,
+    _ => return Err(VerifyEd448Error::ExpectedEd448PublicKey),
+  };
+
+  Ok(ed448.verify(data, signature))
+}
+
+#[derive(Debug, thiserror::Error, deno_error::JsError)]
+#[class(type)]
+pub enum SignMlDsaError {
+  #[error("Expected private key")]
+  ExpectedPrivateKey,
+  #[error("Expected ML-DSA private key")]
+  ExpectedMlDsaPrivateKey,
+  #[error("ML-DSA context must be at most 255 bytes")]
+  ContextTooLong,
+}
+
+#[op2]
+#[buffer]
+pub fn op_node_sign_ml_dsa(
+  #[cppgc] key: &KeyObjectHandle,
+  #[buffer] data: &[u8],
+  #[buffer] context: &[u8],
+) -> Result<Box<[u8]>, SignMlDsaError> {
+  let private = key
+    .as_private_key()
+    .ok_or(SignMlDsaError::ExpectedPrivateKey)?;
+
+  let ml_dsa = match private {
+    AsymmetricPrivateKey::MlDsa(private) => private,
+    _ => return Err(SignMlDsaError::ExpectedMlDsaPrivateKey),
+  };
+
+  let signature = ml_dsa
+    .sign(data, context)
+    .ok_or(SignMlDsaError::ContextTooLong)?;
+
+  Ok(signature.into_boxed_slice())
+}
+
+#[derive(Debug, thiserror::Error, deno_error::JsError)]
+#[class(type)]
+pub enum VerifyMlDsaError {
+  #[error("Expected public key")]
+  ExpectedPublicKey,
+  #[error("Expected ML-DSA public key")]
+  ExpectedMlDsaPublicKey,
+  #[error("ML-DSA context must be at most 255 bytes")]
+  ContextTooLong,
+}
+
+#[op2(fast)]
+pub fn op_node_verify_ml_dsa(
+  #[cppgc] key: &KeyObjectHandle,
+  #[buffer] data: &[u8],
+  #[buffer] signature: &[u8],
+  #[buffer] context: &[u8],
+) -> Result<bool, VerifyMlDsaError> {
+  let public = key
+    .as_public_key()
+    .ok_or(VerifyMlDsaError::ExpectedPublicKey)?;
+
+  let ml_dsa = match &*public {
+    AsymmetricPublicKey::MlDsa(public) => public,
+    _ => return Err(VerifyMlDsaError::ExpectedMlDsaPublicKey),
+  };
+
+  if context.len() > ml_dsa::MAX_CONTEXT_LENGTH {
+    return Err(VerifyMlDsaError::ContextTooLong);
+  }
+
+  Ok(ml_dsa.verify(data, context, signature))
+}
+
+#[derive(Debug, thiserror::Error, deno_error::JsError)]
+#[class(type)]
+pub enum EncapsulateError {
+  #[error("Expected public key")]
+  ExpectedPublicKey,
+  #[error("Expected ML-KEM public key")]
+  ExpectedMlKemPublicKey,
+}
+
+/// Returns the shared secret and the ciphertext. A private key stands in
+/// for its public half.
+#[op2]
+#[serde]
+pub fn op_node_encapsulate(
+  #[cppgc] key: &KeyObjectHandle,
+) -> Result<(ToJsBuffer, ToJsBuffer), EncapsulateError> {
+  let public = key
+    .as_public_key()
+    .ok_or(EncapsulateError::ExpectedPublicKey)?;
+
+  let ml_kem = match &*public {
+    AsymmetricPublicKey::MlKem(public) => public,
+    _ => return Err(EncapsulateError::ExpectedMlKemPublicKey),
+  };
+
+  let (shared_secret, ciphertext) = ml_kem.encapsulate();
+  Ok((
+    shared_secret.to_vec().into_boxed_slice().into(),
+    ciphertext.into_boxed_slice().into(),
+  ))
+}
+
+#[derive(Debug, thiserror::Error, deno_error::JsError)]
+#[class(type)]
+pub enum DecapsulateError {
+  #[error("Expected private key")]
+  ExpectedPrivateKey,
+  #[error("Expected ML-KEM private key")]
+  ExpectedMlKemPrivateKey,
+  #[error("ML-KEM ciphertext must be 1088 bytes")]
+  InvalidCiphertextLength,
+}
+
+/// A ciphertext that wasn't produced for the key gives the implicit
+/// rejection secret of FIPS 203 rather than an error.
+#[op2]
+#[buffer]
+pub fn op_node_decapsulate(
+  #[cppgc] key: &KeyObjectHandle,
+  #[buffer] ciphertext: &[u8],
+) -> Result<Box<[u8]>, DecapsulateError> {
+  let private = key
+    .as_private_key()
+    .ok_or(DecapsulateError::ExpectedPrivateKey)?;
+
+  let ml_kem = match private {
+    AsymmetricPrivateKey::MlKem(private) => private,
+    _ => return Err(DecapsulateError::ExpectedMlKemPrivateKey),
+  };
+
+  let shared_secret = ml_kem
+    .decapsulate(ciphertext)
+    .ok_or(DecapsulateError::InvalidCiphertextLength)?;
+
+  Ok(shared_secret.to_vec().into_boxed_slice())
+}
+
+#[derive(Debug, thiserror::Error, deno_error::JsError)]
+#[class(type)]
+pub enum SignSlhDsaError {
+  #[error("Expected private key")]
+  ExpectedPrivateKey,
+  #[error("Expected SLH-DSA private key")]
+  ExpectedSlhDsaPrivateKey,
+  #[error("SLH-DSA context must be at most 255 bytes")]
+  ContextTooLong,
+}
+
+#[op2]
+#[buffer]
+pub fn op_node_sign_slh_dsa(
+  #[cppgc] key: &KeyObjectHandle,
+  #[buffer] data: &[u8],
+  #[buffer] context: &[u8],
+) -> Result<Box<[u8]>, SignSlhDsaError> {
+  let private = key
+    .as_private_key()
+    .ok_or(SignSlhDsaError::ExpectedPrivateKey)?;
+
+  let slh_dsa = match private {
+    AsymmetricPrivateKey::SlhDsa(private) => private,
+    _ => return Err(SignSlhDsaError::ExpectedSlhDsaPrivateKey),
+  };
+
+  let signature = slh_dsa
+    .sign(data, context)
+    .ok_or(SignSlhDsaError::ContextTooLong)?;
+
+  Ok(signature.into_boxed_slice())
+}
+
+#[derive(Debug, thiserror::Error, deno_error::JsError)]
+#[class(type)]
+pub enum VerifySlhDsaError {
+  #[error("Expected public key")]
+  ExpectedPublicKey,
+  #[error("Expected SLH-DSA public key")]
+  ExpectedSlhDsaPublicKey,
+  #[error("SLH-DSA context must be at most 255 bytes")]
+  ContextTooLong,
+}
+
+#[op2(fast)]
+pub fn op_node_verify_slh_dsa(
+  #[cppgc] key: &KeyObjectHandle,
+  #[buffer] data: &[u8],
+  #[buffer] signature: &[u8],
+  #[buffer] context: &[u8],
+) -> Result<bool, VerifySlhDsaError> {
+  let public = key
+    .as_public_key()
+    .ok_or(VerifySlhDsaError::ExpectedPublicKey)?;
+
+  let slh_dsa = match &*public {
+    AsymmetricPublicKey::SlhDsa(public) => public,
+    _ => return Err(VerifySlhDsaError::ExpectedSlhDsaPublicKey),
+  };
+
+  if context.len() > slh_dsa::MAX_CONTEXT_LENGTH {
+    return Err(VerifySlhDsaError::ContextTooLong);
+  }
+
+  Ok(slh_dsa.verify(data, context, signature))
+}
+
 #[derive(Debug, thiserror::Error, deno_error::JsError)]
 pub enum SpkacError {
   #[error("spkac is too large")]
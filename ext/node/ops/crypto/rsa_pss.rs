@@ -0,0 +1,398 @@
+// Copyright 2018-2025 the Deno authors. MIT license.
+
+//! RSASSA-PSS (RFC 8017, section 8.1) over a precomputed digest.
+//! `rsa::pss` signs and verifies when MGF1 uses the message hash and the
+//! salt length is known. It can't take a different MGF1 hash, which keys
+//! with RSASSA-PSS-params often have (SHA-1 is its default), or recover
+//! the salt length for `RSA_PSS_SALTLEN_AUTO`, so EMSA-PSS is written out
+//! here for those cases.
+
+use digest::Digest;
+use digest::FixedOutputReset;
+use rand::CryptoRng;
+use rand::RngCore;
+use rand::rngs::OsRng;
+use rsa::BigUint;
+use rsa::RsaPrivateKey;
+use rsa::RsaPublicKey;
+use rsa::signature;
+use rsa::signature::SignatureEncoding as _;
+use rsa::signature::hazmat::PrehashVerifier as _;
+use rsa::signature::hazmat::RandomizedPrehashSigner as _;
+use rsa::traits::PublicKeyParts;
+use zeroize::Zeroizing;
+
+use super::keys::RsaPssHashAlgorithm;
+
+/// The longest salt for a key and digest, from EMSA-PSS-ENCODE in
+/// section 9.1.1.
+pub fn max_salt_length(key: &impl PublicKeyParts, hash_len: usize) -> usize {
+  encoded_len(key).saturating_sub(hash_len + 2)
+}
+
+fn encoded_bits(key: &impl PublicKeyParts) -> usize {
+  key.n().bits() - 1
+}
+
+fn encoded_len(key: &impl PublicKeyParts) -> usize {
+  encoded_bits(key).div_ceil(8)
+}
+
+fn mgf1_xor_with<D: Digest>(out: &mut [u8], seed: &[u8]) {
+  for (counter, chunk) in
+    out.chunks_mut(<D as Digest>::output_size()).enumerate()
+  {
+    let mask = D::new()
+      .chain_update(seed)
+      .chain_update((counter as u32).to_be_bytes())
+      .finalize();
+    for (byte, mask) in chunk.iter_mut().zip(mask) {
+      *byte ^= mask;
+    }
+  }
+}
+
+/// MGF1 with `mgf1`, or the message hash `D` if that is `None`.
+fn mgf1_xor<D: Digest>(
+  mgf1: Option<RsaPssHashAlgorithm>,
+  out: &mut [u8],
+  seed: &[u8],
+) {
+  match mgf1 {
+    None => mgf1_xor_with::<D>(out, seed),
+    Some(RsaPssHashAlgorithm::Sha1) => mgf1_xor_with::<sha1::Sha1>(out, seed),
+    Some(RsaPssHashAlgorithm::Sha224) => {
+      mgf1_xor_with::<sha2::Sha224>(out, seed)
+    }
+    Some(RsaPssHashAlgorithm::Sha256) => {
+      mgf1_xor_with::<sha2::Sha256>(out, seed)
+    }
+    Some(RsaPssHashAlgorithm::Sha384) => {
+      mgf1_xor_with::<sha2::Sha384>(out, seed)
+    }
+    Some(RsaPssHashAlgorithm::Sha512) => {
+      mgf1_xor_with::<sha2::Sha512>(out, seed)
+    }
+    Some(RsaPssHashAlgorithm::Sha512_224) => {
+      mgf1_xor_with::<sha2::Sha512_224>(out, seed)
+    }
+    Some(RsaPssHashAlgorithm::Sha512_256) => {
+      mgf1_xor_with::<sha2::Sha512_256>(out, seed)
+    }
+  }
+}
+
+/// `H = Hash(0x00 * 8 || mHash || salt)`.
+fn hash_with_salt<D: Digest>(digest: &[u8], salt: &[u8]) -> Vec<u8> {
+  D::new()
+    .chain_update([0; 8])
+    .chain_update(digest)
+    .chain_update(salt)
+    .finalize()
+    .to_vec()
+}
+
+/// `mgf1` is `None` when MGF1 uses the message hash `D`.
+pub fn sign<D: Digest + FixedOutputReset>(
+  key: &RsaPrivateKey,
+  mgf1: Option<RsaPssHashAlgorithm>,
+  digest: &[u8],
+  salt_length: usize,
+) -> signature::Result<Vec<u8>> {
+  sign_with_rng::<D>(&mut OsRng, key, mgf1, digest, salt_length)
+}
+
+fn sign_with_rng<D: Digest + FixedOutputReset>(
+  rng: &mut (impl CryptoRng + RngCore),
+  key: &RsaPrivateKey,
+  mgf1: Option<RsaPssHashAlgorithm>,
+  digest: &[u8],
+  salt_length: usize,
+) -> signature::Result<Vec<u8>> {
+  if mgf1.is_none() {
+    let signing_key =
+      rsa::pss::SigningKey::<D>::new_with_salt_len(key.clone(), salt_length);
+    return Ok(signing_key.sign_prehash_with_rng(rng, digest)?.to_vec());
+  }
+
+  let hash_len = <D as Digest>::output_size();
+  let em_bits = encoded_bits(key);
+  let em_len = encoded_len(key);
+  if digest.len() != hash_len || em_len < hash_len + salt_length + 2 {
+    return Err(signature::Error::new());
+  }
+
+  let mut salt = vec![0; salt_length];
+  rng.fill_bytes(&mut salt);
+  let h = hash_with_salt::<D>(digest, &salt);
+
+  // EM = maskedDB || H || 0xbc, where DB = PS || 0x01 || salt.
+  let mut em = Zeroizing::new(vec![0; em_len]);
+  let (db, rest) = em.split_at_mut(em_len - hash_len - 1);
+  let separator = db.len() - salt_length - 1;
+  db[separator] = 0x01;
+  db[separator + 1..].copy_from_slice(&salt);
+  mgf1_xor::<D>(mgf1, db, &h);
+  db[0] &= 0xff >> (8 * em_len - em_bits);
+  rest[..hash_len].copy_from_slice(&h);
+  rest[hash_len] = 0xbc;
+
+  let signature = rsa::hazmat::rsa_decrypt_and_check(
+    key,
+    Some(rng),
+    &BigUint::from_bytes_be(&em),
+  )
+  .map_err(signature::Error::from_source)?
+  .to_bytes_be();
+  let mut padded = vec![0; key.size()];
+  padded[key.size() - signature.len()..].copy_from_slice(&signature);
+  Ok(padded)
+}
+
+/// Checks the salt length if one is given, and recovers it from the
+/// encoded message otherwise, for `RSA_PSS_SALTLEN_AUTO`. `mgf1` is `None`
+/// when MGF1 uses the message hash `D`.
+pub fn verify<D: Digest + FixedOutputReset>(
+  key: &RsaPublicKey,
+  mgf1: Option<RsaPssHashAlgorithm>,
+  digest: &[u8],
+  signature: &[u8],
+  salt_length: Option<usize>,
+) -> bool {
+  if signature.len() != key.size() {
+    return false;
+  }
+  if let (None, Some(salt_length)) = (mgf1, salt_length) {
+    let Ok(signature) = rsa::pss::Signature::try_from(signature) else {
+      return false;
+    };
+    return rsa::pss::VerifyingKey::<D>::new_with_salt_len(
+      key.clone(),
+      salt_length,
+    )
+    .verify_prehash(digest, &signature)
+    .is_ok();
+  }
+
+  let hash_len = <D as Digest>::output_size();
+  let em_bits = encoded_bits(key);
+  let em_len = encoded_len(key);
+  if digest.len() != hash_len || em_len < hash_len + 2 {
+    return false;
+  }
+  let Ok(em) =
+    rsa::hazmat::rsa_encrypt(key, &BigUint::from_bytes_be(signature))
+  else {
+    return false;
+  };
+  let em = em.to_bytes_be();
+  if em.len() > em_len {
+    return false;
+  }
+  let mut padded = vec![0; em_len];
+  padded[em_len - em.len()..].copy_from_slice(&em);
+  if padded[em_len - 1] != 0xbc
+    || padded[0] & !(0xff >> (8 * em_len - em_bits)) != 0
+  {
+    return false;
+  }
+
+  let (db, rest) = padded.split_at_mut(em_len - hash_len - 1);
+  let h = &rest[..hash_len];
+  mgf1_xor::<D>(mgf1, db, h);
+  db[0] &= 0xff >> (8 * em_len - em_bits);
+  // DB is zeros, 0x01, then the salt.
+  let Some(separator) = db.iter().position(|byte| *byte != 0) else {
+    return false;
+  };
+  let salt = &db[separator + 1..];
+  if db[separator] != 0x01
+    || salt_length.is_some_and(|length| length != salt.len())
+  {
+    return false;
+  }
+  hash_with_salt::<D>(digest, salt) == h
+}
+
+#[cfg(test)]
+mod tests {
+  use rsa::traits::SignatureScheme;
+
+  use super::*;
+
+  fn unhex(hex: &str) -> Vec<u8> {
+    let mut bytes = vec![0; hex.len() / 2];
+    faster_hex::hex_decode(hex.as_bytes(), &mut bytes).unwrap();
+    bytes
+  }
+
+  /// Returns the salt on the first call, then random bytes for blinding.
+  struct SaltRng<'a>(Option<&'a [u8]>);
+
+  impl RngCore for SaltRng<'_> {
+    fn next_u32(&mut self) -> u32 {
+      OsRng.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+      OsRng.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+      match self.0.take() {
+        Some(salt) => dest.copy_from_slice(salt),
+        None => OsRng.fill_bytes(dest),
+      }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+      self.fill_bytes(dest);
+      Ok(())
+    }
+  }
+
+  impl CryptoRng for SaltRng<'_> {}
+
+  struct Vector {
+    p: &'static str,
+    q: &'static str,
+    message: &'static str,
+    salt: &'static str,
+    signature: &'static str,
+  }
+
+  // pss-vect.txt from the RSA PKCS #1 v2.1 test vectors: examples 1.1,
+  // 2.1 (a 1025-bit modulus) and 10.1, with SHA-1 for the message and
+  // MGF1, and the exponent 65537.
+  const VECTORS: [Vector; 3] = [
+    Vector {
+      p: "e7e8942720a877517273a356053ea2a1bc0c94aa72d55c6e86296b2dfc967948c0a72cbccca7eacb35706e09a1df55a1535bd9b3cc34160b3b6dcd3eda8e6443",
+      q: "b69dca1cf7d4d7ec81e75b90fcca874abcde123fd2700180aa90479b6e48de8d67ed24f9f19d85ba275874f542cd20dc723e6963364a1f9425452b269a6799fd",
+      message: "cdc87da223d786df3b45e0bbbc721326d1ee2af806cc315475cc6f0d9c66e1b62371d45ce2392e1ac92844c310102f156a0d8d52c1f4c40ba3aa65095786cb769757a6563ba958fed0bcc984e8b517a3d5f515b23b8a41e74aa867693f90dfb061a6e86dfaaee64472c00e5f20945729cbebe77f06ce78e08f4098fba41f9d6193c0317e8b60d4b6084acb42d29e3808a3bc372d85e331170fcbf7cc72d0b71c296648b3a4d10f416295d0807aa625cab2744fd9ea8fd223c42537029828bd16be02546f130fd2e33b936d2676e08aed1b73318b750a0167d0",
+      salt: "dee959c7e06411361420ff80185ed57f3e6776af",
+      signature: "9074308fb598e9701b2294388e52f971faac2b60a5145af185df5287b5ed2887e57ce7fd44dc8634e407c8e0e4360bc226f3ec227f9d9e54638e8d31f5051215df6ebb9c2f9579aa77598a38f914b5b9c1bd83c4e2f9f382a0d0aa3542ffee65984a601bc69eb28deb27dca12c82c2d4c3f66cd500f1ff2b994d8a4e30cbb33c",
+    },
+    Vector {
+      p: "016601e926a0f8c9e26ecab769ea65a5e7c52cc9e080ef519457c644da6891c5a104d3ea7955929a22e7c68a7af9fcad777c3ccc2b9e3d3650bce404399b7e59d1",
+      q: "014eafa1d4d0184da7e31f877d1281ddda625664869e8379e67ad3b75eae74a580e9827abd6eb7a002cb5411f5266797768fb8e95ae40e3e8a01f35ff89e56c079",
+      message: "daba032066263faedb659848115278a52c44faa3a76f37515ed336321072c40a9d9b53bc05014078adf520875146aae70ff060226dcb7b1f1fc27e9360",
+      salt: "57bf160bcb02bb1dc7280cf0458530b7d2832ff7",
+      signature: "014c5ba5338328ccc6e7a90bf1c0ab3fd606ff4796d3c12e4b639ed9136a5fec6c16d8884bdd99cfdc521456b0742b736868cf90de099adb8d5ffd1deff39ba4007ab746cefdb22d7df0e225f54627dc65466131721b90af445363a8358b9f607642f78fab0ab0f43b7168d64bae70d8827848d8ef1e421c5754ddf42c2589b5b3",
+    },
+    Vector {
+      p: "cfd50283feeeb97f6f08d73cbc7b3836f82bbcd499479f5e6f76fdfcb8b38c4f71dc9e88bd6a6f76371afd65d2af1862b32afb34a95f71b8b132043ffebe3a952baf7592448148c03f9c69b1d68e4ce5cf32c86baf46fed301ca1ab403069b32f456b91f71898ab081cd8c4252ef5271915c9794b8f295851da7510f99cb73eb",
+      q: "cc4e90d2a1b3a065d3b2d1f5a8fce31b544475664eab561d2971b99fb7bef844e8ec1f360b8c2ac8359692971ea6a38f723fcc211f5dbcb177a0fdac5164a1d4ff7fbb4e829986353cb983659a148cdd420c7d31ba3822ea90a32be46c030e8c17e1fa0ad37859e06b0aa6fa3b216d9cbe6c0e22339769c0a615913e5da719cf",
+      message: "883177e5126b9be2d9a9680327d5370c6f26861f5820c43da67a3ad609",
+      salt: "04e215ee6ff934b9da70d7730c8734abfcecde89",
+      signature: "82c2b160093b8aa3c0f7522b19f87354066c77847abf2a9fce542d0e84e920c5afb49ffdfdace16560ee94a1369601148ebad7a0e151cf16331791a5727d05f21e74e7eb811440206935d744765a15e79f015cb66c532c87a6a05961c8bfad741a9a6657022894393e7223739796c02a77455d0f555b0ec01ddf259b6207fd0fd57614cef1a5573baaff4ec00069951659b85f24300a25160ca8522dc6e6727e57d019d7e63629b8fe5e89e25cc15beb3a647577559299280b9b28f79b0409000be25bbd96408ba3b43cc486184dd1c8e62553fa1af4040f60663de7f5e49c04388e257f1ce89c95dab48a315d9b66b1b7628233876ff2385230d070d07e1666",
+    },
+  ];
+
+  #[test]
+  fn pss_vect() {
+    for vector in VECTORS {
+      let key = RsaPrivateKey::from_p_q(
+        BigUint::from_bytes_be(&unhex(vector.p)),
+        BigUint::from_bytes_be(&unhex(vector.q)),
+        BigUint::from(65537u32),
+      )
+      .unwrap();
+      let public_key = key.to_public_key();
+      let digest = sha1::Sha1::digest(unhex(vector.message));
+      let salt = unhex(vector.salt);
+      let expected = unhex(vector.signature);
+
+      // Through `rsa::pss`, and through EMSA-PSS here with SHA-1 given as
+      // the MGF1 hash.
+      for mgf1 in [None, Some(RsaPssHashAlgorithm::Sha1)] {
+        let signature = sign_with_rng::<sha1::Sha1>(
+          &mut SaltRng(Some(&salt)),
+          &key,
+          mgf1,
+          &digest,
+          20,
+        )
+        .unwrap();
+        assert_eq!(signature, expected);
+        for salt_length in [Some(20), None] {
+          assert!(verify::<sha1::Sha1>(
+            &public_key,
+            mgf1,
+            &digest,
+            &expected,
+            salt_length
+          ));
+        }
+        assert!(!verify::<sha1::Sha1>(
+          &public_key,
+          mgf1,
+          &digest,
+          &expected,
+          Some(19)
+        ));
+        assert!(!verify::<sha1::Sha1>(
+          &public_key,
+          mgf1,
+          &digest,
+          &expected[1..],
+          Some(20)
+        ));
+      }
+    }
+  }
+
+  #[test]
+  fn sign_and_verify() {
+    let key = RsaPrivateKey::new(&mut OsRng, 1025).unwrap();
+    let public_key = key.to_public_key();
+    let digest = sha2::Sha256::digest(b"data");
+    for (mgf1, salt_length) in [
+      (None, 32),
+      (Some(RsaPssHashAlgorithm::Sha1), 0),
+      (Some(RsaPssHashAlgorithm::Sha512), max_salt_length(&key, 32)),
+    ] {
+      let signature =
+        sign::<sha2::Sha256>(&key, mgf1, &digest, salt_length).unwrap();
+      assert!(verify::<sha2::Sha256>(
+        &public_key,
+        mgf1,
+        &digest,
+        &signature,
+        Some(salt_length)
+      ));
+      assert!(verify::<sha2::Sha256>(
+        &public_key,
+        mgf1,
+        &digest,
+        &signature,
+        None
+      ));
+      assert!(!verify::<sha2::Sha256>(
+        &public_key,
+        mgf1,
+        &digest,
+        &signature,
+        Some(salt_length + 1)
+      ));
+      assert!(!verify::<sha2::Sha256>(
+        &public_key,
+        Some(RsaPssHashAlgorithm::Sha384),
+        &digest,
+        &signature,
+        None
+      ));
+    }
+
+    // EMSA-PSS here with SHA-256 as the MGF1 hash matches `rsa::pss`.
+    let signature = sign::<sha2::Sha256>(
+      &key,
+      Some(RsaPssHashAlgorithm::Sha256),
+      &digest,
+      20,
+    )
+    .unwrap();
+    rsa::pss::Pss::new_with_salt::<sha2::Sha256>(20)
+      .verify(&public_key, &digest, &signature)
+      .unwrap();
+  }
+}
@@ -1,12 +1,25 @@
 // Copyright 2018-2025 the Deno authors. MIT license.
 use core::ops::Add;
 
+use digest::Digest;
+use digest::FixedOutputReset;
+use digest::core_api::BlockSizeUser;
 use ecdsa::der::MaxOverhead;
 use ecdsa::der::MaxSize;
+use ecdsa::hazmat::VerifyPrimitive;
+use elliptic_curve::AffinePoint;
+use elliptic_curve::CurveArithmetic;
+use elliptic_curve::FieldBytes;
+use elliptic_curve::FieldBytesEncoding as _;
 use elliptic_curve::FieldBytesSize;
+use elliptic_curve::PrimeCurve;
+use elliptic_curve::Scalar;
+use elliptic_curve::ff::Field as _;
+use elliptic_curve::ff::PrimeField as _;
 use elliptic_curve::generic_array::ArrayLength;
+use elliptic_curve::ops::Reduce as _;
 use rand::rngs::OsRng;
-use rsa::signature::hazmat::PrehashSigner as _;
+use rsa::pkcs1v15::Pkcs1v15Sign;
 use rsa::signature::hazmat::PrehashVerifier as _;
 use rsa::traits::SignatureScheme as _;
 use spki::der::Decode;
@@ -16,9 +29,13 @@ use super::keys::AsymmetricPublicKey;
 use super::keys::EcPrivateKey;
 use super::keys::EcPublicKey;
 use super::keys::KeyObjectHandle;
+use super::keys::RsaPssDetails;
 use super::keys::RsaPssHashAlgorithm;
+use super::rsa_pss;
+use super::sm2;
 use crate::ops::crypto::digest::match_fixed_digest;
 use crate::ops::crypto::digest::match_fixed_digest_with_oid;
+use crate::ops::crypto::digest::resolve_legacy_alias;
 
 fn dsa_signature<C: elliptic_curve::PrimeCurve>(
   encoding: u32,
@@ -39,6 +56,201 @@ where
   }
 }
 
+/// `None` for a signature that doesn't parse, which fails verification
+/// rather than throwing. A P1363 signature must be exactly twice the field
+/// size.
+fn parse_dsa_signature<C: elliptic_curve::PrimeCurve>(
+  encoding: u32,
+  signature: &[u8],
+) -> Result<
+  Option<ecdsa::Signature<C>>,
+  KeyObjectHandlePrehashedSignAndVerifyError,
+>
+where
+  MaxSize<C>: ArrayLength<u8>,
+  <FieldBytesSize<C> as Add>::Output: Add<MaxOverhead> + ArrayLength<u8>,
+{
+  match encoding {
+    0 => Ok(ecdsa::Signature::from_der(signature).ok()),
+    1 => Ok(ecdsa::Signature::from_slice(signature).ok()),
+    _ => Err(
+      KeyObjectHandlePrehashedSignAndVerifyError::InvalidDsaSignatureEncoding,
+    ),
+  }
+}
+
+/// The digest as a field element. Unlike `ecdsa::hazmat::bits2field`, a
+/// digest shorter than half the field is allowed, as in OpenSSL.
+fn ecdsa_field_bytes<C: PrimeCurve>(digest: &[u8]) -> FieldBytes<C> {
+  let mut field = FieldBytes::<C>::default();
+  let len = field.len().min(digest.len());
+  let offset = field.len() - len;
+  field[offset..].copy_from_slice(&digest[..len]);
+  field
+}
+
+/// The nonce of RFC 6979 section 3.2, with `D` as the HMAC digest.
+fn rfc6979_nonce<C, D>(
+  private_key: &FieldBytes<C>,
+  digest: &FieldBytes<C>,
+) -> Scalar<C>
+where
+  C: PrimeCurve + CurveArithmetic,
+  D: Digest + BlockSizeUser + FixedOutputReset,
+{
+  let order = C::ORDER.encode_field_bytes();
+  // bits2int keeps the leftmost qlen bits, which is more than a byte
+  // truncation for P-521.
+  let shift = order[0].leading_zeros();
+  let mut hmac_drbg = rfc6979::HmacDrbg::<D>::new(private_key, digest, &[]);
+  loop {
+    let mut nonce = FieldBytes::<C>::default();
+    hmac_drbg.fill_bytes(&mut nonce);
+    if shift > 0 {
+      for i in (1..nonce.len()).rev() {
+        nonce[i] = (nonce[i] >> shift) | (nonce[i - 1] << (8 - shift));
+      }
+      nonce[0] >>= shift;
+    }
+    let nonce: Option<Scalar<C>> = Scalar::<C>::from_repr(nonce).into();
+    if let Some(nonce) = nonce.filter(|nonce| !bool::from(nonce.is_zero())) {
+      return nonce;
+    }
+  }
+}
+
+/// Deterministic ECDSA as in RFC 6979, with the named digest for HMAC, so
+/// that the same key and digest always give the same signature. `F` is the
+/// HMAC digest when the name isn't known.
+fn ecdsa_sign_prehashed<C, F>(
+  key: &elliptic_curve::SecretKey<C>,
+  digest_type: &str,
+  digest: &[u8],
+) -> Result<ecdsa::Signature<C>, KeyObjectHandlePrehashedSignAndVerifyError>
+where
+  C: PrimeCurve + CurveArithmetic,
+  ecdsa::SignatureSize<C>: ArrayLength<u8>,
+  F: Digest + BlockSizeUser + FixedOutputReset,
+{
+  let z = ecdsa_field_bytes::<C>(digest);
+  let reduced = Scalar::<C>::reduce_bytes(&z).into();
+  let private_key = key.to_bytes();
+  let nonce = match_fixed_digest!(
+    digest_type,
+    fn <D>() {
+      rfc6979_nonce::<C, D>(&private_key, &reduced)
+    },
+    _ => {
+      rfc6979_nonce::<C, F>(&private_key, &reduced)
+    }
+  );
+  ecdsa::hazmat::sign_prehashed::<C, _>(&key.to_nonzero_scalar(), nonce, &z)
+    .map(|(signature, _)| signature)
+    .map_err(|_| KeyObjectHandlePrehashedSignAndVerifyError::FailedToSignDigest)
+}
+
+fn ecdsa_verify_prehashed<C>(
+  key: &elliptic_curve::PublicKey<C>,
+  digest: &[u8],
+  signature: &ecdsa::Signature<C>,
+) -> bool
+where
+  C: PrimeCurve + CurveArithmetic,
+  ecdsa::SignatureSize<C>: ArrayLength<u8>,
+  AffinePoint<C>: VerifyPrimitive<C>,
+{
+  key
+    .as_affine()
+    .verify_prehashed(&ecdsa_field_bytes::<C>(digest), signature)
+    .is_ok()
+}
+
+/// The byte length of each of r and s in a P1363 DSA signature.
+fn dsa_p1363_scalar_len(key: &dsa::VerifyingKey) -> usize {
+  key.components().q().bits().div_ceil(8)
+}
+
+fn dsa_signature_to_bytes(
+  encoding: u32,
+  key: &dsa::VerifyingKey,
+  signature: dsa::Signature,
+) -> Result<Box<[u8]>, KeyObjectHandlePrehashedSignAndVerifyError> {
+  match encoding {
+    0 => Ok(signature.into()),
+    1 => {
+      let len = dsa_p1363_scalar_len(key);
+      let mut bytes = vec![0; 2 * len];
+      for (half, value) in
+        bytes.chunks_mut(len).zip([signature.r(), signature.s()])
+      {
+        let value = value.to_bytes_be();
+        half[len - value.len()..].copy_from_slice(&value);
+      }
+      Ok(bytes.into_boxed_slice())
+    }
+    _ => Err(
+      KeyObjectHandlePrehashedSignAndVerifyError::InvalidDsaSignatureEncoding,
+    ),
+  }
+}
+
+/// `None` for a signature that doesn't parse, like `parse_dsa_signature`.
+fn dsa_signature_from_bytes(
+  encoding: u32,
+  key: &dsa::VerifyingKey,
+  signature: &[u8],
+) -> Result<Option<dsa::Signature>, KeyObjectHandlePrehashedSignAndVerifyError>
+{
+  match encoding {
+    0 => Ok(dsa::Signature::from_der(signature).ok()),
+    1 => {
+      let len = dsa_p1363_scalar_len(key);
+      if signature.len() != 2 * len {
+        return Ok(None);
+      }
+      let (r, s) = signature.split_at(len);
+      Ok(
+        dsa::Signature::from_components(
+          dsa::BigUint::from_bytes_be(r),
+          dsa::BigUint::from_bytes_be(s),
+        )
+        .ok(),
+      )
+    }
+    _ => Err(
+      KeyObjectHandlePrehashedSignAndVerifyError::InvalidDsaSignatureEncoding,
+    ),
+  }
+}
+
+fn sm2_signature_to_bytes(
+  encoding: u32,
+  signature: sm2::Signature,
+) -> Result<Box<[u8]>, KeyObjectHandlePrehashedSignAndVerifyError> {
+  match encoding {
+    0 => Ok(signature.to_der()),
+    1 => Ok(signature.to_bytes()),
+    _ => Err(
+      KeyObjectHandlePrehashedSignAndVerifyError::InvalidDsaSignatureEncoding,
+    ),
+  }
+}
+
+/// `None` for a signature that doesn't parse, like `parse_dsa_signature`.
+fn sm2_signature_from_bytes(
+  encoding: u32,
+  signature: &[u8],
+) -> Result<Option<sm2::Signature>, KeyObjectHandlePrehashedSignAndVerifyError>
+{
+  match encoding {
+    0 => Ok(sm2::Signature::from_der(signature)),
+    1 => Ok(sm2::Signature::from_bytes(signature)),
+    _ => Err(
+      KeyObjectHandlePrehashedSignAndVerifyError::InvalidDsaSignatureEncoding,
+    ),
+  }
+}
+
 #[derive(Debug, thiserror::Error, deno_error::JsError)]
 #[class(type)]
 pub enum KeyObjectHandlePrehashedSignAndVerifyError {
@@ -56,34 +268,215 @@ pub enum KeyObjectHandlePrehashedSignAndVerifyError {
   #[class(generic)]
   #[error("failed to sign digest with RSA-PSS")]
   FailedToSignDigestWithRsaPss,
+  #[error("digest not allowed for DSA signature: {0}")]
+  DigestNotAllowedForDsaSignature(String),
   #[error("failed to sign digest with DSA")]
   FailedToSignDigestWithDsa,
-  #[error(
-    "rsa-pss with different mf1 hash algorithm and hash algorithm is not supported"
-  )]
-  RsaPssHashAlgorithmUnsupported,
+  #[error("digest not allowed for SM2 signature: {0}")]
+  DigestNotAllowedForSm2Signature(String),
+  #[class(generic)]
+  #[property("code" = "ERR_OSSL_INVALID_DIGEST")]
+  #[error("invalid digest")]
+  InvalidSm2Digest,
+  #[error("SM2 ID is too long")]
+  Sm2IdTooLong,
+  #[error("key is not an SM2 key")]
+  KeyIsNotSm2,
+  #[error("key is not a secp256k1 key")]
+  KeyIsNotSecp256k1,
+  #[error("invalid recovery ID: {0}")]
+  InvalidRecoveryId(u8),
+  #[class(generic)]
+  #[error("failed to recover public key")]
+  FailedToRecoverPublicKey,
+  #[class(generic)]
+  #[property("code" = "ERR_OSSL_DIGEST_NOT_ALLOWED")]
   #[error(
     "private key does not allow {actual} to be used, expected {expected}"
   )]
   PrivateKeyDisallowsUsage { actual: String, expected: String },
+  #[class(generic)]
+  #[property("code" = "ERR_OSSL_DIGEST_NOT_ALLOWED")]
+  #[error(
+    "key does not allow {actual} to be used for MGF1, expected {expected}"
+  )]
+  KeyDisallowsMgf1Digest { actual: String, expected: String },
+  #[property("code" = "ERR_CRYPTO_INVALID_DIGEST")]
+  #[error("Invalid MGF1 digest: {0}")]
+  InvalidMgf1Digest(String),
   #[error("failed to sign digest")]
   FailedToSignDigest,
   #[error("x25519 key cannot be used for signing")]
   X25519KeyCannotBeUsedForSigning,
+  #[error("x448 key cannot be used for signing")]
+  X448KeyCannotBeUsedForSigning,
   #[error("Ed25519 key cannot be used for prehashed signing")]
   Ed25519KeyCannotBeUsedForPrehashedSigning,
+  #[error("Ed448 key cannot be used for prehashed signing")]
+  Ed448KeyCannotBeUsedForPrehashedSigning,
+  #[error("ML-DSA key cannot be used for prehashed signing")]
+  MlDsaKeyCannotBeUsedForPrehashedSigning,
+  #[error("SLH-DSA key cannot be used for prehashed signing")]
+  SlhDsaKeyCannotBeUsedForPrehashedSigning,
   #[error("DH key cannot be used for signing")]
   DhKeyCannotBeUsedForSigning,
+  #[error("ML-KEM key cannot be used for signing")]
+  MlKemKeyCannotBeUsedForSigning,
   #[error("key is not a public or private key")]
   KeyIsNotPublicOrPrivate,
-  #[error("Invalid DSA signature")]
-  InvalidDsaSignature,
   #[error("x25519 key cannot be used for verification")]
   X25519KeyCannotBeUsedForVerification,
+  #[error("x448 key cannot be used for verification")]
+  X448KeyCannotBeUsedForVerification,
   #[error("Ed25519 key cannot be used for prehashed verification")]
   Ed25519KeyCannotBeUsedForPrehashedVerification,
+  #[error("Ed448 key cannot be used for prehashed verification")]
+  Ed448KeyCannotBeUsedForPrehashedVerification,
+  #[error("ML-DSA key cannot be used for prehashed verification")]
+  MlDsaKeyCannotBeUsedForPrehashedVerification,
+  #[error("SLH-DSA key cannot be used for prehashed verification")]
+  SlhDsaKeyCannotBeUsedForPrehashedVerification,
   #[error("DH key cannot be used for verification")]
   DhKeyCannotBeUsedForVerification,
+  #[error("ML-KEM key cannot be used for verification")]
+  MlKemKeyCannotBeUsedForVerification,
+  #[class(generic)]
+  #[property("code" = "ERR_OSSL_INVALID_SALT_LENGTH")]
+  #[error("invalid salt length")]
+  InvalidSaltLength,
+  #[class(generic)]
+  #[property("code" = "ERR_OSSL_PSS_SALTLEN_TOO_SMALL")]
+  #[error("pss saltlen too small")]
+  PssSaltLengthTooSmall,
+}
+
+/// The `saltLength` option of an RSA-PSS signature.
+#[derive(Clone, Copy)]
+enum PssSaltLength {
+  /// `RSA_PSS_SALTLEN_DIGEST`: the length of the digest.
+  Digest,
+  /// `RSA_PSS_SALTLEN_MAX_SIGN` when signing, the longest salt that fits;
+  /// `RSA_PSS_SALTLEN_AUTO` when verifying, whatever the signature used.
+  /// Both are -2.
+  Max,
+  Length(usize),
+}
+
+impl PssSaltLength {
+  fn from_option(
+    salt_length: Option<i32>,
+  ) -> Result<Option<Self>, KeyObjectHandlePrehashedSignAndVerifyError> {
+    match salt_length {
+      None => Ok(None),
+      Some(-1) => Ok(Some(Self::Digest)),
+      Some(-2) => Ok(Some(Self::Max)),
+      Some(length) => usize::try_from(length)
+        .map(|length| Some(Self::Length(length)))
+        .map_err(|_| {
+          KeyObjectHandlePrehashedSignAndVerifyError::InvalidSaltLength
+        }),
+    }
+  }
+
+  /// The options override the salt length in the key's parameters. Keys
+  /// without parameters default to the longest salt, as in Node.js.
+  fn resolve(
+    salt_length: Option<i32>,
+    details: Option<&RsaPssDetails>,
+  ) -> Result<Self, KeyObjectHandlePrehashedSignAndVerifyError> {
+    Ok(match Self::from_option(salt_length)? {
+      Some(salt_length) => salt_length,
+      None => match details {
+        Some(details) => Self::Length(details.salt_length as usize),
+        None => Self::Max,
+      },
+    })
+  }
+
+  /// The salt length in the key's parameters is a minimum, as in OpenSSL,
+  /// which also refuses `RSA_PSS_SALTLEN_MAX_SIGN` for such keys.
+  fn check_key_minimum(
+    self,
+    details: Option<&RsaPssDetails>,
+    hash_len: usize,
+    sign: bool,
+  ) -> Result<(), KeyObjectHandlePrehashedSignAndVerifyError> {
+    let Some(details) = details else {
+      return Ok(());
+    };
+    let min_salt_length = details.salt_length as usize;
+    match self {
+      Self::Max if sign => {
+        Err(KeyObjectHandlePrehashedSignAndVerifyError::InvalidSaltLength)
+      }
+      Self::Max => Ok(()),
+      Self::Digest if hash_len < min_salt_length => {
+        Err(KeyObjectHandlePrehashedSignAndVerifyError::PssSaltLengthTooSmall)
+      }
+      Self::Length(length) if length < min_salt_length => {
+        Err(KeyObjectHandlePrehashedSignAndVerifyError::PssSaltLengthTooSmall)
+      }
+      Self::Digest | Self::Length(_) => Ok(()),
+    }
+  }
+}
+
+/// The MGF1 hash from the options, checked against the key's parameters.
+/// `None` means the message digest is used.
+fn resolve_mgf1_hash(
+  mgf1_hash: Option<&str>,
+  details: Option<&RsaPssDetails>,
+) -> Result<
+  Option<RsaPssHashAlgorithm>,
+  KeyObjectHandlePrehashedSignAndVerifyError,
+> {
+  let Some(mgf1_hash) = mgf1_hash else {
+    return Ok(details.map(|details| details.mf1_hash_algorithm));
+  };
+  let name = resolve_legacy_alias(mgf1_hash);
+  let algorithm = match_fixed_digest_with_oid!(
+    name,
+    fn (algorithm: Option<RsaPssHashAlgorithm>) {
+      algorithm
+    },
+    _ => {
+      None
+    }
+  )
+  .ok_or_else(|| {
+    KeyObjectHandlePrehashedSignAndVerifyError::InvalidMgf1Digest(
+      mgf1_hash.to_string(),
+    )
+  })?;
+  if let Some(details) = details {
+    if details.mf1_hash_algorithm != algorithm {
+      return Err(
+        KeyObjectHandlePrehashedSignAndVerifyError::KeyDisallowsMgf1Digest {
+          actual: algorithm.as_str().to_string(),
+          expected: details.mf1_hash_algorithm.as_str().to_string(),
+        },
+      );
+    }
+  }
+  Ok(Some(algorithm))
+}
+
+/// PKCS#1 v1.5 padding for digests whose Rust types don't carry an OID.
+fn pkcs1v15_without_oid(digest_type: &str) -> Option<Pkcs1v15Sign> {
+  match digest_type {
+    // As in OpenSSL's RSA_sign() with NID_md5_sha1, the TLS 1.1 digest is
+    // padded without a DigestInfo.
+    "md5-sha1" => Some(Pkcs1v15Sign::new_unprefixed()),
+    // DigestInfo DER prefix for RIPEMD-256 (1.3.36.3.2.3).
+    "ripemd256" | "rmd256" => Some(Pkcs1v15Sign {
+      hash_len: Some(32),
+      prefix: Box::new([
+        0x30, 0x2d, 0x30, 0x09, 0x06, 0x05, 0x2b, 0x24, 0x03, 0x02, 0x03, 0x05,
+        0x00, 0x04, 0x20,
+      ]),
+    }),
+    _ => None,
+  }
 }
 
 impl KeyObjectHandle {
@@ -91,17 +484,19 @@ impl KeyObjectHandle {
     &self,
     digest_type: &str,
     digest: &[u8],
-    pss_salt_length: Option<u32>,
+    pss_salt_length: Option<i32>,
+    pss_mgf1_hash: Option<&str>,
     dsa_signature_encoding: u32,
   ) -> Result<Box<[u8]>, KeyObjectHandlePrehashedSignAndVerifyError> {
+    let digest_type = resolve_legacy_alias(digest_type);
     let private_key = self
       .as_private_key()
       .ok_or(KeyObjectHandlePrehashedSignAndVerifyError::KeyIsNotPrivate)?;
 
     match private_key {
       AsymmetricPrivateKey::Rsa(key) => {
-        let signer = if digest_type == "md5-sha1" {
-          rsa::pkcs1v15::Pkcs1v15Sign::new_unprefixed()
+        let signer = if let Some(signer) = pkcs1v15_without_oid(digest_type) {
+          signer
         } else {
           match_fixed_digest_with_oid!(
             digest_type,
@@ -120,19 +515,12 @@ impl KeyObjectHandle {
         Ok(signature.into())
       }
       AsymmetricPrivateKey::RsaPss(key) => {
-        let mut hash_algorithm = None;
-        let mut salt_length = None;
-        if let Some(details) = &key.details {
-          if details.hash_algorithm != details.mf1_hash_algorithm {
-            return Err(KeyObjectHandlePrehashedSignAndVerifyError::RsaPssHashAlgorithmUnsupported);
-          }
-          hash_algorithm = Some(details.hash_algorithm);
-          salt_length = Some(details.salt_length as usize);
-        }
-        if let Some(s) = pss_salt_length {
-          salt_length = Some(s as usize);
-        }
-        let pss = match_fixed_digest_with_oid!(
+        let mut hash_algorithm =
+          key.details.as_ref().map(|details| details.hash_algorithm);
+        let mgf1 = resolve_mgf1_hash(pss_mgf1_hash, key.details.as_ref())?;
+        let salt_length =
+          PssSaltLength::resolve(pss_salt_length, key.details.as_ref())?;
+        let signature = match_fixed_digest_with_oid!(
           digest_type,
           fn <D>(algorithm: Option<RsaPssHashAlgorithm>) {
             if let Some(hash_algorithm) = hash_algorithm.take() {
@@ -143,18 +531,21 @@ impl KeyObjectHandle {
                 });
               }
             }
-            if let Some(salt_length) = salt_length {
-              rsa::pss::Pss::new_with_salt::<D>(salt_length)
-            } else {
-              rsa::pss::Pss::new::<D>()
-            }
+            let hash_len = <D as digest::Digest>::output_size();
+            salt_length.check_key_minimum(key.details.as_ref(), hash_len, true)?;
+            let salt_length = match salt_length {
+              PssSaltLength::Digest => hash_len,
+              PssSaltLength::Max => rsa_pss::max_salt_length(&key.key, hash_len),
+              PssSaltLength::Length(length) => length,
+            };
+            let mgf1 = mgf1.filter(|mgf1| Some(*mgf1) != algorithm);
+            rsa_pss::sign::<D>(&key.key, mgf1, digest, salt_length)
           },
           _ => {
             return Err(KeyObjectHandlePrehashedSignAndVerifyError::DigestNotAllowedForRsaPssSignature(digest_type.to_string()));
           }
         );
-        let signature = pss
-          .sign(Some(&mut OsRng), &key.key, digest)
+        let signature = signature
           .map_err(|_| KeyObjectHandlePrehashedSignAndVerifyError::FailedToSignDigestWithRsaPss)?;
         Ok(signature.into())
       }
@@ -165,47 +556,62 @@ impl KeyObjectHandle {
             key.sign_prehashed_rfc6979::<D>(digest)
           },
           _ => {
-            return Err(KeyObjectHandlePrehashedSignAndVerifyError::DigestNotAllowedForRsaSignature(digest_type.to_string()))
+            return Err(KeyObjectHandlePrehashedSignAndVerifyError::DigestNotAllowedForDsaSignature(digest_type.to_string()))
           }
         );
 
         let signature =
           res.map_err(|_| KeyObjectHandlePrehashedSignAndVerifyError::FailedToSignDigestWithDsa)?;
-        Ok(signature.into())
+        dsa_signature_to_bytes(dsa_signature_encoding, key.verifying_key(), signature)
       }
       AsymmetricPrivateKey::Ec(key) => match key {
-        EcPrivateKey::P224(key) => {
-          let signing_key = p224::ecdsa::SigningKey::from(key);
-          let signature: p224::ecdsa::Signature = signing_key
-            .sign_prehash(digest)
-            .map_err(|_| KeyObjectHandlePrehashedSignAndVerifyError::FailedToSignDigest)?;
-
+        EcPrivateKey::P224(key) => dsa_signature(
+          dsa_signature_encoding,
+          ecdsa_sign_prehashed::<_, sha2::Sha224>(key, digest_type, digest)?,
+        ),
+        EcPrivateKey::P256(key) => dsa_signature(
+          dsa_signature_encoding,
+          ecdsa_sign_prehashed::<_, sha2::Sha256>(key, digest_type, digest)?,
+        ),
+        EcPrivateKey::P384(key) => dsa_signature(
+          dsa_signature_encoding,
+          ecdsa_sign_prehashed::<_, sha2::Sha384>(key, digest_type, digest)?,
+        ),
+        EcPrivateKey::P521(key) => dsa_signature(
+          dsa_signature_encoding,
+          ecdsa_sign_prehashed::<_, sha2::Sha512>(key, digest_type, digest)?,
+        ),
+        // k256 keeps s low, and so do we.
+        EcPrivateKey::Secp256k1(key) => {
+          let signature =
+            ecdsa_sign_prehashed::<_, sha2::Sha256>(key, digest_type, digest)?;
+          let signature = signature.normalize_s().unwrap_or(signature);
           dsa_signature(dsa_signature_encoding, signature)
         }
-        EcPrivateKey::P256(key) => {
-          let signing_key = p256::ecdsa::SigningKey::from(key);
-          let signature: p256::ecdsa::Signature = signing_key
-            .sign_prehash(digest)
-            .map_err(|_| KeyObjectHandlePrehashedSignAndVerifyError::FailedToSignDigest)?;
-
-          dsa_signature(dsa_signature_encoding, signature)
-        }
-        EcPrivateKey::P384(key) => {
-          let signing_key = p384::ecdsa::SigningKey::from(key);
-          let signature: p384::ecdsa::Signature = signing_key
-            .sign_prehash(digest)
-            .map_err(|_| KeyObjectHandlePrehashedSignAndVerifyError::FailedToSignDigest)?;
-
-          dsa_signature(dsa_signature_encoding, signature)
+        // As in Node.js, the digest is signed as is, without the ID.
+        EcPrivateKey::Sm2(key) => {
+          let digest = digest.try_into().map_err(|_| {
+            KeyObjectHandlePrehashedSignAndVerifyError::DigestNotAllowedForSm2Signature(digest_type.to_string())
+          })?;
+          sm2_signature_to_bytes(dsa_signature_encoding, key.sign_prehash(digest))
         }
       },
       AsymmetricPrivateKey::X25519(_) => {
         Err(KeyObjectHandlePrehashedSignAndVerifyError::X25519KeyCannotBeUsedForSigning)
       }
+      AsymmetricPrivateKey::X448(_) => {
+        Err(KeyObjectHandlePrehashedSignAndVerifyError::X448KeyCannotBeUsedForSigning)
+      }
       AsymmetricPrivateKey::Ed25519(_) => Err(KeyObjectHandlePrehashedSignAndVerifyError::Ed25519KeyCannotBeUsedForPrehashedSigning),
+      AsymmetricPrivateKey::Ed448(_) => Err(KeyObjectHandlePrehashedSignAndVerifyError::Ed448KeyCannotBeUsedForPrehashedSigning),
+      AsymmetricPrivateKey::MlDsa(_) => Err(KeyObjectHandlePrehashedSignAndVerifyError::MlDsaKeyCannotBeUsedForPrehashedSigning),
+      AsymmetricPrivateKey::SlhDsa(_) => Err(KeyObjectHandlePrehashedSignAndVerifyError::SlhDsaKeyCannotBeUsedForPrehashedSigning),
       AsymmetricPrivateKey::Dh(_) => {
         Err(KeyObjectHandlePrehashedSignAndVerifyError::DhKeyCannotBeUsedForSigning)
       }
+      AsymmetricPrivateKey::MlKem(_) => {
+        Err(KeyObjectHandlePrehashedSignAndVerifyError::MlKemKeyCannotBeUsedForSigning)
+      }
     }
   }
 
@@ -214,17 +620,19 @@ impl KeyObjectHandle {
     digest_type: &str,
     digest: &[u8],
     signature: &[u8],
-    pss_salt_length: Option<u32>,
+    pss_salt_length: Option<i32>,
+    pss_mgf1_hash: Option<&str>,
     dsa_signature_encoding: u32,
   ) -> Result<bool, KeyObjectHandlePrehashedSignAndVerifyError> {
+    let digest_type = resolve_legacy_alias(digest_type);
     let public_key = self.as_public_key().ok_or(
       KeyObjectHandlePrehashedSignAndVerifyError::KeyIsNotPublicOrPrivate,
     )?;
 
     match &*public_key {
       AsymmetricPublicKey::Rsa(key) => {
-        let signer = if digest_type == "md5-sha1" {
-          rsa::pkcs1v15::Pkcs1v15Sign::new_unprefixed()
+        let signer = if let Some(signer) = pkcs1v15_without_oid(digest_type) {
+          signer
         } else {
           match_fixed_digest_with_oid!(
             digest_type,
@@ -240,19 +648,12 @@ impl KeyObjectHandle {
         Ok(signer.verify(key, digest, signature).is_ok())
       }
       AsymmetricPublicKey::RsaPss(key) => {
-        let mut hash_algorithm = None;
-        let mut salt_length = None;
-        if let Some(details) = &key.details {
-          if details.hash_algorithm != details.mf1_hash_algorithm {
-            return Err(KeyObjectHandlePrehashedSignAndVerifyError::RsaPssHashAlgorithmUnsupported);
-          }
-          hash_algorithm = Some(details.hash_algorithm);
-          salt_length = Some(details.salt_length as usize);
-        }
-        if let Some(s) = pss_salt_length {
-          salt_length = Some(s as usize);
-        }
-        let pss = match_fixed_digest_with_oid!(
+        let mut hash_algorithm =
+          key.details.as_ref().map(|details| details.hash_algorithm);
+        let mgf1 = resolve_mgf1_hash(pss_mgf1_hash, key.details.as_ref())?;
+        let salt_length =
+          PssSaltLength::resolve(pss_salt_length, key.details.as_ref())?;
+        let verified = match_fixed_digest_with_oid!(
           digest_type,
           fn <D>(algorithm: Option<RsaPssHashAlgorithm>) {
             if let Some(hash_algorithm) = hash_algorithm.take() {
@@ -263,68 +664,519 @@ impl KeyObjectHandle {
                 });
               }
             }
-            if let Some(salt_length) = salt_length {
-              rsa::pss::Pss::new_with_salt::<D>(salt_length)
-            } else {
-              rsa::pss::Pss::new::<D>()
-            }
+            let hash_len = <D as digest::Digest>::output_size();
+            salt_length.check_key_minimum(key.details.as_ref(), hash_len, false)?;
+            let salt_length = match salt_length {
+              PssSaltLength::Digest => Some(hash_len),
+              PssSaltLength::Max => None,
+              PssSaltLength::Length(length) => Some(length),
+            };
+            let mgf1 = mgf1.filter(|mgf1| Some(*mgf1) != algorithm);
+            rsa_pss::verify::<D>(&key.key, mgf1, digest, signature, salt_length)
           },
           _ => {
             return Err(KeyObjectHandlePrehashedSignAndVerifyError::DigestNotAllowedForRsaPssSignature(digest_type.to_string()));
           }
         );
-        Ok(pss.verify(&key.key, digest, signature).is_ok())
+        Ok(verified)
       }
       AsymmetricPublicKey::Dsa(key) => {
-        let signature = dsa::Signature::from_der(signature)
-          .map_err(|_| KeyObjectHandlePrehashedSignAndVerifyError::InvalidDsaSignature)?;
+        let Some(signature) =
+          dsa_signature_from_bytes(dsa_signature_encoding, key, signature)?
+        else {
+          return Ok(false);
+        };
         Ok(key.verify_prehash(digest, &signature).is_ok())
       }
       AsymmetricPublicKey::Ec(key) => match key {
         EcPublicKey::P224(key) => {
-          let verifying_key = p224::ecdsa::VerifyingKey::from(key);
-          let signature = if dsa_signature_encoding == 0 {
-            p224::ecdsa::Signature::from_der(signature)
-          } else {
-            p224::ecdsa::Signature::from_bytes(signature.into())
-          };
-          let Ok(signature) = signature else {
+          let Some(signature) =
+            parse_dsa_signature::<p224::NistP224>(dsa_signature_encoding, signature)?
+          else {
             return Ok(false);
           };
-          Ok(verifying_key.verify_prehash(digest, &signature).is_ok())
+          Ok(ecdsa_verify_prehashed(key, digest, &signature))
         }
         EcPublicKey::P256(key) => {
-          let verifying_key = p256::ecdsa::VerifyingKey::from(key);
-          let signature = if dsa_signature_encoding == 0 {
-            p256::ecdsa::Signature::from_der(signature)
-          } else {
-            p256::ecdsa::Signature::from_bytes(signature.into())
-          };
-          let Ok(signature) = signature else {
+          let Some(signature) =
+            parse_dsa_signature::<p256::NistP256>(dsa_signature_encoding, signature)?
+          else {
             return Ok(false);
           };
-          Ok(verifying_key.verify_prehash(digest, &signature).is_ok())
+          Ok(ecdsa_verify_prehashed(key, digest, &signature))
         }
         EcPublicKey::P384(key) => {
-          let verifying_key = p384::ecdsa::VerifyingKey::from(key);
-          let signature = if dsa_signature_encoding == 0 {
-            p384::ecdsa::Signature::from_der(signature)
-          } else {
-            p384::ecdsa::Signature::from_bytes(signature.into())
+          let Some(signature) =
+            parse_dsa_signature::<p384::NistP384>(dsa_signature_encoding, signature)?
+          else {
+            return Ok(false);
+          };
+          Ok(ecdsa_verify_prehashed(key, digest, &signature))
+        }
+        EcPublicKey::P521(key) => {
+          let Some(signature) =
+            parse_dsa_signature::<p521::NistP521>(dsa_signature_encoding, signature)?
+          else {
+            return Ok(false);
+          };
+          Ok(ecdsa_verify_prehashed(key, digest, &signature))
+        }
+        EcPublicKey::Secp256k1(key) => {
+          let Some(signature) =
+            parse_dsa_signature::<k256::Secp256k1>(dsa_signature_encoding, signature)?
+          else {
+            return Ok(false);
           };
-          let Ok(signature) = signature else {
+          // k256 only verifies low-s signatures, but OpenSSL allows either.
+          let signature = signature.normalize_s().unwrap_or(signature);
+          Ok(ecdsa_verify_prehashed(key, digest, &signature))
+        }
+        EcPublicKey::Sm2(key) => {
+          let digest = digest.try_into().map_err(|_| {
+            KeyObjectHandlePrehashedSignAndVerifyError::DigestNotAllowedForSm2Signature(digest_type.to_string())
+          })?;
+          let Some(signature) =
+            sm2_signature_from_bytes(dsa_signature_encoding, signature)?
+          else {
             return Ok(false);
           };
-          Ok(verifying_key.verify_prehash(digest, &signature).is_ok())
+          Ok(key.verify_prehash(digest, &signature))
         }
       },
       AsymmetricPublicKey::X25519(_) => {
         Err(KeyObjectHandlePrehashedSignAndVerifyError::X25519KeyCannotBeUsedForVerification)
       }
+      AsymmetricPublicKey::X448(_) => {
+        Err(KeyObjectHandlePrehashedSignAndVerifyError::X448KeyCannotBeUsedForVerification)
+      }
       AsymmetricPublicKey::Ed25519(_) => Err(KeyObjectHandlePrehashedSignAndVerifyError::Ed25519KeyCannotBeUsedForPrehashedVerification),
+      AsymmetricPublicKey::Ed448(_) => Err(KeyObjectHandlePrehashedSignAndVerifyError::Ed448KeyCannotBeUsedForPrehashedVerification),
+      AsymmetricPublicKey::MlDsa(_) => Err(KeyObjectHandlePrehashedSignAndVerifyError::MlDsaKeyCannotBeUsedForPrehashedVerification),
+      AsymmetricPublicKey::SlhDsa(_) => Err(KeyObjectHandlePrehashedSignAndVerifyError::SlhDsaKeyCannotBeUsedForPrehashedVerification),
       AsymmetricPublicKey::Dh(_) => {
         Err(KeyObjectHandlePrehashedSignAndVerifyError::DhKeyCannotBeUsedForVerification)
       }
+      AsymmetricPublicKey::MlKem(_) => {
+        Err(KeyObjectHandlePrehashedSignAndVerifyError::MlKemKeyCannotBeUsedForVerification)
+      }
+    }
+  }
+
+  /// Signs `data` with the SM2 scheme, which hashes the signer's ID and
+  /// public key into the digest. SM3 is the only digest it allows.
+  pub fn sign_sm2(
+    &self,
+    digest_type: &str,
+    data: &[u8],
+    id: &[u8],
+    dsa_signature_encoding: u32,
+  ) -> Result<Box<[u8]>, KeyObjectHandlePrehashedSignAndVerifyError> {
+    let private_key = self
+      .as_private_key()
+      .ok_or(KeyObjectHandlePrehashedSignAndVerifyError::KeyIsNotPrivate)?;
+    let AsymmetricPrivateKey::Ec(EcPrivateKey::Sm2(key)) = private_key else {
+      return Err(KeyObjectHandlePrehashedSignAndVerifyError::KeyIsNotSm2);
+    };
+    if !digest_type.eq_ignore_ascii_case("sm3") {
+      return Err(KeyObjectHandlePrehashedSignAndVerifyError::InvalidSm2Digest);
+    }
+    let digest = key
+      .public_key()
+      .digest(id, data)
+      .ok_or(KeyObjectHandlePrehashedSignAndVerifyError::Sm2IdTooLong)?;
+    sm2_signature_to_bytes(dsa_signature_encoding, key.sign_prehash(&digest))
+  }
+
+  pub fn verify_sm2(
+    &self,
+    digest_type: &str,
+    data: &[u8],
+    signature: &[u8],
+    id: &[u8],
+    dsa_signature_encoding: u32,
+  ) -> Result<bool, KeyObjectHandlePrehashedSignAndVerifyError> {
+    let public_key = self.as_public_key().ok_or(
+      KeyObjectHandlePrehashedSignAndVerifyError::KeyIsNotPublicOrPrivate,
+    )?;
+    let AsymmetricPublicKey::Ec(EcPublicKey::Sm2(key)) = &*public_key else {
+      return Err(KeyObjectHandlePrehashedSignAndVerifyError::KeyIsNotSm2);
+    };
+    if !digest_type.eq_ignore_ascii_case("sm3") {
+      return Err(KeyObjectHandlePrehashedSignAndVerifyError::InvalidSm2Digest);
     }
+    let digest = key
+      .digest(id, data)
+      .ok_or(KeyObjectHandlePrehashedSignAndVerifyError::Sm2IdTooLong)?;
+    let Some(signature) =
+      sm2_signature_from_bytes(dsa_signature_encoding, signature)?
+    else {
+      return Ok(false);
+    };
+    Ok(key.verify_prehash(&digest, &signature))
+  }
+
+  /// Signs `digest` with a secp256k1 key, and appends the recovery ID that
+  /// `recover_secp256k1_public_key` needs. With `low_s`, the signature is
+  /// deterministic and has a low s, as Ethereum requires. Otherwise it is
+  /// made as OpenSSL does, with a random nonce and s as computed.
+  pub fn sign_recoverable(
+    &self,
+    digest: &[u8],
+    low_s: bool,
+    dsa_signature_encoding: u32,
+  ) -> Result<Box<[u8]>, KeyObjectHandlePrehashedSignAndVerifyError> {
+    let private_key = self
+      .as_private_key()
+      .ok_or(KeyObjectHandlePrehashedSignAndVerifyError::KeyIsNotPrivate)?;
+    let AsymmetricPrivateKey::Ec(EcPrivateKey::Secp256k1(key)) = private_key
+    else {
+      return Err(
+        KeyObjectHandlePrehashedSignAndVerifyError::KeyIsNotSecp256k1,
+      );
+    };
+    let (signature, recovery_id) = if low_s {
+      k256::ecdsa::SigningKey::from(key).sign_prehash_recoverable(digest)
+    } else {
+      let nonce = k256::NonZeroScalar::random(&mut OsRng);
+      ecdsa::hazmat::bits2field::<k256::Secp256k1>(digest).and_then(|z| {
+        ecdsa::hazmat::sign_prehashed::<k256::Secp256k1, _>(
+          &key.to_nonzero_scalar(),
+          *nonce,
+          &z,
+        )
+      })
+    }
+    .map_err(|_| {
+      KeyObjectHandlePrehashedSignAndVerifyError::FailedToSignDigest
+    })?;
+
+    let mut signature =
+      dsa_signature(dsa_signature_encoding, signature)?.into_vec();
+    signature.push(recovery_id.to_byte());
+    Ok(signature.into_boxed_slice())
+  }
+}
+
+/// Recovers the secp256k1 public key that made `signature` over `digest`,
+/// as an uncompressed SEC1 point.
+pub fn recover_secp256k1_public_key(
+  digest: &[u8],
+  signature: &[u8],
+  recovery_id: u8,
+  dsa_signature_encoding: u32,
+) -> Result<Box<[u8]>, KeyObjectHandlePrehashedSignAndVerifyError> {
+  let mut recovery_id = k256::ecdsa::RecoveryId::from_byte(recovery_id).ok_or(
+    KeyObjectHandlePrehashedSignAndVerifyError::InvalidRecoveryId(recovery_id),
+  )?;
+  let signature =
+    parse_dsa_signature::<k256::Secp256k1>(dsa_signature_encoding, signature)?
+      .ok_or(
+        KeyObjectHandlePrehashedSignAndVerifyError::FailedToRecoverPublicKey,
+      )?;
+  // k256 only recovers from low-s signatures. Negating s also negates the
+  // recovered point, so the recovery ID's parity flips with it.
+  let signature = match signature.normalize_s() {
+    Some(normalized) => {
+      recovery_id = k256::ecdsa::RecoveryId::new(
+        !recovery_id.is_y_odd(),
+        recovery_id.is_x_reduced(),
+      );
+      normalized
+    }
+    None => signature,
+  };
+  let public_key = k256::ecdsa::VerifyingKey::recover_from_prehash(
+    digest,
+    &signature,
+    recovery_id,
+  )
+  .map_err(|_| {
+    KeyObjectHandlePrehashedSignAndVerifyError::FailedToRecoverPublicKey
+  })?;
+  Ok(public_key.to_encoded_point(false).as_bytes().into())
+}
+
+#[cfg(test)]
+mod tests {
+  use elliptic_curve::sec1::ToEncodedPoint as _;
+  use rsa::signature::hazmat::PrehashSigner as _;
+  use sha3::Digest as _;
+
+  use super::*;
+
+  /// The private key and transaction hash of EIP-155's example.
+  const EIP155_KEY: [u8; 32] = [0x46; 32];
+  const EIP155_HASH: &str =
+    "daf5a779ae972f972197303d7b574746c7ef83eadac0f2791ad23db92e4c8e53";
+  const EIP155_ADDRESS: &str = "9d8a62f656a8d1615c1294fd71e9cfb3e4855a4f";
+
+  fn unhex(value: &str) -> Vec<u8> {
+    let mut bytes = vec![0; value.len() / 2];
+    faster_hex::hex_decode(value.as_bytes(), &mut bytes).unwrap();
+    bytes
+  }
+
+  fn eip155_key() -> KeyObjectHandle {
+    let key = k256::SecretKey::from_slice(&EIP155_KEY).unwrap();
+    KeyObjectHandle::AsymmetricPrivate(AsymmetricPrivateKey::Ec(
+      EcPrivateKey::Secp256k1(key),
+    ))
+  }
+
+  /// The Ethereum address of an uncompressed public key.
+  fn address(public_key: &[u8]) -> String {
+    faster_hex::hex_string(&sha3::Keccak256::digest(&public_key[1..])[12..])
+  }
+
+  #[test]
+  fn eip155_vector() {
+    let hash = unhex(EIP155_HASH);
+    let signature = eip155_key().sign_recoverable(&hash, true, 1).unwrap();
+    // v = 37 is 35 + 2 * chain ID 1 + recovery ID 0.
+    assert_eq!(
+      faster_hex::hex_string(&signature),
+      concat!(
+        "28ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276",
+        "67cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83",
+        "00"
+      )
+    );
+    let public_key =
+      recover_secp256k1_public_key(&hash, &signature[..64], signature[64], 1)
+        .unwrap();
+    assert_eq!(address(&public_key), EIP155_ADDRESS);
+
+    let der = eip155_key().sign_recoverable(&hash, true, 0).unwrap();
+    let (der, recovery_id) = der.split_at(der.len() - 1);
+    assert_eq!(
+      recover_secp256k1_public_key(&hash, der, recovery_id[0], 0).unwrap(),
+      public_key
+    );
+  }
+
+  #[test]
+  fn high_s() {
+    // EIP-155's signature with s negated, which flips the recovery ID.
+    let hash = unhex(EIP155_HASH);
+    let signature = unhex(concat!(
+      "28ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276",
+      "98341627668089e51348fccfb4c7ff31c55912f2d2e47ef09652acf665fad3be",
+    ));
+    let public_key =
+      recover_secp256k1_public_key(&hash, &signature, 1, 1).unwrap();
+    assert_eq!(address(&public_key), EIP155_ADDRESS);
+    assert!(
+      eip155_key()
+        .verify_prehashed("sha256", &hash, &signature, None, None, 1)
+        .unwrap()
+    );
+  }
+
+  #[test]
+  fn random_nonce() {
+    let hash = unhex(EIP155_HASH);
+    let key = k256::SecretKey::from_slice(&EIP155_KEY).unwrap();
+    let expected = key.public_key().to_encoded_point(false);
+    for _ in 0..8 {
+      let signature = eip155_key().sign_recoverable(&hash, false, 1).unwrap();
+      let public_key =
+        recover_secp256k1_public_key(&hash, &signature[..64], signature[64], 1)
+          .unwrap();
+      assert_eq!(&*public_key, expected.as_bytes());
+    }
+
+    assert!(matches!(
+      recover_secp256k1_public_key(&hash, &[0; 64], 4, 1),
+      Err(KeyObjectHandlePrehashedSignAndVerifyError::InvalidRecoveryId(4))
+    ));
+  }
+
+  /// The private keys of RFC 6979 A.2.4 to A.2.7.
+  fn rfc6979_key(curve: &str) -> KeyObjectHandle {
+    let key = match curve {
+      "P-224" => EcPrivateKey::P224(
+        p224::SecretKey::from_slice(&unhex(
+          "f220266e1105bfe3083e03ec7a3a654651f45e37167e88600bf257c1",
+        ))
+        .unwrap(),
+      ),
+      "P-256" => EcPrivateKey::P256(
+        p256::SecretKey::from_slice(&unhex(
+          "c9afa9d845ba75166b5c215767b1d6934e50c3db36e89b127b8a622b120f6721",
+        ))
+        .unwrap(),
+      ),
+      "P-384" => EcPrivateKey::P384(
+        p384::SecretKey::from_slice(&unhex(concat!(
+          "6b9d3dad2e1b8c1c05b19875b6659f4de23c3b667bf297ba9aa47740787137d8",
+          "96d5724e4c70a825f872c9ea60d2edf5",
+        )))
+        .unwrap(),
+      ),
+      "P-521" => EcPrivateKey::P521(
+        p521::SecretKey::from_slice(&unhex(concat!(
+          "00fad06daa62ba3b25d2fb40133da757205de67f5bb0018fee8c86e1b68c7e75",
+          "caa896eb32f1f47c70855836a6d16fcc1466f6d8fbec67db89ec0c08b0e996b8",
+          "3538",
+        )))
+        .unwrap(),
+      ),
+      _ => unreachable!(),
+    };
+    KeyObjectHandle::AsymmetricPrivate(AsymmetricPrivateKey::Ec(key))
+  }
+
+  #[test]
+  fn rfc6979_vectors() {
+    // The signatures of "sample", in P1363 form.
+    let vectors = [
+      (
+        "P-224",
+        "sha1",
+        concat!(
+          "22226f9d40a96e19c4a301ce5b74b115303c0f3a4fd30fc257fb57ac66d1cdd8",
+          "3e3af75605dd6e2feff196d30aa7ed7a2edf7af475403d69",
+        ),
+      ),
+      (
+        "P-224",
+        "sha256",
+        concat!(
+          "61aa3da010e8e8406c656bc477a7a7189895e7e840cdfe8ff42307babc814050",
+          "dab5d23770879494f9e0a680dc1af7161991bde692b10101",
+        ),
+      ),
+      (
+        "P-224",
+        "sha512",
+        concat!(
+          "074bd1d979d5f32bf958ddc61e4fb4872adcafeb2256497cdac30397a4ceca19",
+          "6c3d5a1ff31027b33185dc8ee43f288b21ab342e5d8eb084",
+        ),
+      ),
+      (
+        "P-256",
+        "sha1",
+        concat!(
+          "61340c88c3aaebeb4f6d667f672ca9759a6ccaa9fa8811313039ee4a35471d32",
+          "6d7f147dac089441bb2e2fe8f7a3fa264b9c475098fdcf6e00d7c996e1b8b7eb",
+        ),
+      ),
+      (
+        "P-256",
+        "sha256",
+        concat!(
+          "efd48b2aacb6a8fd1140dd9cd45e81d69d2c877b56aaf991c34d0ea84eaf3716",
+          "f7cb1c942d657c41d436c7a1b6e29f65f3e900dbb9aff4064dc4ab2f843acda8",
+        ),
+      ),
+      (
+        "P-256",
+        "sha512",
+        concat!(
+          "8496a60b5e9b47c825488827e0495b0e3fa109ec4568fd3f8d1097678eb97f00",
+          "2362ab1adbe2b8adf9cb9edab740ea6049c028114f2460f96554f61fae3302fe",
+        ),
+      ),
+      (
+        "P-384",
+        "sha1",
+        concat!(
+          "ec748d839243d6fbef4fc5c4859a7dffd7f3abddf72014540c16d73309834fa3",
+          "7b9ba002899f6fda3a4a9386790d4eb2a3bcfa947beef4732bf247ac17f71676",
+          "cb31a847b9ff0cbc9c9ed4c1a5b3facf26f49ca031d4857570ccb5ca4424a443",
+        ),
+      ),
+      (
+        "P-384",
+        "sha256",
+        concat!(
+          "21b13d1e013c7fa1392d03c5f99af8b30c570c6f98d4ea8e354b63a21d3daa33",
+          "bde1e888e63355d92fa2b3c36d8fb2cdf3aa443fb107745bf4bd77cb38916746",
+          "32068a10ca67e3d45db2266fa7d1feebefdc63eccd1ac42ec0cb8668a4fa0ab0",
+        ),
+      ),
+      (
+        "P-384",
+        "sha512",
+        concat!(
+          "ed0959d5880ab2d869ae7f6c2915c6d60f96507f9cb3e047c0046861da4a799c",
+          "fe30f35cc900056d7c99cd7882433709512c8cceee3890a84058ce1e22dbc219",
+          "8f42323ce8aca9135329f03c068e5112dc7cc3ef3446defceb01a45c2667fdd5",
+        ),
+      ),
+      (
+        "P-521",
+        "sha1",
+        concat!(
+          "00343b6ec45728975ea5cba6659bbb6062a5ff89eea58be3c80b619f322c8791",
+          "0fe092f7d45bb0f8eee01ed3f20babec079d202ae677b243ab40b5431d497c55",
+          "d75d00e7b0e675a9b24413d448b8cc119d2bf7b2d2df032741c096634d6d65d0",
+          "dbe3d5694625fb9e8104d3b842c1b0e2d0b98bea19341e8676aef66ae4eba3d5",
+          "475d5d16",
+        ),
+      ),
+      (
+        "P-521",
+        "sha256",
+        concat!(
+          "01511bb4d675114fe266fc4372b87682baecc01d3cc62cf2303c92b352601265",
+          "9d16876e25c7c1e57648f23b73564d67f61c6f14d527d54972810421e7d87589",
+          "e1a7004a171143a83163d6df460aaf61522695f207a58b95c0644d87e52aa1a3",
+          "47916e4f7a72930b1bc06dbe22ce3f58264afd23704cbb63b29b931f7de6c9d9",
+          "49a7ecfc",
+        ),
+      ),
+      (
+        "P-521",
+        "sha512",
+        concat!(
+          "00c328fafcbd79dd77850370c46325d987cb525569fb63c5d3bc53950e6d4c5f",
+          "174e25a1ee9017b5d450606add152b534931d7d4e8455cc91f9b15bf05ec36e3",
+          "77fa00617cce7cf5064806c467f678d3b4080d6f1cc50af26ca209417308281b",
+          "68af282623eaa63e5b5c0723d8b8c37ff0777b1a20f8ccb1dccc43997f1ee0e4",
+          "4da4a67a",
+        ),
+      ),
+    ];
+    for (curve, digest_type, expected) in vectors {
+      let key = rfc6979_key(curve);
+      let digest = match_fixed_digest!(
+        digest_type,
+        fn <D>() {
+          D::digest(b"sample").to_vec()
+        },
+        _ => {
+          unreachable!()
+        }
+      );
+      let signature = key
+        .sign_prehashed(digest_type, &digest, None, None, 1)
+        .unwrap();
+      assert_eq!(
+        faster_hex::hex_string(&signature),
+        expected,
+        "{curve} {digest_type}"
+      );
+      assert!(
+        key
+          .verify_prehashed(digest_type, &digest, &signature, None, None, 1)
+          .unwrap()
+      );
+    }
+  }
+
+  #[test]
+  fn secp256k1_deterministic() {
+    // As k256 signs, with SHA-256 for HMAC and a low s.
+    let hash = unhex(EIP155_HASH);
+    let key = k256::SecretKey::from_slice(&EIP155_KEY).unwrap();
+    let expected: k256::ecdsa::Signature = k256::ecdsa::SigningKey::from(key)
+      .sign_prehash(&hash)
+      .unwrap();
+    let signature = eip155_key()
+      .sign_prehashed("sha256", &hash, None, None, 1)
+      .unwrap();
+    assert_eq!(&*signature, expected.to_bytes().as_slice());
   }
 }
@@ -0,0 +1,358 @@
+// Copyright 2018-2025 the Deno authors. MIT license.
+
+//! SLH-DSA signatures (FIPS 205) with the twelve SHA2 and SHAKE parameter
+//! sets. Keys are encoded as in FIPS 205 both raw and inside PKCS#8 and
+//! SPKI, under the NIST OIDs that `slh-dsa` carries.
+
+use signature::Keypair as _;
+use slh_dsa::ParameterSet;
+use slh_dsa::Sha2_128f;
+use slh_dsa::Sha2_128s;
+use slh_dsa::Sha2_192f;
+use slh_dsa::Sha2_192s;
+use slh_dsa::Sha2_256f;
+use slh_dsa::Sha2_256s;
+use slh_dsa::Shake128f;
+use slh_dsa::Shake128s;
+use slh_dsa::Shake192f;
+use slh_dsa::Shake192s;
+use slh_dsa::Shake256f;
+use slh_dsa::Shake256s;
+use slh_dsa::Signature;
+use zeroize::Zeroizing;
+
+pub const MAX_CONTEXT_LENGTH: usize = 255;
+
+macro_rules! parameter_sets {
+  ($($variant:ident($params:ty, n = $n:literal) = $name:literal,)*) => {
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub enum Variant {
+      $($variant,)*
+    }
+
+    impl Variant {
+      const ALL: &[Variant] = &[$(Variant::$variant,)*];
+
+      pub fn name(&self) -> &'static str {
+        match self {
+          $(Variant::$variant => $name,)*
+        }
+      }
+
+      pub fn oid(&self) -> const_oid::ObjectIdentifier {
+        match self {
+          $(Variant::$variant => <$params>::ALGORITHM_OID,)*
+        }
+      }
+
+      /// The length of the security parameter `n`, in bytes.
+      fn n(&self) -> usize {
+        match self {
+          $(Variant::$variant => $n,)*
+        }
+      }
+    }
+
+    #[derive(Clone)]
+    pub enum VerifyingKey {
+      $($variant(slh_dsa::VerifyingKey<$params>),)*
+    }
+
+    #[derive(Clone)]
+    pub enum SigningKey {
+      $($variant(slh_dsa::SigningKey<$params>),)*
+    }
+
+    impl VerifyingKey {
+      /// Any string of the right length is a valid public key encoding.
+      pub fn from_bytes(variant: Variant, bytes: &[u8]) -> Option<Self> {
+        Some(match variant {
+          $(Variant::$variant => VerifyingKey::$variant(
+            slh_dsa::VerifyingKey::try_from(bytes).ok()?,
+          ),)*
+        })
+      }
+
+      pub fn variant(&self) -> Variant {
+        match self {
+          $(VerifyingKey::$variant(_) => Variant::$variant,)*
+        }
+      }
+
+      pub fn to_bytes(&self) -> Vec<u8> {
+        match self {
+          $(VerifyingKey::$variant(key) => key.to_vec(),)*
+        }
+      }
+
+      /// `slh_verify_internal`, over the concatenation of `message`.
+      fn verify_internal(&self, message: &[&[u8]], signature: &[u8]) -> bool {
+        match self {
+          $(VerifyingKey::$variant(key) => verify(key, message, signature),)*
+        }
+      }
+    }
+
+    impl SigningKey {
+      /// `slh_keygen_internal`, from `SK.seed || SK.prf || PK.seed`.
+      fn from_seeds(variant: Variant, seeds: &[u8]) -> Option<Self> {
+        let n = variant.n();
+        if seeds.len() != 3 * n {
+          return None;
+        }
+        let (sk_seed, rest) = seeds.split_at(n);
+        let (sk_prf, pk_seed) = rest.split_at(n);
+        Some(match variant {
+          $(Variant::$variant => SigningKey::$variant(
+            slh_dsa::SigningKey::slh_keygen_internal(sk_seed, sk_prf, pk_seed),
+          ),)*
+        })
+      }
+
+      /// The FIPS 205 encoding, which is also the PKCS#8 `privateKey`. The
+      /// public key in it is taken as is rather than recomputed, which
+      /// would cost as much as generating the key.
+      pub fn from_bytes(variant: Variant, bytes: &[u8]) -> Option<Self> {
+        Some(match variant {
+          $(Variant::$variant => SigningKey::$variant(
+            slh_dsa::SigningKey::try_from(bytes).ok()?,
+          ),)*
+        })
+      }
+
+      pub fn variant(&self) -> Variant {
+        match self {
+          $(SigningKey::$variant(_) => Variant::$variant,)*
+        }
+      }
+
+      pub fn to_bytes(&self) -> Zeroizing<Vec<u8>> {
+        Zeroizing::new(match self {
+          $(SigningKey::$variant(key) => key.to_vec(),)*
+        })
+      }
+
+      pub fn verifying_key(&self) -> VerifyingKey {
+        match self {
+          $(SigningKey::$variant(key) => {
+            VerifyingKey::$variant(key.verifying_key())
+          })*
+        }
+      }
+
+      /// `slh_sign_internal`, with `addrnd` or, if it is `None`,
+      /// deterministically.
+      fn sign_internal(
+        &self,
+        message: &[&[u8]],
+        addrnd: Option<&[u8]>,
+      ) -> Vec<u8> {
+        match self {
+          $(SigningKey::$variant(key) => {
+            key.slh_sign_internal(message, addrnd).to_vec()
+          })*
+        }
+      }
+    }
+  };
+}
+
+parameter_sets! {
+  Sha2_128s(Sha2_128s, n = 16) = "slh-dsa-sha2-128s",
+  Sha2_128f(Sha2_128f, n = 16) = "slh-dsa-sha2-128f",
+  Sha2_192s(Sha2_192s, n = 24) = "slh-dsa-sha2-192s",
+  Sha2_192f(Sha2_192f, n = 24) = "slh-dsa-sha2-192f",
+  Sha2_256s(Sha2_256s, n = 32) = "slh-dsa-sha2-256s",
+  Sha2_256f(Sha2_256f, n = 32) = "slh-dsa-sha2-256f",
+  Shake128s(Shake128s, n = 16) = "slh-dsa-shake-128s",
+  Shake128f(Shake128f, n = 16) = "slh-dsa-shake-128f",
+  Shake192s(Shake192s, n = 24) = "slh-dsa-shake-192s",
+  Shake192f(Shake192f, n = 24) = "slh-dsa-shake-192f",
+  Shake256s(Shake256s, n = 32) = "slh-dsa-shake-256s",
+  Shake256f(Shake256f, n = 32) = "slh-dsa-shake-256f",
+}
+
+fn verify<P: ParameterSet>(
+  key: &slh_dsa::VerifyingKey<P>,
+  message: &[&[u8]],
+  signature: &[u8],
+) -> bool {
+  Signature::<P>::try_from(signature)
+    .is_ok_and(|signature| key.slh_verify_internal(message, &signature).is_ok())
+}
+
+impl Variant {
+  pub fn from_name(name: &str) -> Option<Self> {
+    Self::ALL
+      .iter()
+      .copied()
+      .find(|variant| variant.name() == name)
+  }
+
+  pub fn from_oid(oid: const_oid::ObjectIdentifier) -> Option<Self> {
+    Self::ALL
+      .iter()
+      .copied()
+      .find(|variant| variant.oid() == oid)
+  }
+}
+
+impl VerifyingKey {
+  /// Returns false for a malformed signature rather than an error, as
+  /// OpenSSL does. Callers keep the context within `MAX_CONTEXT_LENGTH`
+  /// bytes.
+  pub fn verify(
+    &self,
+    message: &[u8],
+    context: &[u8],
+    signature: &[u8],
+  ) -> bool {
+    let Ok(context_length) = u8::try_from(context.len()) else {
+      return false;
+    };
+    self.verify_internal(&[&[0, context_length], context, message], signature)
+  }
+}
+
+impl SigningKey {
+  pub fn generate(variant: Variant) -> Self {
+    let mut seeds = Zeroizing::new(vec![0; 3 * variant.n()]);
+    rand::RngCore::fill_bytes(&mut rand::thread_rng(), &mut seeds);
+    Self::from_seeds(variant, &seeds).unwrap()
+  }
+
+  /// Signs with `n` bytes of fresh randomness, the hedged variant of
+  /// FIPS 205. Returns `None` if the context is over `MAX_CONTEXT_LENGTH`
+  /// bytes.
+  pub fn sign(&self, message: &[u8], context: &[u8]) -> Option<Vec<u8>> {
+    let context_length = u8::try_from(context.len()).ok()?;
+    let mut addrnd = vec![0; self.variant().n()];
+    rand::RngCore::fill_bytes(&mut rand::thread_rng(), &mut addrnd);
+    Some(
+      self.sign_internal(
+        &[&[0, context_length], context, message],
+        Some(&addrnd),
+      ),
+    )
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use sha2::Digest as _;
+  use sha2::Sha256;
+
+  use super::*;
+
+  fn unhex(hex: &str) -> Vec<u8> {
+    let mut bytes = vec![0; hex.len() / 2];
+    faster_hex::hex_decode(hex.as_bytes(), &mut bytes).unwrap();
+    bytes
+  }
+
+  fn sha256_hex(bytes: &[u8]) -> String {
+    faster_hex::hex_string(&Sha256::digest(bytes))
+  }
+
+  #[test]
+  fn nist_oids() {
+    for (i, variant) in Variant::ALL.iter().enumerate() {
+      assert_eq!(
+        variant.oid().to_string(),
+        format!("2.16.840.1.101.3.4.3.{}", 20 + i)
+      );
+      assert_eq!(Variant::from_oid(variant.oid()), Some(*variant));
+      assert_eq!(Variant::from_name(variant.name()), Some(*variant));
+    }
+  }
+
+  // The first test cases for SLH-DSA-SHA2-128s and SLH-DSA-SHAKE-256f in
+  // NIST's ACVP SLH-DSA-keyGen-FIPS205 vectors, with the SHA-256 digests of
+  // the public and private keys.
+  #[test]
+  fn acvp_key_gen() {
+    let vectors = [
+      (
+        Variant::Sha2_128s,
+        "2f896d61d9cd9038ca303394fadaa22a24ac5ec1d86a989ca2196c3c8632419c1a05a42fe300e87b16aee116cb2e2363",
+        "532b7653b8d76aa2464284811355057220ef97701f055a6a47b0963fe05d4c09",
+        "aade2c5e40baa0e4dbc4633425a0407873fe79250c46722622d4d979ba0a8c45",
+      ),
+      (
+        Variant::Shake256f,
+        "758161eaf6db91c65f185b26fd490afd808e987b339d2cbb5b3045bc2ed9a33d5a703a6a2a687a8ae1883a9fc957c26e17f5569d5a28c144780df013c9aa83240a0ee387983fc9fbe9d5b80a2787c39c6fc1be3364b9d1fd0c6dc4edf70e28aa",
+        "fcb14c9b9be41e2c8eeb9fec86a37a30dd879e171af672c71e8acd7d71b7e1d4",
+        "e03f239e16f687dee7142f191cb87dd1404e849440b78c096fd5b2a296a0c879",
+      ),
+    ];
+    for (variant, seeds, public, private) in vectors {
+      let key = SigningKey::from_seeds(variant, &unhex(seeds)).unwrap();
+      assert_eq!(sha256_hex(&key.verifying_key().to_bytes()), public);
+      assert_eq!(sha256_hex(&key.to_bytes()), private);
+    }
+  }
+
+  // From NIST's ACVP SLH-DSA-sigGen-FIPS205 vectors, which sign the
+  // message as is: a deterministic case (12) and a hedged one (85), with
+  // the SHA-256 digests of the signatures. Each signature is verified too.
+  #[test]
+  fn acvp_sig_gen() {
+    let vectors = [
+      (
+        Variant::Sha2_256f,
+        "a6bf226c31c203276be4ee7cc025ff2ccff5eb7e56f74201e5e296df30a43a01de78b0d3db09395612dc12672880735fe4710e3f9f3f722f1c4ce7e1b8544a7b7e4871dda761426d693f97a2f9d2de92192b44af0a9008b245ee3d9634db5083e04f40147b963edd14560f61f9c04aaccbbc0485229ffa8b368384502242d8d2",
+        None,
+        "59",
+        "1e24262996a73359989c86a029859f5c28a680c3075a7312cd2fff7eb8002372",
+      ),
+      (
+        Variant::Shake128f,
+        "1804cb1eaabd94a530bd7556ed83c37b2af21e2528df4106e38a765efe2ab987fa1eb3b6be7e3c0ffd720c7698da8878d0b99cefa50474cdd10d7c6a9d278203",
+        Some("860b959515e69f3804e3bf2417e4aa9d"),
+        "42be4fd16bc2a1c53d5e7352246ccd73c9f9f95b55ca3779c48ae7c9a57e6556dd9acbcb9d8281115f21e9e136582f17871ad552d9a0150e8aec54ecd401e7263faaab5384548d9e4cd3fb6bd9c1661876bcca8ca5a40188ee50202a21cde55b8cc3a29d71a5d451a67af6db29045faa1775b9b95c74eae2471cf959a6331843",
+        "b385c42fccc5c7b4be23d8520f3d61fd046aaec715e8a0d45959159e3d1e1023",
+      ),
+    ];
+    for (variant, private, addrnd, message, signature) in vectors {
+      let key = SigningKey::from_bytes(variant, &unhex(private)).unwrap();
+      let addrnd = addrnd.map(unhex);
+      let message = unhex(message);
+      let signed = key.sign_internal(&[&message], addrnd.as_deref());
+      assert_eq!(sha256_hex(&signed), signature);
+
+      let public = key.verifying_key();
+      assert!(public.verify_internal(&[&message], &signed));
+      assert!(!public.verify_internal(&[b"other"], &signed));
+      let mut tampered = signed.clone();
+      tampered[signed.len() / 2] ^= 1;
+      assert!(!public.verify_internal(&[&message], &tampered));
+    }
+  }
+
+  #[test]
+  fn sign_and_verify() {
+    let key = SigningKey::generate(Variant::Shake128f);
+    let public = key.verifying_key();
+    let signature = key.sign(b"message", b"context").unwrap();
+    assert_eq!(signature.len(), 17088);
+    assert!(public.verify(b"message", b"context", &signature));
+    assert!(!public.verify(b"message", b"", &signature));
+    assert!(!public.verify(b"other", b"context", &signature));
+    assert!(!public.verify(b"message", b"context", &signature[1..]));
+    // Hedged signing gives a different signature each time.
+    assert_ne!(key.sign(b"message", b"context").unwrap(), signature);
+
+    let bytes = public.to_bytes();
+    assert_eq!(bytes.len(), 32);
+    let public = VerifyingKey::from_bytes(Variant::Shake128f, &bytes).unwrap();
+    assert!(public.verify(b"message", b"context", &signature));
+    let private = SigningKey::from_bytes(Variant::Shake128f, &key.to_bytes());
+    assert_eq!(private.unwrap().verifying_key().to_bytes(), bytes);
+    assert!(
+      VerifyingKey::from_bytes(Variant::Shake128s, &bytes[1..]).is_none()
+    );
+
+    let context = [0; MAX_CONTEXT_LENGTH + 1];
+    assert!(key.sign(b"message", &context).is_none());
+  }
+}
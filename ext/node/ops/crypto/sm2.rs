@@ -0,0 +1,345 @@
+// Copyright 2018-2025 the Deno authors. MIT license.
+
+//! SM2 signatures (GB/T 32918.2) over the sm2p256v1 curve, on top of the
+//! `sm2` crate. Node takes the signer's ID as bytes, while the crate's
+//! `DistId` is a `str`, so the digest `e = SM3(Z || M)` is computed here
+//! and the crate signs and verifies it as a prehash.
+
+use der::Decode as _;
+use der::Encode as _;
+use der::asn1::UintRef;
+use elliptic_curve::bigint::Encoding as _;
+use elliptic_curve::bigint::U256;
+use elliptic_curve::sec1::ToEncodedPoint as _;
+use rand::rngs::OsRng;
+use signature::hazmat::PrehashVerifier as _;
+use signature::hazmat::RandomizedPrehashSigner as _;
+use sm3::Digest as _;
+use zeroize::Zeroizing;
+
+/// The crate hashes this ID into its own `Z`, which is never used because
+/// only prehashes are signed and verified.
+const UNUSED_DIST_ID: &str = "";
+
+/// The short Weierstrass parameters that go into `Z`.
+struct Curve {
+  a: U256,
+  b: U256,
+  gx: U256,
+  gy: U256,
+}
+
+/// sm2p256v1, from GB/T 32918.5.
+const SM2P256V1: Curve = Curve {
+  a: U256::from_be_hex(
+    "FFFFFFFEFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF00000000FFFFFFFFFFFFFFFC",
+  ),
+  b: U256::from_be_hex(
+    "28E9FA9E9D9F5E344D5A9E4BCF6509A7F39789F515AB8F92DDBCBD414D940E93",
+  ),
+  gx: U256::from_be_hex(
+    "32C4AE2C1F1981195F9904466A39C9948FE30BBFF2660BE1715A4589334C74C7",
+  ),
+  gy: U256::from_be_hex(
+    "BC3736A2F4F6779C59BDCEE36B692153D0A9877CC62A474002DF32E52139F0A0",
+  ),
+};
+
+/// `Z = SM3(ENTL || ID || a || b || xG || yG || xA || yA)`, from section
+/// 5.5 of GB/T 32918.2. The ID must be shorter than 8192 bytes.
+fn z(curve: &Curve, id: &[u8], x: &[u8], y: &[u8]) -> Option<[u8; 32]> {
+  let entl = u16::try_from(id.len().checked_mul(8)?).ok()?;
+  let mut hasher = sm3::Sm3::new();
+  hasher.update(entl.to_be_bytes());
+  hasher.update(id);
+  for value in [curve.a, curve.b, curve.gx, curve.gy] {
+    hasher.update(value.to_be_bytes());
+  }
+  hasher.update(x);
+  hasher.update(y);
+  Some(hasher.finalize().into())
+}
+
+fn digest(
+  curve: &Curve,
+  id: &[u8],
+  x: &[u8],
+  y: &[u8],
+  message: &[u8],
+) -> Option<[u8; 32]> {
+  let z = z(curve, id, x, y)?;
+  Some(
+    sm3::Sm3::new()
+      .chain_update(z)
+      .chain_update(message)
+      .finalize()
+      .into(),
+  )
+}
+
+#[derive(der::Sequence)]
+struct DerSignature<'a> {
+  r: UintRef<'a>,
+  s: UintRef<'a>,
+}
+
+#[derive(Clone)]
+pub struct Signature(sm2::dsa::Signature);
+
+impl Signature {
+  fn field_bytes(bytes: &[u8]) -> Option<sm2::FieldBytes> {
+    let mut padded = sm2::FieldBytes::default();
+    padded
+      .get_mut(32usize.checked_sub(bytes.len())?..)?
+      .copy_from_slice(bytes);
+    Some(padded)
+  }
+
+  /// `None` unless `r` and `s` are in `[1, n - 1]`.
+  fn from_scalars(r: &[u8], s: &[u8]) -> Option<Self> {
+    sm2::dsa::Signature::from_scalars(
+      Self::field_bytes(r)?,
+      Self::field_bytes(s)?,
+    )
+    .ok()
+    .map(Self)
+  }
+
+  pub fn from_der(bytes: &[u8]) -> Option<Self> {
+    let signature = DerSignature::from_der(bytes).ok()?;
+    Self::from_scalars(signature.r.as_bytes(), signature.s.as_bytes())
+  }
+
+  pub fn to_der(&self) -> Box<[u8]> {
+    let r = self.0.r_bytes();
+    let s = self.0.s_bytes();
+    DerSignature {
+      r: UintRef::new(&r).unwrap(),
+      s: UintRef::new(&s).unwrap(),
+    }
+    .to_der()
+    .unwrap()
+    .into_boxed_slice()
+  }
+
+  /// `r || s`, each 32 bytes.
+  pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+    if bytes.len() != 64 {
+      return None;
+    }
+    let (r, s) = bytes.split_at(32);
+    Self::from_scalars(r, s)
+  }
+
+  pub fn to_bytes(&self) -> Box<[u8]> {
+    self.0.to_bytes().into()
+  }
+}
+
+#[derive(Clone)]
+pub struct PublicKey(sm2::dsa::VerifyingKey);
+
+impl PublicKey {
+  /// Accepts uncompressed and compressed points.
+  pub fn from_sec1_bytes(bytes: &[u8]) -> Result<Self, elliptic_curve::Error> {
+    let public_key = sm2::PublicKey::from_sec1_bytes(bytes)?;
+    Ok(Self(
+      sm2::dsa::VerifyingKey::new(UNUSED_DIST_ID, public_key)
+        .map_err(|_| elliptic_curve::Error)?,
+    ))
+  }
+
+  /// The uncompressed point.
+  pub fn to_sec1_bytes(&self) -> Box<[u8]> {
+    self.0.to_encoded_point(false).to_bytes()
+  }
+
+  /// `e = SM3(Z || M)`, the digest that is signed. `None` for an ID of
+  /// 8192 bytes or more.
+  pub fn digest(&self, id: &[u8], message: &[u8]) -> Option<[u8; 32]> {
+    let point = self.0.to_encoded_point(false);
+    digest(&SM2P256V1, id, point.x()?, point.y()?, message)
+  }
+
+  pub fn verify_prehash(&self, e: &[u8; 32], signature: &Signature) -> bool {
+    self.0.verify_prehash(e, &signature.0).is_ok()
+  }
+}
+
+// The signing key carries its own copy of the public key and the
+// distinguishing ID, so it is boxed to keep the key enums small.
+#[derive(Clone)]
+pub struct SecretKey {
+  key: Box<sm2::dsa::SigningKey>,
+  public: PublicKey,
+}
+
+impl SecretKey {
+  /// The key must be in `[1, n - 2]`, so that `1 + d` has an inverse.
+  pub fn from_bytes(bytes: &[u8]) -> Result<Self, elliptic_curve::Error> {
+    let key = sm2::dsa::SigningKey::from_slice(UNUSED_DIST_ID, bytes)
+      .map_err(|_| elliptic_curve::Error)?;
+    let d = Zeroizing::new(U256::from_be_slice(&key.to_bytes()));
+    let n = <sm2::Sm2 as elliptic_curve::Curve>::ORDER;
+    if *d >= n.wrapping_sub(&U256::ONE) {
+      return Err(elliptic_curve::Error);
+    }
+    let public = PublicKey(key.verifying_key().clone());
+    Ok(Self {
+      key: Box::new(key),
+      public,
+    })
+  }
+
+  pub fn random() -> Self {
+    loop {
+      let key = sm2::SecretKey::random(&mut OsRng);
+      if let Ok(key) = Self::from_bytes(&key.to_bytes()) {
+        return key;
+      }
+    }
+  }
+
+  pub fn to_bytes(&self) -> Zeroizing<[u8; 32]> {
+    Zeroizing::new(self.key.to_bytes().into())
+  }
+
+  pub fn public_key(&self) -> &PublicKey {
+    &self.public
+  }
+
+  pub fn sign_prehash(&self, e: &[u8; 32]) -> Signature {
+    loop {
+      if let Ok(signature) = self.key.sign_prehash_with_rng(&mut OsRng, e) {
+        return Signature(signature);
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn unhex(hex: &str) -> Vec<u8> {
+    let mut bytes = vec![0; hex.len() / 2];
+    faster_hex::hex_decode(hex.as_bytes(), &mut bytes).unwrap();
+    bytes
+  }
+
+  /// The example curve of GB/T 32918.2, appendix A. The `sm2` crate only
+  /// has sm2p256v1, so the example signature can't be reproduced with it.
+  const EXAMPLE: Curve = Curve {
+    a: U256::from_be_hex(
+      "787968B4FA32C3FD2417842E73BBFEFF2F3C848B6831D7E0EC65228B3937E498",
+    ),
+    b: U256::from_be_hex(
+      "63E4C6D3B23B0C849CF84241484BFE48F61D59A5B16BA06E6E12D1DA27C5249A",
+    ),
+    gx: U256::from_be_hex(
+      "421DEBD61B62EAB6746434EBC3CC315E32220B3BADD50BDC4C4E6C147FEDD43D",
+    ),
+    gy: U256::from_be_hex(
+      "0680512BCBB42C07D47349D2153B70C4E5D7FDFCBFA36EA1A85841B9E46E09A2",
+    ),
+  };
+
+  const ID: &[u8] = b"ALICE123@YAHOO.COM";
+  const MESSAGE: &[u8] = b"message digest";
+
+  #[test]
+  fn example_digest() {
+    let x =
+      unhex("0AE4C7798AA0F119471BEE11825BE46202BB79E2A5844495E97C04FF4DF2548A");
+    let y =
+      unhex("7C0240F88F1CD4E16352A73C17B7F16F07353E53A176D684A9FE0C6BB798E857");
+    assert_eq!(
+      z(&EXAMPLE, ID, &x, &y).unwrap().to_vec(),
+      unhex("F4A38489E32B45B6F876E3AC2168CA392362DC8F23459C1D1146FC3DBFB7BC9A")
+    );
+    assert_eq!(
+      digest(&EXAMPLE, ID, &x, &y, MESSAGE).unwrap().to_vec(),
+      unhex("B524F552CD82B8B028476E005C377FB19A87E6FC682D48BB5D42E3D9B9EFFE76")
+    );
+  }
+
+  /// The example ID and message on sm2p256v1, signed with a fixed nonce
+  /// and checked with `openssl pkeyutl -verify -rawin -digest sm3`.
+  #[test]
+  fn sm2p256v1_vector() {
+    let key = SecretKey::from_bytes(&unhex(
+      "3945208F7B2144B13F36E38AC6D39F95889393692860B51A42FB81EF4DF7C5B8",
+    ))
+    .unwrap();
+    let public = key.public_key();
+    assert_eq!(
+      public.to_sec1_bytes().to_vec(),
+      unhex(concat!(
+        "04",
+        "09F9DF311E5421A150DD7D161E4BC5C672179FAD1833FC076BB08FF356F35020",
+        "CCEA490CE26775A52DC6EA718CC1AA600AED05FBF35E084A6632F6072DA9AD13",
+      ))
+    );
+
+    let e = public.digest(ID, MESSAGE).unwrap();
+    assert_eq!(
+      e.to_vec(),
+      unhex("ABF7EB631D94615FD1A941D40E99932DDB1899E1DFAE7179B4A79417EA3743E5")
+    );
+
+    let signature = Signature::from_bytes(&unhex(concat!(
+      "B0E3E7D4AC2178F833AD73FA9D1191E41C76C8BFEDB5AD89040BA2E5184BDE58",
+      "CC8D096578F7DD2669AC1AC42F7E722BCFA42B9E0BE0B1B5DF7CA0B53FDD5750",
+    )))
+    .unwrap();
+    assert!(public.verify_prehash(&e, &signature));
+    assert!(
+      !public.verify_prehash(&public.digest(b"", MESSAGE).unwrap(), &signature)
+    );
+
+    // The crate's own `Z` for a `str` ID agrees with `digest`.
+    let signing_key = sm2::dsa::SigningKey::from_slice(
+      std::str::from_utf8(ID).unwrap(),
+      &key.to_bytes()[..],
+    )
+    .unwrap();
+    let signature = signature::Signer::sign(&signing_key, MESSAGE);
+    assert!(public.verify_prehash(&e, &Signature(signature)));
+  }
+
+  #[test]
+  fn sign_and_verify() {
+    let key = SecretKey::random();
+    let public = key.public_key();
+    let e = public.digest(b"1234567812345678", b"data").unwrap();
+    let signature = key.sign_prehash(&e);
+    assert!(public.verify_prehash(&e, &signature));
+    assert!(
+      !public.verify_prehash(&public.digest(b"", b"data").unwrap(), &signature)
+    );
+
+    let der = signature.to_der();
+    assert!(public.verify_prehash(&e, &Signature::from_der(&der).unwrap()));
+    let bytes = signature.to_bytes();
+    assert!(public.verify_prehash(&e, &Signature::from_bytes(&bytes).unwrap()));
+    assert!(Signature::from_bytes(&[0; 64]).is_none());
+
+    let compressed = {
+      let uncompressed = public.to_sec1_bytes();
+      let mut compressed = vec![0x02 | (uncompressed[64] & 1)];
+      compressed.extend_from_slice(&uncompressed[1..33]);
+      compressed
+    };
+    let decoded = PublicKey::from_sec1_bytes(&compressed).unwrap();
+    assert_eq!(decoded.to_sec1_bytes(), public.to_sec1_bytes());
+
+    let n = <sm2::Sm2 as elliptic_curve::Curve>::ORDER;
+    assert!(
+      SecretKey::from_bytes(&n.wrapping_sub(&U256::ONE).to_be_bytes()).is_err()
+    );
+    assert!(SecretKey::from_bytes(&[0; 32]).is_err());
+    let mut off_curve = public.to_sec1_bytes().to_vec();
+    off_curve[64] ^= 1;
+    assert!(PublicKey::from_sec1_bytes(&off_curve).is_err());
+  }
+}
@@ -0,0 +1,90 @@
+// Copyright 2018-2025 the Deno authors. MIT license.
+
+//! Wiping of key-derived state in the RustCrypto hash and MAC contexts,
+//! which don't implement `Zeroize` themselves.
+
+use std::ops::Deref;
+use std::ops::DerefMut;
+
+use zeroize::Zeroize;
+
+/// Overwrites the bytes of `value` with zeros, using the volatile writes of
+/// `zeroize` so that they aren't optimized out.
+///
+/// # Safety
+///
+/// `T` must be plain data: no pointers, no `Drop` impl that reads its
+/// fields, and all-zero bytes must be a valid `T`. The RustCrypto digest
+/// and MAC contexts are made of state arrays, block buffers and counters.
+pub unsafe fn wipe<T>(value: &mut T) {
+  // SAFETY: the bytes of `value` are valid for writes and, per the
+  // caller, zero bytes leave a valid `T` behind.
+  let bytes = unsafe {
+    std::slice::from_raw_parts_mut(
+      (value as *mut T).cast::<u8>(),
+      size_of::<T>(),
+    )
+  };
+  bytes.zeroize();
+}
+
+/// A boxed context that is wiped before its allocation is freed.
+pub struct Wiped<T>(Box<T>);
+
+impl<T> Wiped<T> {
+  /// # Safety
+  ///
+  /// `T` must meet the requirements of [`wipe`].
+  pub unsafe fn new(value: T) -> Self {
+    Self(Box::new(value))
+  }
+
+  /// Copies the context out, for an API that consumes it. The copy isn't
+  /// wiped.
+  pub fn clone_inner(&self) -> T
+  where
+    T: Clone,
+  {
+    T::clone(&self.0)
+  }
+}
+
+impl<T: Clone> Clone for Wiped<T> {
+  fn clone(&self) -> Self {
+    Self(self.0.clone())
+  }
+}
+
+impl<T> Deref for Wiped<T> {
+  type Target = T;
+
+  fn deref(&self) -> &T {
+    &self.0
+  }
+}
+
+impl<T> DerefMut for Wiped<T> {
+  fn deref_mut(&mut self) -> &mut T {
+    &mut self.0
+  }
+}
+
+impl<T> Drop for Wiped<T> {
+  fn drop(&mut self) {
+    // SAFETY: checked by the caller of `Wiped::new`.
+    unsafe { wipe(&mut *self.0) };
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn wipes() {
+    let mut state = ([0xaau8; 13], 0x1234u32, 7u64);
+    // SAFETY: a tuple of integers.
+    unsafe { wipe(&mut state) };
+    assert_eq!(state, ([0; 13], 0, 0));
+  }
+}
@@ -0,0 +1,132 @@
+// Copyright 2018-2025 the Deno authors. MIT license.
+
+//! X448 key agreement (RFC 7748, section 5). `ed448-goldilocks` provides
+//! the Montgomery ladder but leaves the clamping of scalars to the caller.
+
+use ed448_goldilocks::Scalar;
+use ed448_goldilocks::curve::MontgomeryPoint;
+use zeroize::Zeroize;
+use zeroize::Zeroizing;
+
+use super::wipe::wipe;
+
+pub const KEY_LENGTH: usize = 56;
+
+/// The u-coordinate of the base point.
+const BASE_POINT: [u8; KEY_LENGTH] = {
+  let mut u = [0; KEY_LENGTH];
+  u[0] = 5;
+  u
+};
+
+fn x448(k: &[u8; KEY_LENGTH], u: &[u8; KEY_LENGTH]) -> [u8; KEY_LENGTH] {
+  let mut clamped = *k;
+  clamped[0] &= 0xfc;
+  clamped[55] |= 0x80;
+  let mut scalar = Scalar::from_bytes(clamped);
+  clamped.zeroize();
+  let point = &MontgomeryPoint(*u) * &scalar;
+  // SAFETY: a scalar is an array of integers.
+  unsafe { wipe(&mut scalar) };
+  point.0
+}
+
+#[derive(Clone)]
+pub struct PublicKey([u8; KEY_LENGTH]);
+
+impl PublicKey {
+  pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+    Some(Self(bytes.try_into().ok()?))
+  }
+
+  pub fn as_bytes(&self) -> &[u8; KEY_LENGTH] {
+    &self.0
+  }
+}
+
+#[derive(Clone)]
+pub struct StaticSecret {
+  bytes: Zeroizing<[u8; KEY_LENGTH]>,
+  public: PublicKey,
+}
+
+impl StaticSecret {
+  pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+    let bytes = Zeroizing::new(<[u8; KEY_LENGTH]>::try_from(bytes).ok()?);
+    let public = PublicKey(x448(&bytes, &BASE_POINT));
+    Some(Self { bytes, public })
+  }
+
+  pub fn generate() -> Self {
+    let mut bytes = Zeroizing::new([0; KEY_LENGTH]);
+    rand::RngCore::fill_bytes(&mut rand::thread_rng(), &mut *bytes);
+    Self::from_bytes(&*bytes).unwrap()
+  }
+
+  pub fn as_bytes(&self) -> &[u8; KEY_LENGTH] {
+    &self.bytes
+  }
+
+  pub fn public_key(&self) -> &PublicKey {
+    &self.public
+  }
+
+  /// Returns `None` for the all-zero secret of a low-order public key, as
+  /// RFC 7748, section 6.2 requires.
+  pub fn diffie_hellman(
+    &self,
+    public: &PublicKey,
+  ) -> Option<Zeroizing<[u8; KEY_LENGTH]>> {
+    let secret = Zeroizing::new(x448(&self.bytes, &public.0));
+    let zero = secret.iter().fold(0, |acc, byte| acc | byte);
+    (zero != 0).then_some(secret)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn unhex(hex: &str) -> Vec<u8> {
+    let mut bytes = vec![0; hex.len() / 2];
+    faster_hex::hex_decode(hex.as_bytes(), &mut bytes).unwrap();
+    bytes
+  }
+
+  // RFC 7748, section 6.2.
+  const ALICE_PRIVATE: &str = "9a8f4925d1519f5775cf46b04b5800d4ee9ee8bae8bc5565d498c28dd9c9baf574a9419744897391006382a6f127ab1d9ac2d8c0a598726b";
+  const ALICE_PUBLIC: &str = "9b08f7cc31b7e3e67d22d5aea121074a273bd2b83de09c63faa73d2c22c5d9bbc836647241d953d40c5b12da88120d53177f80e532c41fa0";
+  const BOB_PRIVATE: &str = "1c306a7ac2a0e2e0990b294470cba339e6453772b075811d8fad0d1d6927c120bb5ee8972b0d3e21374c9c921b09d1b0366f10b65173992d";
+  const BOB_PUBLIC: &str = "3eb7a829b0cd20f5bcfc0b599b6feccf6da4627107bdb0d4f345b43027d8b972fc3e34fb4232a13ca706dcb57aec3dae07bdc1c67bf33609";
+  const SHARED_SECRET: &str = "07fff4181ac6cc95ec1c16a94a0f74d12da232ce40a77552281d282bb60c0b56fd2464c335543936521c24403085d59a449a5037514a879d";
+
+  #[test]
+  fn rfc7748_vectors() {
+    let alice = StaticSecret::from_bytes(&unhex(ALICE_PRIVATE)).unwrap();
+    let bob = StaticSecret::from_bytes(&unhex(BOB_PRIVATE)).unwrap();
+    assert_eq!(
+      faster_hex::hex_string(alice.public_key().as_bytes()),
+      ALICE_PUBLIC
+    );
+    assert_eq!(
+      faster_hex::hex_string(bob.public_key().as_bytes()),
+      BOB_PUBLIC
+    );
+
+    let secret = alice.diffie_hellman(bob.public_key()).unwrap();
+    assert_eq!(faster_hex::hex_string(&*secret), SHARED_SECRET);
+    let secret = bob.diffie_hellman(alice.public_key()).unwrap();
+    assert_eq!(faster_hex::hex_string(&*secret), SHARED_SECRET);
+  }
+
+  #[test]
+  fn rejects_low_order_public_keys() {
+    let alice = StaticSecret::from_bytes(&unhex(ALICE_PRIVATE)).unwrap();
+    let mut one = [0; KEY_LENGTH];
+    one[0] = 1;
+    for u in [[0; KEY_LENGTH], one] {
+      let public = PublicKey::from_bytes(&u).unwrap();
+      assert!(alice.diffie_hellman(&public).is_none());
+    }
+  }
+}
@@ -2,8 +2,11 @@
 
 use std::ops::Deref;
 
+use deno_core::ToJsBuffer;
 use deno_core::op2;
 use digest::Digest;
+use rsa::traits::PublicKeyParts as _;
+use spki::EncodePublicKey as _;
 use x509_parser::der_parser::asn1_rs::Any;
 use x509_parser::der_parser::asn1_rs::Tag;
 use x509_parser::der_parser::oid::Oid;
@@ -15,6 +18,16 @@ use yoke::Yoke;
 use yoke::Yokeable;
 
 use super::KeyObjectHandle;
+use super::digest::Hash;
+use super::digest::HashError;
+use super::keys::AsymmetricPublicKey;
+use super::keys::EcPublicKey;
+use super::keys::RsaPssDetails;
+use super::keys::RsaPssParameters;
+use super::keys::RsaPssPublicKey;
+use super::keys::parse_rsa_pss_params;
+use super::rsa_pss;
+use crate::ops::crypto::digest::match_fixed_digest_with_oid;
 
 enum CertificateSources {
   Der(Box<[u8]>),
@@ -37,22 +50,27 @@ impl deno_core::GarbageCollected for Certificate {
 }
 
 impl Certificate {
-  fn fingerprint<D: Digest>(&self) -> Option<String> {
-    if let CertificateSources::Pem(pem) = self.inner.backing_cart().as_ref() {
-      let mut hasher = D::new();
-      hasher.update(&pem.contents);
-      let bytes = hasher.finalize();
-      // OpenSSL returns colon separated upper case hex values.
-      let mut hex = String::with_capacity(bytes.len() * 2);
-      for byte in bytes {
-        hex.push_str(&format!("{:02X}:", byte));
-      }
-      hex.pop();
-      Some(hex)
-    } else {
-      None
+  /// The certificate's DER encoding.
+  fn der(&self) -> &[u8] {
+    match self.inner.backing_cart().as_ref() {
+      CertificateSources::Der(buf) => buf,
+      CertificateSources::Pem(pem) => &pem.contents,
     }
   }
+
+  fn fingerprint<D: Digest>(&self) -> String {
+    print_fingerprint(&D::digest(self.der()))
+  }
+}
+
+// OpenSSL returns colon separated upper case hex values.
+fn print_fingerprint(bytes: &[u8]) -> String {
+  let mut hex = String::with_capacity(bytes.len() * 3);
+  for byte in bytes {
+    hex.push_str(&format!("{:02X}:", byte));
+  }
+  hex.pop();
+  hex
 }
 
 impl<'a> Deref for CertificateView<'a> {
@@ -92,107 +110,705 @@ pub fn op_node_x509_parse(
   Ok(Certificate { inner })
 }
 
+/// Like OpenSSL's `X509_check_ca()` returning 1: basicConstraints must
+/// mark a CA, and keyUsage, if present, must allow signing certificates.
+fn is_ca(cert: &X509Certificate) -> bool {
+  if let Ok(Some(key_usage)) = cert.key_usage() {
+    if !key_usage.value.key_cert_sign() {
+      return false;
+    }
+  }
+  matches!(cert.basic_constraints(), Ok(Some(constraints)) if constraints.value.ca)
+}
+
 #[op2(fast)]
 pub fn op_node_x509_ca(#[cppgc] cert: &Certificate) -> bool {
-  let cert = cert.inner.get().deref();
-  cert.is_ca()
+  is_ca(cert.inner.get().deref())
 }
 
-#[op2(fast)]
-pub fn op_node_x509_check_email(
-  #[cppgc] cert: &Certificate,
-  #[string] email: &str,
-) -> bool {
-  let cert = cert.inner.get().deref();
-  let subject = cert.subject();
-  if subject
-    .iter_email()
-    .any(|e| e.as_str().unwrap_or("") == email)
+#[derive(Debug, thiserror::Error, deno_error::JsError)]
+#[class(type)]
+#[property("code" = "ERR_INVALID_ARG_VALUE")]
+pub enum X509CheckError {
+  #[error("Invalid name")]
+  InvalidName,
+  #[error("Invalid IP")]
+  InvalidIp,
+}
+
+// The `X509_CHECK_FLAG_*` values from OpenSSL, which the options of
+// checkHost() and checkEmail() map to.
+const CHECK_FLAG_ALWAYS_CHECK_SUBJECT: u32 = 0x1;
+const CHECK_FLAG_NO_WILDCARDS: u32 = 0x2;
+const CHECK_FLAG_NO_PARTIAL_WILDCARDS: u32 = 0x4;
+const CHECK_FLAG_MULTI_LABEL_WILDCARDS: u32 = 0x8;
+const CHECK_FLAG_SINGLE_LABEL_SUBDOMAINS: u32 = 0x10;
+const CHECK_FLAG_NEVER_CHECK_SUBJECT: u32 = 0x20;
+// Set for host names starting with a dot, which match any subdomain.
+const CHECK_FLAG_DOT_SUBDOMAINS: u32 = 0x8000;
+
+#[derive(Clone, Copy)]
+enum CheckType {
+  Email,
+  Dns,
+  Ip,
+}
+
+/// Matches a name against the certificate as OpenSSL's `do_x509_check()`
+/// does, returning the certificate's spelling of the matching name.
+/// subjectAltName entries of the checked type take precedence: the subject
+/// is only consulted when there are none, unless the flags say otherwise.
+fn check_names(
+  cert: &X509Certificate,
+  check_type: CheckType,
+  name: &[u8],
+  mut flags: u32,
+) -> Option<String> {
+  let equal: fn(&[u8], &[u8], u32) -> bool = match check_type {
+    CheckType::Email => equal_email,
+    CheckType::Dns => {
+      if name.len() > 1 && name[0] == b'.' {
+        flags |= CHECK_FLAG_DOT_SUBDOMAINS;
+      }
+      if flags & CHECK_FLAG_NO_WILDCARDS != 0 {
+        equal_nocase
+      } else {
+        equal_wildcard
+      }
+    }
+    CheckType::Ip => equal_case,
+  };
+
+  if let Some(subject_alt) = cert.subject_alternative_name().ok().flatten() {
+    let mut present = false;
+    for general_name in &subject_alt.value.general_names {
+      let value = match (check_type, general_name) {
+        (CheckType::Email, GeneralName::RFC822Name(value))
+        | (CheckType::Dns, GeneralName::DNSName(value)) => value.as_bytes(),
+        (CheckType::Ip, GeneralName::IPAddress(value)) => value,
+        _ => continue,
+      };
+      present = true;
+      if !value.is_empty() && equal(value, name, flags) {
+        return Some(String::from_utf8_lossy(value).into_owned());
+      }
+    }
+    if present && flags & CHECK_FLAG_ALWAYS_CHECK_SUBJECT == 0 {
+      return None;
+    }
+  }
+
+  let oid = match check_type {
+    CheckType::Email => oid_registry::OID_PKCS9_EMAIL_ADDRESS,
+    CheckType::Dns => oid_registry::OID_X509_COMMON_NAME,
+    CheckType::Ip => return None,
+  };
+  if flags & CHECK_FLAG_NEVER_CHECK_SUBJECT != 0 {
+    return None;
+  }
+  cert
+    .subject()
+    .iter_by_oid(&oid)
+    .filter_map(|attr| attr.as_str().ok())
+    .find(|value| !value.is_empty() && equal(value.as_bytes(), name, flags))
+    .map(str::to_string)
+}
+
+/// With `CHECK_FLAG_DOT_SUBDOMAINS`, a pattern longer than the subject
+/// matches on its suffix, e.g. `www.example.com` for `.example.com`.
+fn skip_prefix(pattern: &[u8], subject_len: usize, flags: u32) -> &[u8] {
+  if flags & CHECK_FLAG_DOT_SUBDOMAINS == 0 {
+    return pattern;
+  }
+  let mut suffix = pattern;
+  while suffix.len() > subject_len && suffix[0] != 0 {
+    if flags & CHECK_FLAG_SINGLE_LABEL_SUBDOMAINS != 0 && suffix[0] == b'.' {
+      break;
+    }
+    suffix = &suffix[1..];
+  }
+  if suffix.len() == subject_len {
+    suffix
+  } else {
+    pattern
+  }
+}
+
+fn equal_case(pattern: &[u8], subject: &[u8], flags: u32) -> bool {
+  skip_prefix(pattern, subject.len(), flags) == subject
+}
+
+fn equal_nocase(pattern: &[u8], subject: &[u8], flags: u32) -> bool {
+  let pattern = skip_prefix(pattern, subject.len(), flags);
+  !pattern.contains(&0) && pattern.eq_ignore_ascii_case(subject)
+}
+
+/// The domain is compared case-insensitively. The '@' is searched for from
+/// the end, so that quoted local parts don't need to be parsed.
+fn equal_email(pattern: &[u8], subject: &[u8], _flags: u32) -> bool {
+  if pattern.len() != subject.len() {
+    return false;
+  }
+  match (0..pattern.len())
+    .rev()
+    .find(|&i| pattern[i] == b'@' || subject[i] == b'@')
   {
-    return true;
+    Some(at) if at > 0 => {
+      equal_nocase(&pattern[at..], &subject[at..], 0)
+        && pattern[..at] == subject[..at]
+    }
+    _ => pattern == subject,
   }
+}
 
-  let subject_alt = cert
-    .extensions()
-    .iter()
-    .find(|e| e.oid == x509_parser::oid_registry::OID_X509_EXT_SUBJECT_ALT_NAME)
-    .and_then(|e| match e.parsed_extension() {
-      extensions::ParsedExtension::SubjectAlternativeName(s) => Some(s),
-      _ => None,
-    });
+fn equal_wildcard(pattern: &[u8], subject: &[u8], flags: u32) -> bool {
+  // Subjects starting with a dot only match patterns on their suffix.
+  let star = if subject.len() > 1 && subject[0] == b'.' {
+    None
+  } else {
+    valid_star(pattern, flags)
+  };
+  match star {
+    Some(star) => {
+      wildcard_match(&pattern[..star], &pattern[star + 1..], subject, flags)
+    }
+    None => equal_nocase(pattern, subject, flags),
+  }
+}
 
-  if let Some(subject_alt) = subject_alt {
-    for name in &subject_alt.general_names {
-      if let extensions::GeneralName::RFC822Name(n) = name {
-        if *n == email {
-          return true;
+/// The position of the only wildcard allowed by RFC 6125: at the start or
+/// end of the first label, which must not be an IDNA label or the final
+/// one, with at least two dots after it.
+fn valid_star(pattern: &[u8], flags: u32) -> Option<usize> {
+  const LABEL_START: u8 = 1 << 0;
+  const LABEL_IDNA: u8 = 1 << 1;
+  const LABEL_HYPHEN: u8 = 1 << 2;
+
+  let mut star = None;
+  let mut state = LABEL_START;
+  let mut dots = 0;
+  for (i, &c) in pattern.iter().enumerate() {
+    match c {
+      b'*' => {
+        let at_start = state & LABEL_START != 0;
+        let at_end = i == pattern.len() - 1 || pattern[i + 1] == b'.';
+        if star.is_some() || state & LABEL_IDNA != 0 || dots > 0 {
+          return None;
+        }
+        if flags & CHECK_FLAG_NO_PARTIAL_WILDCARDS != 0
+          && (!at_start || !at_end)
+        {
+          return None;
+        }
+        // No `foo*bar` wildcards.
+        if !at_start && !at_end {
+          return None;
+        }
+        star = Some(i);
+        state &= !LABEL_START;
+      }
+      c if c.is_ascii_alphanumeric() => {
+        if state & LABEL_START != 0
+          && pattern.len() - i >= 4
+          && pattern[i..i + 4].eq_ignore_ascii_case(b"xn--")
+        {
+          state |= LABEL_IDNA;
+        }
+        state &= !(LABEL_HYPHEN | LABEL_START);
+      }
+      b'.' => {
+        if state & (LABEL_HYPHEN | LABEL_START) != 0 {
+          return None;
+        }
+        state = LABEL_START;
+        dots += 1;
+      }
+      b'-' => {
+        if state & LABEL_START != 0 {
+          return None;
         }
+        state |= LABEL_HYPHEN;
       }
+      _ => return None,
+    }
+  }
+  if state & (LABEL_START | LABEL_HYPHEN) != 0 || dots < 2 {
+    return None;
+  }
+  star
+}
+
+fn wildcard_match(
+  prefix: &[u8],
+  suffix: &[u8],
+  subject: &[u8],
+  flags: u32,
+) -> bool {
+  if subject.len() < prefix.len() + suffix.len() {
+    return false;
+  }
+  let (head, rest) = subject.split_at(prefix.len());
+  let (wildcard, tail) = rest.split_at(rest.len() - suffix.len());
+  if !equal_nocase(prefix, head, flags) || !equal_nocase(tail, suffix, flags) {
+    return false;
+  }
+  let mut allow_multi = false;
+  let mut allow_idna = false;
+  // A wildcard making up the whole first label matches at least one
+  // character.
+  if prefix.is_empty() && suffix.first() == Some(&b'.') {
+    if wildcard.is_empty() {
+      return false;
     }
+    allow_idna = true;
+    allow_multi = flags & CHECK_FLAG_MULTI_LABEL_WILDCARDS != 0;
+  }
+  // IDNA labels can't match partial wildcards.
+  if !allow_idna
+    && subject.len() >= 4
+    && subject[..4].eq_ignore_ascii_case(b"xn--")
+  {
+    return false;
+  }
+  // The wildcard may match a literal '*'.
+  if wildcard == b"*" {
+    return true;
+  }
+  wildcard.iter().all(|&c| {
+    c.is_ascii_alphanumeric() || c == b'-' || (allow_multi && c == b'.')
+  })
+}
+
+/// Like OpenSSL, embedded NULs are rejected, except for a terminating one.
+fn check_name(name: &str) -> Result<&[u8], X509CheckError> {
+  let name = name.as_bytes();
+  let name = match name {
+    [rest @ .., 0] if name.len() > 1 => rest,
+    _ => name,
+  };
+  if name.contains(&0) {
+    return Err(X509CheckError::InvalidName);
   }
+  Ok(name)
+}
 
-  false
+/// Parses an IP address as OpenSSL does, which allows leading zeros in the
+/// parts of an IPv4 address.
+fn parse_ip(ip: &str) -> Option<Vec<u8>> {
+  if ip.contains(':') {
+    return ip
+      .parse::<std::net::Ipv6Addr>()
+      .ok()
+      .map(|ip| ip.octets().to_vec());
+  }
+  let octets = ip
+    .split('.')
+    .map(|part| {
+      if part.bytes().all(|b| b.is_ascii_digit()) {
+        part.parse::<u8>().ok()
+      } else {
+        None
+      }
+    })
+    .collect::<Option<Vec<_>>>()?;
+  (octets.len() == 4).then_some(octets)
 }
 
 #[op2(fast)]
+pub fn op_node_x509_check_email(
+  #[cppgc] cert: &Certificate,
+  #[string] email: &str,
+  flags: u32,
+) -> Result<bool, X509CheckError> {
+  let cert = cert.inner.get().deref();
+  let email = check_name(email)?;
+  Ok(check_names(cert, CheckType::Email, email, flags).is_some())
+}
+
+#[op2]
+#[string]
 pub fn op_node_x509_check_host(
   #[cppgc] cert: &Certificate,
   #[string] host: &str,
-) -> bool {
+  flags: u32,
+) -> Result<Option<String>, X509CheckError> {
   let cert = cert.inner.get().deref();
+  let host = check_name(host)?;
+  Ok(check_names(cert, CheckType::Dns, host, flags))
+}
 
-  let subject = cert.subject();
-  if subject
-    .iter_common_name()
-    .any(|e| e.as_str().unwrap_or("") == host)
-  {
-    return true;
+#[op2(fast)]
+pub fn op_node_x509_check_ip(
+  #[cppgc] cert: &Certificate,
+  #[string] ip: &str,
+) -> Result<bool, X509CheckError> {
+  let cert = cert.inner.get().deref();
+  let ip = parse_ip(ip).ok_or(X509CheckError::InvalidIp)?;
+  Ok(check_names(cert, CheckType::Ip, &ip, 0).is_some())
+}
+
+/// How a certificate signature algorithm signs, and with which digest.
+#[derive(Clone, Copy)]
+enum SignatureScheme {
+  Pkcs1(&'static str),
+  Pss,
+  Ecdsa(&'static str),
+  Dsa(&'static str),
+  Ed25519,
+  Ed448,
+}
+
+const SIGNATURE_ALGORITHMS: &[(&str, SignatureScheme)] = &[
+  ("1.2.840.113549.1.1.4", SignatureScheme::Pkcs1("md5")),
+  ("1.2.840.113549.1.1.5", SignatureScheme::Pkcs1("sha1")),
+  ("1.2.840.113549.1.1.14", SignatureScheme::Pkcs1("sha224")),
+  ("1.2.840.113549.1.1.11", SignatureScheme::Pkcs1("sha256")),
+  ("1.2.840.113549.1.1.12", SignatureScheme::Pkcs1("sha384")),
+  ("1.2.840.113549.1.1.13", SignatureScheme::Pkcs1("sha512")),
+  (
+    "1.2.840.113549.1.1.15",
+    SignatureScheme::Pkcs1("sha512-224"),
+  ),
+  (
+    "1.2.840.113549.1.1.16",
+    SignatureScheme::Pkcs1("sha512-256"),
+  ),
+  (
+    "2.16.840.1.101.3.4.3.13",
+    SignatureScheme::Pkcs1("sha3-224"),
+  ),
+  (
+    "2.16.840.1.101.3.4.3.14",
+    SignatureScheme::Pkcs1("sha3-256"),
+  ),
+  (
+    "2.16.840.1.101.3.4.3.15",
+    SignatureScheme::Pkcs1("sha3-384"),
+  ),
+  (
+    "2.16.840.1.101.3.4.3.16",
+    SignatureScheme::Pkcs1("sha3-512"),
+  ),
+  ("1.2.840.113549.1.1.10", SignatureScheme::Pss),
+  ("1.2.840.10045.4.1", SignatureScheme::Ecdsa("sha1")),
+  ("1.2.840.10045.4.3.1", SignatureScheme::Ecdsa("sha224")),
+  ("1.2.840.10045.4.3.2", SignatureScheme::Ecdsa("sha256")),
+  ("1.2.840.10045.4.3.3", SignatureScheme::Ecdsa("sha384")),
+  ("1.2.840.10045.4.3.4", SignatureScheme::Ecdsa("sha512")),
+  ("2.16.840.1.101.3.4.3.9", SignatureScheme::Ecdsa("sha3-224")),
+  (
+    "2.16.840.1.101.3.4.3.10",
+    SignatureScheme::Ecdsa("sha3-256"),
+  ),
+  (
+    "2.16.840.1.101.3.4.3.11",
+    SignatureScheme::Ecdsa("sha3-384"),
+  ),
+  (
+    "2.16.840.1.101.3.4.3.12",
+    SignatureScheme::Ecdsa("sha3-512"),
+  ),
+  ("1.2.840.10040.4.3", SignatureScheme::Dsa("sha1")),
+  ("2.16.840.1.101.3.4.3.1", SignatureScheme::Dsa("sha224")),
+  ("2.16.840.1.101.3.4.3.2", SignatureScheme::Dsa("sha256")),
+  ("1.3.101.112", SignatureScheme::Ed25519),
+  ("1.3.101.113", SignatureScheme::Ed448),
+];
+
+fn signature_scheme(
+  algorithm: &AlgorithmIdentifier,
+) -> Option<SignatureScheme> {
+  let oid = algorithm.algorithm.to_id_string();
+  SIGNATURE_ALGORITHMS
+    .iter()
+    .find(|(candidate, _)| *candidate == oid)
+    .map(|(_, scheme)| *scheme)
+}
+
+/// The hash, MGF1 hash and salt length of an RSASSA-PSS signature. Unlike
+/// a key's, the salt length defaults to 20 whatever the hash.
+fn pss_signature_params(
+  algorithm: &AlgorithmIdentifier,
+) -> Option<RsaPssDetails> {
+  let params = algorithm.parameters.as_ref()?;
+  if params.tag() != Tag::Sequence {
+    return None;
   }
+  let params =
+    spki::der::asn1::AnyRef::new(spki::der::Tag::Sequence, params.data).ok()?;
+  let salt_length = RsaPssParameters::try_from(params).ok()?.salt_length;
+  let details = parse_rsa_pss_params(Some(params)).ok()??;
+  Some(RsaPssDetails {
+    salt_length: salt_length.unwrap_or(20),
+    ..details
+  })
+}
+
+/// Like OpenSSL's `X509_verify()`: whether `key` signed the certificate.
+/// A key of the wrong type or an unsupported algorithm fails verification.
+fn verify_signature(cert: &X509Certificate, key: &KeyObjectHandle) -> bool {
+  let Some(public_key) = key.as_public_key() else {
+    return false;
+  };
+  let Some(scheme) = signature_scheme(&cert.signature_algorithm) else {
+    return false;
+  };
+  let tbs = cert.tbs_certificate.as_ref();
+  let signature = cert.signature_value.data.as_ref();
 
-  let subject_alt = cert
+  let digest_type = match (scheme, &*public_key) {
+    (SignatureScheme::Ed25519, AsymmetricPublicKey::Ed25519(key)) => {
+      return aws_lc_rs::signature::UnparsedPublicKey::new(
+        &aws_lc_rs::signature::ED25519,
+        key.as_bytes().as_slice(),
+      )
+      .verify(tbs, signature)
+      .is_ok();
+    }
+    (SignatureScheme::Ed448, AsymmetricPublicKey::Ed448(key)) => {
+      return key.verify(tbs, signature);
+    }
+    (
+      SignatureScheme::Pss,
+      AsymmetricPublicKey::Rsa(key)
+      | AsymmetricPublicKey::RsaPss(RsaPssPublicKey { key, .. }),
+    ) => {
+      let Some(details) = pss_signature_params(&cert.signature_algorithm)
+      else {
+        return false;
+      };
+      return match_fixed_digest_with_oid!(
+        details.hash_algorithm.as_str(),
+        fn <D>() {
+          rsa_pss::verify::<D>(
+            key,
+            Some(details.mf1_hash_algorithm)
+              .filter(|mgf1| *mgf1 != details.hash_algorithm),
+            &D::digest(tbs),
+            signature,
+            Some(details.salt_length as usize),
+          )
+        },
+        _ => {
+          false
+        }
+      );
+    }
+    (SignatureScheme::Pkcs1(digest_type), AsymmetricPublicKey::Rsa(_))
+    | (SignatureScheme::Ecdsa(digest_type), AsymmetricPublicKey::Ec(_))
+    | (SignatureScheme::Dsa(digest_type), AsymmetricPublicKey::Dsa(_)) => {
+      digest_type
+    }
+    _ => return false,
+  };
+
+  let Some(digest) = Hash::digest_oneshot(digest_type, tbs) else {
+    return false;
+  };
+  key
+    .verify_prehashed(digest_type, &digest, signature, None, None, 0)
+    .unwrap_or(false)
+}
+
+#[op2(fast)]
+pub fn op_node_x509_verify(
+  #[cppgc] cert: &Certificate,
+  #[cppgc] key: &KeyObjectHandle,
+) -> bool {
+  verify_signature(cert.inner.get().deref(), key)
+}
+
+/// A string attribute value in UTF-8, like OpenSSL's
+/// `ASN1_STRING_to_UTF8()`.
+fn decode_string(value: &Any) -> Option<String> {
+  let data = value.data;
+  let decoded = match value.tag() {
+    Tag::Utf8String
+    | Tag::PrintableString
+    | Tag::NumericString
+    | Tag::Ia5String
+    | Tag::VisibleString => String::from_utf8(data.to_vec()).ok()?,
+    Tag::T61String => data.iter().map(|&byte| byte as char).collect(),
+    Tag::BmpString => {
+      if data.len() % 2 != 0 {
+        return None;
+      }
+      let units = data
+        .chunks(2)
+        .map(|unit| u16::from_be_bytes([unit[0], unit[1]]));
+      char::decode_utf16(units).collect::<Result<_, _>>().ok()?
+    }
+    Tag::UniversalString => {
+      if data.len() % 4 != 0 {
+        return None;
+      }
+      data
+        .chunks(4)
+        .map(|unit| {
+          char::from_u32(u32::from_be_bytes([
+            unit[0], unit[1], unit[2], unit[3],
+          ]))
+        })
+        .collect::<Option<_>>()?
+    }
+    _ => return None,
+  };
+  Some(decoded)
+}
+
+/// A string attribute value as OpenSSL canonicalizes it to compare names:
+/// in UTF-8, with ASCII lowercased, and with runs of whitespace collapsed
+/// and trimmed. Numeric strings are compared as they are encoded.
+fn canonical_string(value: &Any) -> Option<String> {
+  if value.tag() == Tag::NumericString {
+    return None;
+  }
+  let decoded = decode_string(value)?;
+  let words = decoded
+    .split(|c: char| c.is_ascii_whitespace() || c == '\x0b')
+    .filter(|word| !word.is_empty())
+    .collect::<Vec<_>>();
+  Some(words.join(" ").to_ascii_lowercase())
+}
+
+/// An attribute's type and its canonical value, or the encoded value if it
+/// isn't a string.
+type CanonicalAttribute<'a> = (String, Result<String, &'a [u8]>);
+
+/// Each RDN's attributes, canonicalized and sorted, so that two names
+/// compare equal when OpenSSL's `X509_NAME_cmp()` would return 0.
+fn canonical_name<'a>(name: &'a X509Name) -> Vec<Vec<CanonicalAttribute<'a>>> {
+  name
+    .iter_rdn()
+    .map(|rdn| {
+      let mut attrs = rdn
+        .iter()
+        .map(|attr| {
+          let value = attr.attr_value();
+          let canonical =
+            canonical_string(value).ok_or_else(|| value.as_bytes());
+          (attr.attr_type().to_id_string(), canonical)
+        })
+        .collect::<Vec<_>>();
+      attrs.sort();
+      attrs
+    })
+    .collect()
+}
+
+fn names_equal(a: &X509Name, b: &X509Name) -> bool {
+  canonical_name(a) == canonical_name(b)
+}
+
+/// Like OpenSSL's `X509_check_issued()` returning `X509_V_OK`. The issuer
+/// must be named as the subject's issuer, match its authority key
+/// identifier, have a key of the type the subject's signature uses, and be
+/// allowed by its keyUsage to sign certificates. The signature itself is
+/// not checked.
+fn check_issued(issuer: &X509Certificate, subject: &X509Certificate) -> bool {
+  if !names_equal(issuer.subject(), subject.issuer()) {
+    return false;
+  }
+
+  let akid = subject
     .extensions()
     .iter()
-    .find(|e| e.oid == x509_parser::oid_registry::OID_X509_EXT_SUBJECT_ALT_NAME)
-    .and_then(|e| match e.parsed_extension() {
-      extensions::ParsedExtension::SubjectAlternativeName(s) => Some(s),
+    .find_map(|extension| match extension.parsed_extension() {
+      ParsedExtension::AuthorityKeyIdentifier(akid) => Some(akid),
       _ => None,
     });
-
-  if let Some(subject_alt) = subject_alt {
-    for name in &subject_alt.general_names {
-      if let extensions::GeneralName::DNSName(n) = name {
-        if *n == host {
-          return true;
-        }
+  if let Some(akid) = akid {
+    let skid = issuer
+      .extensions()
+      .iter()
+      .find_map(|extension| match extension.parsed_extension() {
+        ParsedExtension::SubjectKeyIdentifier(skid) => Some(skid),
+        _ => None,
+      });
+    if let (Some(key_id), Some(skid)) = (&akid.key_identifier, skid) {
+      if key_id.0 != skid.0 {
+        return false;
+      }
+    }
+    if let Some(serial) = akid.authority_cert_serial {
+      if serial != issuer.raw_serial() {
+        return false;
+      }
+    }
+    let dir_name =
+      akid
+        .authority_cert_issuer
+        .iter()
+        .flatten()
+        .find_map(|name| match name {
+          GeneralName::DirectoryName(name) => Some(name),
+          _ => None,
+        });
+    if let Some(dir_name) = dir_name {
+      if !names_equal(dir_name, issuer.issuer()) {
+        return false;
       }
     }
   }
 
-  false
+  let key_algorithm = issuer.public_key().algorithm.algorithm.to_id_string();
+  let key_matches = match signature_scheme(&subject.tbs_certificate.signature) {
+    Some(SignatureScheme::Pkcs1(_)) => key_algorithm == "1.2.840.113549.1.1.1",
+    Some(SignatureScheme::Pss) => {
+      key_algorithm == "1.2.840.113549.1.1.1"
+        || key_algorithm == "1.2.840.113549.1.1.10"
+    }
+    Some(SignatureScheme::Ecdsa(_)) => key_algorithm == "1.2.840.10045.2.1",
+    Some(SignatureScheme::Dsa(_)) => key_algorithm == "1.2.840.10040.4.1",
+    Some(SignatureScheme::Ed25519) => key_algorithm == "1.3.101.112",
+    Some(SignatureScheme::Ed448) => key_algorithm == "1.3.101.113",
+    None => false,
+  };
+  if !key_matches {
+    return false;
+  }
+
+  !matches!(issuer.key_usage(), Ok(Some(key_usage)) if !key_usage.value.key_cert_sign())
+}
+
+#[op2(fast)]
+pub fn op_node_x509_check_issued(
+  #[cppgc] cert: &Certificate,
+  #[cppgc] issuer: &Certificate,
+) -> bool {
+  check_issued(issuer.inner.get().deref(), cert.inner.get().deref())
 }
 
 #[op2]
 #[string]
-pub fn op_node_x509_fingerprint(#[cppgc] cert: &Certificate) -> Option<String> {
+pub fn op_node_x509_fingerprint(#[cppgc] cert: &Certificate) -> String {
   cert.fingerprint::<sha1::Sha1>()
 }
 
 #[op2]
 #[string]
-pub fn op_node_x509_fingerprint256(
-  #[cppgc] cert: &Certificate,
-) -> Option<String> {
+pub fn op_node_x509_fingerprint256(#[cppgc] cert: &Certificate) -> String {
   cert.fingerprint::<sha2::Sha256>()
 }
 
 #[op2]
 #[string]
-pub fn op_node_x509_fingerprint512(
-  #[cppgc] cert: &Certificate,
-) -> Option<String> {
+pub fn op_node_x509_fingerprint512(#[cppgc] cert: &Certificate) -> String {
   cert.fingerprint::<sha2::Sha512>()
 }
 
+/// The fingerprint with any digest `createHash()` supports.
+#[op2]
+#[string]
+pub fn op_node_x509_fingerprint_with(
+  #[cppgc] cert: &Certificate,
+  #[string] algorithm: &str,
+) -> Result<String, HashError> {
+  let mut hash = Hash::new(algorithm, None, None)?;
+  hash.update(cert.der());
+  Ok(print_fingerprint(&hash.digest_and_drop()))
+}
+
 #[op2]
 #[string]
 pub fn op_node_x509_get_issuer(
@@ -282,40 +898,458 @@ fn x509name_to_string(
   })
 }
 
+/// Prints a time like OpenSSL's `ASN1_TIME_print()`, e.g.
+/// `Sep  3 21:40:37 2022 GMT`.
+fn print_time(time: ASN1Time) -> String {
+  const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct",
+    "Nov", "Dec",
+  ];
+  let time = time.to_datetime();
+  format!(
+    "{} {:2} {:02}:{:02}:{:02} {} GMT",
+    MONTHS[u8::from(time.month()) as usize - 1],
+    time.day(),
+    time.hour(),
+    time.minute(),
+    time.second(),
+    time.year()
+  )
+}
+
+/// Prints a big-endian integer like OpenSSL's `BN_bn2hex()`: upper case,
+/// a whole number of bytes, and without leading zero bytes.
+fn print_hex_integer(bytes: &[u8]) -> String {
+  let start = bytes.iter().position(|&byte| byte != 0);
+  match start {
+    Some(start) => data_encoding::HEXUPPER.encode(&bytes[start..]),
+    None => "0".to_string(),
+  }
+}
+
 #[op2]
 #[string]
 pub fn op_node_x509_get_valid_from(#[cppgc] cert: &Certificate) -> String {
   let cert = cert.inner.get().deref();
-  cert.validity().not_before.to_string()
+  print_time(cert.validity().not_before)
 }
 
 #[op2]
 #[string]
 pub fn op_node_x509_get_valid_to(#[cppgc] cert: &Certificate) -> String {
   let cert = cert.inner.get().deref();
-  cert.validity().not_after.to_string()
+  print_time(cert.validity().not_after)
 }
 
 #[op2]
 #[string]
 pub fn op_node_x509_get_serial_number(#[cppgc] cert: &Certificate) -> String {
   let cert = cert.inner.get().deref();
-  let mut s = cert.serial.to_str_radix(16);
-  s.make_ascii_uppercase();
-  s
+  print_hex_integer(cert.raw_serial())
 }
 
-#[op2(fast)]
-pub fn op_node_x509_key_usage(#[cppgc] cert: &Certificate) -> u16 {
-  let cert = cert.inner.get().deref();
-  let key_usage = cert
-    .extensions()
+fn ext_key_usage(cert: &X509Certificate) -> Option<Vec<String>> {
+  let extension = cert.extensions().iter().find(|e| {
+    e.oid == x509_parser::oid_registry::OID_X509_EXT_EXTENDED_KEY_USAGE
+  })?;
+  // Not `ParsedExtension::ExtendedKeyUsage`, which loses the order.
+  let (_, oids) = <Vec<Oid>>::from_der(extension.value).ok()?;
+  Some(oids.iter().map(Oid::to_id_string).collect())
+}
+
+/// The extended key usage OIDs, which Node.js calls `keyUsage`.
+#[op2]
+#[serde]
+pub fn op_node_x509_ext_key_usage(
+  #[cppgc] cert: &Certificate,
+) -> Option<Vec<String>> {
+  ext_key_usage(cert.inner.get().deref())
+}
+
+fn subject_alt_name(cert: &X509Certificate) -> Option<String> {
+  let subject_alt = cert.subject_alternative_name().ok()??;
+  let names = subject_alt
+    .value
+    .general_names
     .iter()
-    .find(|e| e.oid == x509_parser::oid_registry::OID_X509_EXT_KEY_USAGE)
-    .and_then(|e| match e.parsed_extension() {
-      extensions::ParsedExtension::KeyUsage(k) => Some(k),
-      _ => None,
-    });
+    .map(print_general_name)
+    .collect::<Vec<_>>();
+  Some(names.join(", "))
+}
+
+#[op2]
+#[string]
+pub fn op_node_x509_get_subject_alt_name(
+  #[cppgc] cert: &Certificate,
+) -> Option<String> {
+  subject_alt_name(cert.inner.get().deref())
+}
+
+/// Each access description as `<method> - <location>`.
+fn info_access(cert: &X509Certificate) -> Option<Vec<String>> {
+  let info_access =
+    cert
+      .extensions()
+      .iter()
+      .find_map(|e| match e.parsed_extension() {
+        extensions::ParsedExtension::AuthorityInfoAccess(aia) => Some(aia),
+        _ => None,
+      })?;
+  let lines = info_access
+    .accessdescs
+    .iter()
+    .map(|desc| {
+      let method = match desc.access_method.to_id_string().as_str() {
+        "1.3.6.1.5.5.7.48.1" => "OCSP".to_string(),
+        "1.3.6.1.5.5.7.48.2" => "CA Issuers".to_string(),
+        "1.3.6.1.5.5.7.48.3" => "AD Time Stamping".to_string(),
+        "1.3.6.1.5.5.7.48.5" => "CA Repository".to_string(),
+        oid => oid.to_string(),
+      };
+      format!("{method} - {}", print_general_name(&desc.access_location))
+    })
+    .collect::<Vec<_>>();
+  Some(lines)
+}
 
-  key_usage.map(|k| k.flags).unwrap_or(0)
+#[op2]
+#[string]
+pub fn op_node_x509_get_info_access(
+  #[cppgc] cert: &Certificate,
+) -> Option<String> {
+  let lines = info_access(cert.inner.get().deref())?;
+  Some(lines.join("\n"))
+}
+
+/// Values in order of their first key, which serializes as an object.
+struct OrderedMap<V>(Vec<(String, V)>);
+
+impl<V: serde::Serialize> serde::Serialize for OrderedMap<V> {
+  fn serialize<S: serde::Serializer>(
+    &self,
+    serializer: S,
+  ) -> Result<S::Ok, S::Error> {
+    use serde::ser::SerializeMap;
+    let mut map = serializer.serialize_map(Some(self.0.len()))?;
+    for (key, value) in &self.0 {
+      map.serialize_entry(key, value)?;
+    }
+    map.end()
+  }
+}
+
+impl<V> OrderedMap<Vec<V>> {
+  fn push(&mut self, key: String, value: V) {
+    match self.0.iter_mut().find(|(existing, _)| *existing == key) {
+      Some((_, values)) => values.push(value),
+      None => self.0.push((key, vec![value])),
+    }
+  }
+}
+
+/// An attribute value, or the values of a repeated attribute.
+#[derive(serde::Serialize)]
+#[serde(untagged)]
+enum LegacyNameValue {
+  Single(String),
+  Multiple(Vec<String>),
+}
+
+/// A name as an object keyed by the attributes' short names, like Node.js.
+/// It is `None` if a value isn't a string, as Node.js leaves it undefined.
+fn legacy_name(name: &X509Name) -> Option<OrderedMap<LegacyNameValue>> {
+  let mut attrs = OrderedMap(Vec::new());
+  for attr in name.iter_attributes() {
+    let value = decode_string(attr.attr_value())?;
+    attrs.push(short_name(attr.attr_type()), value);
+  }
+  Some(OrderedMap(
+    attrs
+      .0
+      .into_iter()
+      .map(|(key, mut values)| {
+        let value = if values.len() == 1 {
+          LegacyNameValue::Single(values.remove(0))
+        } else {
+          LegacyNameValue::Multiple(values)
+        };
+        (key, value)
+      })
+      .collect(),
+  ))
+}
+
+/// The object of `X509Certificate.prototype.toLegacyObject()`, in the
+/// order of Node.js's `X509ToObject()`.
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LegacyObject {
+  subject: Option<OrderedMap<LegacyNameValue>>,
+  issuer: Option<OrderedMap<LegacyNameValue>>,
+  #[serde(rename = "subjectaltname", skip_serializing_if = "Option::is_none")]
+  subject_alt_name: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  info_access: Option<OrderedMap<Vec<String>>>,
+  ca: bool,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  modulus: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  bits: Option<usize>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  exponent: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pubkey: Option<ToJsBuffer>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  asn1_curve: Option<&'static str>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  nist_curve: Option<&'static str>,
+  #[serde(rename = "valid_from")]
+  valid_from: String,
+  #[serde(rename = "valid_to")]
+  valid_to: String,
+  fingerprint: String,
+  fingerprint256: String,
+  fingerprint512: String,
+  #[serde(rename = "ext_key_usage", skip_serializing_if = "Option::is_none")]
+  ext_key_usage: Option<Vec<String>>,
+  serial_number: String,
+  raw: ToJsBuffer,
+}
+
+#[op2]
+#[serde]
+pub fn op_node_x509_to_legacy_object(
+  #[cppgc] certificate: &Certificate,
+) -> LegacyObject {
+  let cert = certificate.inner.get().deref();
+
+  // Node.js splits each line at its first colon, and parses quoted
+  // locations as JSON.
+  let info_access = info_access(cert).map(|lines| {
+    let mut entries = OrderedMap(Vec::new());
+    for line in lines {
+      let (key, value) = line.split_once(':').unwrap_or((line.as_str(), ""));
+      let value = if value.starts_with('"') {
+        deno_core::serde_json::from_str(value)
+          .unwrap_or_else(|_| value.to_string())
+      } else {
+        value.to_string()
+      };
+      entries.push(key.to_string(), value);
+    }
+    entries
+  });
+
+  let mut object = LegacyObject {
+    subject: legacy_name(cert.subject()),
+    issuer: legacy_name(cert.issuer()),
+    subject_alt_name: subject_alt_name(cert),
+    info_access,
+    ca: is_ca(cert),
+    modulus: None,
+    bits: None,
+    exponent: None,
+    pubkey: None,
+    asn1_curve: None,
+    nist_curve: None,
+    valid_from: print_time(cert.validity().not_before),
+    valid_to: print_time(cert.validity().not_after),
+    fingerprint: certificate.fingerprint::<sha1::Sha1>(),
+    fingerprint256: certificate.fingerprint::<sha2::Sha256>(),
+    fingerprint512: certificate.fingerprint::<sha2::Sha512>(),
+    ext_key_usage: ext_key_usage(cert),
+    serial_number: print_hex_integer(cert.raw_serial()),
+    raw: certificate.der().to_vec().into(),
+  };
+
+  let public_key = KeyObjectHandle::new_x509_public_key(cert.public_key()).ok();
+  if let Some(key) =
+    public_key.as_ref().and_then(KeyObjectHandle::as_public_key)
+  {
+    match &*key {
+      AsymmetricPublicKey::Rsa(key)
+      | AsymmetricPublicKey::RsaPss(RsaPssPublicKey { key, .. }) => {
+        object.modulus = Some(print_hex_integer(&key.n().to_bytes_be()));
+        object.bits = Some(key.n().bits());
+        let mut exponent = key.e().to_str_radix(16);
+        exponent.make_ascii_uppercase();
+        object.exponent = Some(format!("0x{exponent}"));
+        object.pubkey = key
+          .to_public_key_der()
+          .ok()
+          .map(|der| der.into_vec().into());
+      }
+      AsymmetricPublicKey::Ec(key) => {
+        let (bits, asn1_curve, nist_curve) = match key {
+          EcPublicKey::P224(_) => (224, "secp224r1", Some("P-224")),
+          EcPublicKey::P256(_) => (256, "prime256v1", Some("P-256")),
+          EcPublicKey::P384(_) => (384, "secp384r1", Some("P-384")),
+          EcPublicKey::P521(_) => (521, "secp521r1", Some("P-521")),
+          EcPublicKey::Secp256k1(_) => (256, "secp256k1", None),
+          EcPublicKey::Sm2(_) => (256, "SM2", None),
+        };
+        object.bits = Some(bits);
+        // The point as the certificate encodes it, compressed or not.
+        object.pubkey =
+          Some(cert.public_key().subject_public_key.data.to_vec().into());
+        object.asn1_curve = Some(asn1_curve);
+        object.nist_curve = nist_curve;
+      }
+      _ => {}
+    }
+  }
+
+  object
+}
+
+/// Prints a name like Node.js, which mostly follows OpenSSL but quotes and
+/// escapes names that would be ambiguous, e.g. because of commas.
+fn print_general_name(name: &GeneralName) -> String {
+  match name {
+    GeneralName::DNSName(name) => {
+      format!("DNS:{}", print_alt_name(name.as_bytes(), false, None))
+    }
+    GeneralName::RFC822Name(name) => {
+      format!("email:{}", print_alt_name(name.as_bytes(), false, None))
+    }
+    GeneralName::URI(name) => {
+      format!("URI:{}", print_alt_name(name.as_bytes(), false, None))
+    }
+    GeneralName::DirectoryName(name) => format!(
+      "DirName:{}",
+      print_alt_name(print_name_rfc2253(name).as_bytes(), true, None)
+    ),
+    GeneralName::IPAddress(ip) => match ip.len() {
+      4 => format!("IP Address:{}.{}.{}.{}", ip[0], ip[1], ip[2], ip[3]),
+      16 => {
+        let pairs = ip
+          .chunks(2)
+          .map(|pair| format!("{:X}", u16::from_be_bytes([pair[0], pair[1]])))
+          .collect::<Vec<_>>();
+        format!("IP Address:{}", pairs.join(":"))
+      }
+      len => format!("IP Address:<invalid length={len}>"),
+    },
+    GeneralName::RegisteredID(oid) => {
+      format!("Registered ID:{}", oid.to_id_string())
+    }
+    GeneralName::OtherName(oid, value) => {
+      let (prefix, utf8) = match oid.to_id_string().as_str() {
+        "1.3.6.1.5.5.7.8.9" => ("SmtpUTF8Mailbox", true),
+        "1.3.6.1.5.5.7.8.5" => ("XmppAddr", true),
+        "1.3.6.1.5.5.7.8.7" => ("SRVName", false),
+        "1.3.6.1.4.1.311.20.2.3" => ("UPN", true),
+        "1.3.6.1.5.5.7.8.8" => ("NAIRealm", true),
+        _ => return "othername:<unsupported>".to_string(),
+      };
+      // The value is explicitly tagged [0].
+      let value = Any::from_der(value)
+        .and_then(|(_, tagged)| Any::from_der(tagged.data))
+        .ok()
+        .map(|(_, value)| value);
+      let expected = if utf8 {
+        Tag::Utf8String
+      } else {
+        Tag::Ia5String
+      };
+      match value {
+        Some(value) if value.tag() == expected => format!(
+          "othername:{}",
+          print_alt_name(value.data, utf8, Some(prefix))
+        ),
+        _ => "othername:<unsupported>".to_string(),
+      }
+    }
+    GeneralName::X400Address(_) => "X400Name:<unsupported>".to_string(),
+    GeneralName::EDIPartyName(_) => "EdiPartyName:<unsupported>".to_string(),
+  }
+}
+
+/// Names are printed as they are unless they contain characters that
+/// would make the list ambiguous. Those are printed as JSON strings, with
+/// commas escaped too.
+fn print_alt_name(name: &[u8], utf8: bool, prefix: Option<&str>) -> String {
+  let safe = name.iter().all(|&c| match c {
+    b'"' | b'\\' | b',' | b'\'' => false,
+    _ if utf8 => c >= b' ' && c != 0x7f,
+    _ => (b' '..=b'~').contains(&c),
+  });
+  let prefix = prefix
+    .map(|prefix| format!("{prefix}:"))
+    .unwrap_or_default();
+  if safe {
+    return format!("{prefix}{}", String::from_utf8_lossy(name));
+  }
+
+  let mut out = Vec::with_capacity(name.len() + prefix.len() + 2);
+  out.push(b'"');
+  out.extend_from_slice(prefix.as_bytes());
+  for &c in name {
+    match c {
+      b'\\' => out.extend_from_slice(b"\\\\"),
+      b'"' => out.extend_from_slice(b"\\\""),
+      b' '..=b'~' if c != b',' => out.push(c),
+      _ if utf8 && c & 0x80 != 0 => out.push(c),
+      // Anything else is printed as a Latin-1 code point.
+      _ => out.extend_from_slice(format!("\\u00{c:02x}").as_bytes()),
+    }
+  }
+  out.push(b'"');
+  String::from_utf8_lossy(&out).into_owned()
+}
+
+/// An attribute type's OpenSSL short name, or its OID if it has none.
+fn short_name(oid: &Oid) -> String {
+  // OpenSSL's short names differ from the registry's for a few types.
+  match oid2abbrev(oid, oid_registry()) {
+    Ok("Email") => "emailAddress".to_string(),
+    Ok("streetAddress") => "street".to_string(),
+    Ok("givenName") => "GN".to_string(),
+    Ok("surname") => "SN".to_string(),
+    Ok(short_name) => short_name.to_string(),
+    Err(_) => oid.to_id_string(),
+  }
+}
+
+/// Prints a name like OpenSSL's `X509_NAME_print_ex()` with the RFC 2253
+/// flags that Node.js uses: the attributes in reverse order, with RFC 2253
+/// escaping but without escaping control or non-ASCII characters.
+fn print_name_rfc2253(name: &X509Name) -> String {
+  let mut out = String::new();
+  let rdns = name.iter_rdn().collect::<Vec<_>>();
+  for (i, rdn) in rdns.into_iter().rev().enumerate() {
+    if i > 0 {
+      out.push(',');
+    }
+    let attrs = rdn.iter().collect::<Vec<_>>();
+    for (j, attr) in attrs.into_iter().rev().enumerate() {
+      if j > 0 {
+        out.push('+');
+      }
+      out.push_str(&short_name(attr.attr_type()));
+      out.push('=');
+      match attr.as_str() {
+        Ok(value) => {
+          let len = value.chars().count();
+          for (k, c) in value.chars().enumerate() {
+            let escape = matches!(c, ',' | '+' | '"' | '\\' | '<' | '>' | ';')
+              || (k == 0 && (c == '#' || c == ' '))
+              || (k == len - 1 && c == ' ');
+            if escape {
+              out.push('\\');
+            }
+            out.push(c);
+          }
+        }
+        // Values that aren't strings are dumped as DER.
+        Err(_) => {
+          out.push('#');
+          out.push_str(
+            &data_encoding::HEXLOWER.encode(attr.attr_value().as_bytes()),
+          );
+        }
+      }
+    }
+  }
+  out
 }
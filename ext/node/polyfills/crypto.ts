@@ -4,12 +4,17 @@
 // TODO(petamoriken): enable prefer-primordials for node polyfills
 // deno-lint-ignore-file prefer-primordials
 
+import {
+  op_node_crypto_hash,
+  op_node_crypto_hash_str,
+} from "ext:core/ops";
 import { ERR_CRYPTO_FIPS_FORCED } from "ext:deno_node/internal/errors.ts";
 import { crypto as constants } from "ext:deno_node/internal_binding/constants.ts";
 import { getOptionValue } from "ext:deno_node/internal/options.ts";
 import {
   getFipsCrypto,
   setFipsCrypto,
+  setLegacyProvider,
   timingSafeEqual,
 } from "ext:deno_node/internal_binding/crypto.ts";
 import {
@@ -55,10 +60,16 @@ import type {
   ED448KeyPairOptions,
   KeyPairKeyObjectResult,
   KeyPairSyncResult,
+  MLDSAKeyPairKeyObjectOptions,
+  MLDSAKeyPairOptions,
+  MLKEMKeyPairKeyObjectOptions,
+  MLKEMKeyPairOptions,
   RSAKeyPairKeyObjectOptions,
   RSAKeyPairOptions,
   RSAPSSKeyPairKeyObjectOptions,
   RSAPSSKeyPairOptions,
+  SLHDSAKeyPairKeyObjectOptions,
+  SLHDSAKeyPairOptions,
   X25519KeyPairKeyObjectOptions,
   X25519KeyPairOptions,
   X448KeyPairKeyObjectOptions,
@@ -83,6 +94,11 @@ import {
   DiffieHellmanGroup,
   ECDH,
 } from "ext:deno_node/internal/crypto/diffiehellman.ts";
+import {
+  decapsulate,
+  encapsulate,
+} from "ext:deno_node/internal/crypto/kem.ts";
+import type { EncapsulateResult } from "ext:deno_node/internal/crypto/kem.ts";
 import {
   Cipheriv,
   Decipheriv,
@@ -122,8 +138,11 @@ import type {
   KeyFormat,
   KeyType,
   LegacyCharacterEncoding,
+  MLDSAKeyType,
+  MLKEMKeyType,
   PrivateKeyInput,
   PublicKeyInput,
+  SLHDSAKeyType,
 } from "ext:deno_node/internal/crypto/types.ts";
 import {
   Sign,
@@ -142,6 +161,7 @@ import type {
 } from "ext:deno_node/internal/crypto/sig.ts";
 import {
   createHash,
+  encodeDigest,
   getHashes,
   Hash as Hash_,
   Hmac as Hmac_,
@@ -174,6 +194,12 @@ import { deprecate } from "node:util";
 
 const subtle = webcrypto.subtle;
 const fipsForced = getOptionValue("--force-fips");
+if (fipsForced) {
+  setFipsCrypto(true);
+}
+if (getOptionValue("--openssl-legacy-provider")) {
+  setLegacyProvider(true);
+}
 
 const Hash = deprecate(
   Hash_,
@@ -219,9 +245,14 @@ function hash(
       }
     }
   }
-  const hash = createHash(algorithm);
-  hash.update(data);
-  return hash.digest(outputEncoding);
+  const name = algorithm.toLowerCase();
+  const digest = typeof data === "string"
+    ? op_node_crypto_hash_str(name, data)
+    : op_node_crypto_hash(name, data);
+  if (digest === null) {
+    throw new ERR_INVALID_ARG_VALUE("algorithm", algorithm);
+  }
+  return encodeDigest(digest, outputEncoding);
 }
 
 function createCipheriv(
@@ -387,11 +418,13 @@ export default {
   createSecretKey,
   createSign,
   createVerify,
+  decapsulate,
   Decipheriv,
   DiffieHellman,
   diffieHellman,
   DiffieHellmanGroup,
   ECDH,
+  encapsulate,
   getRandomValues,
   generateKey,
   generateKeyPair,
@@ -471,6 +504,7 @@ export type {
   ED25519KeyPairOptions,
   ED448KeyPairKeyObjectOptions,
   ED448KeyPairOptions,
+  EncapsulateResult,
   Encoding,
   GeneratePrimeOptions,
   GeneratePrimeOptionsArrayBuffer,
@@ -487,6 +521,12 @@ export type {
   KeyType,
   LargeNumberLike,
   LegacyCharacterEncoding,
+  MLDSAKeyPairKeyObjectOptions,
+  MLDSAKeyPairOptions,
+  MLDSAKeyType,
+  MLKEMKeyPairKeyObjectOptions,
+  MLKEMKeyPairOptions,
+  MLKEMKeyType,
   NormalizedAlgorithms,
   PeerCertificate,
   PrivateKeyInput,
@@ -499,6 +539,9 @@ export type {
   SigningOptions,
   SignKeyObjectInput,
   SignPrivateKeyInput,
+  SLHDSAKeyPairKeyObjectOptions,
+  SLHDSAKeyPairOptions,
+  SLHDSAKeyType,
   VerifyKeyObjectInput,
   VerifyPublicKeyInput,
   X25519KeyPairKeyObjectOptions,
@@ -526,11 +569,13 @@ export {
   createSecretKey,
   createSign,
   createVerify,
+  decapsulate,
   Decipheriv,
   DiffieHellman,
   diffieHellman,
   DiffieHellmanGroup,
   ECDH,
+  encapsulate,
   generateKey,
   generateKeyPair,
   generateKeyPairSync,
@@ -6,9 +6,12 @@
 // TODO(petamoriken): enable prefer-primordials for node polyfills
 // deno-lint-ignore-file prefer-primordials
 
-import { kKeyObject } from "ext:deno_node/internal/crypto/constants.ts";
+import { primordials } from "ext:core/mod.js";
+import { CryptoKey } from "ext:deno_crypto/00_crypto.js";
 import type { KeyObject } from "ext:deno_node/internal/crypto/keys.ts";
 
+const { ObjectPrototypeIsPrototypeOf } = primordials;
+
 export const kKeyType = Symbol("kKeyType");
 
 export function isKeyObject(obj: unknown): obj is KeyObject {
@@ -20,7 +23,5 @@ export function isKeyObject(obj: unknown): obj is KeyObject {
 export function isCryptoKey(
   obj: unknown,
 ): obj is CryptoKey {
-  return (
-    obj != null && (obj as Record<symbol, unknown>)[kKeyObject] !== undefined
-  );
+  return ObjectPrototypeIsPrototypeOf(CryptoKey.prototype, obj);
 }
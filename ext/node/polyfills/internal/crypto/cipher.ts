@@ -24,11 +24,11 @@ import {
   op_node_decipheriv_set_aad,
   op_node_private_decrypt,
   op_node_private_encrypt,
+  op_node_public_decrypt,
   op_node_public_encrypt,
 } from "ext:core/ops";
 
 import { Buffer } from "node:buffer";
-import { notImplemented } from "ext:deno_node/_utils.ts";
 import type { TransformOptions } from "ext:deno_node/_stream.d.ts";
 import { Transform } from "node:stream";
 import {
@@ -543,8 +543,15 @@ export function prepareKey(key) {
   throw new TypeError("Invalid key type");
 }
 
-export function publicDecrypt() {
-  notImplemented("crypto.publicDecrypt");
+export function publicDecrypt(
+  publicKey: ArrayBufferView | string | KeyObject,
+  buffer: ArrayBufferView | string | KeyObject,
+): Buffer {
+  const { data } = prepareKey(publicKey);
+  const padding = publicKey.padding || 1;
+
+  buffer = getArrayBufferOrView(buffer, "buffer");
+  return Buffer.from(op_node_public_decrypt(data, buffer, padding));
 }
 
 export default {
@@ -7,15 +7,44 @@
 import {
   Hasher,
   op_node_create_hash,
+  op_node_create_hmac,
+  op_node_create_hmac_from_key,
+  op_node_create_multi_hash,
   op_node_export_secret_key,
   op_node_get_hashes,
   op_node_hash_clone,
   op_node_hash_digest,
+  op_node_hash_digest_encoded,
   op_node_hash_digest_hex,
+  op_node_hash_export_state,
+  op_node_hash_file,
+  op_node_hash_import_state,
+  op_node_hash_read_resource,
+  op_node_hash_reset,
+  op_node_hash_squeeze,
   op_node_hash_update,
+  op_node_hash_update_async,
   op_node_hash_update_str,
+  op_node_hash_update_str_encoded,
+  op_node_hash_update_vectored,
+  op_node_hash_verify,
+  op_node_hash_verify_str,
+  op_node_hmac_batch,
+  op_node_hmac_digest,
+  op_node_hmac_oneshot,
+  op_node_hmac_oneshot_encoded,
+  op_node_hmac_update,
+  op_node_hmac_update_str,
+  op_node_hmac_validate,
+  op_node_hmac_verify,
+  op_node_hmac_verify_str,
+  op_node_hmac_verify_with_key,
+  op_node_hmac_verify_with_key_str,
+  op_node_multi_hash_digest,
+  op_node_multi_hash_update,
+  op_node_multi_hash_update_str,
 } from "ext:core/ops";
-import { primordials } from "ext:core/mod.js";
+import { internals, primordials } from "ext:core/mod.js";
 
 import { Buffer } from "node:buffer";
 import { Transform } from "node:stream";
@@ -23,13 +52,16 @@ import {
   forgivingBase64Encode as encodeToBase64,
   forgivingBase64UrlEncode as encodeToBase64Url,
 } from "ext:deno_web/00_infra.js";
+import { TransformStream } from "ext:deno_web/06_streams.js";
 import type { TransformOptions } from "ext:deno_node/_stream.d.ts";
 import {
+  validateArray,
   validateEncoding,
   validateString,
   validateUint32,
 } from "ext:deno_node/internal/validators.mjs";
 import type {
+  BinaryLike,
   BinaryToTextEncoding,
   Encoding,
 } from "ext:deno_node/internal/crypto/types.ts";
@@ -40,30 +72,51 @@ import {
 import {
   ERR_CRYPTO_HASH_FINALIZED,
   ERR_INVALID_ARG_TYPE,
-  NodeError,
+  ERR_INVALID_ARG_VALUE,
+  ERR_INVALID_STATE,
 } from "ext:deno_node/internal/errors.ts";
 import LazyTransform from "ext:deno_node/internal/streams/lazy_transform.js";
+import { getValidatedPathToString } from "ext:deno_node/internal/fs/utils.mjs";
 import {
   getDefaultEncoding,
   toBuf,
 } from "ext:deno_node/internal/crypto/util.ts";
+import {
+  normalizeEncoding,
+} from "ext:deno_node/internal/normalize_encoding.mjs";
 import {
   isAnyArrayBuffer,
   isArrayBufferView,
 } from "ext:deno_node/internal/util/types.ts";
 
-const { ReflectApply, ObjectSetPrototypeOf } = primordials;
+const {
+  ArrayIsArray,
+  ObjectSetPrototypeOf,
+  PromisePrototypeThen,
+  ReflectApply,
+  SafePromisePrototypeFinally,
+  SafeSet,
+} = primordials;
 
 function unwrapErr(ok: boolean) {
   if (!ok) throw new ERR_CRYPTO_HASH_FINALIZED();
 }
 
 const kHandle = Symbol("kHandle");
+const kPending = Symbol("kPending");
+const kFlushedDigest = Symbol("kFlushedDigest");
 
 export function Hash(
   this: Hash,
   algorithm: string | Hasher,
-  options?: { outputLength?: number },
+  options?: {
+    outputLength?: number;
+    key?: BinaryLike | KeyObject;
+    functionName?: BinaryLike;
+    customization?: BinaryLike;
+    salt?: BinaryLike;
+    personalization?: BinaryLike;
+  },
 ): Hash {
   if (!(this instanceof Hash)) {
     return new Hash(algorithm, options);
@@ -79,58 +132,208 @@ export function Hash(
     validateUint32(xofLen, "options.outputLength");
   }
 
-  try {
-    this[kHandle] = isCopy
-      ? op_node_hash_clone(algorithm, xofLen)
-      : op_node_create_hash(algorithm.toLowerCase(), xofLen);
-  } catch (err) {
-    // TODO(lucacasonato): don't do this
-    if (err.message === "Output length mismatch for non-extendable algorithm") {
-      throw new NodeError(
-        "ERR_OSSL_EVP_NOT_XOF_OR_INVALID_LENGTH",
-        "Invalid XOF digest length",
-      );
-    } else {
-      throw err;
+  let key, functionName, customization, salt, personalization;
+  if (typeof options === "object" && options !== null) {
+    key = options.key instanceof KeyObject
+      ? op_node_export_secret_key(prepareSecretKey(options.key, undefined))
+      : optionBytes(options.key, "options.key");
+    // cSHAKE and KMAC domain separation parameters (NIST SP 800-185).
+    functionName = optionBytes(options.functionName, "options.functionName");
+    customization = optionBytes(
+      options.customization,
+      "options.customization",
+    );
+    // BLAKE2 parameter block fields, as in libsodium's generichash.
+    salt = optionBytes(options.salt, "options.salt");
+    personalization = optionBytes(
+      options.personalization,
+      "options.personalization",
+    );
+    if (key !== undefined) {
+      // Don't keep the key alive through the stream options.
+      const { key: _, ...rest } = options;
+      options = rest;
     }
   }
 
+  this[kHandle] = isCopy
+    ? op_node_hash_clone(algorithm, xofLen)
+    : op_node_create_hash(
+      algorithm.toLowerCase(),
+      xofLen,
+      key,
+      functionName,
+      customization,
+      salt,
+      personalization,
+    );
+
   if (this[kHandle] === null) throw new ERR_CRYPTO_HASH_FINALIZED();
 
   ReflectApply(LazyTransform, this, [options]);
 }
 
+function optionBytes(value: BinaryLike | undefined, name: string) {
+  if (value === undefined) return undefined;
+  if (typeof value !== "string" && !isArrayBufferView(value)) {
+    throw new ERR_INVALID_ARG_TYPE(
+      name,
+      ["string", "Buffer", "TypedArray", "DataView"],
+      value,
+    );
+  }
+  return toBuf(value);
+}
+
 interface Hash {
   [kHandle]: object;
+  [kPending]?: Promise<void>;
+  [kFlushedDigest]?: Buffer;
 }
 
 ObjectSetPrototypeOf(Hash.prototype, LazyTransform.prototype);
 ObjectSetPrototypeOf(Hash, LazyTransform);
 
 Hash.prototype.copy = function copy(options?: { outputLength: number }) {
-  return new Hash(this[kHandle], options);
+  return new Hash(handle(this), options);
 };
 
+// The stream side feeds the handle directly, like Node.js: both chunk types
+// are already validated by Writable, and nothing is buffered in JS.
 Hash.prototype._transform = function _transform(
   chunk: string | Buffer,
   encoding: Encoding | "buffer",
-  callback: () => void,
+  callback: (err?: Error) => void,
 ) {
-  this.update(chunk, encoding);
+  try {
+    updateHandle(handle(this), chunk, encoding);
+  } catch (err) {
+    callback(err as Error);
+    return;
+  }
   callback();
 };
 
-Hash.prototype._flush = function _flush(callback: () => void) {
-  this.push(this.digest());
+Hash.prototype._flush = function _flush(callback: (err?: Error) => void) {
+  let digest;
+  try {
+    digest = op_node_hash_digest(handle(this));
+  } catch (err) {
+    callback(err as Error);
+    return;
+  }
+  if (digest !== null) {
+    const buf = encodeDigest(digest, "buffer") as Buffer;
+    // A later digest() call returns this once, as in Node.js.
+    this[kFlushedDigest] = buf;
+    this.push(buf);
+  }
   callback();
 };
 
 Hash.prototype.update = function update(
-  data: string | Buffer,
+  data: string | Buffer | ArrayBufferView[],
   encoding: Encoding | "buffer",
 ) {
+  // Unlike Node.js, an array of views is accepted and hashed in order in a
+  // single op call, for data that arrives in many small pieces.
+  if (ArrayIsArray(data)) {
+    for (let i = 0; i < data.length; i++) {
+      if (!isArrayBufferView(data[i])) {
+        throw new ERR_INVALID_ARG_TYPE(
+          `data[${i}]`,
+          ["Buffer", "TypedArray", "DataView"],
+          data[i],
+        );
+      }
+    }
+    unwrapErr(op_node_hash_update_vectored(handle(this), data));
+    return this;
+  }
+
   encoding = encoding || getDefaultEncoding();
+  validateUpdateData(data, encoding);
+  unwrapErr(updateHandle(handle(this), data, encoding));
+  return this;
+};
 
+/**
+ * Feeds `data` to the native hasher. Strings in UTF-8, Latin-1 and UTF-16LE
+ * are encoded by the op itself rather than through a `Buffer`.
+ */
+function updateHandle(
+  handle: object,
+  data: string | Buffer,
+  encoding: Encoding | "buffer",
+): boolean {
+  if (typeof data === "string") {
+    if (encoding === "utf8" || encoding === "buffer") {
+      return op_node_hash_update_str(handle, data);
+    }
+    const normalized = normalizeEncoding(encoding);
+    if (normalized === "latin1" || normalized === "utf16le") {
+      return op_node_hash_update_str_encoded(handle, data, normalized);
+    }
+  }
+  return op_node_hash_update(handle, toBuf(data, encoding));
+}
+
+/**
+ * Like `update()`, but hashes `data` on a background thread so that large
+ * buffers don't block the event loop. Calls are applied in order. Until the
+ * returned promise settles, `data` must not be modified and synchronous
+ * methods throw `ERR_INVALID_STATE`.
+ */
+Hash.prototype.updateAsync = function updateAsync(
+  data: string | Buffer,
+  encoding?: Encoding | "buffer",
+): Promise<Hash> {
+  encoding = encoding || getDefaultEncoding();
+  validateUpdateData(data, encoding);
+  const buf = toBuf(data, encoding);
+
+  const run = async () => {
+    unwrapErr(await op_node_hash_update_async(this[kHandle], buf));
+  };
+  const previous = this[kPending];
+  const pending = previous ? PromisePrototypeThen(previous, run, run) : run();
+  this[kPending] = pending;
+  return PromisePrototypeThen(
+    SafePromisePrototypeFinally(pending, () => {
+      if (this[kPending] === pending) this[kPending] = undefined;
+    }),
+    () => this,
+  );
+};
+
+/**
+ * Reads the resource `rid` (a file, socket or child process pipe) until EOF
+ * and hashes its contents natively, without passing each chunk through JS.
+ * Queues behind pending `updateAsync()` calls like they do with each other,
+ * and resolves to the number of bytes hashed. Closing the resource stops the
+ * read early. Not part of Node.js.
+ */
+Hash.prototype.updateFromResource = function updateFromResource(
+  rid: number,
+): Promise<number> {
+  validateUint32(rid, "rid");
+
+  let bytes = 0;
+  const run = async () => {
+    bytes = await op_node_hash_read_resource(this[kHandle], rid);
+  };
+  const previous = this[kPending];
+  const pending = previous ? PromisePrototypeThen(previous, run, run) : run();
+  this[kPending] = pending;
+  return PromisePrototypeThen(
+    SafePromisePrototypeFinally(pending, () => {
+      if (this[kPending] === pending) this[kPending] = undefined;
+    }),
+    () => bytes,
+  );
+};
+
+function validateUpdateData(data: string | Buffer, encoding: string) {
   if (typeof data === "string") {
     validateEncoding(data, encoding);
   } else if (!isArrayBufferView(data)) {
@@ -139,32 +342,106 @@ Hash.prototype.update = function update(
       ["string", "Buffer", "TypedArray", "DataView"],
       data,
     );
+  } else if (data.buffer.detached) {
+    // The ops borrow the backing store, which would read as empty.
+    throw new ERR_INVALID_ARG_VALUE(
+      "data",
+      data,
+      "must not be backed by a detached ArrayBuffer",
+    );
   }
+}
 
-  if (
-    typeof data === "string" && (encoding === "utf8" || encoding === "buffer")
-  ) {
-    unwrapErr(op_node_hash_update_str(this[kHandle], data));
-  } else {
-    unwrapErr(op_node_hash_update(this[kHandle], toBuf(data, encoding)));
+/** The native hasher, unless an `updateAsync()` call holds it. */
+function handle(hash: Hash): object {
+  if (hash[kPending] !== undefined) {
+    throw new ERR_INVALID_STATE("hash.updateAsync() is in progress");
   }
-
-  return this;
-};
+  return hash[kHandle];
+}
 
 Hash.prototype.digest = function digest(outputEncoding: Encoding | "buffer") {
   outputEncoding = outputEncoding || getDefaultEncoding();
   outputEncoding = `${outputEncoding}`;
 
+  const flushed = this[kFlushedDigest];
+  if (flushed !== undefined) {
+    this[kFlushedDigest] = undefined;
+    // Don't hand out the chunk that was pushed to the readable side.
+    return outputEncoding === "buffer"
+      ? Buffer.from(flushed)
+      : encodeDigest(flushed, outputEncoding as Encoding);
+  }
+
   if (outputEncoding === "hex") {
-    const result = op_node_hash_digest_hex(this[kHandle]);
+    const result = op_node_hash_digest_hex(handle(this));
     if (result === null) throw new ERR_CRYPTO_HASH_FINALIZED();
     return result;
   }
 
-  const digest = op_node_hash_digest(this[kHandle]);
+  if (outputEncoding !== "buffer") {
+    // Returns null for encodings it doesn't handle, leaving the hash intact.
+    const result = op_node_hash_digest_encoded(handle(this), outputEncoding);
+    if (result !== null) return result;
+  }
+
+  const digest = op_node_hash_digest(handle(this));
   if (digest === null) throw new ERR_CRYPTO_HASH_FINALIZED();
 
+  return encodeDigest(digest, outputEncoding);
+};
+
+/**
+ * Reads the next `length` bytes of output from an XOF (shake128/shake256)
+ * without fixing the total output length up front. The first call finalizes
+ * the hash; `update()` and `digest()` throw afterwards.
+ */
+Hash.prototype.squeeze = function squeeze(length: number): Buffer {
+  validateUint32(length, "length");
+  const output = op_node_hash_squeeze(handle(this), length);
+  if (output === null) throw new ERR_CRYPTO_HASH_FINALIZED();
+  return Buffer.from(output);
+};
+
+/**
+ * Discards any data passed to `update()` so far, allowing the hash to be
+ * reused after `digest()` without creating a new one.
+ */
+Hash.prototype.reset = function reset() {
+  op_node_hash_reset(handle(this));
+  return this;
+};
+
+/**
+ * Serializes the current state of the hash so it can be resumed later with
+ * `Hash.fromState()`, including in another process. Only the SHA-2
+ * digests sha224, sha256, sha384 and sha512 are resumable; other algorithms
+ * throw.
+ */
+Hash.prototype.exportState = function exportState(): Buffer {
+  const state = op_node_hash_export_state(handle(this));
+  if (state === null) throw new ERR_CRYPTO_HASH_FINALIZED();
+  return Buffer.from(state);
+};
+
+/**
+ * Rebuilds a hash from the output of `hash.exportState()`.
+ */
+Hash.fromState = function fromState(state: ArrayBufferView): Hash {
+  if (!isArrayBufferView(state)) {
+    throw new ERR_INVALID_ARG_TYPE(
+      "state",
+      ["Buffer", "TypedArray", "DataView"],
+      state,
+    );
+  }
+  return new Hash(op_node_hash_import_state(state));
+};
+
+export function encodeDigest(
+  digest: Uint8Array,
+  outputEncoding: Encoding | "buffer",
+) {
   // TODO(@littedivy): Fast paths for below encodings.
   switch (outputEncoding) {
     case "binary":
@@ -175,11 +452,13 @@ Hash.prototype.digest = function digest(outputEncoding: Encoding | "buffer") {
       return encodeToBase64Url(digest);
     case undefined:
     case "buffer":
-      return Buffer.from(digest);
+      // The ops return a fresh ArrayBuffer backed by the digest's own
+      // allocation, so view it instead of copying.
+      return Buffer.from(digest.buffer, digest.byteOffset, digest.byteLength);
     default:
       return Buffer.from(digest).toString(outputEncoding);
   }
-};
+}
 
 export function Hmac(
   hmac: string,
@@ -191,12 +470,30 @@ export function Hmac(
 
 type Hmac = HmacImpl;
 
+// String key encodings that the HMAC ops decode themselves.
+const NATIVE_KEY_ENCODINGS = new SafeSet([
+  "utf8",
+  "latin1",
+  "hex",
+  "base64",
+  "base64url",
+]);
+
+// The largest message kept for a single op call. Longer ones are fed to
+// the native Hmac right away instead of being copied first.
+const HMAC_ONESHOT_MAX_LENGTH = 64 * 1024;
+
 class HmacImpl extends Transform {
-  #ipad: Uint8Array;
-  #opad: Uint8Array;
-  #ZEROES = Buffer.alloc(128);
   #algorithm: string;
-  #hash: Hash;
+  // Until a second update() or a long message, the key and the message are
+  // kept here and the MAC is computed by a single op call, without a
+  // native Hmac object.
+  #handle: unknown;
+  #key: Uint8Array | string | undefined;
+  // How a string #key is decoded; ignored for byte keys.
+  #keyEncoding = "utf8";
+  #message: Uint8Array | undefined;
+  #finalized = false;
 
   constructor(
     hmac: string,
@@ -219,67 +516,389 @@ class HmacImpl extends Transform {
 
     validateString(hmac, "hmac");
 
-    key = prepareSecretKey(key, options?.encoding);
-    let keyData;
-    if (isArrayBufferView(key)) {
-      keyData = key;
-    } else if (isAnyArrayBuffer(key)) {
-      keyData = new Uint8Array(key);
-    } else {
-      keyData = op_node_export_secret_key(key);
-    }
-
     const alg = hmac.toLowerCase();
     this.#algorithm = alg;
-    const blockSize = (alg === "sha512" || alg === "sha384") ? 128 : 64;
-    const keySize = keyData.length;
-
-    let bufKey: Buffer;
-
-    if (keySize > blockSize) {
-      const hash = new Hash(alg, options);
-      bufKey = hash.update(keyData).digest() as Buffer;
+    const keyEncoding = typeof key === "string"
+      ? normalizeEncoding(options?.encoding)
+      : undefined;
+    if (keyEncoding !== undefined && NATIVE_KEY_ENCODINGS.has(keyEncoding)) {
+      // Decoded by the op, without a Buffer in between. Strings are
+      // immutable, so this needs no copy.
+      op_node_hmac_validate(alg);
+      this.#key = key as string;
+      this.#keyEncoding = keyEncoding;
+      return;
+    }
+    key = prepareSecretKey(key, options?.encoding);
+    if (isArrayBufferView(key) || isAnyArrayBuffer(key)) {
+      op_node_hmac_validate(alg);
+      this.#key = copyBytes(key);
     } else {
-      bufKey = Buffer.concat([keyData, this.#ZEROES], blockSize);
+      this.#handle = op_node_create_hmac_from_key(alg, key);
     }
+  }
 
-    this.#ipad = Buffer.allocUnsafe(blockSize);
-    this.#opad = Buffer.allocUnsafe(blockSize);
-
-    for (let i = 0; i < blockSize; i++) {
-      this.#ipad[i] = bufKey[i] ^ 0x36;
-      this.#opad[i] = bufKey[i] ^ 0x5C;
+  #native(): unknown {
+    if (this.#handle === undefined) {
+      this.#handle = op_node_create_hmac(
+        this.#algorithm,
+        this.#key,
+        this.#keyEncoding,
+      );
+      this.#key = undefined;
+      if (this.#message !== undefined) {
+        op_node_hmac_update(this.#handle, this.#message);
+        this.#message = undefined;
+      }
     }
-
-    this.#hash = new Hash(alg);
-    this.#hash.update(this.#ipad);
+    return this.#handle;
   }
 
   digest(): Buffer;
   digest(encoding: BinaryToTextEncoding): string;
   digest(encoding?: BinaryToTextEncoding): Buffer | string {
-    const result = this.#hash.digest();
+    // Unlike Hash, Node.js returns an empty digest on later calls instead of
+    // throwing. update() after digest() still throws.
+    if (this.#finalized) {
+      return encodeDigest(new Uint8Array(), encoding);
+    }
+    this.#finalized = true;
+    if (this.#handle !== undefined) {
+      return encodeDigest(op_node_hmac_digest(this.#handle), encoding);
+    }
 
-    return new Hash(this.#algorithm).update(this.#opad).update(result)
-      .digest(
+    const key = this.#key;
+    const message = this.#message ?? new Uint8Array();
+    this.#key = this.#message = undefined;
+    if (encoding !== undefined && encoding !== "buffer") {
+      const encoded = op_node_hmac_oneshot_encoded(
+        this.#algorithm,
+        key,
+        this.#keyEncoding,
+        message,
         encoding,
       );
+      if (encoded !== null) return encoded;
+    }
+    return encodeDigest(
+      op_node_hmac_oneshot(this.#algorithm, key, this.#keyEncoding, message),
+      encoding,
+    );
   }
 
   update(data: string | ArrayBuffer, inputEncoding?: Encoding): this {
-    this.#hash.update(data, inputEncoding);
+    if (this.#finalized) throw new ERR_CRYPTO_HASH_FINALIZED();
+    const encoding = inputEncoding || getDefaultEncoding();
+    validateUpdateData(data as string | Buffer, encoding);
+    const length = typeof data === "string"
+      ? data.length
+      : (data as ArrayBufferView).byteLength;
+    if (
+      this.#handle === undefined && this.#message === undefined &&
+      length <= HMAC_ONESHOT_MAX_LENGTH
+    ) {
+      // Later changes to `data` must not affect the MAC.
+      this.#message = typeof data === "string"
+        ? toBuf(data, encoding)
+        : copyBytes(data);
+      return this;
+    }
+
+    const handle = this.#native();
+    const isUtf8 = encoding === "utf8" || encoding === "buffer";
+    unwrapErr(
+      typeof data === "string" && isUtf8
+        ? op_node_hmac_update_str(handle, data)
+        : op_node_hmac_update(handle, toBuf(data as string, encoding)),
+    );
     return this;
   }
 }
 
+function copyBytes(data: ArrayBufferView | ArrayBuffer): Uint8Array {
+  const bytes = isAnyArrayBuffer(data)
+    ? new Uint8Array(data)
+    : new Uint8Array(data.buffer, data.byteOffset, data.byteLength);
+  return new Uint8Array(bytes);
+}
+
 Hmac.prototype = HmacImpl.prototype;
 
 /**
  * Creates and returns a Hash object that can be used to generate hash digests
  * using the given `algorithm`. Optional `options` argument controls stream behavior.
+ *
+ * Not part of Node.js: `options.key` selects keyed BLAKE2, and is required
+ * for `kmac128`, `kmac256`, `siphash24` and `siphash13`. SipHash takes a
+ * 16-byte key and an `outputLength` of 8 (the default) or 16 bytes. These
+ * keyed algorithms are not listed by `getHashes()`.
  */
 export function createHash(algorithm: string, opts?: TransformOptions) {
-  return new Hash(algorithm, opts);
+  try {
+    return new Hash(algorithm, opts);
+  } catch (err) {
+    // Other call sites report unknown digests as ERR_CRYPTO_INVALID_DIGEST.
+    if (err.code === "ERR_CRYPTO_INVALID_DIGEST") {
+      throw new ERR_INVALID_ARG_VALUE("algorithm", algorithm);
+    }
+    throw err;
+  }
+}
+
+/**
+ * Returns a web `TransformStream` that passes chunks through unchanged while
+ * hashing them, along with the digest once the stream closes. The digest
+ * rejects if the stream errors or is cancelled. Not part of the public
+ * `node:crypto` API.
+ */
+export function hashStream(
+  algorithm: string,
+  options?: { outputLength?: number },
+): {
+  transform: TransformStream<string | ArrayBufferView>;
+  digest(): Promise<Uint8Array>;
+} {
+  const handle = createHash(algorithm, options)[kHandle];
+  let resolveDigest: (digest: Uint8Array) => void;
+  let rejectDigest: (reason: unknown) => void;
+  const digest = new Promise<Uint8Array>((resolve, reject) => {
+    resolveDigest = resolve;
+    rejectDigest = reject;
+  });
+  // Callers that only pipe the data shouldn't get an unhandled rejection.
+  PromisePrototypeThen(digest, undefined, () => {});
+
+  const transform = new TransformStream<string | ArrayBufferView>({
+    transform(chunk, controller) {
+      try {
+        validateUpdateData(chunk as string | Buffer, "utf8");
+        if (typeof chunk === "string") {
+          op_node_hash_update_str(handle, chunk);
+        } else {
+          op_node_hash_update(handle, chunk);
+        }
+      } catch (err) {
+        rejectDigest(err);
+        throw err;
+      }
+      controller.enqueue(chunk);
+    },
+    flush() {
+      resolveDigest(op_node_hash_digest(handle));
+    },
+    cancel(reason) {
+      rejectDigest(reason);
+    },
+  });
+  return { transform, digest: () => digest };
+}
+
+/**
+ * Like `hashStream()`, with an HMAC of the data computed by a native Hmac
+ * and resolved by `mac()` once the stream closes. Not part of the public
+ * `node:crypto` API.
+ */
+export function hmacStream(
+  algorithm: string,
+  key: BinaryLike | KeyObject,
+): {
+  transform: TransformStream<string | ArrayBufferView>;
+  mac(): Promise<Uint8Array>;
+} {
+  validateString(algorithm, "algorithm");
+  const alg = algorithm.toLowerCase();
+  const secret = prepareSecretKey(key, undefined);
+  const handle = isArrayBufferView(secret) || isAnyArrayBuffer(secret)
+    ? op_node_create_hmac(alg, secret, "utf8")
+    : op_node_create_hmac_from_key(alg, secret);
+  let resolveMac: (mac: Uint8Array) => void;
+  let rejectMac: (reason: unknown) => void;
+  const mac = new Promise<Uint8Array>((resolve, reject) => {
+    resolveMac = resolve;
+    rejectMac = reject;
+  });
+  // Callers that only pipe the data shouldn't get an unhandled rejection.
+  PromisePrototypeThen(mac, undefined, () => {});
+
+  const transform = new TransformStream<string | ArrayBufferView>({
+    transform(chunk, controller) {
+      try {
+        validateUpdateData(chunk as string | Buffer, "utf8");
+        if (typeof chunk === "string") {
+          op_node_hmac_update_str(handle, chunk);
+        } else {
+          op_node_hmac_update(handle, chunk);
+        }
+      } catch (err) {
+        rejectMac(err);
+        throw err;
+      }
+      controller.enqueue(chunk);
+    },
+    flush() {
+      // The only place the Hmac is finalized, so mac() can't race it.
+      resolveMac(op_node_hmac_digest(handle));
+    },
+    cancel(reason) {
+      rejectMac(reason);
+    },
+  });
+  return { transform, mac: () => mac };
+}
+
+/**
+ * Computes the HMAC of `prefix || chunk` for each `[prefix, chunk]` pair
+ * with the same key, in a single op call, and returns the MACs in order.
+ * Large batches are spread across threads. Not part of the public
+ * `node:crypto` API.
+ */
+export function hmacBatch(
+  algorithm: string,
+  key: BinaryLike | KeyObject,
+  pairs: [ArrayBufferView, ArrayBufferView][],
+): Uint8Array[] {
+  validateString(algorithm, "algorithm");
+  validateArray(pairs, "pairs");
+  const secret = prepareSecretKey(key, undefined);
+  return op_node_hmac_batch(
+    algorithm.toLowerCase(),
+    isAnyArrayBuffer(secret) ? new Uint8Array(secret) : secret,
+    pairs,
+  );
+}
+
+/**
+ * Computes digests of the same data with several algorithms in one pass:
+ * each chunk given to `update()` crosses into native code once and is fed
+ * to every hash there. `digest()` returns the digests in the order of
+ * `algorithms`. Unsupported algorithms throw here rather than on first use.
+ * Not part of the public `node:crypto` API.
+ */
+export function createMultiHash(algorithms: string[]): MultiHash {
+  return new MultiHash(algorithms);
+}
+
+export class MultiHash {
+  #handle: unknown;
+
+  constructor(algorithms: string[]) {
+    validateArray(algorithms, "algorithms");
+    const names = [];
+    for (let i = 0; i < algorithms.length; i++) {
+      validateString(algorithms[i], `algorithms[${i}]`);
+      names.push(algorithms[i].toLowerCase());
+    }
+    this.#handle = op_node_create_multi_hash(names);
+  }
+
+  update(data: BinaryLike, encoding?: Encoding): this {
+    encoding = encoding || getDefaultEncoding();
+    validateUpdateData(data as string | Buffer, encoding);
+    const isUtf8 = encoding === "utf8" || encoding === "buffer";
+    unwrapErr(
+      typeof data === "string" && isUtf8
+        ? op_node_multi_hash_update_str(this.#handle, data)
+        : op_node_multi_hash_update(this.#handle, toBuf(data, encoding)),
+    );
+    return this;
+  }
+
+  digest(): Buffer[];
+  digest(encoding: BinaryToTextEncoding): string[];
+  digest(encoding?: BinaryToTextEncoding): (Buffer | string)[] {
+    const digests = op_node_multi_hash_digest(this.#handle);
+    if (digests === null) throw new ERR_CRYPTO_HASH_FINALIZED();
+    return digests.map((digest: Uint8Array) => encodeDigest(digest, encoding));
+  }
+}
+
+/**
+ * Hashes the file at `path` without reading its contents into JS. Used by
+ * `node:fs`-style helpers; not part of the public `node:crypto` API.
+ */
+export async function hashFile(
+  algorithm: string,
+  path: string | Buffer | URL,
+  outputLength?: number,
+): Promise<Buffer> {
+  validateString(algorithm, "algorithm");
+  if (outputLength !== undefined) {
+    validateUint32(outputLength, "outputLength");
+  }
+  const digest = await op_node_hash_file(
+    algorithm.toLowerCase(),
+    getValidatedPathToString(path),
+    outputLength,
+  );
+  return Buffer.from(digest);
+}
+
+/**
+ * Checks `data` against an `expected` digest, given as bytes or as a hex,
+ * base64 or base64url string, comparing in constant time. Returns false on
+ * a mismatch and throws for unknown algorithms or an expected digest of the
+ * wrong length. Used by integrity helpers; not part of the public
+ * `node:crypto` API.
+ */
+export function verifyDigest(
+  algorithm: string,
+  data: BinaryLike,
+  expected: string | ArrayBufferView,
+  encoding: "hex" | "base64" | "base64url" = "hex",
+): boolean {
+  validateString(algorithm, "algorithm");
+  const buf = toBuf(data as string | Buffer);
+  if (typeof expected === "string") {
+    return op_node_hash_verify_str(
+      algorithm.toLowerCase(),
+      buf,
+      expected,
+      encoding,
+    );
+  }
+  if (!isArrayBufferView(expected)) {
+    throw new ERR_INVALID_ARG_TYPE(
+      "expected",
+      ["string", "Buffer", "TypedArray", "DataView"],
+      expected,
+    );
+  }
+  return op_node_hash_verify(algorithm.toLowerCase(), buf, expected);
+}
+
+/**
+ * Checks an HMAC `expected` tag over `data`, given as bytes or as a hex,
+ * base64 or base64url string. The tag is computed and compared in constant
+ * time natively and never reaches JS. Returns false on a mismatch, including
+ * a tag of the wrong length. Not part of the public `node:crypto` API.
+ */
+export function verifyHmac(
+  algorithm: string,
+  key: BinaryLike | KeyObject,
+  data: BinaryLike,
+  expected: string | ArrayBufferView,
+  encoding: "hex" | "base64" | "base64url" = "hex",
+): boolean {
+  validateString(algorithm, "algorithm");
+  const alg = algorithm.toLowerCase();
+  const secret = prepareSecretKey(key, undefined);
+  const buf = toBuf(data as string | Buffer);
+  const isKeyObject = !isArrayBufferView(secret) && !isAnyArrayBuffer(secret);
+  if (typeof expected === "string") {
+    return isKeyObject
+      ? op_node_hmac_verify_with_key_str(alg, secret, buf, expected, encoding)
+      : op_node_hmac_verify_str(alg, secret, buf, expected, encoding);
+  }
+  if (!isArrayBufferView(expected)) {
+    throw new ERR_INVALID_ARG_TYPE(
+      "expected",
+      ["string", "Buffer", "TypedArray", "DataView"],
+      expected,
+    );
+  }
+  return isKeyObject
+    ? op_node_hmac_verify_with_key(alg, secret, buf, expected)
+    : op_node_hmac_verify(alg, secret, buf, expected);
 }
 
 /**
@@ -290,6 +909,18 @@ export function getHashes() {
   return op_node_get_hashes();
 }
 
+// Helpers that are not part of the public `node:crypto` API, reachable
+// through `Deno[Deno.internal].nodeCrypto` for tests.
+internals.nodeCrypto = {
+  createMultiHash,
+  hashFile,
+  hashStream,
+  hmacBatch,
+  hmacStream,
+  verifyDigest,
+  verifyHmac,
+};
+
 export default {
   Hash,
   Hmac,
@@ -5,6 +5,7 @@
 // deno-lint-ignore-file prefer-primordials
 
 import {
+  op_node_get_fips,
   op_node_get_hash_size,
   op_node_hkdf,
   op_node_hkdf_async,
@@ -157,9 +158,13 @@ export function hkdfSync(
 }
 
 let hashes: Set<string> | null = null;
+let hashesFips = false;
 function validateAlgorithm(algorithm: string) {
-  if (hashes === null) {
+  // The list shrinks while FIPS mode is on, so rebuild it when that changes.
+  const fips = op_node_get_fips();
+  if (hashes === null || hashesFips !== fips) {
     hashes = new Set(getHashes());
+    hashesFips = fips;
   }
 
   if (!hashes.has(algorithm)) {
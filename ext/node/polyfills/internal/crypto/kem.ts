@@ -0,0 +1,106 @@
+// Copyright 2018-2025 the Deno authors. MIT license.
+// Copyright Joyent, Inc. and Node.js contributors. All rights reserved. MIT license.
+
+// TODO(petamoriken): enable prefer-primordials for node polyfills
+// deno-lint-ignore-file prefer-primordials
+
+import {
+  op_node_create_private_key,
+  op_node_create_public_key,
+  op_node_decapsulate,
+  op_node_encapsulate,
+} from "ext:core/ops";
+
+import { validateFunction } from "ext:deno_node/internal/validators.mjs";
+import { Buffer } from "node:buffer";
+import type {
+  PrivateKeyInput,
+  PublicKeyInput,
+} from "ext:deno_node/internal/crypto/types.ts";
+import {
+  getArrayBufferOrView,
+  kConsumePrivate,
+  kConsumePublic,
+  KeyHandleContext,
+  prepareAsymmetricKey,
+} from "ext:deno_node/internal/crypto/keys.ts";
+import type { KeyLike } from "ext:deno_node/internal/crypto/sig.ts";
+
+export interface EncapsulateResult {
+  sharedKey: Buffer;
+  ciphertext: Buffer;
+}
+
+function getHandle(
+  key: KeyLike | PublicKeyInput | PrivateKeyInput,
+  ctx: KeyHandleContext,
+) {
+  const res = prepareAsymmetricKey(key, ctx);
+  if ("handle" in res) {
+    return res.handle;
+  }
+  const create = ctx === kConsumePublic
+    ? op_node_create_public_key
+    : op_node_create_private_key;
+  return create(res.data, res.format, res.type ?? "", res.passphrase);
+}
+
+/**
+ * Generates a shared secret and encapsulates it for the holder of the
+ * ML-KEM private key.
+ */
+export function encapsulate(
+  key: KeyLike | PublicKeyInput | PrivateKeyInput,
+  callback?: (error: Error | null, result: EncapsulateResult) => void,
+): EncapsulateResult | void {
+  if (callback !== undefined) {
+    validateFunction(callback, "callback");
+  }
+
+  const [sharedKey, ciphertext] = op_node_encapsulate(
+    getHandle(key, kConsumePublic),
+  );
+  const result = {
+    sharedKey: Buffer.from(sharedKey),
+    ciphertext: Buffer.from(ciphertext),
+  };
+
+  if (callback) {
+    setTimeout(() => callback(null, result));
+  } else {
+    return result;
+  }
+}
+
+/**
+ * Recovers the shared secret from a ciphertext of `encapsulate()`. A
+ * ciphertext that wasn't made for the key yields an unrelated secret
+ * rather than an error, as FIPS 203 requires.
+ */
+export function decapsulate(
+  key: KeyLike | PrivateKeyInput,
+  ciphertext: ArrayBuffer | ArrayBufferView | string,
+  callback?: (error: Error | null, sharedKey: Buffer) => void,
+): Buffer | void {
+  if (callback !== undefined) {
+    validateFunction(callback, "callback");
+  }
+
+  const result = Buffer.from(
+    op_node_decapsulate(
+      getHandle(key, kConsumePrivate),
+      getArrayBufferOrView(ciphertext, "ciphertext"),
+    ),
+  );
+
+  if (callback) {
+    setTimeout(() => callback(null, result));
+  } else {
+    return result;
+  }
+}
+
+export default {
+  decapsulate,
+  encapsulate,
+};
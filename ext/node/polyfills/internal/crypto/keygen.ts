@@ -11,7 +11,6 @@ import {
   PublicKeyObject,
   SecretKeyObject,
 } from "ext:deno_node/internal/crypto/keys.ts";
-import { notImplemented } from "ext:deno_node/_utils.ts";
 import {
   ERR_INCOMPATIBLE_OPTION_PAIR,
   ERR_INVALID_ARG_VALUE,
@@ -28,7 +27,13 @@ import {
   validateUint32,
 } from "ext:deno_node/internal/validators.mjs";
 import { Buffer } from "node:buffer";
-import { KeyFormat, KeyType } from "ext:deno_node/internal/crypto/types.ts";
+import {
+  KeyFormat,
+  KeyType,
+  MLDSAKeyType,
+  MLKEMKeyType,
+  SLHDSAKeyType,
+} from "ext:deno_node/internal/crypto/types.ts";
 import process from "node:process";
 import { promisify } from "node:util";
 
@@ -43,14 +48,24 @@ import {
   op_node_generate_ec_key_async,
   op_node_generate_ed25519_key,
   op_node_generate_ed25519_key_async,
+  op_node_generate_ed448_key,
+  op_node_generate_ed448_key_async,
+  op_node_generate_ml_dsa_key,
+  op_node_generate_ml_dsa_key_async,
+  op_node_generate_ml_kem_key,
+  op_node_generate_ml_kem_key_async,
   op_node_generate_rsa_key,
   op_node_generate_rsa_key_async,
   op_node_generate_rsa_pss_key,
   op_node_generate_rsa_pss_key_async,
+  op_node_generate_slh_dsa_key,
+  op_node_generate_slh_dsa_key_async,
   op_node_generate_secret_key,
   op_node_generate_secret_key_async,
   op_node_generate_x25519_key,
   op_node_generate_x25519_key_async,
+  op_node_generate_x448_key,
+  op_node_generate_x448_key_async,
   op_node_get_private_key_from_pair,
   op_node_get_public_key_from_pair,
 } from "ext:core/ops";
@@ -238,6 +253,45 @@ export interface ED448KeyPairOptions<
   };
 }
 
+export interface MLDSAKeyPairOptions<
+  PubF extends KeyFormat,
+  PrivF extends KeyFormat,
+> {
+  publicKeyEncoding: {
+    type: "spki";
+    format: PubF;
+  };
+  privateKeyEncoding: BasePrivateKeyEncodingOptions<PrivF> & {
+    type: "pkcs8";
+  };
+}
+
+export interface MLKEMKeyPairOptions<
+  PubF extends KeyFormat,
+  PrivF extends KeyFormat,
+> {
+  publicKeyEncoding: {
+    type: "spki";
+    format: PubF;
+  };
+  privateKeyEncoding: BasePrivateKeyEncodingOptions<PrivF> & {
+    type: "pkcs8";
+  };
+}
+
+export interface SLHDSAKeyPairOptions<
+  PubF extends KeyFormat,
+  PrivF extends KeyFormat,
+> {
+  publicKeyEncoding: {
+    type: "spki";
+    format: PubF;
+  };
+  privateKeyEncoding: BasePrivateKeyEncodingOptions<PrivF> & {
+    type: "pkcs8";
+  };
+}
+
 export interface X25519KeyPairOptions<
   PubF extends KeyFormat,
   PrivF extends KeyFormat,
@@ -317,6 +371,15 @@ export interface ED25519KeyPairKeyObjectOptions {}
 // deno-lint-ignore no-empty-interface
 export interface ED448KeyPairKeyObjectOptions {}
 
+// deno-lint-ignore no-empty-interface
+export interface MLDSAKeyPairKeyObjectOptions {}
+
+// deno-lint-ignore no-empty-interface
+export interface MLKEMKeyPairKeyObjectOptions {}
+
+// deno-lint-ignore no-empty-interface
+export interface SLHDSAKeyPairKeyObjectOptions {}
+
 // deno-lint-ignore no-empty-interface
 export interface X25519KeyPairKeyObjectOptions {}
 
@@ -504,6 +567,93 @@ export function generateKeyPair(
     privateKey: KeyObject,
   ) => void,
 ): void;
+export function generateKeyPair(
+  type: MLDSAKeyType,
+  options: MLDSAKeyPairOptions<"pem", "pem">,
+  callback: (err: Error | null, publicKey: string, privateKey: string) => void,
+): void;
+export function generateKeyPair(
+  type: MLDSAKeyType,
+  options: MLDSAKeyPairOptions<"pem", "der">,
+  callback: (err: Error | null, publicKey: string, privateKey: Buffer) => void,
+): void;
+export function generateKeyPair(
+  type: MLDSAKeyType,
+  options: MLDSAKeyPairOptions<"der", "pem">,
+  callback: (err: Error | null, publicKey: Buffer, privateKey: string) => void,
+): void;
+export function generateKeyPair(
+  type: MLDSAKeyType,
+  options: MLDSAKeyPairOptions<"der", "der">,
+  callback: (err: Error | null, publicKey: Buffer, privateKey: Buffer) => void,
+): void;
+export function generateKeyPair(
+  type: MLDSAKeyType,
+  options: MLDSAKeyPairKeyObjectOptions | undefined,
+  callback: (
+    err: Error | null,
+    publicKey: KeyObject,
+    privateKey: KeyObject,
+  ) => void,
+): void;
+export function generateKeyPair(
+  type: MLKEMKeyType,
+  options: MLKEMKeyPairOptions<"pem", "pem">,
+  callback: (err: Error | null, publicKey: string, privateKey: string) => void,
+): void;
+export function generateKeyPair(
+  type: MLKEMKeyType,
+  options: MLKEMKeyPairOptions<"pem", "der">,
+  callback: (err: Error | null, publicKey: string, privateKey: Buffer) => void,
+): void;
+export function generateKeyPair(
+  type: MLKEMKeyType,
+  options: MLKEMKeyPairOptions<"der", "pem">,
+  callback: (err: Error | null, publicKey: Buffer, privateKey: string) => void,
+): void;
+export function generateKeyPair(
+  type: MLKEMKeyType,
+  options: MLKEMKeyPairOptions<"der", "der">,
+  callback: (err: Error | null, publicKey: Buffer, privateKey: Buffer) => void,
+): void;
+export function generateKeyPair(
+  type: MLKEMKeyType,
+  options: MLKEMKeyPairKeyObjectOptions | undefined,
+  callback: (
+    err: Error | null,
+    publicKey: KeyObject,
+    privateKey: KeyObject,
+  ) => void,
+): void;
+export function generateKeyPair(
+  type: SLHDSAKeyType,
+  options: SLHDSAKeyPairOptions<"pem", "pem">,
+  callback: (err: Error | null, publicKey: string, privateKey: string) => void,
+): void;
+export function generateKeyPair(
+  type: SLHDSAKeyType,
+  options: SLHDSAKeyPairOptions<"pem", "der">,
+  callback: (err: Error | null, publicKey: string, privateKey: Buffer) => void,
+): void;
+export function generateKeyPair(
+  type: SLHDSAKeyType,
+  options: SLHDSAKeyPairOptions<"der", "pem">,
+  callback: (err: Error | null, publicKey: Buffer, privateKey: string) => void,
+): void;
+export function generateKeyPair(
+  type: SLHDSAKeyType,
+  options: SLHDSAKeyPairOptions<"der", "der">,
+  callback: (err: Error | null, publicKey: Buffer, privateKey: Buffer) => void,
+): void;
+export function generateKeyPair(
+  type: SLHDSAKeyType,
+  options: SLHDSAKeyPairKeyObjectOptions | undefined,
+  callback: (
+    err: Error | null,
+    publicKey: KeyObject,
+    privateKey: KeyObject,
+  ) => void,
+): void;
 export function generateKeyPair(
   type: "x25519",
   options: X25519KeyPairOptions<"pem", "pem">,
@@ -740,6 +890,66 @@ export function generateKeyPairSync(
   type: "ed448",
   options?: ED448KeyPairKeyObjectOptions,
 ): KeyPairKeyObjectResult;
+export function generateKeyPairSync(
+  type: MLDSAKeyType,
+  options: MLDSAKeyPairOptions<"pem", "pem">,
+): KeyPairSyncResult<string, string>;
+export function generateKeyPairSync(
+  type: MLDSAKeyType,
+  options: MLDSAKeyPairOptions<"pem", "der">,
+): KeyPairSyncResult<string, Buffer>;
+export function generateKeyPairSync(
+  type: MLDSAKeyType,
+  options: MLDSAKeyPairOptions<"der", "pem">,
+): KeyPairSyncResult<Buffer, string>;
+export function generateKeyPairSync(
+  type: MLDSAKeyType,
+  options: MLDSAKeyPairOptions<"der", "der">,
+): KeyPairSyncResult<Buffer, Buffer>;
+export function generateKeyPairSync(
+  type: MLDSAKeyType,
+  options?: MLDSAKeyPairKeyObjectOptions,
+): KeyPairKeyObjectResult;
+export function generateKeyPairSync(
+  type: MLKEMKeyType,
+  options: MLKEMKeyPairOptions<"pem", "pem">,
+): KeyPairSyncResult<string, string>;
+export function generateKeyPairSync(
+  type: MLKEMKeyType,
+  options: MLKEMKeyPairOptions<"pem", "der">,
+): KeyPairSyncResult<string, Buffer>;
+export function generateKeyPairSync(
+  type: MLKEMKeyType,
+  options: MLKEMKeyPairOptions<"der", "pem">,
+): KeyPairSyncResult<Buffer, string>;
+export function generateKeyPairSync(
+  type: MLKEMKeyType,
+  options: MLKEMKeyPairOptions<"der", "der">,
+): KeyPairSyncResult<Buffer, Buffer>;
+export function generateKeyPairSync(
+  type: MLKEMKeyType,
+  options?: MLKEMKeyPairKeyObjectOptions,
+): KeyPairKeyObjectResult;
+export function generateKeyPairSync(
+  type: SLHDSAKeyType,
+  options: SLHDSAKeyPairOptions<"pem", "pem">,
+): KeyPairSyncResult<string, string>;
+export function generateKeyPairSync(
+  type: SLHDSAKeyType,
+  options: SLHDSAKeyPairOptions<"pem", "der">,
+): KeyPairSyncResult<string, Buffer>;
+export function generateKeyPairSync(
+  type: SLHDSAKeyType,
+  options: SLHDSAKeyPairOptions<"der", "pem">,
+): KeyPairSyncResult<Buffer, string>;
+export function generateKeyPairSync(
+  type: SLHDSAKeyType,
+  options: SLHDSAKeyPairOptions<"der", "der">,
+): KeyPairSyncResult<Buffer, Buffer>;
+export function generateKeyPairSync(
+  type: SLHDSAKeyType,
+  options?: SLHDSAKeyPairKeyObjectOptions,
+): KeyPairKeyObjectResult;
 export function generateKeyPairSync(
   type: "x25519",
   options: X25519KeyPairOptions<"pem", "pem">,
@@ -914,7 +1124,13 @@ function createJob(mode, type, options) {
 
       let { divisorLength } = options;
       if (divisorLength == null) {
-        divisorLength = 256;
+        // The FIPS 186-4 size for the modulus. OpenSSL defaults to 224,
+        // which is the same for 2048-bit keys.
+        divisorLength = modulusLength < 2048
+          ? 160
+          : modulusLength < 3072
+          ? 224
+          : 256;
       } else {
         validateInt32(divisorLength, "options.divisorLength", 0);
       }
@@ -960,10 +1176,48 @@ function createJob(mode, type, options) {
       }
       return op_node_generate_x25519_key_async();
     }
-    case "ed448":
+    case "ed448": {
+      if (mode === kSync) {
+        return op_node_generate_ed448_key();
+      }
+      return op_node_generate_ed448_key_async();
+    }
+    case "ml-dsa-44":
+    case "ml-dsa-65":
+    case "ml-dsa-87": {
+      if (mode === kSync) {
+        return op_node_generate_ml_dsa_key(type);
+      }
+      return op_node_generate_ml_dsa_key_async(type);
+    }
+    case "ml-kem-768": {
+      if (mode === kSync) {
+        return op_node_generate_ml_kem_key(type);
+      }
+      return op_node_generate_ml_kem_key_async(type);
+    }
+    case "slh-dsa-sha2-128s":
+    case "slh-dsa-sha2-128f":
+    case "slh-dsa-sha2-192s":
+    case "slh-dsa-sha2-192f":
+    case "slh-dsa-sha2-256s":
+    case "slh-dsa-sha2-256f":
+    case "slh-dsa-shake-128s":
+    case "slh-dsa-shake-128f":
+    case "slh-dsa-shake-192s":
+    case "slh-dsa-shake-192f":
+    case "slh-dsa-shake-256s":
+    case "slh-dsa-shake-256f": {
+      if (mode === kSync) {
+        return op_node_generate_slh_dsa_key(type);
+      }
+      return op_node_generate_slh_dsa_key_async(type);
+    }
     case "x448": {
-      notImplemented(type);
-      break;
+      if (mode === kSync) {
+        return op_node_generate_x448_key();
+      }
+      return op_node_generate_x448_key_async();
     }
     case "dh": {
       validateObject(options, "options");
@@ -13,26 +13,40 @@ const {
 
 import {
   op_node_create_ec_jwk,
-  op_node_create_ed_raw,
+  op_node_create_key_object_from_crypto_key,
+  op_node_create_ml_dsa_raw,
+  op_node_create_ml_kem_raw,
+  op_node_create_okp_jwk,
   op_node_create_private_key,
   op_node_create_public_key,
   op_node_create_rsa_jwk,
   op_node_create_secret_key,
+  op_node_create_secret_key_from_string,
   op_node_derive_public_key_from_private_key,
+  op_node_export_crypto_key_data,
   op_node_export_private_key_der,
   op_node_export_private_key_jwk,
   op_node_export_private_key_pem,
+  op_node_export_private_key_raw,
   op_node_export_public_key_der,
   op_node_export_public_key_jwk,
   op_node_export_public_key_pem,
+  op_node_export_public_key_raw,
   op_node_export_secret_key,
   op_node_export_secret_key_b64url,
   op_node_get_asymmetric_key_details,
   op_node_get_asymmetric_key_type,
   op_node_get_symmetric_key_size,
+  op_node_key_equals,
   op_node_key_type,
 } from "ext:core/ops";
 
+import * as webidl from "ext:deno_webidl/00_webidl.js";
+import {
+  createCryptoKey,
+  getCryptoKeyData,
+  normalizeAlgorithm,
+} from "ext:deno_crypto/00_crypto.js";
 import { kHandle } from "ext:deno_node/internal/crypto/constants.ts";
 import { isStringOrBuffer } from "ext:deno_node/internal/crypto/cipher.ts";
 import {
@@ -43,6 +57,9 @@ import {
   ERR_INVALID_ARG_VALUE,
 } from "ext:deno_node/internal/errors.ts";
 import { notImplemented } from "ext:deno_node/_utils.ts";
+import {
+  normalizeEncoding,
+} from "ext:deno_node/internal/normalize_encoding.mjs";
 import type {
   KeyFormat,
   PrivateKeyInput,
@@ -164,6 +181,23 @@ export interface JwkKeyExportOptions {
   format: "jwk";
 }
 
+/**
+ * The FIPS 204 or FIPS 203 public key, the seed or the expanded private key
+ * of an ML-DSA or ML-KEM key.
+ */
+export type RawKeyFormat = "raw-public" | "raw-seed" | "raw-private";
+
+export interface RawKeyExportOptions {
+  format: RawKeyFormat;
+}
+
+export interface RawKeyInput {
+  key: string | ArrayBuffer | ArrayBufferView;
+  format: RawKeyFormat;
+  asymmetricKeyType: "ml-dsa-44" | "ml-dsa-65" | "ml-dsa-87" | "ml-kem-768";
+  encoding?: string;
+}
+
 export enum KeyHandleContext {
   kConsumePublic = 0,
   kConsumePrivate = 1,
@@ -176,11 +210,6 @@ export const kConsumePrivate = KeyHandleContext.kConsumePrivate;
 export const kCreatePublic = KeyHandleContext.kCreatePublic;
 export const kCreatePrivate = KeyHandleContext.kCreatePrivate;
 
-function isJwk(obj: unknown): obj is { kty: unknown } {
-  // @ts-ignore this is fine
-  return typeof obj === "object" && obj != null && obj.kty !== undefined;
-}
-
 export type KeyObjectHandle = { ___keyObjectHandle: true };
 
 export class KeyObject {
@@ -209,7 +238,27 @@ export class KeyObject {
     if (!isCryptoKey(key)) {
       throw new ERR_INVALID_ARG_TYPE("key", "CryptoKey", key);
     }
-    notImplemented("crypto.KeyObject.prototype.from");
+    const keyData = getCryptoKeyData(key);
+    if (key.type === "secret") {
+      return new SecretKeyObject(op_node_create_secret_key(keyData.data));
+    }
+
+    const { name, namedCurve } = key.algorithm as EcKeyAlgorithm;
+    // ext/crypto keeps the bare keys of the CFRG curves, and RSA and EC key
+    // pairs share the private key material.
+    const [data, isPrivateData] = isArrayBufferView(keyData)
+      ? [keyData, key.type === "private"]
+      : [keyData.data, keyData.type === "private"];
+    const handle = op_node_create_key_object_from_crypto_key(
+      kCryptoKeyAlgorithms[name] ?? name,
+      namedCurve ?? "",
+      data,
+      isPrivateData,
+      key.type === "public",
+    );
+    return key.type === "private"
+      ? new PrivateKeyObject(handle)
+      : new PublicKeyObject(handle);
   }
 
   equals(otherKeyObject: KeyObject): boolean {
@@ -221,7 +270,7 @@ export class KeyObject {
       );
     }
 
-    notImplemented("crypto.KeyObject.prototype.equals");
+    return op_node_key_equals(this[kHandle], otherKeyObject[kHandle]);
   }
 
   export(options: KeyExportOptions<"pem">): string | Buffer;
@@ -241,6 +290,69 @@ ObjectDefineProperties(KeyObject.prototype, {
   },
 });
 
+const kCryptoKeyAlgorithms: Record<string, string> = {
+  "RSASSA-PKCS1-v1_5": "rsa",
+  "RSA-PSS": "rsa",
+  "RSA-OAEP": "rsa",
+  "ECDSA": "ec",
+  "ECDH": "ec",
+};
+
+const kNamedCurveAliases: Record<string, string> = {
+  "P-256": "prime256v1",
+  "P-384": "secp384r1",
+  "P-521": "secp521r1",
+};
+
+function normalizeToCryptoKeyArguments(
+  algorithm: AlgorithmIdentifier,
+  extractable: boolean,
+  keyUsages: KeyUsage[],
+) {
+  const prefix = "Failed to execute 'toCryptoKey' on 'KeyObject'";
+  return {
+    algorithm: normalizeAlgorithm(
+      webidl.converters.AlgorithmIdentifier(algorithm, prefix, "Argument 1"),
+      "importKey",
+    ),
+    extractable: webidl.converters.boolean(extractable, prefix, "Argument 2"),
+    keyUsages: webidl.converters["sequence<KeyUsage>"](
+      keyUsages,
+      prefix,
+      "Argument 3",
+    ),
+  };
+}
+
+function validateKeyUsages(
+  keyUsages: KeyUsage[],
+  allowed: KeyUsage[],
+  keyName: string,
+) {
+  if (keyUsages.some((usage) => !allowed.includes(usage))) {
+    throw new DOMException(
+      `Unsupported key usage for ${keyName} key`,
+      "SyntaxError",
+    );
+  }
+}
+
+function createCryptoKeyFromKeyObject(
+  type: KeyObjectType,
+  extractable: boolean,
+  keyUsages: KeyUsage[],
+  algorithm: KeyAlgorithm,
+  keyData: unknown,
+): CryptoKey {
+  if (type !== "public" && keyUsages.length === 0) {
+    throw new DOMException(
+      `Usages cannot be empty when importing a ${type} key.`,
+      "SyntaxError",
+    );
+  }
+  return createCryptoKey(type, extractable, keyUsages, algorithm, keyData);
+}
+
 export interface JsonWebKeyInput {
   key: JsonWebKey;
   format: "jwk";
@@ -270,6 +382,9 @@ export function getKeyObjectHandle(key: KeyObject, ctx: KeyHandleContext) {
   return key[kHandle];
 }
 
+const kOkpJwkCurves = ["Ed25519", "Ed448", "X25519", "X448"];
+const kEcJwkCurves = ["P-256", "secp256k1", "P-384", "P-521"];
+
 function getKeyObjectHandleFromJwk(key, ctx) {
   validateObject(key, "key");
   validateOneOf(
@@ -281,48 +396,31 @@ function getKeyObjectHandleFromJwk(key, ctx) {
 
   if (key.kty === "OKP") {
     validateString(key.crv, "key.crv");
-    validateOneOf(
-      key.crv,
-      "key.crv",
-      ["Ed25519", "Ed448", "X25519", "X448"],
-    );
+    if (kEcJwkCurves.includes(key.crv)) {
+      throw new ERR_CRYPTO_INVALID_JWK();
+    }
+    validateOneOf(key.crv, "key.crv", kOkpJwkCurves);
     validateString(key.x, "key.x");
 
+    const jwk = {
+      crv: key.crv,
+      x: key.x,
+    };
+
     if (!isPublic) {
       validateString(key.d, "key.d");
+      jwk.d = key.d;
     }
 
-    let keyData;
-    if (isPublic) {
-      keyData = Buffer.from(key.x, "base64");
-    } else {
-      keyData = Buffer.from(key.d, "base64");
-    }
-
-    switch (key.crv) {
-      case "Ed25519":
-      case "X25519":
-        if (keyData.byteLength !== 32) {
-          throw new ERR_CRYPTO_INVALID_JWK();
-        }
-        break;
-      case "Ed448":
-        if (keyData.byteLength !== 57) {
-          throw new ERR_CRYPTO_INVALID_JWK();
-        }
-        break;
-      case "X448":
-        if (keyData.byteLength !== 56) {
-          throw new ERR_CRYPTO_INVALID_JWK();
-        }
-        break;
-    }
-
-    return op_node_create_ed_raw(key.crv, keyData, isPublic);
+    return op_node_create_okp_jwk(jwk, isPublic);
   }
 
   if (key.kty === "EC") {
     validateString(key.crv, "key.crv");
+    if (kOkpJwkCurves.includes(key.crv)) {
+      throw new ERR_CRYPTO_INVALID_JWK();
+    }
+    validateOneOf(key.crv, "key.crv", kEcJwkCurves);
     validateString(key.x, "key.x");
     validateString(key.y, "key.y");
 
@@ -358,9 +456,43 @@ function getKeyObjectHandleFromJwk(key, ctx) {
     jwk.qi = key.qi;
   }
 
+  if (typeof key.alg === "string") {
+    jwk.alg = key.alg;
+  }
+
   return op_node_create_rsa_jwk(jwk, isPublic);
 }
 
+function getKeyObjectHandleFromRaw(key: RawKeyInput, ctx) {
+  const { key: data, format, asymmetricKeyType } = key;
+  validateOneOf(
+    asymmetricKeyType,
+    "key.asymmetricKeyType",
+    ["ml-dsa-44", "ml-dsa-65", "ml-dsa-87", "ml-kem-768"],
+  );
+  // A public key can be derived from a seed, but not the other way round.
+  if (
+    format === "raw-public" &&
+    (ctx === kConsumePrivate || ctx === kCreatePrivate)
+  ) {
+    throw new ERR_INVALID_ARG_VALUE("key.format", format);
+  }
+
+  const create = asymmetricKeyType === "ml-kem-768"
+    ? op_node_create_ml_kem_raw
+    : op_node_create_ml_dsa_raw;
+  return create(
+    asymmetricKeyType,
+    format,
+    getArrayBufferOrView(data, "key.key", key.encoding),
+  );
+}
+
+function isRawKeyFormat(format: unknown): format is RawKeyFormat {
+  return format === "raw-public" || format === "raw-seed" ||
+    format === "raw-private";
+}
+
 export function prepareAsymmetricKey(
   key:
     | string
@@ -371,10 +503,11 @@ export function prepareAsymmetricKey(
     | CryptoKey
     | PrivateKeyInput
     | PublicKeyInput
-    | JsonWebKeyInput,
+    | JsonWebKeyInput
+    | RawKeyInput,
   ctx: KeyHandleContext,
 ):
-  | { handle: KeyObjectHandle; format?: "jwk" }
+  | { handle: KeyObjectHandle; format?: "jwk" | RawKeyFormat }
   | {
     data: ArrayBuffer | ArrayBufferView;
     format: KeyFormat;
@@ -389,7 +522,11 @@ export function prepareAsymmetricKey(
       handle: getKeyObjectHandle(key, ctx),
     };
   } else if (isCryptoKey(key)) {
-    notImplemented("using CryptoKey as input");
+    return {
+      // @ts-ignore __proto__ is magic
+      __proto__: null,
+      handle: getKeyObjectHandle(KeyObject.from(key), ctx),
+    };
   } else if (isStringOrBuffer(key)) {
     // Expect PEM by default, mostly for backward compatibility.
     return {
@@ -409,14 +546,26 @@ export function prepareAsymmetricKey(
         handle: getKeyObjectHandle(data, ctx),
       };
     } else if (isCryptoKey(data)) {
-      notImplemented("using CryptoKey as input");
-    } else if (isJwk(data) && format === "jwk") {
+      return {
+        // @ts-ignore __proto__ is magic
+        __proto__: null,
+        handle: getKeyObjectHandle(KeyObject.from(data), ctx),
+      };
+    } else if (format === "jwk") {
+      validateObject(data, "key.key");
       return {
         // @ts-ignore __proto__ is magic
         __proto__: null,
         handle: getKeyObjectHandleFromJwk(data, ctx),
         format,
       };
+    } else if (isRawKeyFormat(format)) {
+      return {
+        // @ts-ignore __proto__ is magic
+        __proto__: null,
+        handle: getKeyObjectHandleFromRaw(key as RawKeyInput, ctx),
+        format,
+      };
     }
     // Either PEM or DER using PKCS#1 or SPKI.
     if (!isStringOrBuffer(data)) {
@@ -646,7 +795,7 @@ function parsePrivateKeyEncoding(
 }
 
 export function createPrivateKey(
-  key: PrivateKeyInput | string | Buffer | JsonWebKeyInput,
+  key: PrivateKeyInput | string | Buffer | JsonWebKeyInput | RawKeyInput,
 ): PrivateKeyObject {
   const res = prepareAsymmetricKey(key, kCreatePrivate);
   if ("handle" in res) {
@@ -668,7 +817,7 @@ export function createPrivateKey(
 }
 
 export function createPublicKey(
-  key: PublicKeyInput | string | Buffer | JsonWebKeyInput,
+  key: PublicKeyInput | string | Buffer | JsonWebKeyInput | RawKeyInput,
 ): PublicKeyObject {
   const res = prepareAsymmetricKey(
     key,
@@ -724,7 +873,10 @@ export function prepareSecretKey(
       }
       return key[kHandle];
     } else if (isCryptoKey(key)) {
-      notImplemented("using CryptoKey as input");
+      if (key.type !== "secret") {
+        throw new ERR_CRYPTO_INVALID_KEY_OBJECT_TYPE(key.type, "secret");
+      }
+      return KeyObject.from(key)[kHandle];
     }
   }
   if (
@@ -755,6 +907,75 @@ export class SecretKeyObject extends KeyObject {
     return undefined;
   }
 
+  toCryptoKey(
+    algorithm: AlgorithmIdentifier | HmacImportParams,
+    extractable: boolean,
+    keyUsages: KeyUsage[],
+  ): CryptoKey {
+    const normalized = normalizeToCryptoKeyArguments(
+      algorithm,
+      extractable,
+      keyUsages,
+    );
+    const length = this.symmetricKeySize * 8;
+    let keyAlgorithm;
+    switch (normalized.algorithm.name) {
+      case "HMAC":
+        validateKeyUsages(
+          normalized.keyUsages,
+          ["sign", "verify"],
+          "an HMAC",
+        );
+        if (length === 0) {
+          throw new DOMException(
+            "Zero-length key is not supported",
+            "DataError",
+          );
+        }
+        if (
+          normalized.algorithm.length !== undefined &&
+          normalized.algorithm.length !== length
+        ) {
+          throw new DOMException("Invalid key length", "DataError");
+        }
+        keyAlgorithm = {
+          name: "HMAC",
+          length,
+          hash: normalized.algorithm.hash,
+        };
+        break;
+      case "AES-CTR":
+      case "AES-CBC":
+      case "AES-GCM":
+      case "AES-KW":
+        validateKeyUsages(
+          normalized.keyUsages,
+          normalized.algorithm.name === "AES-KW"
+            ? ["wrapKey", "unwrapKey"]
+            : ["encrypt", "decrypt", "wrapKey", "unwrapKey"],
+          "an AES",
+        );
+        if (length !== 128 && length !== 192 && length !== 256) {
+          throw new DOMException("Invalid key length", "DataError");
+        }
+        keyAlgorithm = { name: normalized.algorithm.name, length };
+        break;
+      default:
+        throw new ERR_INVALID_ARG_VALUE(
+          "algorithm.name",
+          normalized.algorithm.name,
+        );
+    }
+
+    return createCryptoKeyFromKeyObject(
+      "secret",
+      normalized.extractable,
+      normalized.keyUsages,
+      keyAlgorithm,
+      { type: "secret", data: op_node_export_secret_key(this[kHandle]) },
+    );
+  }
+
   export(options?: { format?: "buffer" | "jwk" }): Buffer | JsonWebKey {
     let format: "buffer" | "jwk" = "buffer";
     if (options !== undefined) {
@@ -790,6 +1011,107 @@ class AsymmetricKeyObject extends KeyObject {
   get asymmetricKeyDetails() {
     return { ...op_node_get_asymmetric_key_details(this[kHandle]) };
   }
+
+  toCryptoKey(
+    algorithm: AlgorithmIdentifier | RsaHashedImportParams | EcKeyImportParams,
+    extractable: boolean,
+    keyUsages: KeyUsage[],
+  ): CryptoKey {
+    const normalized = normalizeToCryptoKeyArguments(
+      algorithm,
+      extractable,
+      keyUsages,
+    );
+    const { name } = normalized.algorithm;
+    const isPublic = this.type === "public";
+    let keyAlgorithm;
+    switch (name) {
+      case "RSASSA-PKCS1-v1_5":
+      case "RSA-PSS":
+      case "RSA-OAEP": {
+        let allowed: KeyUsage[];
+        if (name === "RSA-OAEP") {
+          allowed = isPublic
+            ? ["encrypt", "wrapKey"]
+            : ["decrypt", "unwrapKey"];
+        } else {
+          allowed = isPublic ? ["verify"] : ["sign"];
+        }
+        validateKeyUsages(normalized.keyUsages, allowed, `an ${name}`);
+        if (this.asymmetricKeyType !== "rsa") {
+          throw new DOMException("Invalid key type", "DataError");
+        }
+        const { modulusLength, publicExponent } = this.asymmetricKeyDetails;
+        let exponent = publicExponent.toString(16);
+        if (exponent.length % 2 === 1) {
+          exponent = `0${exponent}`;
+        }
+        keyAlgorithm = {
+          name,
+          modulusLength,
+          publicExponent: new Uint8Array(Buffer.from(exponent, "hex")),
+          hash: normalized.algorithm.hash,
+        };
+        break;
+      }
+      case "ECDSA":
+      case "ECDH": {
+        let allowed: KeyUsage[];
+        if (name === "ECDSA") {
+          allowed = isPublic ? ["verify"] : ["sign"];
+        } else {
+          allowed = isPublic ? [] : ["deriveKey", "deriveBits"];
+        }
+        validateKeyUsages(normalized.keyUsages, allowed, `a ${name}`);
+        const { namedCurve } = normalized.algorithm;
+        if (!Object.hasOwn(kNamedCurveAliases, namedCurve)) {
+          throw new DOMException(
+            "Unrecognized namedCurve",
+            "NotSupportedError",
+          );
+        }
+        if (this.asymmetricKeyType !== "ec") {
+          throw new DOMException("Invalid key type", "DataError");
+        }
+        if (
+          kNamedCurveAliases[namedCurve] !==
+            this.asymmetricKeyDetails.namedCurve
+        ) {
+          throw new DOMException("Named curve mismatch", "DataError");
+        }
+        keyAlgorithm = { name, namedCurve };
+        break;
+      }
+      case "Ed25519":
+      case "X25519":
+      case "X448": {
+        let allowed: KeyUsage[];
+        if (name === "Ed25519") {
+          allowed = isPublic ? ["verify"] : ["sign"];
+        } else {
+          allowed = isPublic ? [] : ["deriveKey", "deriveBits"];
+        }
+        validateKeyUsages(normalized.keyUsages, allowed, `a ${name}`);
+        if (this.asymmetricKeyType !== name.toLowerCase()) {
+          throw new DOMException("Invalid key type", "DataError");
+        }
+        keyAlgorithm = { name };
+        break;
+      }
+      default:
+        throw new ERR_INVALID_ARG_VALUE("algorithm.name", name);
+    }
+
+    const data = op_node_export_crypto_key_data(this[kHandle]);
+    return createCryptoKeyFromKeyObject(
+      this.type,
+      normalized.extractable,
+      normalized.keyUsages,
+      keyAlgorithm,
+      // ext/crypto keeps the bare keys of the CFRG curves.
+      kCryptoKeyAlgorithms[name] ? { type: this.type, data } : data,
+    );
+  }
 }
 
 export class PrivateKeyObject extends AsymmetricKeyObject {
@@ -797,19 +1119,41 @@ export class PrivateKeyObject extends AsymmetricKeyObject {
     super("private", handle);
   }
 
-  export(options: JwkKeyExportOptions | KeyExportOptions<KeyFormat>) {
+  export(
+    options:
+      | JwkKeyExportOptions
+      | RawKeyExportOptions
+      | KeyExportOptions<KeyFormat>,
+  ) {
     if (options && options.format === "jwk") {
       return op_node_export_private_key_jwk(this[kHandle]);
     }
+    if (
+      options &&
+      (options.format === "raw-seed" || options.format === "raw-private")
+    ) {
+      return Buffer.from(
+        op_node_export_private_key_raw(this[kHandle], options.format),
+      );
+    }
     const {
       format,
       type,
+      cipher,
+      passphrase,
     } = parsePrivateKeyEncoding(options, this.asymmetricKeyType);
 
     if (format === "pem") {
-      return op_node_export_private_key_pem(this[kHandle], type);
+      return op_node_export_private_key_pem(
+        this[kHandle],
+        type,
+        cipher,
+        passphrase,
+      );
     } else {
-      return Buffer.from(op_node_export_private_key_der(this[kHandle], type));
+      return Buffer.from(
+        op_node_export_private_key_der(this[kHandle], type, cipher, passphrase),
+      );
     }
   }
 }
@@ -819,10 +1163,18 @@ export class PublicKeyObject extends AsymmetricKeyObject {
     super("public", handle);
   }
 
-  export(options: JwkKeyExportOptions | KeyExportOptions<KeyFormat>) {
+  export(
+    options:
+      | JwkKeyExportOptions
+      | RawKeyExportOptions
+      | KeyExportOptions<KeyFormat>,
+  ) {
     if (options && options.format === "jwk") {
       return op_node_export_public_key_jwk(this[kHandle]);
     }
+    if (options && options.format === "raw-public") {
+      return Buffer.from(op_node_export_public_key_raw(this[kHandle]));
+    }
 
     const {
       format,
@@ -837,10 +1189,29 @@ export class PublicKeyObject extends AsymmetricKeyObject {
   }
 }
 
+// Encodings `op_node_create_secret_key_from_string` decodes itself.
+const kNativeSecretKeyEncodings = new Set([
+  "utf8",
+  "hex",
+  "base64",
+  "base64url",
+  "latin1",
+]);
+
 export function createSecretKey(
   key: string | ArrayBufferView | ArrayBuffer | KeyObject | CryptoKey,
   encoding?: string,
 ): KeyObject {
+  if (typeof key === "string") {
+    const normalized = encoding === "buffer"
+      ? "utf8"
+      : normalizeEncoding(encoding);
+    if (kNativeSecretKeyEncodings.has(normalized)) {
+      return new SecretKeyObject(
+        op_node_create_secret_key_from_string(key, normalized),
+      );
+    }
+  }
   const preparedKey = prepareSecretKey(key, encoding, true);
   if (isArrayBufferView(preparedKey) || isAnyArrayBuffer(preparedKey)) {
     const handle = op_node_create_secret_key(preparedKey);
@@ -13,14 +13,24 @@ const {
 import {
   op_node_create_private_key,
   op_node_create_public_key,
+  op_node_get_asymmetric_key_details,
   op_node_get_asymmetric_key_type,
   op_node_sign,
   op_node_sign_ed25519,
+  op_node_sign_ed448,
+  op_node_sign_ml_dsa,
+  op_node_sign_slh_dsa,
+  op_node_sign_sm2,
   op_node_verify,
   op_node_verify_ed25519,
+  op_node_verify_ed448,
+  op_node_verify_ml_dsa,
+  op_node_verify_slh_dsa,
+  op_node_verify_sm2,
 } from "ext:core/ops";
 
 import {
+  validateBoolean,
   validateFunction,
   validateString,
 } from "ext:deno_node/internal/validators.mjs";
@@ -35,6 +45,7 @@ import type {
   PublicKeyInput,
 } from "ext:deno_node/internal/crypto/types.ts";
 import {
+  getArrayBufferOrView,
   kConsumePrivate,
   kConsumePublic,
   KeyObject,
@@ -42,8 +53,13 @@ import {
   PrivateKeyObject,
   PublicKeyObject,
 } from "ext:deno_node/internal/crypto/keys.ts";
-import { createHash } from "ext:deno_node/internal/crypto/hash.ts";
-import { ERR_CRYPTO_SIGN_KEY_REQUIRED } from "ext:deno_node/internal/errors.ts";
+import { Hash } from "ext:deno_node/internal/crypto/hash.ts";
+import {
+  ERR_CRYPTO_SIGN_KEY_REQUIRED,
+  ERR_INVALID_ARG_TYPE,
+  ERR_INVALID_ARG_VALUE,
+} from "ext:deno_node/internal/errors.ts";
+import { isArrayBufferView } from "ext:deno_node/internal/util/types.ts";
 
 const FastBuffer = Buffer[SymbolSpecies];
 
@@ -52,7 +68,20 @@ export type DSAEncoding = "der" | "ieee-p1363";
 export interface SigningOptions {
   padding?: number | undefined;
   saltLength?: number | undefined;
+  mgf1HashAlgorithm?: string | undefined;
   dsaEncoding?: DSAEncoding | undefined;
+  /** The signer's ID for SM2 keys, "1234567812345678" by default. */
+  sm2Id?: BinaryLike | undefined;
+  /**
+   * Ed25519ph: the data is the SHA-512 digest of the message. Not in
+   * Node.js.
+   */
+  prehashed?: boolean | undefined;
+  /**
+   * The ML-DSA or SLH-DSA context, or the Ed25519ctx or Ed25519ph one,
+   * which is not in Node.js. Up to 255 bytes.
+   */
+  context?: BinaryLike | undefined;
 }
 
 export interface SignPrivateKeyInput extends PrivateKeyInput, SigningOptions {}
@@ -70,6 +99,55 @@ function getSaltLength(options) {
   return getIntOption("saltLength", options);
 }
 
+function getMgf1Hash(options) {
+  const { mgf1HashAlgorithm } = options;
+  if (mgf1HashAlgorithm !== undefined) {
+    validateString(mgf1HashAlgorithm, "options.mgf1HashAlgorithm");
+  }
+  return mgf1HashAlgorithm;
+}
+
+const kSm2DefaultId = Buffer.from("1234567812345678");
+
+function getSm2Id(options) {
+  if (typeof options === "object" && options.sm2Id !== undefined) {
+    return getArrayBufferOrView(options.sm2Id, "options.sm2Id");
+  }
+  return kSm2DefaultId;
+}
+
+const kEmptyContext = new Uint8Array(0);
+
+function getContext(options): ArrayBufferView {
+  if (typeof options !== "object" || options.context === undefined) {
+    return kEmptyContext;
+  }
+  return getArrayBufferOrView(options.context, "options.context");
+}
+
+function getEd25519Options(options): [boolean, ArrayBufferView] {
+  if (typeof options !== "object") {
+    return [false, kEmptyContext];
+  }
+  const { prehashed = false } = options;
+  validateBoolean(prehashed, "options.prehashed");
+  return [prehashed, getContext(options)];
+}
+
+function isMlDsaKey(keyType: string) {
+  return keyType === "ml-dsa-44" || keyType === "ml-dsa-65" ||
+    keyType === "ml-dsa-87";
+}
+
+function isSlhDsaKey(keyType: string) {
+  return keyType.startsWith("slh-dsa-");
+}
+
+function isSm2Key(handle, keyType: string) {
+  return keyType === "ec" &&
+    op_node_get_asymmetric_key_details(handle).namedCurve === "SM2";
+}
+
 function getDSASignatureEncoding(options) {
   if (typeof options === "object") {
     const { dsaEncoding = "der" } = options;
@@ -98,6 +176,8 @@ function getIntOption(name, options) {
 export type KeyLike = string | Buffer | KeyObject;
 
 export class SignImpl extends Writable {
+  // Updates go straight to the native hasher, so nothing is buffered in JS
+  // however much is signed.
   hash: Hash;
   #digestType: string;
 
@@ -114,7 +194,9 @@ export class SignImpl extends Writable {
     algorithm = algorithm.toLowerCase();
 
     this.#digestType = algorithm;
-    this.hash = createHash(this.#digestType);
+    // Not createHash(), so unknown digests are reported as
+    // ERR_CRYPTO_INVALID_DIGEST like in Node.js.
+    this.hash = new Hash(this.#digestType);
   }
 
   sign(
@@ -126,6 +208,7 @@ export class SignImpl extends Writable {
 
     // Options specific to RSA-PSS
     const pssSaltLength = getSaltLength(privateKey);
+    const pssMgf1Hash = getMgf1Hash(privateKey);
 
     // Options specific to (EC)DSA
     const dsaSigEnc = getDSASignatureEncoding(privateKey);
@@ -146,6 +229,7 @@ export class SignImpl extends Writable {
       this.hash.digest(),
       this.#digestType,
       pssSaltLength,
+      pssMgf1Hash,
       dsaSigEnc,
     ));
     return encoding ? ret.toString(encoding) : ret;
@@ -183,7 +267,7 @@ export class VerifyImpl extends Writable {
     algorithm = algorithm.toLowerCase();
 
     this.#digestType = algorithm;
-    this.hash = createHash(this.#digestType);
+    this.hash = new Hash(this.#digestType);
   }
 
   update(data: BinaryLike, encoding?: string): this {
@@ -201,6 +285,7 @@ export class VerifyImpl extends Writable {
 
     // Options specific to RSA-PSS
     const pssSaltLength = getSaltLength(publicKey);
+    const pssMgf1Hash = getMgf1Hash(publicKey);
 
     // Options specific to (EC)DSA
     const dsaSigEnc = getDSASignatureEncoding(publicKey);
@@ -222,6 +307,7 @@ export class VerifyImpl extends Writable {
       this.#digestType,
       Buffer.from(signature, encoding),
       pssSaltLength,
+      pssMgf1Hash,
       dsaSigEnc,
     );
   }
@@ -235,7 +321,7 @@ Verify.prototype = VerifyImpl.prototype;
 
 export function signOneShot(
   algorithm: string | null | undefined,
-  data: ArrayBufferView,
+  data: BinaryLike,
   key: KeyLike | SignKeyObjectInput | SignPrivateKeyInput,
   callback?: (error: Error | null, data: Buffer) => void,
 ): Buffer | void {
@@ -247,6 +333,8 @@ export function signOneShot(
     validateFunction(callback, "callback");
   }
 
+  data = getArrayBufferOrView(data, "data");
+
   if (!key) {
     throw new ERR_CRYPTO_SIGN_KEY_REQUIRED();
   }
@@ -265,19 +353,55 @@ export function signOneShot(
   }
 
   let result: Buffer;
-  if (op_node_get_asymmetric_key_type(handle) === "ed25519") {
+  const keyType = op_node_get_asymmetric_key_type(handle);
+  if (keyType === "ed25519") {
     if (algorithm != null && algorithm !== "sha512") {
       throw new TypeError("Only 'sha512' is supported for Ed25519 keys");
     }
+    const [prehashed, context] = getEd25519Options(key);
     result = new FastBuffer(64);
-    op_node_sign_ed25519(handle, data, result);
-  } else if (algorithm == null) {
-    throw new TypeError(
-      "Algorithm must be specified when using non-Ed25519 keys",
+    op_node_sign_ed25519(handle, data, result, prehashed, context);
+  } else if (keyType === "ed448") {
+    if (algorithm != null) {
+      throw new TypeError("Algorithm must be null for Ed448 keys");
+    }
+    result = new FastBuffer(114);
+    op_node_sign_ed448(handle, data, result);
+  } else if (isMlDsaKey(keyType)) {
+    if (algorithm != null) {
+      throw new TypeError("Algorithm must be null for ML-DSA keys");
+    }
+    result = Buffer.from(op_node_sign_ml_dsa(handle, data, getContext(key)));
+  } else if (isSlhDsaKey(keyType)) {
+    if (algorithm != null) {
+      throw new TypeError("Algorithm must be null for SLH-DSA keys");
+    }
+    // Signatures are up to 49,856 bytes, so wrap them instead of copying.
+    const signature = op_node_sign_slh_dsa(handle, data, getContext(key));
+    result = Buffer.from(
+      signature.buffer,
+      signature.byteOffset,
+      signature.byteLength,
     );
+  } else if (isSm2Key(handle, keyType)) {
+    // Unlike Sign, this hashes the ID and public key into the digest.
+    result = Buffer.from(op_node_sign_sm2(
+      handle,
+      data,
+      algorithm ?? "",
+      getSm2Id(key),
+      getDSASignatureEncoding(key),
+    ));
   } else {
-    result = Sign(algorithm!).update(data)
-      .sign(new PrivateKeyObject(handle));
+    const { saltLength, mgf1HashAlgorithm, dsaEncoding } =
+      key as SigningOptions;
+    // Without an algorithm, OpenSSL's default of SHA-256.
+    result = Sign(algorithm ?? "sha256").update(data).sign({
+      key: new PrivateKeyObject(handle),
+      saltLength,
+      mgf1HashAlgorithm,
+      dsaEncoding,
+    });
   }
 
   if (callback) {
@@ -291,7 +415,7 @@ export function verifyOneShot(
   algorithm: string | null | undefined,
   data: BinaryLike,
   key: KeyLike | VerifyKeyObjectInput | VerifyPublicKeyInput,
-  signature: BinaryLike,
+  signature: ArrayBufferView,
   callback?: (error: Error | null, result: boolean) => void,
 ): boolean | void {
   if (algorithm != null) {
@@ -302,6 +426,16 @@ export function verifyOneShot(
     validateFunction(callback, "callback");
   }
 
+  data = getArrayBufferOrView(data, "data");
+
+  if (!isArrayBufferView(signature)) {
+    throw new ERR_INVALID_ARG_TYPE(
+      "signature",
+      ["Buffer", "TypedArray", "DataView"],
+      signature,
+    );
+  }
+
   if (!key) {
     throw new ERR_CRYPTO_SIGN_KEY_REQUIRED();
   }
@@ -320,18 +454,52 @@ export function verifyOneShot(
   }
 
   let result: boolean;
-  if (op_node_get_asymmetric_key_type(handle) === "ed25519") {
+  const keyType = op_node_get_asymmetric_key_type(handle);
+  if (keyType === "ed25519") {
     if (algorithm != null && algorithm !== "sha512") {
       throw new TypeError("Only 'sha512' is supported for Ed25519 keys");
     }
-    result = op_node_verify_ed25519(handle, data, signature);
-  } else if (algorithm == null) {
-    throw new TypeError(
-      "Algorithm must be specified when using non-Ed25519 keys",
+    const [prehashed, context] = getEd25519Options(key);
+    result = op_node_verify_ed25519(
+      handle,
+      data,
+      signature,
+      prehashed,
+      context,
+    );
+  } else if (keyType === "ed448") {
+    if (algorithm != null) {
+      throw new TypeError("Algorithm must be null for Ed448 keys");
+    }
+    result = op_node_verify_ed448(handle, data, signature);
+  } else if (isMlDsaKey(keyType)) {
+    if (algorithm != null) {
+      throw new TypeError("Algorithm must be null for ML-DSA keys");
+    }
+    result = op_node_verify_ml_dsa(handle, data, signature, getContext(key));
+  } else if (isSlhDsaKey(keyType)) {
+    if (algorithm != null) {
+      throw new TypeError("Algorithm must be null for SLH-DSA keys");
+    }
+    result = op_node_verify_slh_dsa(handle, data, signature, getContext(key));
+  } else if (isSm2Key(handle, keyType)) {
+    result = op_node_verify_sm2(
+      handle,
+      data,
+      algorithm ?? "",
+      signature,
+      getSm2Id(key),
+      getDSASignatureEncoding(key),
     );
   } else {
-    result = Verify(algorithm!).update(data)
-      .verify(new PublicKeyObject(handle), signature);
+    const { saltLength, mgf1HashAlgorithm, dsaEncoding } =
+      key as SigningOptions;
+    result = Verify(algorithm ?? "sha256").update(data).verify({
+      key: new PublicKeyObject(handle),
+      saltLength,
+      mgf1HashAlgorithm,
+      dsaEncoding,
+    }, signature);
   }
 
   if (callback) {
@@ -31,7 +31,28 @@ export type KeyType =
   | "ed25519"
   | "ed448"
   | "x25519"
-  | "x448";
+  | "x448"
+  | MLDSAKeyType
+  | MLKEMKeyType
+  | SLHDSAKeyType;
+
+export type MLDSAKeyType = "ml-dsa-44" | "ml-dsa-65" | "ml-dsa-87";
+
+export type MLKEMKeyType = "ml-kem-768";
+
+export type SLHDSAKeyType =
+  | "slh-dsa-sha2-128s"
+  | "slh-dsa-sha2-128f"
+  | "slh-dsa-sha2-192s"
+  | "slh-dsa-sha2-192f"
+  | "slh-dsa-sha2-256s"
+  | "slh-dsa-sha2-256f"
+  | "slh-dsa-shake-128s"
+  | "slh-dsa-shake-128f"
+  | "slh-dsa-shake-192s"
+  | "slh-dsa-shake-192f"
+  | "slh-dsa-shake-256s"
+  | "slh-dsa-shake-256f";
 
 export interface PrivateKeyInput {
   key: string | Buffer;
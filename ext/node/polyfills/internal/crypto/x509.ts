@@ -8,27 +8,43 @@ import {
   op_node_x509_ca,
   op_node_x509_check_email,
   op_node_x509_check_host,
+  op_node_x509_check_ip,
+  op_node_x509_check_issued,
+  op_node_x509_ext_key_usage,
   op_node_x509_fingerprint,
   op_node_x509_fingerprint256,
   op_node_x509_fingerprint512,
+  op_node_x509_fingerprint_with,
+  op_node_x509_get_info_access,
   op_node_x509_get_issuer,
   op_node_x509_get_serial_number,
   op_node_x509_get_subject,
+  op_node_x509_get_subject_alt_name,
   op_node_x509_get_valid_from,
   op_node_x509_get_valid_to,
-  op_node_x509_key_usage,
   op_node_x509_parse,
   op_node_x509_public_key,
+  op_node_x509_to_legacy_object,
+  op_node_x509_verify,
 } from "ext:core/ops";
 
 import {
   KeyObject,
   PublicKeyObject,
 } from "ext:deno_node/internal/crypto/keys.ts";
+import { isKeyObject } from "ext:deno_node/internal/crypto/_keys.ts";
+import { kHandle } from "ext:deno_node/internal/crypto/constants.ts";
 import { Buffer } from "node:buffer";
-import { ERR_INVALID_ARG_TYPE } from "ext:deno_node/internal/errors.ts";
+import {
+  ERR_INVALID_ARG_TYPE,
+  ERR_INVALID_ARG_VALUE,
+} from "ext:deno_node/internal/errors.ts";
 import { isArrayBufferView } from "ext:deno_node/internal/util/types.ts";
-import { validateString } from "ext:deno_node/internal/validators.mjs";
+import {
+  validateBoolean,
+  validateObject,
+  validateString,
+} from "ext:deno_node/internal/validators.mjs";
 import { notImplemented } from "ext:deno_node/_utils.ts";
 import { BinaryLike } from "ext:deno_node/internal/crypto/types.ts";
 
@@ -37,25 +53,71 @@ export type PeerCertificate = any;
 
 export interface X509CheckOptions {
   /**
-   * @default 'always'
+   * @default 'default'
    */
-  subject: "always" | "never";
+  subject?: "default" | "always" | "never";
   /**
    * @default true
    */
-  wildcards: boolean;
+  wildcards?: boolean;
   /**
    * @default true
    */
-  partialWildcards: boolean;
+  partialWildcards?: boolean;
   /**
    * @default false
    */
-  multiLabelWildcards: boolean;
+  multiLabelWildcards?: boolean;
   /**
    * @default false
    */
-  singleLabelSubdomains: boolean;
+  singleLabelSubdomains?: boolean;
+}
+
+// OpenSSL's X509_CHECK_FLAG_* values.
+const X509_CHECK_FLAG_ALWAYS_CHECK_SUBJECT = 0x1;
+const X509_CHECK_FLAG_NO_WILDCARDS = 0x2;
+const X509_CHECK_FLAG_NO_PARTIAL_WILDCARDS = 0x4;
+const X509_CHECK_FLAG_MULTI_LABEL_WILDCARDS = 0x8;
+const X509_CHECK_FLAG_SINGLE_LABEL_SUBDOMAINS = 0x10;
+const X509_CHECK_FLAG_NEVER_CHECK_SUBJECT = 0x20;
+
+function getFlags(options: X509CheckOptions = {}): number {
+  validateObject(options, "options");
+  const {
+    subject = "default",
+    wildcards = true,
+    partialWildcards = true,
+    multiLabelWildcards = false,
+    singleLabelSubdomains = false,
+  } = { ...options };
+  let flags = 0;
+  validateString(subject, "options.subject");
+  validateBoolean(wildcards, "options.wildcards");
+  validateBoolean(partialWildcards, "options.partialWildcards");
+  validateBoolean(multiLabelWildcards, "options.multiLabelWildcards");
+  validateBoolean(singleLabelSubdomains, "options.singleLabelSubdomains");
+
+  switch (subject) {
+    case "default":
+      // The subject is only checked without subjectAltName entries of the
+      // checked type.
+      break;
+    case "always":
+      flags |= X509_CHECK_FLAG_ALWAYS_CHECK_SUBJECT;
+      break;
+    case "never":
+      flags |= X509_CHECK_FLAG_NEVER_CHECK_SUBJECT;
+      break;
+    default:
+      throw new ERR_INVALID_ARG_VALUE("options.subject", subject);
+  }
+
+  if (!wildcards) flags |= X509_CHECK_FLAG_NO_WILDCARDS;
+  if (!partialWildcards) flags |= X509_CHECK_FLAG_NO_PARTIAL_WILDCARDS;
+  if (multiLabelWildcards) flags |= X509_CHECK_FLAG_MULTI_LABEL_WILDCARDS;
+  if (singleLabelSubdomains) flags |= X509_CHECK_FLAG_SINGLE_LABEL_SUBDOMAINS;
+  return flags;
 }
 
 export class X509Certificate {
@@ -83,27 +145,40 @@ export class X509Certificate {
 
   checkEmail(
     email: string,
-    _options?: Pick<X509CheckOptions, "subject">,
+    options?: Pick<X509CheckOptions, "subject">,
   ): string | undefined {
     validateString(email, "email");
-    if (op_node_x509_check_email(this.#handle, email)) {
+    if (op_node_x509_check_email(this.#handle, email, getFlags(options))) {
       return email;
     }
   }
 
-  checkHost(name: string, _options?: X509CheckOptions): string | undefined {
+  /**
+   * Returns the certificate's name matching `name`, which can be a
+   * wildcard.
+   */
+  checkHost(name: string, options?: X509CheckOptions): string | undefined {
     validateString(name, "name");
-    if (op_node_x509_check_host(this.#handle, name)) {
-      return name;
-    }
+    return op_node_x509_check_host(this.#handle, name, getFlags(options)) ??
+      undefined;
   }
 
-  checkIP(_ip: string): string | undefined {
-    notImplemented("crypto.X509Certificate.prototype.checkIP");
+  checkIP(ip: string): string | undefined {
+    validateString(ip, "ip");
+    if (op_node_x509_check_ip(this.#handle, ip)) {
+      return ip;
+    }
   }
 
-  checkIssued(_otherCert: X509Certificate): boolean {
-    notImplemented("crypto.X509Certificate.prototype.checkIssued");
+  /**
+   * Whether `otherCert` could have issued this certificate. The signature
+   * is not checked; use `verify()` for that.
+   */
+  checkIssued(otherCert: X509Certificate): boolean {
+    if (!(otherCert instanceof X509Certificate)) {
+      throw new ERR_INVALID_ARG_TYPE("otherCert", "X509Certificate", otherCert);
+    }
+    return op_node_x509_check_issued(this.#handle, otherCert.#handle);
   }
 
   checkPrivateKey(_privateKey: KeyObject): boolean {
@@ -122,10 +197,20 @@ export class X509Certificate {
     return op_node_x509_fingerprint512(this.#handle);
   }
 
-  get infoAccess(): string | undefined {
-    notImplemented("crypto.X509Certificate.prototype.infoAccess");
+  /**
+   * The fingerprint with any digest that `createHash()` supports, e.g.
+   * `sha3-256`. This is not in Node.js.
+   */
+  fingerprintWith(algorithm: string): string {
+    validateString(algorithm, "algorithm");
+    return op_node_x509_fingerprint_with(
+      this.#handle,
+      algorithm.toLowerCase(),
+    );
+  }
 
-    return "";
+  get infoAccess(): string | undefined {
+    return op_node_x509_get_info_access(this.#handle) ?? undefined;
   }
 
   get issuer(): string {
@@ -136,20 +221,9 @@ export class X509Certificate {
     return undefined;
   }
 
+  /** The extended key usage OIDs. */
   get keyUsage(): string[] | undefined {
-    const flags = op_node_x509_key_usage(this.#handle);
-    if (flags === 0) return undefined;
-    const result: string[] = [];
-    if (flags & 0x01) result.push("DigitalSignature");
-    if (flags >> 1 & 0x01) result.push("NonRepudiation");
-    if (flags >> 2 & 0x01) result.push("KeyEncipherment");
-    if (flags >> 3 & 0x01) result.push("DataEncipherment");
-    if (flags >> 4 & 0x01) result.push("KeyAgreement");
-    if (flags >> 5 & 0x01) result.push("KeyCertSign");
-    if (flags >> 6 & 0x01) result.push("CRLSign");
-    if (flags >> 7 & 0x01) result.push("EncipherOnly");
-    if (flags >> 8 & 0x01) result.push("DecipherOnly");
-    return result;
+    return op_node_x509_ext_key_usage(this.#handle) ?? undefined;
   }
 
   get publicKey(): PublicKeyObject {
@@ -172,7 +246,7 @@ export class X509Certificate {
   }
 
   get subjectAltName(): string | undefined {
-    return undefined;
+    return op_node_x509_get_subject_alt_name(this.#handle) ?? undefined;
   }
 
   toJSON(): string {
@@ -180,7 +254,24 @@ export class X509Certificate {
   }
 
   toLegacyObject(): PeerCertificate {
-    notImplemented("crypto.X509Certificate.prototype.toLegacyObject");
+    const object = op_node_x509_to_legacy_object(this.#handle);
+    // The names and infoAccess are null-prototype objects, as in Node.js.
+    for (const key of ["subject", "issuer", "infoAccess"]) {
+      if (object[key]) {
+        object[key] = { __proto__: null, ...object[key] };
+      }
+    }
+    for (const key of ["pubkey", "raw"]) {
+      if (object[key]) {
+        const bytes = object[key];
+        object[key] = Buffer.from(
+          bytes.buffer,
+          bytes.byteOffset,
+          bytes.byteLength,
+        );
+      }
+    }
+    return object;
   }
 
   toString(): string {
@@ -195,8 +286,15 @@ export class X509Certificate {
     return op_node_x509_get_valid_to(this.#handle);
   }
 
-  verify(_publicKey: KeyObject): boolean {
-    notImplemented("crypto.X509Certificate.prototype.verify");
+  /** Whether `pkey` signed this certificate. */
+  verify(pkey: KeyObject): boolean {
+    if (!isKeyObject(pkey)) {
+      throw new ERR_INVALID_ARG_TYPE("pkey", "KeyObject", pkey);
+    }
+    if (pkey.type !== "public") {
+      throw new ERR_INVALID_ARG_VALUE("pkey", pkey, "must be a public key");
+    }
+    return op_node_x509_verify(this.#handle, pkey[kHandle]);
   }
 }
 
@@ -995,9 +995,9 @@ export class ERR_CRYPTO_INVALID_KEYLEN extends NodeRangeError {
   }
 }
 
-export class ERR_CRYPTO_INVALID_JWK extends NodeError {
+export class ERR_CRYPTO_INVALID_JWK extends NodeTypeError {
   constructor() {
-    super("ERR_CRYPTO_INVALID_JWK", "Invalid JWK");
+    super("ERR_CRYPTO_INVALID_JWK", "Invalid JWK data");
   }
 }
 
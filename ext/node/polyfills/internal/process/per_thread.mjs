@@ -135,6 +135,7 @@ export function buildAllowedFlags() {
     "--experimental-top-level-await",
     "--no-experimental-top-level-await",
     "--openssl-config",
+    "--openssl-legacy-provider",
     "--icu-data-dir",
     "--v8-pool-size",
     "--report-on-fatalerror",
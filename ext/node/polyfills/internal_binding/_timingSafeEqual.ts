@@ -3,52 +3,42 @@
 // TODO(petamoriken): enable prefer-primordials for node polyfills
 // deno-lint-ignore-file prefer-primordials
 
+import { op_node_timing_safe_equal } from "ext:core/ops";
 import { Buffer } from "node:buffer";
-import { ERR_CRYPTO_TIMING_SAFE_EQUAL_LENGTH } from "ext:deno_node/internal/errors.ts";
-import { validateBuffer } from "ext:deno_node/internal/validators.mjs";
+import { ERR_INVALID_ARG_TYPE } from "ext:deno_node/internal/errors.ts";
+import {
+  isAnyArrayBuffer,
+  isArrayBufferView,
+  isSharedArrayBuffer,
+} from "ext:deno_node/internal/util/types.ts";
 
-function toDataView(ab: ArrayBufferLike | ArrayBufferView): DataView {
-  if (ArrayBuffer.isView(ab)) {
-    return new DataView(ab.buffer, ab.byteOffset, ab.byteLength);
+function validateInput(
+  buf: unknown,
+  name: string,
+): ArrayBufferView | ArrayBuffer {
+  if (isSharedArrayBuffer(buf)) {
+    // The op takes shared memory through a view, without copying.
+    return new Uint8Array(buf as SharedArrayBuffer);
   }
-  return new DataView(ab);
-}
-
-/** Compare to array buffers or data views in a way that timing based attacks
- * cannot gain information about the platform. */
-function stdTimingSafeEqual(
-  a: ArrayBufferView | ArrayBufferLike | DataView,
-  b: ArrayBufferView | ArrayBufferLike | DataView,
-): boolean {
-  if (a.byteLength !== b.byteLength) {
-    throw new ERR_CRYPTO_TIMING_SAFE_EQUAL_LENGTH();
-  }
-  if (!(a instanceof DataView)) {
-    a = toDataView(a);
-  }
-  if (!(b instanceof DataView)) {
-    b = toDataView(b);
+  if (!isAnyArrayBuffer(buf) && !isArrayBufferView(buf)) {
+    throw new ERR_INVALID_ARG_TYPE(
+      name,
+      ["ArrayBuffer", "Buffer", "TypedArray", "DataView"],
+      buf,
+    );
   }
-  const length = a.byteLength;
-  let out = 0;
-  let i = -1;
-  while (++i < length) {
-    out |= a.getUint8(i) ^ b.getUint8(i);
-  }
-  return out === 0;
+  return buf as ArrayBufferView | ArrayBuffer;
 }
 
+/** Compare two buffers in a way that timing based attacks cannot gain
+ * information about the platform. Views are compared byte for byte and
+ * throw ERR_CRYPTO_TIMING_SAFE_EQUAL_LENGTH if their byte lengths differ. */
 export const timingSafeEqual = (
   buf1: Buffer | DataView | ArrayBuffer,
   buf2: Buffer | DataView | ArrayBuffer,
 ): boolean => {
-  validateBuffer(buf1, "buf1");
-  validateBuffer(buf2, "buf2");
-  if (buf1 instanceof Buffer) {
-    buf1 = new DataView(buf1.buffer, buf1.byteOffset, buf1.byteLength);
-  }
-  if (buf2 instanceof Buffer) {
-    buf2 = new DataView(buf2.buffer, buf2.byteOffset, buf2.byteLength);
-  }
-  return stdTimingSafeEqual(buf1, buf2);
+  return op_node_timing_safe_equal(
+    validateInput(buf1, "buf1"),
+    validateInput(buf2, "buf2"),
+  );
 };
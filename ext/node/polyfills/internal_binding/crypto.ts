@@ -2,14 +2,20 @@
 // Copyright Joyent, Inc. and Node.js contributors. All rights reserved. MIT license.
 
 export { timingSafeEqual } from "ext:deno_node/internal_binding/_timingSafeEqual.ts";
-import { primordials } from "ext:core/mod.js";
+import {
+  op_node_get_fips,
+  op_node_set_fips,
+  op_node_set_legacy_provider,
+} from "ext:core/ops";
 
-const { Error } = primordials;
+export function getFipsCrypto(): number {
+  return op_node_get_fips() ? 1 : 0;
+}
 
-export function getFipsCrypto(): boolean {
-  return false;
+export function setFipsCrypto(fips: boolean) {
+  op_node_set_fips(!!fips);
 }
 
-export function setFipsCrypto(_fips: boolean) {
-  throw new Error("FIPS mode is not supported in Deno.");
+export function setLegacyProvider(enabled: boolean) {
+  op_node_set_legacy_provider(!!enabled);
 }
@@ -20,6 +20,7 @@ export function getOptions() {
   const options = new SafeMap([
     ["--warnings", { value: true }],
     ["--pending-deprecation", { value: false }],
+    ["--openssl-legacy-provider", { value: false }],
   ]);
 
   const nodeOptions = Deno.env.get("NODE_OPTIONS");
@@ -34,6 +35,9 @@ export function getOptions() {
       case "--pending-deprecation":
         options.set("--pending-deprecation", { value: true });
         break;
+      case "--openssl-legacy-provider":
+        options.set("--openssl-legacy-provider", { value: true });
+        break;
       // TODO(kt3k): Handle other options.
       default:
         break;
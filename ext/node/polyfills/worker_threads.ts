@@ -12,6 +12,8 @@ import {
   op_worker_threads_filename,
 } from "ext:core/ops";
 import {
+  ClonedHostObject,
+  ClonedHostObjectPrototype,
   deserializeJsMessageData,
   MessageChannel,
   MessagePort,
@@ -29,13 +31,21 @@ import { EventEmitter } from "node:events";
 import { BroadcastChannel } from "ext:deno_broadcast_channel/01_broadcast_channel.js";
 import { untransferableSymbol } from "ext:deno_node/internal_binding/util.ts";
 import process from "node:process";
+import { Hash } from "ext:deno_node/internal/crypto/hash.ts";
 
 const { JSONParse, JSONStringify, ObjectPrototypeIsPrototypeOf } = primordials;
 const {
+  ArrayFrom,
+  ArrayIsArray,
+  ArrayPrototypePush,
+  ArrayPrototypeSlice,
   Error,
+  ObjectGetPrototypeOf,
   ObjectHasOwn,
+  ObjectPrototype,
   PromiseResolve,
   FunctionPrototypeCall,
+  SafeArrayIterator,
   SafeSet,
   Symbol,
   SymbolFor,
@@ -44,6 +54,7 @@ const {
   SafeWeakMap,
   SafeMap,
   TypeError,
+  Uint8Array,
   encodeURIComponent,
 } = primordials;
 
@@ -155,12 +166,16 @@ class NodeWorker extends EventEmitter {
     if (options?.env) {
       env_ = JSONParse(JSONStringify(options?.env));
     }
+    const { 0: workerData, 1: transferList } = cloneHashesForMessage(
+      options?.workerData,
+      options?.transferList ?? [],
+    );
     const serializedWorkerMetadata = serializeJsMessageData({
-      workerData: options?.workerData,
+      workerData,
       environmentData: environmentData,
       env: env_,
       isWorkerThread: true,
-    }, options?.transferList ?? []);
+    }, transferList);
     const id = op_create_worker(
       {
         // deno-lint-ignore prefer-primordials
@@ -255,7 +270,7 @@ class NodeWorker extends EventEmitter {
       let message, _transferables;
       try {
         const v = deserializeJsMessageData(data);
-        message = v[0];
+        message = patchMessagePortIfFound(v[0]);
         _transferables = v[1];
       } catch (err) {
         this.emit("messageerror", err);
@@ -298,8 +313,11 @@ class NodeWorker extends EventEmitter {
         "Argument 2",
       );
     }
-    const { transfer } = options;
-    const data = serializeJsMessageData(message, transfer);
+    const { 0: cloned, 1: transfer } = cloneHashesForMessage(
+      message,
+      options.transfer,
+    );
+    const data = serializeJsMessageData(cloned, transfer);
     if (this.#status === "RUNNING") {
       op_host_post_message(this.#id, data);
     }
@@ -397,11 +415,11 @@ internals.__initWorkerThreads = (
         process.env = env;
       }
     }
-    defaultExport.workerData = workerData;
     defaultExport.parentPort = parentPort;
     defaultExport.threadId = threadId;
 
-    patchMessagePortIfFound(workerData);
+    workerData = patchMessagePortIfFound(workerData);
+    defaultExport.workerData = workerData;
 
     parentPort.off = parentPort.removeListener = function (
       this: ParentPort,
@@ -419,8 +437,7 @@ internals.__initWorkerThreads = (
     ) {
       // deno-lint-ignore no-explicit-any
       const _listener = (ev: any) => {
-        const message = ev.data;
-        patchMessagePortIfFound(message);
+        const message = patchMessagePortIfFound(ev.data);
         return listener(message);
       };
       listeners.set(listener, _listener);
@@ -431,8 +448,7 @@ internals.__initWorkerThreads = (
     parentPort.once = function (this: ParentPort, name, listener) {
       // deno-lint-ignore no-explicit-any
       const _listener = (ev: any) => {
-        const message = ev.data;
-        patchMessagePortIfFound(message);
+        const message = patchMessagePortIfFound(ev.data);
         return listener(message);
       };
       listeners.set(listener, _listener);
@@ -446,6 +462,16 @@ internals.__initWorkerThreads = (
     parentPort.eventNames = () => [""];
     parentPort.listenerCount = () => 0;
 
+    const webPostMessage = parentPort.postMessage;
+    parentPort.postMessage = (message, transferOrOptions) =>
+      FunctionPrototypeCall(
+        webPostMessage,
+        parentPort,
+        ...new SafeArrayIterator(
+          cloneHashesForMessage(message, transferOrOptions),
+        ),
+      );
+
     parentPort.emit = () => notImplemented("parentPort.emit");
     parentPort.removeAllListeners = () =>
       notImplemented("parentPort.removeAllListeners");
@@ -506,8 +532,7 @@ export function receiveMessageOnPort(port: MessagePort): object | undefined {
   port[MessagePortReceiveMessageOnPortSymbol] = true;
   const data = op_message_port_recv_message_sync(port[MessagePortIdSymbol]);
   if (data === null) return undefined;
-  const message = deserializeJsMessageData(data)[0];
-  patchMessagePortIfFound(message);
+  const message = patchMessagePortIfFound(deserializeJsMessageData(data)[0]);
   return { message };
 }
 
@@ -532,8 +557,7 @@ function webMessagePortToNodeMessagePort(port: MessagePort) {
   port.on = port.addListener = function (this: MessagePort, name, listener) {
     // deno-lint-ignore no-explicit-any
     const _listener = (ev: any) => {
-      patchMessagePortIfFound(ev.data);
-      listener(ev.data);
+      listener(patchMessagePortIfFound(ev.data));
     };
     if (name == "message") {
       if (port.onmessage === null) {
@@ -593,8 +617,7 @@ function webMessagePortToNodeMessagePort(port: MessagePort) {
     return FunctionPrototypeCall(
       webPostMessage,
       port,
-      message,
-      transferList,
+      ...new SafeArrayIterator(cloneHashesForMessage(message, transferList)),
     );
   };
   port.once = (name: string | symbol, listener) => {
@@ -607,26 +630,116 @@ function webMessagePortToNodeMessagePort(port: MessagePort) {
   return port;
 }
 
+// Name of the `ClonedHostObject` that stands in for a `Hash` in a message.
+const kHashName = "node:crypto.Hash";
+
+function isPlainObjectOrArray(data: object) {
+  if (ArrayIsArray(data)) return true;
+  const proto = ObjectGetPrototypeOf(data);
+  return proto === ObjectPrototype || proto === null;
+}
+
+/**
+ * `Hash` objects wrap a native handle that structured clone can't copy, so
+ * they are swapped for a `ClonedHostObject` holding their exported state,
+ * which is added to the transfer list, and rebuilt by
+ * `patchMessagePortIfFound` on the receiving side. Plain data can't pass
+ * for one, unlike a marker property. Only the arrays and plain objects on
+ * the path to a `Hash` are copied; the rest of the message is cloned as
+ * usual. Returns the message and the transfer list (or options) to post.
+ */
+// deno-lint-ignore no-explicit-any
+function cloneHashesForMessage(message: any, transferOrOptions: any) {
+  const standIns: object[] = [];
+  message = replaceHashes(message, standIns, new SafeSet());
+  if (standIns.length === 0) {
+    return [message, transferOrOptions];
+  }
+  const transfer = transferOrOptions == null
+    ? []
+    : ArrayFrom(
+      transferOrOptions[SymbolIterator] === undefined
+        ? transferOrOptions.transfer ?? []
+        : transferOrOptions,
+    );
+  for (let i = 0; i < standIns.length; i++) {
+    ArrayPrototypePush(transfer, standIns[i]);
+  }
+  return [message, transfer];
+}
+
+// deno-lint-ignore no-explicit-any
+function replaceHashes(data: any, standIns: object[], seen: Set<any>): any {
+  if (data === null || typeof data !== "object" || seen.has(data)) {
+    return data;
+  }
+  if (ObjectPrototypeIsPrototypeOf(Hash.prototype, data)) {
+    let state;
+    try {
+      state = data.exportState();
+    } catch (err) {
+      throw new DOMException(
+        `Hash could not be cloned: ${err.message}`,
+        "DataCloneError",
+      );
+    }
+    // Posting detaches the state, which may share a pooled buffer.
+    const standIn = new ClonedHostObject(kHashName, new Uint8Array(state));
+    ArrayPrototypePush(standIns, standIn);
+    return standIn;
+  }
+  if (!isPlainObjectOrArray(data)) {
+    return data;
+  }
+  seen.add(data);
+
+  let copy;
+  for (const key in data) {
+    if (ObjectHasOwn(data, key)) {
+      const value = data[key];
+      const cloned = replaceHashes(value, standIns, seen);
+      if (cloned !== value) {
+        copy ??= ArrayIsArray(data)
+          ? ArrayPrototypeSlice(data)
+          : { __proto__: ObjectGetPrototypeOf(data), ...data };
+        copy[key] = cloned;
+      }
+    }
+  }
+  return copy ?? data;
+}
+
 // TODO(@marvinhagemeister): Recursively iterating over all message
 // properties seems slow.
 // Maybe there is a way we can patch the prototype of MessagePort _only_
 // inside worker_threads? For now correctness is more important than perf.
+// Returns `data`, or the `Hash` it stands in for if it is a cloned one.
 // deno-lint-ignore no-explicit-any
-function patchMessagePortIfFound(data: any, seen = new SafeSet<any>()) {
+function patchMessagePortIfFound(data: any, seen = new SafeSet<any>()): any {
   if (data === null || typeof data !== "object" || seen.has(data)) {
-    return;
+    return data;
   }
   seen.add(data);
 
   if (ObjectPrototypeIsPrototypeOf(MessagePortPrototype, data)) {
     webMessagePortToNodeMessagePort(data);
+  } else if (
+    ObjectPrototypeIsPrototypeOf(ClonedHostObjectPrototype, data) &&
+    data.name === kHashName
+  ) {
+    return Hash.fromState(data.data);
   } else {
     for (const obj in data as Record<string, unknown>) {
       if (ObjectHasOwn(data, obj)) {
-        patchMessagePortIfFound(data[obj], seen);
+        const value = data[obj];
+        const patched = patchMessagePortIfFound(value, seen);
+        if (patched !== value) {
+          data[obj] = patched;
+        }
       }
     }
   }
+  return data;
 }
 
 export {
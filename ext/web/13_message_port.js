@@ -120,6 +120,27 @@ function createMessagePort(id) {
   return port;
 }
 
+/**
+ * Stands in for a host object that is posted by value, as its `name` and
+ * serialized `data`, which is detached when posted. Listed in a transfer
+ * list, it is serialized as a host object and arrives as a
+ * `ClonedHostObject` again, which structured clone can't produce from
+ * plain data.
+ */
+class ClonedHostObject {
+  /**
+   * @param {string} name
+   * @param {Uint8Array} data
+   */
+  constructor(name, data) {
+    this[core.hostObjectBrand] = core.hostObjectBrand;
+    this.name = name;
+    this.data = data;
+  }
+}
+
+const ClonedHostObjectPrototype = ClonedHostObject.prototype;
+
 function nodeWorkerThreadMaybeInvokeCloseCb(port) {
   if (
     typeof port[nodeWorkerThreadCloseCb] == "function" &&
@@ -377,6 +398,11 @@ function deserializeJsMessageData(messageData) {
           ArrayPrototypePush(arrayBufferIdsInTransferables, index);
           break;
         }
+        case "clonedHostObject": {
+          const { 0: name, 1: data } = transferable.data;
+          ArrayPrototypePush(hostObjects, new ClonedHostObject(name, data));
+          break;
+        }
         default:
           throw new TypeError("Unreachable");
       }
@@ -422,7 +448,10 @@ function serializeJsMessageData(data, transferables) {
         }
         j++;
         ArrayPrototypePush(transferredArrayBuffers, t);
-      } else if (ObjectPrototypeIsPrototypeOf(MessagePortPrototype, t)) {
+      } else if (
+        ObjectPrototypeIsPrototypeOf(MessagePortPrototype, t) ||
+        ObjectPrototypeIsPrototypeOf(ClonedHostObjectPrototype, t)
+      ) {
         ArrayPrototypePush(hostObjects, t);
       }
     }
@@ -463,6 +492,13 @@ function serializeJsMessageData(data, transferables) {
         data: transferredArrayBuffers[arrayBufferI],
       });
       arrayBufferI++;
+    } else if (
+      ObjectPrototypeIsPrototypeOf(ClonedHostObjectPrototype, transferable)
+    ) {
+      ArrayPrototypePush(serializedTransferables, {
+        kind: "clonedHostObject",
+        data: [transferable.name, transferable.data],
+      });
     } else {
       throw new DOMException("Value not transferable", "DataCloneError");
     }
@@ -501,6 +537,8 @@ function structuredClone(value, options) {
 }
 
 export {
+  ClonedHostObject,
+  ClonedHostObjectPrototype,
   deserializeJsMessageData,
   MessageChannel,
   MessagePort,
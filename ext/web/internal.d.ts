@@ -105,6 +105,9 @@ declare module "ext:deno_web/13_message_port.js" {
   } | {
     kind: "arrayBuffer";
     data: number;
+  } | {
+    kind: "clonedHostObject";
+    data: [string, Uint8Array];
   };
   interface MessageData {
     data: Uint8Array;
@@ -42,6 +42,7 @@ pub enum MessagePortError {
 pub enum Transferable {
   MessagePort(MessagePort),
   ArrayBuffer(u32),
+  ClonedHostObject(String, DetachedBuffer),
 }
 
 type MessagePortMessage = (DetachedBuffer, Vec<Transferable>);
@@ -158,6 +159,9 @@ pub enum JsTransferable {
   #[serde(rename_all = "camelCase")]
   MessagePort(ResourceId),
   ArrayBuffer(u32),
+  /// The name and serialized state of a host object that is posted by
+  /// value.
+  ClonedHostObject(String, DetachedBuffer),
 }
 
 pub fn deserialize_js_transferables(
@@ -180,6 +184,9 @@ pub fn deserialize_js_transferables(
       JsTransferable::ArrayBuffer(id) => {
         transferables.push(Transferable::ArrayBuffer(id));
       }
+      JsTransferable::ClonedHostObject(name, data) => {
+        transferables.push(Transferable::ClonedHostObject(name, data));
+      }
     }
   }
   Ok(transferables)
@@ -202,6 +209,9 @@ pub fn serialize_transferables(
       Transferable::ArrayBuffer(id) => {
         js_transferables.push(JsTransferable::ArrayBuffer(id));
       }
+      Transferable::ClonedHostObject(name, data) => {
+        js_transferables.push(JsTransferable::ClonedHostObject(name, data));
+      }
     }
   }
   js_transferables
@@ -152,6 +152,14 @@ pub static FEATURE_DESCRIPTIONS: &[UnstableFeatureDescription] = &[
     config_option: ConfigFileOption::SameAsFlagName,
     env_var: None,
   },
+  UnstableFeatureDescription {
+    name: "pqc",
+    help_text: "Enable unstable post-quantum cryptography in node:crypto",
+    show_in_help: false,
+    kind: UnstableFeatureKind::Runtime,
+    config_option: ConfigFileOption::SameAsFlagName,
+    env_var: None,
+  },
   UnstableFeatureDescription {
     name: "process",
     help_text: "Enable unstable process APIs",
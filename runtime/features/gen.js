@@ -17,11 +17,12 @@ export const unstableIds = {
   noLegacyAbort: 12,
   nodeGlobals: 13,
   otel: 15,
-  process: 16,
-  rawImports: 17,
-  temporal: 20,
-  unsafeProto: 21,
-  vsock: 22,
-  webgpu: 23,
-  workerOptions: 24,
+  pqc: 16,
+  process: 17,
+  rawImports: 18,
+  temporal: 21,
+  unsafeProto: 22,
+  vsock: 23,
+  webgpu: 24,
+  workerOptions: 25,
 };
@@ -151,12 +151,21 @@ pub static UNSTABLE_FEATURES: &[UnstableFeatureDefinition] = &[
     kind: UnstableFeatureKind::Runtime,
     config_file_option: "otel",
   },
+  UnstableFeatureDefinition {
+    name: "pqc",
+    flag_name: "unstable-pqc",
+    help_text: "Enable unstable post-quantum cryptography in node:crypto",
+    show_in_help: false,
+    id: 16,
+    kind: UnstableFeatureKind::Runtime,
+    config_file_option: "pqc",
+  },
   UnstableFeatureDefinition {
     name: "process",
     flag_name: "unstable-process",
     help_text: "Enable unstable process APIs",
     show_in_help: false,
-    id: 16,
+    id: 17,
     kind: UnstableFeatureKind::Runtime,
     config_file_option: "process",
   },
@@ -165,7 +174,7 @@ pub static UNSTABLE_FEATURES: &[UnstableFeatureDefinition] = &[
     flag_name: "unstable-raw-imports",
     help_text: "Enable unstable 'bytes' and 'text' imports.",
     show_in_help: true,
-    id: 17,
+    id: 18,
     kind: UnstableFeatureKind::Runtime,
     config_file_option: "raw-imports",
   },
@@ -174,7 +183,7 @@ pub static UNSTABLE_FEATURES: &[UnstableFeatureDefinition] = &[
     flag_name: "unstable-sloppy-imports",
     help_text: "Enable unstable resolving of specifiers by extension probing, .js to .ts, and directory probing",
     show_in_help: true,
-    id: 18,
+    id: 19,
     kind: UnstableFeatureKind::Cli,
     config_file_option: "sloppy-imports",
   },
@@ -183,7 +192,7 @@ pub static UNSTABLE_FEATURES: &[UnstableFeatureDefinition] = &[
     flag_name: "unstable-subdomain-wildcards",
     help_text: "Enable subdomain wildcards support for the `--allow-net` flag",
     show_in_help: false,
-    id: 19,
+    id: 20,
     kind: UnstableFeatureKind::Cli,
     config_file_option: "subdomain-wildcards",
   },
@@ -192,7 +201,7 @@ pub static UNSTABLE_FEATURES: &[UnstableFeatureDefinition] = &[
     flag_name: "unstable-temporal",
     help_text: "Enable unstable Temporal API",
     show_in_help: true,
-    id: 20,
+    id: 21,
     kind: UnstableFeatureKind::Runtime,
     config_file_option: "temporal",
   },
@@ -201,7 +210,7 @@ pub static UNSTABLE_FEATURES: &[UnstableFeatureDefinition] = &[
     flag_name: "unstable-unsafe-proto",
     help_text: "Enable unsafe __proto__ support. This is a security risk.",
     show_in_help: true,
-    id: 21,
+    id: 22,
     kind: UnstableFeatureKind::Runtime,
     config_file_option: "unsafe-proto",
   },
@@ -210,7 +219,7 @@ pub static UNSTABLE_FEATURES: &[UnstableFeatureDefinition] = &[
     flag_name: "unstable-vsock",
     help_text: "Enable unstable VSOCK APIs",
     show_in_help: false,
-    id: 22,
+    id: 23,
     kind: UnstableFeatureKind::Runtime,
     config_file_option: "vsock",
   },
@@ -219,7 +228,7 @@ pub static UNSTABLE_FEATURES: &[UnstableFeatureDefinition] = &[
     flag_name: "unstable-webgpu",
     help_text: "Enable unstable WebGPU APIs",
     show_in_help: true,
-    id: 23,
+    id: 24,
     kind: UnstableFeatureKind::Runtime,
     config_file_option: "webgpu",
   },
@@ -228,7 +237,7 @@ pub static UNSTABLE_FEATURES: &[UnstableFeatureDefinition] = &[
     flag_name: "unstable-worker-options",
     help_text: "Enable unstable Web Worker APIs",
     show_in_help: true,
-    id: 24,
+    id: 25,
     kind: UnstableFeatureKind::Runtime,
     config_file_option: "worker-options",
   },
@@ -116,6 +116,7 @@ fn node_unit_test(test: String) {
     .arg("--no-lock")
     .arg("--unstable-broadcast-channel")
     .arg("--unstable-net")
+    .arg("--unstable-pqc")
     .arg("-A");
 
   // Some tests require the root CA cert file to be loaded.
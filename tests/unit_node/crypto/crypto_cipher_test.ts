@@ -85,6 +85,109 @@ Deno.test({
   },
 });
 
+Deno.test({
+  name: "rsa private encrypt and public decrypt",
+  fn() {
+    // From Node.js, which signs with PKCS #1 v1.5 block type 1 here.
+    const encrypted = Buffer.from(
+      "82424e1d1ffc29afab1b9f40cd97225df67ead8945d4285cb0996141ef72c7a315753797b2b9b0f2707b8536527cb0fac8bfd1a07db6c2a6e110d3b8c87a066d6ae5b51789811b46959ff6d9d441821a2018d983d77cabe07a37af631eea95948f2c9041582ce8d42925960506845c940b146a60e24712ba3f4d466e1d1fccf63a5555dbc75dd508a32b13778861a7099de08fe22ce65775cd91e270e486bbba5c220c351eb32e5fff6d213faa438a7776abffb9fbcdcc84bbec0538b7f650b4603631108b41ca0bcd51308f9e0dd9162b3cf95118c2c4fa401484f94b94ccb042d51eae79e0bd71e56c0b0910699945c10fcb803506e421f12507e26b5957a0",
+      "hex",
+    );
+    assertEquals(crypto.publicDecrypt(rsaPublicKey, encrypted), input);
+    assertEquals(crypto.publicDecrypt(rsaPrivateKey, encrypted), input);
+  },
+});
+
+Deno.test({
+  name: "rsa no padding",
+  fn() {
+    const { RSA_NO_PADDING: padding } = crypto.constants;
+    const block = Buffer.alloc(256);
+    input.copy(block, block.length - input.length);
+
+    const encrypted = crypto.privateEncrypt(
+      { key: rsaPrivateKey, padding },
+      block,
+    );
+    assertEquals(
+      encrypted.toString("hex"),
+      "5b6c50fa811e321b1213a41bdbf75036b75315dffe9e25dc4d289277797383fb44109e2015e9a7cb9eb39db2a241dc2c89703dd7923316cdd9f435d0e8e87662d1c59e5e002115b7fb317dd92ae5145703fa982b4a900a6c51630a04458904d17ec07b1c2398d09384f0336f6323c513983053baac3150cf01d1243e0d8ec53eb587b7ff060553b79eacfff173f568d8c712cda717c4d28ac4959d7169db2a50710cbd006be86024d05bcb84fbab6d9cc1413e30c1e21c276c171362b4a20677569c0d5a99ae906a3939244e53f52798378b776c8c48c1097681cefb53fdfe03e67201c9d37890d1d5c44a1a5e98e5f198316c7a70aa3ab6a4809f2e7754ea0e",
+    );
+    assertEquals(
+      crypto.publicDecrypt({ key: rsaPublicKey, padding }, encrypted),
+      block,
+    );
+    assertEquals(
+      crypto.privateDecrypt(
+        { key: rsaPrivateKey, padding },
+        crypto.publicEncrypt({ key: rsaPublicKey, padding }, block),
+      ),
+      block,
+    );
+
+    for (
+      const [fn, key, data, code] of [
+        [
+          crypto.privateEncrypt,
+          rsaPrivateKey,
+          input,
+          "ERR_OSSL_RSA_DATA_TOO_SMALL_FOR_KEY_SIZE",
+        ],
+        [
+          crypto.publicEncrypt,
+          rsaPublicKey,
+          Buffer.alloc(257),
+          "ERR_OSSL_RSA_DATA_TOO_LARGE_FOR_KEY_SIZE",
+        ],
+        [
+          crypto.publicDecrypt,
+          rsaPublicKey,
+          Buffer.alloc(257),
+          "ERR_OSSL_RSA_DATA_GREATER_THAN_MOD_LEN",
+        ],
+        [
+          crypto.privateDecrypt,
+          rsaPrivateKey,
+          Buffer.alloc(256, 0xff),
+          "ERR_OSSL_RSA_DATA_TOO_LARGE_FOR_MODULUS",
+        ],
+      ] as const
+    ) {
+      const err = assertThrows(() => fn({ key, padding }, data));
+      // deno-lint-ignore no-explicit-any
+      assertEquals((err as any).code, code);
+    }
+  },
+});
+
+Deno.test({
+  name: "rsa x931 padding is not supported",
+  fn() {
+    const { RSA_X931_PADDING: padding } = crypto.constants;
+    const block = Buffer.alloc(256);
+    for (
+      const [fn, key, code] of [
+        [crypto.privateEncrypt, rsaPrivateKey, "ERR_OSSL_INVALID_X931_DIGEST"],
+        [crypto.publicDecrypt, rsaPublicKey, "ERR_OSSL_INVALID_X931_DIGEST"],
+        [
+          crypto.publicEncrypt,
+          rsaPublicKey,
+          "ERR_OSSL_RSA_UNKNOWN_PADDING_TYPE",
+        ],
+        [
+          crypto.privateDecrypt,
+          rsaPrivateKey,
+          "ERR_OSSL_RSA_UNKNOWN_PADDING_TYPE",
+        ],
+      ] as const
+    ) {
+      const err = assertThrows(() => fn({ key, padding }, block));
+      // deno-lint-ignore no-explicit-any
+      assertEquals((err as any).code, code);
+    }
+  },
+});
+
 Deno.test({
   name: "createCipheriv - multiple chunk inputs",
   fn() {
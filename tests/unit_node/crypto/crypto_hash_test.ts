@@ -1,8 +1,38 @@
 // Copyright 2018-2025 the Deno authors. MIT license.
-import { createHash, createHmac, getHashes, hash } from "node:crypto";
+import {
+  createHash,
+  createHmac,
+  createSecretKey,
+  createSign,
+  createVerify,
+  generateKeyPairSync,
+  getFips,
+  getHashes,
+  Hash,
+  hash,
+  hkdfSync,
+  KeyObject,
+  pbkdf2,
+  pbkdf2Sync,
+  setFips,
+  sign,
+} from "node:crypto";
 import { Buffer } from "node:buffer";
-import { Readable } from "node:stream";
-import { assert, assertEquals } from "@std/assert";
+import { closeSync, openSync } from "node:fs";
+import { PassThrough, Readable } from "node:stream";
+import {
+  assert,
+  assertEquals,
+  assertRejects,
+  assertThrows,
+} from "@std/assert";
+// `crypto.getHashes()` from Node.js built against OpenSSL 3.0.
+import nodeHashes from "./testdata/get_hashes.json" with { type: "json" };
+
+// Helpers that are not part of the public `node:crypto` API.
+const { createMultiHash, hashStream, hmacBatch, hmacStream } =
+  // @ts-expect-error TypeScript does not support indexing namespaces by symbol
+  Deno[Deno.internal].nodeCrypto;
 
 // https://github.com/denoland/deno/issues/18140
 Deno.test({
@@ -109,6 +139,47 @@ Deno.test("[node/crypto.Hash] streaming usage", async () => {
   );
 });
 
+// https://github.com/nodejs/node/blob/v22.0.0/test/parallel/test-crypto-hash-stream-pipe.js
+Deno.test("[node/crypto.Hash] digest() after piping", async () => {
+  const expected = "36a38a2a35e698974d4e5791a3f05b05" +
+    "198235381e864f91a0e8cd6a26b677ec" +
+    "dcde8e2b069bd7355fabd68abd6fc801" +
+    "19659f25e92f8efc961ee3a7c815c758";
+  const source = new PassThrough();
+  const hash = createHash("sha3-512");
+  const chunks: string[] = [];
+  const ended = new Promise((resolve) => {
+    source.pipe(hash).setEncoding("hex").on("data", (chunk) => {
+      chunks.push(chunk);
+      assertEquals(hash.digest("hex"), expected);
+    }).on("end", resolve);
+  });
+  source.end("aoeu");
+  await ended;
+  assertEquals(chunks, [expected]);
+  assertThrows(() => hash.digest("hex"), Error, "Digest already called");
+});
+
+Deno.test("[node/crypto.Hash] stream chunks are not queued", async () => {
+  const hash = createHash("sha256", { highWaterMark: 16 });
+  const expected = createHash("sha256");
+  const chunk = new Uint8Array(1024);
+  for (let i = 0; i < 64; i++) {
+    // Each chunk goes straight to the native hash, so none are queued.
+    assert(hash.write(chunk));
+    assertEquals(hash.writableLength, 0);
+    expected.update(chunk);
+  }
+
+  const digests: Buffer[] = [];
+  hash.on("data", (digest) => digests.push(digest));
+  await new Promise((resolve) => {
+    Readable.from(Array(64).fill(chunk)).pipe(hash).on("end", resolve);
+    for (let i = 0; i < 64; i++) expected.update(chunk);
+  });
+  assertEquals(digests, [expected.digest()]);
+});
+
 Deno.test("[node/crypto.getHashes]", () => {
   for (const algorithm of getHashes()) {
     const d = createHash(algorithm).update("abc").digest();
@@ -117,6 +188,16 @@ Deno.test("[node/crypto.getHashes]", () => {
   }
 });
 
+Deno.test("[node/crypto.getHashes] matches Node.js", () => {
+  const hashes = getHashes();
+  assertEquals(hashes.slice(0, nodeHashes.length), nodeHashes);
+  // Deno-only additions all come after Node.js's list.
+  for (const algorithm of hashes.slice(nodeHashes.length)) {
+    assert(!nodeHashes.includes(algorithm), algorithm);
+  }
+  assertEquals(new Set(hashes).size, hashes.length);
+});
+
 Deno.test("[node/crypto.hash] supports buffer args", () => {
   const buffer = Buffer.from("abc");
   const d = createHash("sha1").update(buffer).digest("hex");
@@ -128,11 +209,215 @@ Deno.test("[node/crypto.hash] does not leak", () => {
   hasher.update("abc");
 });
 
+Deno.test("[node/crypto.Hash] digest encodings", () => {
+  const raw = createHash("sha256").update("abc").digest();
+  for (const encoding of ["base64", "base64url", "latin1", "binary"] as const) {
+    assertEquals(
+      createHash("sha256").update("abc").digest(encoding),
+      raw.toString(encoding),
+    );
+  }
+  assertEquals(
+    createHash("sha256").update("abc").digest("ucs2"),
+    raw.toString("ucs2"),
+  );
+});
+
+// Mirrors the copy() cases in Node's test-crypto-hash.js
+Deno.test("[node/crypto.Hash] copy with outputLength", () => {
+  assertEquals(
+    createHash("shake128", { outputLength: 5 }).copy().digest("hex"),
+    "7f9c2ba4e8",
+  );
+  assertEquals(
+    createHash("shake128", { outputLength: 5 })
+      .copy({ outputLength: 0 })
+      .digest("hex"),
+    "",
+  );
+  assertEquals(
+    createHash("shake128", { outputLength: 0 })
+      .copy({ outputLength: 5 })
+      .digest("hex"),
+    "7f9c2ba4e8",
+  );
+  // outputLength is ignored for non-XOF algorithms.
+  assertEquals(
+    createHash("sha256").copy({ outputLength: 5 }).digest("hex"),
+    "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855",
+  );
+});
+
+Deno.test("[node/crypto.Hash] outputLength of zero", () => {
+  for (const algorithm of ["shake128", "shake256", "blake3"]) {
+    const empty = createHash(algorithm, { outputLength: 0 }).update("abc");
+    assertEquals(empty.copy().digest(), Buffer.alloc(0));
+    assertEquals(empty.digest("hex"), "");
+
+    const copy = createHash(algorithm).update("abc").copy({ outputLength: 0 });
+    assertEquals(copy.digest(), Buffer.alloc(0));
+  }
+  for (const algorithm of ["sha256", "md5"]) {
+    assertThrows(
+      () => createHash(algorithm, { outputLength: 0 }),
+      RangeError,
+      "Invalid XOF digest length",
+    );
+  }
+});
+
+Deno.test("[node/crypto.Hash] squeeze shake256", () => {
+  const expected = createHash("shake256", { outputLength: 100 })
+    .update("abc")
+    .digest("hex");
+  // deno-lint-ignore no-explicit-any
+  const h: any = createHash("shake256").update("abc");
+  const copy = h.copy({ outputLength: 100 });
+  const chunks = [h.squeeze(1), h.squeeze(31), h.squeeze(0), h.squeeze(68)];
+  assertEquals(Buffer.concat(chunks).toString("hex"), expected);
+  assertThrows(() => h.update("abc"), Error, "Digest already called");
+  assertThrows(() => h.digest(), Error, "Digest already called");
+  assertEquals(copy.digest("hex"), expected);
+});
+
+// Ported from Node.js test/parallel/test-crypto-hash.js and
+// test-crypto-hmac.js.
+Deno.test("[node/crypto.Hash] update() and digest() after digest()", () => {
+  const h = createHash("sha1");
+  h.update("foo");
+  h.digest();
+  for (const fn of [() => h.digest(), () => h.update("foo")]) {
+    const err = assertThrows(fn, Error, "Digest already called");
+    // deno-lint-ignore no-explicit-any
+    assertEquals((err as any).code, "ERR_CRYPTO_HASH_FINALIZED");
+    assertEquals(err.name, "Error");
+  }
+});
+
+Deno.test("[node/crypto.Hmac] digest() after digest() is empty", () => {
+  const expected = createHmac("sha1", "key").update("data").digest("latin1");
+  const h = createHmac("sha1", "key").update("data");
+  assertEquals(h.digest("latin1"), expected);
+  assertEquals(h.digest("latin1"), "");
+  const b = createHmac("sha1", "key").update("data");
+  assertEquals(b.digest("buffer"), Buffer.from(expected, "latin1"));
+  assertEquals(b.digest("buffer"), Buffer.from(""));
+  assertEquals(b.digest("hex"), "");
+  const err = assertThrows(() => b.update("foo"), Error, "Digest already");
+  // deno-lint-ignore no-explicit-any
+  assertEquals((err as any).code, "ERR_CRYPTO_HASH_FINALIZED");
+});
+
+Deno.test("[node/crypto.Hash] squeeze rejects non-XOF digests", () => {
+  // deno-lint-ignore no-explicit-any
+  const h: any = createHash("sha256");
+  assertThrows(() => h.squeeze(32), TypeError, "does not support squeezing");
+});
+
+Deno.test("[node/crypto.Hash] algorithm names are case-insensitive", () => {
+  const expected = createHash("sha512-256").update("abc").digest("hex");
+  assertEquals(createHash("Sha-512/256").update("abc").digest("hex"), expected);
+  assertEquals(hash("SHA-512/256", "abc"), expected);
+  assertEquals(
+    createHash("RSA-SHA1").update("abc").digest("hex"),
+    createHash("sha1").update("abc").digest("hex"),
+  );
+});
+
+Deno.test("[node/crypto.Hash] digests named by OID", () => {
+  for (
+    const [oid, name] of [
+      ["2.16.840.1.101.3.4.2.1", "sha256"],
+      ["2.16.840.1.101.3.4.2.8", "sha3-256"],
+      ["1.3.14.3.2.26", "sha1"],
+      ["1.2.840.113549.2.5", "md5"],
+      ["1.3.36.3.2.1", "ripemd160"],
+      ["1.2.156.10197.1.401", "sm3"],
+    ]
+  ) {
+    const expected = createHash(name).update("abc").digest("hex");
+    assertEquals(createHash(oid).update("abc").digest("hex"), expected);
+    const asn1Name = `id-${name}`;
+    assertEquals(createHash(asn1Name).update("abc").digest("hex"), expected);
+    assertEquals(hash(oid, "abc"), expected);
+    assert(!getHashes().includes(oid));
+  }
+  assertThrows(
+    () => createHash("2.16.840.1.101.3.4.2.99"),
+    TypeError,
+    "The argument 'algorithm' is invalid",
+  );
+});
+
+Deno.test("[node/crypto.Hash] string encodings hashed in the op", () => {
+  const strings = [
+    "",
+    "plain ascii",
+    "caf\u00e9 \u00ff",
+    "\u20ac \u{1f980} \u4e2d\u6587",
+    // Lone surrogates.
+    "\ud800 \udfff x\ud83d",
+    "\u00e9".repeat(10000),
+    "\u{1f980}".repeat(5000),
+  ];
+  const encodings = ["utf8", "latin1", "binary", "utf16le", "ucs2"] as const;
+  for (const string of strings) {
+    for (const encoding of encodings) {
+      const bytes = Buffer.from(string, encoding);
+      assertEquals(
+        createHash("sha256").update(string, encoding).digest("hex"),
+        createHash("sha256").update(bytes).digest("hex"),
+        `${encoding}: ${string.slice(0, 16)}`,
+      );
+    }
+  }
+});
+
+Deno.test("[node/crypto.Hash] reset", () => {
+  for (const algorithm of ["sha256", "sha512", "sha1", "shake128"]) {
+    const expected = createHash(algorithm).update("abc").digest("hex");
+    // deno-lint-ignore no-explicit-any
+    const h: any = createHash(algorithm).update("discarded");
+    assertEquals(h.reset().update("abc").digest("hex"), expected);
+    assertThrows(() => h.digest(), Error, "Digest already called");
+    assertEquals(h.reset().update("abc").digest("hex"), expected);
+  }
+});
+
 Deno.test("[node/crypto.hash] oneshot hash API", () => {
   const d = hash("sha1", "Node.js");
   assertEquals(d, "10b3493287f831e81a438811a1ffba01f8cec4b7");
 });
 
+Deno.test("[node/crypto.hash] oneshot hash API accepts buffers", () => {
+  const d = hash("SHA256", new TextEncoder().encode("Node.js"), "base64");
+  assertEquals(d, createHash("sha256").update("Node.js").digest("base64"));
+});
+
+Deno.test("[node/crypto.hash] buffer output owns its memory", () => {
+  const expected = createHash("sha256").update("Node.js").digest();
+  const a = hash("sha256", "Node.js", "buffer");
+  const b = hash("sha256", "Node.js", "buffer");
+  assert(Buffer.isBuffer(a));
+  assertEquals(a, expected);
+  // Not a view into a shared pool or into the other result.
+  assertEquals(a.byteOffset, 0);
+  assertEquals(a.buffer.byteLength, 32);
+  assert(a.buffer !== b.buffer);
+
+  a.fill(0);
+  assertEquals(b, expected);
+  assertEquals(hash("sha256", "Node.js", "buffer"), expected);
+});
+
+Deno.test("[node/crypto.hash] oneshot hash API unknown algorithm", () => {
+  assertThrows(
+    () => hash("sha0", "Node.js"),
+    TypeError,
+    "The argument 'algorithm' is invalid. Received 'sha0'",
+  );
+});
+
 Deno.test("[node/crypto.hash] shake-128 alias", () => {
   const d = hash("shake-128", "Node.js", "base64url");
   assertEquals(d, "Nkx9-EgHpFkeXY5OPsL0rg");
@@ -142,3 +427,1034 @@ Deno.test("[node/crypto.hash] shake-256 alias", () => {
   const d = hash("shake-256", "Node.js", "base64url");
   assertEquals(d, "JdelDxiwp92tkk9jYjEFPMlHD0gC8bMbYtHRCIM6TTQ");
 });
+
+Deno.test("[node/crypto.Hash] blake3", () => {
+  const d = createHash("blake3").update("abc").digest("hex");
+  assertEquals(
+    d,
+    "6437b3ac38465133ffb63b75273a8db548c558465d79db03fd359c6cd5bd9d85",
+  );
+});
+
+Deno.test("[node/crypto.Hash] blake3 with outputLength", () => {
+  const hasher = createHash("blake3", { outputLength: 64 }).update("abc");
+  const copy = hasher.copy();
+  const expected =
+    "6437b3ac38465133ffb63b75273a8db548c558465d79db03fd359c6cd5bd9d85" +
+    "1fb250ae7393f5d02813b65d521a0d492d9ba09cf7ce7f4cffd900f23374bf0b";
+  assertEquals(hasher.digest("hex"), expected);
+  assertEquals(copy.digest("hex"), expected);
+});
+
+Deno.test("[node/crypto.Hash] blake2 with outputLength", () => {
+  const hasher = createHash("blake2b512", { outputLength: 20 });
+  hasher.update("abc");
+  const copy = hasher.copy();
+  const expected = "384264f676f39536840523f284921cdc68b6846b";
+  assertEquals(hasher.digest("hex"), expected);
+  assertEquals(copy.digest("hex"), expected);
+  assertEquals(
+    createHash("blake2b512", { outputLength: 48 }).update("abc").digest("hex"),
+    "6f56a82c8e7ef526dfe182eb5212f7db9df1317e57815dbda46083fc30f54ee6" +
+      "c66ba83be64b302d7cba6ce15bb556f4",
+  );
+  assertEquals(
+    createHash("blake2s256", { outputLength: 16 }).update("abc").digest("hex"),
+    "aa4938119b1dc7b87cbad0ffd200d0ae",
+  );
+  assertThrows(() => createHash("blake2b512", { outputLength: 65 }));
+  assertThrows(() => createHash("blake2s256", { outputLength: 33 }));
+});
+
+// `key` is not part of @types/node's HashOptions.
+// deno-lint-ignore no-explicit-any
+const keyed = (key: Uint8Array): any => ({ key });
+
+// https://github.com/BLAKE2/BLAKE2/tree/master/testvectors
+Deno.test("[node/crypto.Hash] keyed blake2", () => {
+  const input = new Uint8Array(255).map((_, i) => i);
+  const blake2bKey = new Uint8Array(64).map((_, i) => i);
+  assertEquals(
+    createHash("blake2b512", keyed(blake2bKey)).digest("hex"),
+    "10ebb67700b1868efb4417987acf4690ae9d972fb7a590c2f02871799aaa4786" +
+      "b5e996e8f0f4eb981fc214b005f42d2ff4233499391653df7aefcbc13fc51568",
+  );
+  const hasher = createHash("blake2b512", keyed(blake2bKey)).update(input);
+  const copy = hasher.copy();
+  const expected =
+    "142709d62e28fcccd0af97fad0f8465b971e82201dc51070faa0372aa43e9248" +
+    "4be1c1e73ba10906d5d1853db6a4106e0a7bf9800d373d6dee2d46d62ef2a461";
+  assertEquals(hasher.digest("hex"), expected);
+  assertEquals(copy.digest("hex"), expected);
+
+  const blake2sKey = new Uint8Array(32).map((_, i) => i);
+  assertEquals(
+    createHash("blake2s256", keyed(blake2sKey)).digest("hex"),
+    "48a8997da407876b3d79c0d92325ad3b89cbb754d86ab71aee047ad345fd2c49",
+  );
+  assertEquals(
+    createHash("blake2s256", keyed(blake2sKey)).update(input).digest("hex"),
+    "3fb735061abc519dfe979e54c1ee5bfad0a9d858b3315bad34bde999efd724dd",
+  );
+
+  assertThrows(() => createHash("sha256", keyed(blake2sKey)), TypeError);
+  assertThrows(() => createHash("blake2s256", keyed(blake2bKey)), TypeError);
+});
+
+Deno.test("[node/crypto.Hash] blake2 salt and personalization", () => {
+  const data = "The quick brown fox jumps over the lazy dog";
+  // deno-lint-ignore no-explicit-any
+  const salted = (options: any) => options;
+  // From libsodium's crypto_generichash_blake2b_salt_personal.
+  const hasher = createHash(
+    "blake2b512",
+    salted({ salt: "0123456789abcdef", personalization: "deno personalize" }),
+  ).update(data);
+  const copy = hasher.copy();
+  const expected =
+    "fa2c5803c5b5f26bea8e0cc5ce670c713c5bb038a03e0dd4b5a29914d6fabb1e" +
+    "0ad1c426b0e0a4effc4ddfc5526a37661f795323b67e55f5c0c894e27a67f65a";
+  assertEquals(hasher.digest("hex"), expected);
+  assertEquals(copy.digest("hex"), expected);
+  assertEquals(
+    createHash(
+      "blake2b512",
+      salted({
+        key: "secret key",
+        salt: Buffer.from("salt"),
+        personalization: "persona",
+        outputLength: 32,
+      }),
+    ).update(data).digest("hex"),
+    "1efb10d3651984c0bd5fd60e14df233208c0ed1b11479b7727a5a49f9ab13a63",
+  );
+  // From Python's hashlib.blake2s.
+  assertEquals(
+    createHash(
+      "blake2s256",
+      salted({ salt: "01234567", personalization: "personal" }),
+    ).update(data).digest("hex"),
+    "e70c1d1f0f91ca0b642b5693ae3bef413c9ade84e817bfa4c22da2602a08597e",
+  );
+
+  assertThrows(
+    () => createHash("blake2s256", salted({ salt: "012345678" })),
+    RangeError,
+    "salt",
+  );
+  assertThrows(
+    () => createHash("blake2b512", salted({ personalization: "x".repeat(17) })),
+    RangeError,
+    "personalization",
+  );
+  assertThrows(
+    () => createHash("sha256", salted({ salt: "salt" })),
+    RangeError,
+  );
+});
+
+Deno.test("[node/crypto.Hash] whirlpool", () => {
+  const hasher = createHash("whirlpool").update("abc");
+  const copy = hasher.copy();
+  const expected =
+    "4e2448a4c6f486bb16b6562c73b4020bf3043e3a731bce721ae1b303d97e6d4c" +
+    "7181eebdb6c57e277d0e34957114cbd6c797fc9d95d8b582d225292076d4eef5";
+  assertEquals(hasher.digest("hex"), expected);
+  assertEquals(copy.digest("hex"), expected);
+  assertEquals(
+    createHmac("whirlpool", "key")
+      .update("The quick brown fox jumps over the lazy dog")
+      .digest("hex"),
+    "7f7192e3a155cb6a8171584ba146882f26821658112dfd2601272db013517a31" +
+      "e573637d146584596f86a884eb0decc9514dde000ecf2476dc5d436a92197527",
+  );
+});
+
+Deno.test("[node/crypto.Hash] legacy keccak", () => {
+  assertEquals(
+    createHash("keccak224").digest("hex"),
+    "f71837502ba8e10837bdd8d365adb85591895602fc552b48b7390abd",
+  );
+  assertEquals(
+    createHash("keccak256").digest("hex"),
+    "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470",
+  );
+  assertEquals(
+    createHash("keccak384").digest("hex"),
+    "2c23146a63a29acf99e73b88f8c24eaa7dc60aa771780ccc006afbfa8fe2479b" +
+      "2dd2b21362337441ac12b515911957ff",
+  );
+  assertEquals(
+    createHash("keccak512").digest("hex"),
+    "0eab42de4c3ceb9235fc91acffe746b29c29a8c366b7c60e4e67c466f36a4304" +
+      "c00fa9caf9d87976ba469bcbe06713b435f091ef2769fb160cdab33d3670680e",
+  );
+
+  const hasher = createHash("keccak256").update("a");
+  const copy = hasher.copy();
+  hasher.update("bc");
+  copy.update("bc");
+  const expected =
+    "4e03657aea45a94fc7d47ba826c8d667c0d1e6e33a64a036ec44f58fa12d6c45";
+  assertEquals(hasher.digest("hex"), expected);
+  assertEquals(copy.digest("hex"), expected);
+});
+
+Deno.test("[node/crypto.Hash] FIPS mode rejects non-approved digests", () => {
+  setFips(true);
+  try {
+    assertEquals(getFips(), 1);
+    assertThrows(
+      () => createHash("md5"),
+      Error,
+      "Digest method not allowed in FIPS mode",
+    );
+    assertThrows(() => createHmac("ripemd160", "key"));
+    const hashes = getHashes();
+    assert(!hashes.includes("md5"));
+    assert(hashes.includes("sha256"));
+    assertEquals(
+      createHash("sha256").update("hello").digest("hex"),
+      "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824",
+    );
+  } finally {
+    setFips(false);
+  }
+  assertEquals(getFips(), 0);
+  assert(getHashes().includes("md5"));
+});
+
+Deno.test("[node/crypto.getHashes] follows FIPS mode at runtime", () => {
+  const restricted = ["md4", "md5", "ripemd160", "sm3"];
+  const hkdfMd5 = () => hkdfSync("md5", "key", "salt", "info", 16);
+  assert(restricted.every((name) => getHashes().includes(name)));
+  hkdfMd5();
+
+  setFips(true);
+  try {
+    const hashes = getHashes();
+    for (const name of restricted) {
+      assert(!hashes.includes(name), name);
+      assertThrows(() => createHash(name), Error, "FIPS mode", name);
+    }
+    assertThrows(hkdfMd5, TypeError, "Invalid digest: md5");
+  } finally {
+    setFips(false);
+  }
+
+  assert(restricted.every((name) => getHashes().includes(name)));
+  createHash("md5");
+  hkdfMd5();
+});
+
+Deno.test("[node/crypto.Hmac] secret KeyObject keys", () => {
+  const key = Buffer.from("0123456789abcdef");
+  const expected = createHmac("sha256", key).update("data").digest("hex");
+  assertEquals(
+    createHmac("sha256", createSecretKey(key)).update("data").digest("hex"),
+    expected,
+  );
+  // The key is read from the native handle, never exported to JS.
+  const secret = createSecretKey(Buffer.alloc(200, 0xaa));
+  const { export: exportKey } = KeyObject.prototype;
+  KeyObject.prototype.export = () => {
+    throw new Error("KeyObject exported");
+  };
+  try {
+    assertEquals(
+      createHmac("sha512", secret).update("da").update("ta").digest("hex"),
+      createHmac("sha512", Buffer.alloc(200, 0xaa)).update("data")
+        .digest("hex"),
+    );
+  } finally {
+    KeyObject.prototype.export = exportKey;
+  }
+  // Node.js HMAC is defined over md5-sha1 like any other digest.
+  assertEquals(
+    createHmac("md5-sha1", "key").update("x").digest("hex"),
+    "82ac2463f35e28a163189e419707023929ea2fa52e330d3aea1ac386261e04ce" +
+      "baaefe14",
+  );
+});
+
+Deno.test("[node/crypto.Hmac] single update() matches streaming", () => {
+  const data = "The quick brown fox jumps over the lazy dog";
+  const expected =
+    "f7bc83f430538424b13298e6aa6fb143ef4d59a14946175997479dbc2d1a3cd8";
+  assertEquals(
+    createHmac("sha256", "key").update(data).digest("hex"),
+    expected,
+  );
+  assertEquals(
+    createHmac("sha256", "key").update(data.slice(0, 9)).update(data.slice(9))
+      .digest("hex"),
+    expected,
+  );
+  assertEquals(
+    createHmac("sha256", createSecretKey(Buffer.from("key"))).update(data)
+      .digest("hex"),
+    expected,
+  );
+  assertEquals(
+    createHmac("sha256", "key").digest("hex"),
+    "5d5d139563c95b5967b9bd9a8c9b233a9dedb45072794cd232dc1b74832607d0",
+  );
+
+  const bytes = Buffer.from(expected, "hex");
+  for (const encoding of ["base64", "base64url", "latin1", "ucs2"] as const) {
+    assertEquals(
+      createHmac("sha256", "key").update(data).digest(encoding),
+      bytes.toString(encoding),
+      encoding,
+    );
+  }
+  assertEquals(createHmac("sha256", "key").update(data).digest(), bytes);
+
+  // The key and the message are copied, as with the streaming path.
+  const key = Buffer.from("key");
+  const message = Buffer.from(data);
+  const hmac = createHmac("sha256", key).update(message);
+  key.fill(0);
+  message.fill(0);
+  assertEquals(hmac.digest("hex"), expected);
+});
+
+Deno.test("[node/crypto.Hmac] single update() of a long message", () => {
+  // Above 64 KiB the message goes to the native Hmac without a copy.
+  const expected =
+    "cd5023f6361b800d5dcc7e0f72991fe1db078d09be30f856f4a7ff952736e902";
+  const message = Buffer.alloc(100000, "a");
+  assertEquals(
+    createHmac("sha256", "key").update(message).digest("hex"),
+    expected,
+  );
+  assertEquals(
+    createHmac("sha256", "key").update("a".repeat(100000)).digest("hex"),
+    expected,
+  );
+  const hmac = createHmac("sha256", "key").update(message);
+  message.fill(0);
+  assertEquals(hmac.digest("hex"), expected);
+});
+
+Deno.test("[node/crypto.Hmac] rejects XOFs and unknown digests", () => {
+  for (const algorithm of ["shake128", "shake256", "blake3", "nope"]) {
+    const err = assertThrows(
+      () => createHmac(algorithm, "key"),
+      TypeError,
+      `Invalid digest: ${algorithm}`,
+    );
+    // deno-lint-ignore no-explicit-any
+    assertEquals((err as any).code, "ERR_CRYPTO_INVALID_DIGEST");
+    assertEquals(err.message.includes("kmac256"), algorithm !== "nope");
+  }
+  // Also with a KeyObject key, and whatever the case of the name.
+  assertThrows(
+    () => createHmac("shake256", createSecretKey(Buffer.from("key"))),
+    TypeError,
+    "Invalid digest: shake256",
+  );
+  assertThrows(
+    () => createHmac("SHAKE128", "key"),
+    TypeError,
+    "Invalid digest: shake128",
+  );
+});
+
+// https://www.rfc-editor.org/rfc/rfc4231#section-4.7
+Deno.test("[node/crypto.Hmac] keys longer than the block size", () => {
+  const key = Buffer.alloc(131, 0xaa);
+  const data = "Test Using Larger Than Block-Size Key - Hash Key First";
+  const expected: Record<string, string> = {
+    sha224: "95e9a0db962095adaebe9b2d6f0dbce2d499f112f2d2b7273fa6870e",
+    sha256: "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54",
+    sha384:
+      "4ece084485813e9088d2c63a041bc5b44f9ef1012a2b588f3cd11f05033ac4c6" +
+      "0c2ef6ab4030fe8296248df163f44952",
+    sha512:
+      "80b24263c7c1a3ebb71493c1dd7be8b49b46d1f41b4aeec1121b013783f8f352" +
+      "6b56d037e05f2598bd0fd2215d6a1e5295e64f73f63f0aec8b915a985d786598",
+    "sha3-224": "b4a1f04c00287a9b7f6075b313d279b833bc8f75124352d05fb9995f",
+    "sha3-256":
+      "ed73a374b96c005235f948032f09674a58c0ce555cfc1f223b02356560312c3b",
+    "sha3-384":
+      "0fc19513bf6bd878037016706a0e57bc528139836b9a42c3d419e498e0e1fb96" +
+      "16fd669138d33a1105e07c72b6953bcc",
+    "sha3-512":
+      "00f751a9e50695b090ed6911a4b65524951cdc15a73a5d58bb55215ea2cd839a" +
+      "c79d2b44a39bafab27e83fde9e11f6340b11d991b1b91bf2eee7fc872426c3a4",
+    blake2b512:
+      "a54b2943b2a20227d41ca46c0945af09bc1faefb2f49894c23aebc557fb79c48" +
+      "89dca74408dc865086667aedee4a3185c53a49c80b814c4c5813ea0c8b38a8f8",
+    blake2s256:
+      "d23d79394f53d536a096e6514447eeaabb05ded01be32c1937da6a8f7103bc4e",
+  };
+  for (const [algorithm, mac] of Object.entries(expected)) {
+    assertEquals(
+      createHmac(algorithm, key).update(data).digest("hex"),
+      mac,
+      algorithm,
+    );
+  }
+});
+
+Deno.test("[node/crypto.Hmac] sm3 and sha3-256", () => {
+  const key = Buffer.from(Array.from({ length: 32 }, (_, i) => i + 1));
+  assertEquals(
+    createHmac("sm3", key).update("abc").digest("hex"),
+    "44809d3ff5336f44071be1917887380730ae145f940a53819279801784c65e17",
+  );
+  assertEquals(
+    createHmac("sm3", key).update("abcd".repeat(16)).digest("hex"),
+    "d27f13a69be209923ca693683e382b3c4d87a8d54ed9a52bea35494f8284a93d",
+  );
+  // NIST HMAC-SHA3-256 example with a key longer than the 136-byte block.
+  const longKey = Buffer.from(Array.from({ length: 168 }, (_, i) => i));
+  assertEquals(
+    createHmac("sha3-256", longKey)
+      .update("Sample message for keylen>blocklen")
+      .digest("hex"),
+    "9bcf2c238e235c3ce88404e813bd2f3a97185ac6f238c63d6229a00b07974258",
+  );
+});
+
+Deno.test("[node/crypto.Hmac] keys between 64 bytes and the block size", () => {
+  // These keys would be hashed if the block size were assumed to be 64.
+  const key = (length: number) =>
+    Buffer.from(Array.from({ length }, (_, i) => i));
+  const cases: [string, number, string][] = [
+    [
+      "sha384",
+      100,
+      "0bea904bb2f633bfc86e43ff4c3227d59ebfd6730d130598e896a5770f7b1e5a" +
+      "1fcf740fa3800d542351a9141772edb2",
+    ],
+    [
+      "sha512",
+      100,
+      "c277ac32c51079656a010db2495a176b975ebcf70cf3a83f36e7386f70bd6685" +
+      "623f5d96665dfe1dbe09e6348af8baeb01a82c5a50da9b87e209a359534bd90d",
+    ],
+    [
+      "sha3-224",
+      140,
+      "9fe7e2259b7b1424cb59001b97ff46f874681c44da0ae000739a5a80",
+    ],
+    [
+      "sha3-256",
+      140,
+      "1f8ea0b1764ca13ccd8b0ae82644e26a8cb6b3bf7c524a0d11bf8d5451080921",
+    ],
+    [
+      "sha3-384",
+      100,
+      "14ca3d8112b202aca5a150669afff6ab4f370b287d378edee654d4f2f9a0a6c6" +
+      "db80d5d3ea895fc4b21fab22ac7dff81",
+    ],
+    [
+      "sha3-512",
+      80,
+      "cc26e9f8754d6da6a47afb7838c5c13825d2d953be9e35f20c8e55868d98df3d" +
+      "2c7bfb5272249cfd0b99604420a2836debd59d583b174e07e1e754c1f14c0d9f",
+    ],
+    [
+      "blake2b512",
+      100,
+      "97a3698e5b6dc30cf4a3e51dd3542945d671d2eb3c021027fcb7b3c4b60053c0" +
+      "7f88d3d7091685246cfbc525bb50ad126c04d67400a76f22af47563144c1a65b",
+    ],
+  ];
+  for (const [algorithm, length, mac] of cases) {
+    assertEquals(
+      createHmac(algorithm, key(length)).update("abc").digest("hex"),
+      mac,
+      algorithm,
+    );
+  }
+});
+
+Deno.test("[node/crypto.Hmac] string keys in each encoding", () => {
+  // Expected values from Node.js. Malformed hex and base64 keys decode as
+  // Buffer.from() decodes them, and a lone surrogate becomes U+FFFD.
+  const cases: [string, BufferEncoding, string][] = [
+    [
+      "héllo 🦕",
+      "utf8",
+      "0b40ceb505e26da06408a23a31eb841351475f1c756685d50666d39154ba1313",
+    ],
+    [
+      "\ud800k",
+      "utf8",
+      "10a0ac60deed158fadb8b3f218b06b627c1744b3e09642d23be596846e1d9fca",
+    ],
+    [
+      "éÿĀx",
+      "latin1",
+      "7783e66f6d4ecc53b2c2c384775b335c311c24b4af8723ced3d84c799acc6b14",
+    ],
+    [
+      "0a1b2c",
+      "hex",
+      "e778b142378c8cf23f50d94f3ba56a6b101adf48ce1867c3ac2b61fcae0d3748",
+    ],
+    [
+      "abc",
+      "hex",
+      "d0f2f351716440b5aa80d4b7793a32fc63ca85444668442c9201502e181b0f1d",
+    ],
+    [
+      "zz12",
+      "hex",
+      "e528c4d99e6177f5841f712a143b90843299a4aa181a06501422d9ca862bd2a5",
+    ],
+    [
+      "aGVsbG8=",
+      "base64",
+      "70caee68f4c7d200bd78d1951f46b416edda36ce15fdca97d227edeff9e7aeab",
+    ],
+    [
+      "aGV sbG8",
+      "base64",
+      "70caee68f4c7d200bd78d1951f46b416edda36ce15fdca97d227edeff9e7aeab",
+    ],
+    [
+      "aG!V-s_bw",
+      "base64url",
+      "e77afa88ed53e62a360aa0defde9cbcbcb0d746151c2128f531dd6b544bd2548",
+    ],
+  ];
+  for (const [key, encoding, expected] of cases) {
+    const options = { encoding };
+    assertEquals(
+      createHmac("sha256", key, options).update("data").digest("hex"),
+      expected,
+    );
+    const streamed = createHmac("sha256", key, options).update("da");
+    assertEquals(streamed.update("ta").digest("hex"), expected);
+    assertEquals(
+      createHmac("sha256", Buffer.from(key, encoding))
+        .update("data").digest("hex"),
+      expected,
+    );
+  }
+  // Encodings the ops don't decode still go through Buffer.from().
+  assertEquals(
+    createHmac("sha256", "k\u0000", { encoding: "utf16le" })
+      .update("data").digest("hex"),
+    createHmac("sha256", Buffer.from("k\u0000", "utf16le"))
+      .update("data").digest("hex"),
+  );
+});
+
+Deno.test("[node/crypto.Hash] export and import state", () => {
+  // deno-lint-ignore no-explicit-any
+  const HashCtor: any = Hash;
+  for (const algorithm of ["sha224", "sha256", "sha384", "sha512"]) {
+    // deno-lint-ignore no-explicit-any
+    const h: any = createHash(algorithm).update("hello ");
+    const state = h.exportState();
+    assert(Buffer.isBuffer(state));
+    const resumed = HashCtor.fromState(state).update("world");
+    h.update("world");
+    assertEquals(resumed.digest("hex"), h.digest("hex"), algorithm);
+    assertEquals(
+      HashCtor.fromState(state).update("world").digest("hex"),
+      createHash(algorithm).update("hello world").digest("hex"),
+      algorithm,
+    );
+  }
+
+  // deno-lint-ignore no-explicit-any
+  const md5: any = createHash("md5");
+  assertThrows(() => md5.exportState(), TypeError, "exporting its state");
+
+  const state = HashCtor("sha256").exportState();
+  state[0] = 0xff;
+  assertThrows(() => HashCtor.fromState(state), TypeError, "version 255");
+  assertThrows(
+    () => HashCtor.fromState(Buffer.from([1, 3])),
+    TypeError,
+    "Invalid hash state",
+  );
+});
+
+Deno.test("[node/crypto.Hash] update with an array of views", () => {
+  const chunks = [
+    new Uint8Array([0x68, 0x65]),
+    new Uint8Array(0),
+    Buffer.from("llo "),
+    new DataView(new TextEncoder().encode("xworldx").buffer, 1, 5),
+  ];
+  // deno-lint-ignore no-explicit-any
+  const h: any = createHash("sha256");
+  assertEquals(
+    h.update(chunks).update([]).digest("hex"),
+    createHash("sha256").update("hello world").digest("hex"),
+  );
+  assertThrows(() => h.update([Buffer.from("a")]), Error, "Digest already");
+
+  // deno-lint-ignore no-explicit-any
+  const invalid: any = createHash("sha256");
+  assertThrows(
+    () => invalid.update([Buffer.from("a"), "b"]),
+    TypeError,
+    'The "data[1]" argument must be',
+  );
+});
+
+// https://csrc.nist.gov/projects/cryptographic-standards-and-guidelines/example-values
+Deno.test("[node/crypto.Hash] cSHAKE with a customization string", () => {
+  const data = Buffer.from([0, 1, 2, 3]);
+  const customization = "Email Signature";
+  // deno-lint-ignore no-explicit-any
+  const cshake = (algorithm: string, options: any) =>
+    createHash(algorithm, options);
+  assertEquals(
+    cshake("cshake128", { outputLength: 32, customization })
+      .update(data)
+      .digest("hex"),
+    "c1c36925b6409a04f1b504fcbca9d82b4017277cb5ed2b2065fc1d3814d5aaf5",
+  );
+  assertEquals(
+    cshake("cshake256", { outputLength: 64, customization })
+      .update(data)
+      .digest("hex"),
+    "d008828e2b80ac9d2218ffee1d070c48b8e4c87bff32c9699d5b6896eee0edd1" +
+      "64020e2be0560858d9c00c037e34a96937c561a74c412bb4c746469527281c8c",
+  );
+
+  // Without a function name or customization string it is plain SHAKE.
+  assertEquals(
+    createHash("cshake128").update("abc").digest("hex"),
+    createHash("shake128").update("abc").digest("hex"),
+  );
+  assertEquals(
+    createHash("cshake256", { outputLength: 10 }).update("abc").digest("hex"),
+    createHash("shake256", { outputLength: 10 }).update("abc").digest("hex"),
+  );
+  assertThrows(
+    () => cshake("sha256", { customization }),
+    TypeError,
+    "does not support a customization string",
+  );
+});
+
+Deno.test("[node/crypto.Hash] KMAC", () => {
+  const key = Buffer.from(Array.from({ length: 32 }, (_, i) => 0x40 + i));
+  const data = Buffer.from([0, 1, 2, 3]);
+  const customization = "My Tagged Application";
+  // deno-lint-ignore no-explicit-any
+  const kmac = (algorithm: string, options: any) =>
+    createHash(algorithm, options);
+
+  // NIST SP 800-185 KMAC samples 1 and 4.
+  assertEquals(
+    kmac("kmac128", { key }).update(data).digest("hex"),
+    "e5780b0d3ea6f7d3a429c5706aa43a00fadbd7d49628839e3187243f456ee14e",
+  );
+  assertEquals(
+    kmac("kmac256", { key: createSecretKey(key), customization })
+      .update(data)
+      .digest("hex"),
+    "20c570c31346f703c9ac36c61c03cb64c3970d0cfc787e9b79599d273a68d2f7" +
+      "f69d4cc3de9d104a351689f27cf6f5951f0103f33f4f24871024d9c27773a8dd",
+  );
+
+  // Squeezing gives KMACXOF output (KMACXOF sample 1).
+  // deno-lint-ignore no-explicit-any
+  const xof = kmac("kmac128", { key }).update(data) as any;
+  assertEquals(
+    Buffer.concat([xof.squeeze(16), xof.squeeze(16)]).toString("hex"),
+    "cd83740bbd92ccc8cf032b1481a0f4460e7ca9dd12b08a0c4031178bacd6ec35",
+  );
+
+  assertThrows(() => createHash("kmac128"), TypeError, "requires a key");
+  assert(!getHashes().some((name) => name.startsWith("kmac")));
+});
+
+Deno.test("[node/crypto.Hash] SipHash", () => {
+  const key = Buffer.from(Array.from({ length: 16 }, (_, i) => i));
+  const data = Buffer.from(Array.from({ length: 15 }, (_, i) => i));
+  // deno-lint-ignore no-explicit-any
+  const siphash = (algorithm: string, options: any) =>
+    createHash(algorithm, options);
+
+  // The example in Appendix A of the SipHash paper, and the matching
+  // 128-bit value from the reference implementation's vectors.h.
+  assertEquals(
+    siphash("siphash24", { key }).update(data).digest("hex"),
+    "e545be4961ca29a1",
+  );
+  assertEquals(
+    siphash("siphash24", { key: createSecretKey(key), outputLength: 16 })
+      .update(data.subarray(0, 5))
+      .update(data.subarray(5))
+      .digest("hex"),
+    "5493e99933b0a8117e08ec0f97cfc3d9",
+  );
+  assertEquals(
+    siphash("siphash13", { key }).update(data).digest("hex"),
+    "5699512a6dd820d3",
+  );
+
+  assertThrows(() => createHash("siphash24"), TypeError, "requires a key");
+  assertThrows(
+    () => siphash("siphash24", { key: key.subarray(1) }),
+    TypeError,
+    "must be 16 bytes",
+  );
+  assertThrows(
+    () => siphash("siphash13", { key, outputLength: 32 }),
+    RangeError,
+    "must be 8 or 16",
+  );
+  assertThrows(() => createHmac("siphash24", key), Error, "Invalid digest");
+  assert(!getHashes().some((name) => name.startsWith("siphash")));
+});
+
+Deno.test("[node/crypto.Hash] MD2", () => {
+  assertEquals(
+    createHash("md2").update("message digest").digest("hex"),
+    "ab4f496bfb2a530b219ff33031fe06b0",
+  );
+  assertEquals(hash("RSA-MD2", "abc"), "da853b0d3f88d99b30283a69e6ded6bb");
+  assert(getHashes().includes("md2"));
+});
+
+// https://homes.esat.kuleuven.be/~bosselae/ripemd160.html
+Deno.test("[node/crypto.Hash] RIPEMD-256 and RIPEMD-320", () => {
+  assertEquals(
+    createHash("ripemd256").update("message digest").digest("hex"),
+    "87e971759a1ce47a514d5c914c392c9018c7c46bc14465554afcdf54a5070c0e",
+  );
+  assertEquals(
+    hash("rmd320", "abc"),
+    "de4c01b3054f8930a79d09ae738e92301e5a17085beffdc1" +
+      "b8d116713e74f82fa942d64cdbc4682d",
+  );
+  assert(getHashes().includes("ripemd256"));
+  assert(getHashes().includes("ripemd320"));
+
+  const message = "The quick brown fox jumps over the lazy dog";
+  assertEquals(
+    createHmac("ripemd256", "key").update(message).digest("hex"),
+    "39f102599868d204bbf6165139f79eaa856a75cf92d785492907e2fee4168097",
+  );
+  assertEquals(
+    createHmac("ripemd320", "key").update(message).digest("hex"),
+    "dfca8756189fc556323fb344001a927c161f83a9d8f402d0" +
+      "92c537346ae977113c4d02cca757a7ad",
+  );
+
+  const { privateKey, publicKey } = generateKeyPairSync("rsa", {
+    modulusLength: 1024,
+  });
+  const signature = createSign("ripemd256").update(message).sign(privateKey);
+  assert(
+    createVerify("ripemd256").update(message).verify(publicKey, signature),
+  );
+  assert(
+    !createVerify("ripemd256").update("other").verify(publicKey, signature),
+  );
+});
+
+Deno.test("[node/crypto.Hash] legacy digest aliases", async () => {
+  assertThrows(
+    () => createHash("dss1"),
+    Error,
+    "NODE_OPTIONS=--openssl-legacy-provider",
+  );
+
+  const command = new Deno.Command(Deno.execPath(), {
+    args: [
+      "eval",
+      `
+      import { createHash } from "node:crypto";
+      for (const name of ["sha", "dss1", "dsaEncryption", "DSA-SHA1"]) {
+        console.log(createHash(name).update("abc").digest("hex"));
+      }
+      `,
+    ],
+    env: { NODE_OPTIONS: "--openssl-legacy-provider" },
+  });
+  const { code, stdout } = await command.output();
+  assertEquals(code, 0);
+  const sha1 = createHash("sha1").update("abc").digest("hex");
+  assertEquals(
+    new TextDecoder().decode(stdout).trim().split("\n"),
+    [sha1, sha1, sha1, sha1],
+  );
+});
+
+Deno.test("[node/crypto] digest errors have Node.js codes", () => {
+  const assertCode = (
+    fn: () => unknown,
+    ErrorClass: ErrorConstructor,
+    code: string,
+  ) => {
+    // deno-lint-ignore no-explicit-any
+    const err: any = assertThrows(fn, ErrorClass);
+    assertEquals(err.code, code);
+    return err.message;
+  };
+  const invalidDigest = (fn: () => unknown) =>
+    assertCode(fn, TypeError, "ERR_CRYPTO_INVALID_DIGEST");
+
+  assertEquals(
+    assertCode(() => createHash("foo"), TypeError, "ERR_INVALID_ARG_VALUE"),
+    "The argument 'algorithm' is invalid. Received 'foo'",
+  );
+  assertEquals(
+    invalidDigest(() => createHmac("foo", "key")),
+    "Invalid digest: foo",
+  );
+  invalidDigest(() => pbkdf2Sync("password", "salt", 1, 32, "foo"));
+  invalidDigest(() => pbkdf2("password", "salt", 1, 32, "foo", () => {}));
+  invalidDigest(() => createSign("foo"));
+  invalidDigest(() => createVerify("foo"));
+  const { privateKey } = generateKeyPairSync("ec", { namedCurve: "P-256" });
+  invalidDigest(() => sign("foo", Buffer.from("data"), privateKey));
+
+  assertCode(
+    () => createHash("sha256", { outputLength: 5 }),
+    RangeError,
+    "ERR_OSSL_EVP_NOT_XOF_OR_INVALID_LENGTH",
+  );
+  assertCode(
+    () => createHash("blake2b512", { outputLength: 65 }),
+    RangeError,
+    "ERR_OUT_OF_RANGE",
+  );
+});
+
+Deno.test("[node/crypto.Hash] update() borrows large buffers", () => {
+  const size = 256 * 1024 * 1024;
+  const data = Buffer.alloc(size, 0x61);
+  const before = Deno.memoryUsage();
+  const digest = createHash("sha256").update(data).digest("hex");
+  const after = Deno.memoryUsage();
+  assertEquals(digest.length, 64);
+  // A copy across the op boundary would grow one of these by `size`.
+  const rssGrowth = after.rss - before.rss;
+  assert(rssGrowth < size / 8, `rss grew by ${rssGrowth} bytes`);
+  assert(after.external - before.external < size / 8);
+});
+
+Deno.test("[node/crypto.Hash] update() rejects detached buffers", () => {
+  const data = new Uint8Array(8);
+  structuredClone(data.buffer, { transfer: [data.buffer] });
+  assertThrows(
+    () => createHash("sha256").update(data),
+    TypeError,
+    "detached ArrayBuffer",
+  );
+});
+
+Deno.test("[node/crypto.Hash] updateAsync() doesn't block timers", async () => {
+  const data = Buffer.alloc(256 * 1024 * 1024, 0x61);
+  // deno-lint-ignore no-explicit-any
+  const hash: any = createHash("sha512");
+  const start = performance.now();
+  const timer = new Promise<number>((resolve) =>
+    setTimeout(() => resolve(performance.now() - start), 10)
+  );
+  const update = hash.updateAsync(data);
+  const delay = await timer;
+  assert(delay < 10 + 16, `timer fired after ${delay}ms`);
+  assertEquals(await update, hash);
+  assertEquals(
+    hash.digest("hex"),
+    createHash("sha512").update(data).digest("hex"),
+  );
+});
+
+Deno.test("[node/crypto.Hash] updateAsync() ordering", async () => {
+  // deno-lint-ignore no-explicit-any
+  const h: any = createHash("sha256").update("a");
+  const first = h.updateAsync("b");
+  const second = h.updateAsync(Buffer.from("c"));
+  const inProgress = "updateAsync() is in progress";
+  assertThrows(() => h.update("d"), Error, inProgress);
+  assertThrows(() => h.digest(), Error, inProgress);
+  await first;
+  // The second call is still running.
+  assertThrows(() => h.copy(), Error, inProgress);
+  await second;
+  assertEquals(h.digest("hex"), hash("sha256", "abc"));
+
+  await assertRejects(() => h.updateAsync("x"), Error, "Digest already called");
+});
+
+Deno.test("[node/crypto.Hash] updateFromResource()", async () => {
+  const data = new Uint8Array(200_000).map((_, i) => i * 7);
+  const path = await Deno.makeTempFile();
+  await Deno.writeFile(path, data);
+  const fd = openSync(path);
+  try {
+    // deno-lint-ignore no-explicit-any
+    const h: any = createHash("sha256").update("head");
+    const read = h.updateFromResource(fd);
+    assertThrows(() => h.update("x"), Error, "in progress");
+    assertEquals(await read, data.length);
+    // The file is at EOF now.
+    assertEquals(await h.updateFromResource(fd), 0);
+    h.update("tail");
+    assertEquals(
+      h.digest("hex"),
+      createHash("sha256").update("head").update(data).update("tail")
+        .digest("hex"),
+    );
+
+    const reopened = openSync(path);
+    try {
+      await assertRejects(
+        () => h.updateFromResource(reopened),
+        Error,
+        "Digest already called",
+      );
+    } finally {
+      closeSync(reopened);
+    }
+  } finally {
+    closeSync(fd);
+    await Deno.remove(path);
+  }
+});
+
+Deno.test("[node/crypto] hashStream() passes data through", async () => {
+  const { transform, digest } = hashStream("sha256");
+  const chunks = [Buffer.from("a"), "b", new Uint8Array([0x63])];
+  const output = await Array.fromAsync(
+    ReadableStream.from(chunks).pipeThrough(transform),
+  );
+  assertEquals(output, chunks);
+  assertEquals(
+    Buffer.from(await digest()).toString("hex"),
+    hash("sha256", "abc"),
+  );
+});
+
+Deno.test("[node/crypto] hashStream() rejects on errors", async () => {
+  const { transform, digest } = hashStream("sha256");
+  const source = new ReadableStream({
+    pull(controller) {
+      controller.enqueue(new Uint8Array(16));
+      controller.error(new Error("boom"));
+    },
+  });
+  await assertRejects(
+    () => source.pipeThrough(transform).pipeTo(new WritableStream()),
+    Error,
+    "boom",
+  );
+  await assertRejects(digest, Error, "boom");
+
+  const invalid = hashStream("sha256");
+  const writer = invalid.transform.writable.getWriter();
+  await assertRejects(() => writer.write(42), TypeError);
+  await assertRejects(invalid.digest, TypeError);
+});
+
+Deno.test("[node/crypto] hmacStream()", async () => {
+  const expected = createHmac("sha256", "key").update("abc").digest("hex");
+  for (const key of ["key", createSecretKey(Buffer.from("key"))]) {
+    const { transform, mac } = hmacStream("sha256", key);
+    const chunks = [Buffer.from("a"), "b", new Uint8Array([0x63])];
+    const output = await Array.fromAsync(
+      ReadableStream.from(chunks).pipeThrough(transform),
+    );
+    assertEquals(output, chunks);
+    // Every call to mac() resolves to the same MAC.
+    assertEquals(await mac(), await mac());
+    assertEquals(Buffer.from(await mac()).toString("hex"), expected);
+  }
+
+  const { transform, mac } = hmacStream("sha256", "key");
+  const pending = mac();
+  const writer = transform.writable.getWriter();
+  await writer.abort(new Error("aborted"));
+  await assertRejects(() => pending, Error, "aborted");
+  assertThrows(() => hmacStream("shake256", "key"), TypeError);
+});
+
+Deno.test("[node/crypto] hmacBatch()", () => {
+  const chunk = new Uint8Array(64 * 1024).fill(0x5a);
+  // Enough data for the batch to be split across threads.
+  const pairs = Array.from(
+    { length: 20 },
+    (_, i) => [new Uint8Array(new Uint32Array([i]).buffer), chunk],
+  );
+  const expected = pairs.map(([prefix, chunk]) =>
+    createHmac("sha256", "key").update(prefix).update(chunk).digest("hex")
+  );
+  for (const key of ["key", createSecretKey(Buffer.from("key"))]) {
+    const macs = hmacBatch("sha256", key, pairs);
+    assertEquals(
+      macs.map((mac: Uint8Array) => Buffer.from(mac).toString("hex")),
+      expected,
+    );
+  }
+  assertEquals(hmacBatch("sha256", "key", []), []);
+
+  const detached = new Uint8Array(8);
+  detached.buffer.transfer();
+  assertThrows(
+    () => hmacBatch("sha256", "key", [pairs[0], [detached, chunk]]),
+    TypeError,
+    "pairs[1] is backed by a detached ArrayBuffer",
+  );
+  assertThrows(
+    () => hmacBatch("sha256", "key", [pairs[0], pairs[1], [chunk]]),
+    TypeError,
+    "pairs[2] is not a [prefix, chunk] pair",
+  );
+
+  // A getter on the second pair detaches a buffer the op has already seen.
+  const detachingBatch = (view: Uint8Array) => {
+    const batch = [pairs[0]];
+    Object.defineProperty(batch, 1, {
+      get() {
+        view.buffer.transfer();
+        return pairs[1];
+      },
+    });
+    return batch;
+  };
+  const key = new Uint8Array(16).fill(1);
+  assertThrows(
+    () => hmacBatch("sha256", key, detachingBatch(key)),
+    TypeError,
+    "key is backed by a detached ArrayBuffer",
+  );
+  const earlier = new Uint8Array(chunk);
+  const batch = detachingBatch(earlier);
+  batch[0] = [pairs[0][0], earlier];
+  assertThrows(
+    () => hmacBatch("sha256", "key", batch),
+    TypeError,
+    "pairs[0] is backed by a detached ArrayBuffer",
+  );
+});
+
+Deno.test("[node/crypto] createMultiHash()", () => {
+  const algorithms = ["sha1", "SHA256", "sha512"];
+  const data = Buffer.from("tarball contents");
+  const multi = createMultiHash(algorithms)
+    .update("header:")
+    .update(data.subarray(0, 7))
+    .update(data.toString("hex", 7), "hex");
+  const expected = algorithms.map((algorithm) =>
+    createHash(algorithm).update("header:").update(data).digest("base64")
+  );
+  assertEquals(multi.digest("base64"), expected);
+  assertThrows(() => multi.update("x"), Error, "Digest already called");
+  assertThrows(() => multi.digest(), Error, "Digest already called");
+
+  const buffers = createMultiHash(["md5", "sha256"]).update("abc").digest();
+  assert(buffers.every(Buffer.isBuffer));
+  assertEquals(buffers[1], createHash("sha256").update("abc").digest());
+
+  assertThrows(() => createMultiHash(["sha256", "nope"]), TypeError, "nope");
+  assertThrows(() => createMultiHash("sha256"), TypeError);
+  assertEquals(createMultiHash([]).update("abc").digest(), []);
+});
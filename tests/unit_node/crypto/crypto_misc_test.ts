@@ -43,3 +43,35 @@ Deno.test("[node/crypto.timingSafeEqual] RangeError on Buffer with different byt
 
   assertThrows(() => timingSafeEqual(a, b), RangeError);
 });
+
+Deno.test("[node/crypto.timingSafeEqual] compares bytes of any view", () => {
+  const view = new DataView(new ArrayBuffer(8));
+  assert(timingSafeEqual(view, new Float64Array(1)));
+  assert(timingSafeEqual(new ArrayBuffer(8), new Uint16Array(4)));
+  // -0 and 0 are equal as numbers but not as bytes.
+  assert(!timingSafeEqual(new Float64Array([0]), new Float64Array([-0])));
+  assert(timingSafeEqual(new Float64Array([NaN]), new Float64Array([NaN])));
+
+  const shared = new SharedArrayBuffer(4);
+  new Uint8Array(shared).set([1, 2, 3, 4]);
+  assert(timingSafeEqual(new Uint8Array(shared), Buffer.from([1, 2, 3, 4])));
+  // @ts-ignore: SharedArrayBuffer arguments are valid.
+  assert(timingSafeEqual(shared, new Uint8Array([1, 2, 3, 4])));
+});
+
+Deno.test("[node/crypto.timingSafeEqual] errors match Node.js", () => {
+  const err = assertThrows(
+    () => timingSafeEqual(new Uint8Array(1), new Uint16Array(1)),
+    RangeError,
+    "Input buffers must have the same byte length",
+  );
+  // deno-lint-ignore no-explicit-any
+  assertEquals((err as any).code, "ERR_CRYPTO_TIMING_SAFE_EQUAL_LENGTH");
+  assertThrows(
+    // @ts-ignore: testing an invalid argument.
+    () => timingSafeEqual("a", new Uint8Array(1)),
+    TypeError,
+    'The "buf1" argument must be an instance of ArrayBuffer, Buffer, ' +
+      "TypedArray, or DataView.",
+  );
+});
@@ -10,6 +10,7 @@ import {
 import { fromFileUrl, relative, SEPARATOR } from "@std/path";
 import * as workerThreads from "node:worker_threads";
 import { EventEmitter, once } from "node:events";
+import { createHash } from "node:crypto";
 import process from "node:process";
 
 Deno.test("[node/worker_threads] BroadcastChannel is exported", () => {
@@ -864,3 +865,57 @@ Deno.test("[node/worker_threads] Worker runs async ops correctly", async () => {
 
   await recvMessage.promise;
 });
+
+Deno.test({
+  name: "[node/worker_threads] Hash can be posted to a worker",
+  async fn() {
+    const worker = new workerThreads.Worker(
+      `
+      import { parentPort } from "node:worker_threads";
+      parentPort.once("message", ({ hash, rest }) => {
+        parentPort.postMessage({ hash: hash.update(rest) });
+      });
+      `,
+      { eval: true },
+    );
+    const hash = createHash("sha256").update("hello ");
+    worker.postMessage({ hash, rest: "world" });
+    const [message] = await once(worker, "message");
+    assertEquals(
+      message.hash.digest("hex"),
+      createHash("sha256").update("hello world").digest("hex"),
+    );
+    // The original is independent of the copy that was sent.
+    assertEquals(
+      hash.digest("hex"),
+      createHash("sha256").update("hello ").digest("hex"),
+    );
+
+    assertThrows(
+      () => worker.postMessage(createHash("md5")),
+      DOMException,
+      "Hash could not be cloned",
+    );
+    worker.terminate();
+  },
+});
+
+Deno.test({
+  name: "[node/worker_threads] Hash state key in plain data is kept as is",
+  async fn() {
+    const worker = new workerThreads.Worker(
+      `
+      import { parentPort } from "node:worker_threads";
+      parentPort.once("message", (message) => {
+        parentPort.postMessage(message);
+      });
+      `,
+      { eval: true },
+    );
+    const message = { __nodeCryptoHashState: new Uint8Array([1, 2, 3]) };
+    worker.postMessage(message);
+    const [received] = await once(worker, "message");
+    assertEquals(received, message);
+    worker.terminate();
+  },
+});